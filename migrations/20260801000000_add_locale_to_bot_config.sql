@@ -0,0 +1,5 @@
+-- Per-guild language for every user-facing message (recaps, analyses, slash
+-- command responses). Existing guilds keep French, the bot's original
+-- language. Changed with /set-language.
+ALTER TABLE bot_config
+    ADD COLUMN IF NOT EXISTS locale VARCHAR(5) NOT NULL DEFAULT 'fr';
@@ -0,0 +1,36 @@
+-- Scheduled "semaine SFG" / daily recap posts. One row per guild and period;
+-- `schedule` is a 5-field cron expression evaluated in `timezone` (IANA
+-- name). `last_sent_at` holds the fire time of the last post so a restart
+-- never posts the same digest twice.
+CREATE TABLE IF NOT EXISTS digest_schedules (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  guild_id BIGINT NOT NULL REFERENCES bot_config(guild_id) ON DELETE CASCADE,
+  period VARCHAR(10) NOT NULL CHECK (period IN ('daily', 'weekly')),
+  schedule VARCHAR(100) NOT NULL,
+  timezone VARCHAR(64) NOT NULL DEFAULT 'Europe/Paris',
+  enabled BOOLEAN NOT NULL DEFAULT TRUE,
+  last_sent_at TIMESTAMPTZ,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  UNIQUE (guild_id, period)
+);
+
+-- Every configured guild gets the Monday-morning weekly digest.
+INSERT INTO digest_schedules (guild_id, period, schedule)
+SELECT guild_id, 'weekly', '0 9 * * 1' FROM bot_config
+ON CONFLICT (guild_id, period) DO NOTHING;
+
+-- Ranked standing captured when a digest is built; the LP net change of a
+-- period is the difference with the latest snapshot taken before it.
+CREATE TABLE IF NOT EXISTS rank_snapshots (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  summoner_id UUID NOT NULL REFERENCES summoners(id) ON DELETE CASCADE,
+  queue_type VARCHAR(30) NOT NULL,
+  tier VARCHAR(20) NOT NULL,
+  division VARCHAR(5),
+  league_points INT NOT NULL,
+  captured_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_rank_snapshots_summoner_captured
+  ON rank_snapshots (summoner_id, captured_at DESC);
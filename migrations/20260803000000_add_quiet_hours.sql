@@ -0,0 +1,15 @@
+-- Per-guild quiet hours. Between `start_time` and `end_time` (local time in
+-- `timezone`, wrapping past midnight when start > end) notifications stay in
+-- notification_queue and are posted afterwards as one summary. Event types
+-- listed in `bypass_event_types` are still sent immediately.
+CREATE TABLE IF NOT EXISTS quiet_hours (
+  guild_id BIGINT PRIMARY KEY REFERENCES bot_config(guild_id) ON DELETE CASCADE,
+  start_time TIME NOT NULL,
+  end_time TIME NOT NULL,
+  timezone VARCHAR(64) NOT NULL DEFAULT 'Europe/Paris',
+  bypass_event_types TEXT[] NOT NULL DEFAULT '{}',
+  enabled BOOLEAN NOT NULL DEFAULT TRUE,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CHECK (start_time <> end_time)
+);
@@ -0,0 +1,15 @@
+-- Where a guild's notifications are delivered besides (or instead of) its
+-- Discord channel. A guild without rows only uses Discord; a disabled
+-- 'discord' row turns the channel off (headless runs).
+CREATE TABLE IF NOT EXISTS notification_sinks (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  guild_id BIGINT NOT NULL REFERENCES bot_config(guild_id) ON DELETE CASCADE,
+  kind VARCHAR(20) NOT NULL CHECK (kind IN ('discord', 'webhook', 'stdout')),
+  -- Target of the 'webhook' sink; unused by the others.
+  url TEXT,
+  enabled BOOLEAN NOT NULL DEFAULT TRUE,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  UNIQUE (guild_id, kind),
+  CHECK (kind <> 'webhook' OR url IS NOT NULL)
+);
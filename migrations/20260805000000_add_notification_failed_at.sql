@@ -0,0 +1,17 @@
+-- Marks notifications that exhausted their retries (the dead letters), so
+-- they can be told apart from rows delivered after a transient failure,
+-- which keep their last `error_message`.
+ALTER TABLE notification_queue
+    ADD COLUMN IF NOT EXISTS failed_at TIMESTAMPTZ;
+
+-- Rows parked before this column existed: the last retry is retry_count 2.
+UPDATE notification_queue
+SET failed_at = processed_at
+WHERE processed AND error_message IS NOT NULL AND retry_count >= 2 AND failed_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_notification_queue_failed
+    ON notification_queue (failed_at DESC) WHERE failed_at IS NOT NULL;
+
+-- Retention pruning scans processed rows by age.
+CREATE INDEX IF NOT EXISTS idx_notification_queue_processed_at
+    ON notification_queue (processed_at) WHERE processed;
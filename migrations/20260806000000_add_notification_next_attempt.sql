@@ -0,0 +1,9 @@
+-- Retries are scheduled instead of picked up on every poll: a failed send
+-- pushes `next_attempt_at` back with an exponential backoff, and only due
+-- events are read.
+ALTER TABLE notification_queue
+    ADD COLUMN IF NOT EXISTS next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+DROP INDEX IF EXISTS idx_notification_queue_pending;
+CREATE INDEX IF NOT EXISTS idx_notification_queue_pending
+    ON notification_queue (next_attempt_at) WHERE NOT processed;
@@ -0,0 +1,16 @@
+-- Wakes the notification processor instead of having it poll: every row that
+-- becomes pending (new event, rescheduled retry, requeued dead letter)
+-- signals the `notification_queue` channel with its id.
+CREATE OR REPLACE FUNCTION notify_notification_queue() RETURNS trigger AS $$
+BEGIN
+    PERFORM pg_notify('notification_queue', NEW.id::text);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS notification_queue_notify ON notification_queue;
+CREATE TRIGGER notification_queue_notify
+    AFTER INSERT OR UPDATE ON notification_queue
+    FOR EACH ROW
+    WHEN (NOT NEW.processed)
+    EXECUTE FUNCTION notify_notification_queue();
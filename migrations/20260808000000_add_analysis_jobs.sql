@@ -0,0 +1,14 @@
+-- Outbox for post-game analyses: a job is written in the same transaction
+-- as the match and its GAME_ENDED event, and completed once the analysis
+-- was posted, so analyses interrupted by a restart are resumed.
+CREATE TABLE IF NOT EXISTS analysis_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    summoner_id UUID NOT NULL REFERENCES summoners(id) ON DELETE CASCADE,
+    match_id VARCHAR(100) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ,
+    UNIQUE (summoner_id, match_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_analysis_jobs_pending
+    ON analysis_jobs (created_at) WHERE completed_at IS NULL;
@@ -0,0 +1,23 @@
+-- One event per summoner, type and game: the spectator and featured-mode
+-- paths, or duplicated polling loops, may detect the same game end twice.
+-- Keep the oldest row of each existing duplicate group.
+DELETE FROM notification_queue a
+USING notification_queue b
+WHERE a.summoner_id = b.summoner_id
+  AND a.event_type = b.event_type
+  AND a.game_id = b.game_id
+  AND (a.created_at, a.id) > (b.created_at, b.id);
+
+DELETE FROM notification_queue a
+USING notification_queue b
+WHERE a.summoner_id = b.summoner_id
+  AND a.event_type = b.event_type
+  AND a.match_id = b.match_id
+  AND (a.created_at, a.id) > (b.created_at, b.id);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_notification_queue_unique_game
+    ON notification_queue (summoner_id, event_type, game_id);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_notification_queue_unique_match
+    ON notification_queue (summoner_id, event_type, match_id)
+    WHERE match_id IS NOT NULL;
@@ -0,0 +1,16 @@
+-- Analysis jobs are run by a bounded worker pool instead of one task per
+-- game: a job is claimed (`running`), then either completed, rescheduled
+-- with a backoff (`pending` + `next_attempt_at`) or given up (`failed`).
+ALTER TABLE analysis_jobs
+    ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'running', 'done', 'failed')),
+    ADD COLUMN IF NOT EXISTS attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ADD COLUMN IF NOT EXISTS started_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS error_message TEXT;
+
+UPDATE analysis_jobs SET status = 'done' WHERE completed_at IS NOT NULL;
+
+DROP INDEX IF EXISTS idx_analysis_jobs_pending;
+CREATE INDEX IF NOT EXISTS idx_analysis_jobs_pending
+    ON analysis_jobs (next_attempt_at) WHERE status = 'pending';
@@ -0,0 +1,4 @@
+-- Tier band whose benchmarks a summoner's games are graded against, when
+-- set with /benchmark-tier. NULL follows the player's current ranked tier.
+ALTER TABLE summoners
+    ADD COLUMN IF NOT EXISTS benchmark_tier VARCHAR(20);
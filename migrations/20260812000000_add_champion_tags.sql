@@ -0,0 +1,4 @@
+-- Data Dragon class tags (Tank, Fighter, Assassin, Mage, Marksman,
+-- Support), primary first. Filled by the champion cache refresh.
+ALTER TABLE champions
+    ADD COLUMN IF NOT EXISTS tags TEXT[] NOT NULL DEFAULT '{}';
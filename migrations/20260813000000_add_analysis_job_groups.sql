@@ -0,0 +1,10 @@
+-- Group analyses: when several tracked players of one team finished the
+-- same game, the notification processor merges their pending jobs into the
+-- oldest one, which lists every member in `group_summoner_ids`; the other
+-- jobs are closed as `grouped`.
+ALTER TABLE analysis_jobs
+    ADD COLUMN IF NOT EXISTS group_summoner_ids UUID[] NOT NULL DEFAULT '{}';
+
+ALTER TABLE analysis_jobs DROP CONSTRAINT IF EXISTS analysis_jobs_status_check;
+ALTER TABLE analysis_jobs ADD CONSTRAINT analysis_jobs_status_check
+    CHECK (status IN ('pending', 'running', 'done', 'failed', 'grouped'));
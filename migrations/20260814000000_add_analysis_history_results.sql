@@ -0,0 +1,8 @@
+-- Full coach answer of each analysis, with what it was generated from, so
+-- reruns of the same match are served from cache while the prompt and the
+-- model are unchanged. NULL for rows stored before, and for group reports
+-- (one answer for several players).
+ALTER TABLE analysis_history
+  ADD COLUMN IF NOT EXISTS analysis_result TEXT,
+  ADD COLUMN IF NOT EXISTS prompt_hash TEXT,
+  ADD COLUMN IF NOT EXISTS model TEXT;
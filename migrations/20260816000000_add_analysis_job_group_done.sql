@@ -0,0 +1,4 @@
+-- Members of a group job already posted by the per-member fallback, so a
+-- retry of the job does not post their analyses again.
+ALTER TABLE analysis_jobs
+    ADD COLUMN IF NOT EXISTS group_done_summoner_ids UUID[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,180 @@
+//! Champion classes, so a tank top and a Riven top — or an enchanter and an
+//! engage support — are not judged alike by the role benchmarks.
+//!
+//! A champion's class is its primary Data Dragon tag, unless
+//! [`CLASS_OVERRIDES`] says otherwise. Each class shifts the
+//! [`SkillImportance`] of a few skills and adds a notes block to the
+//! composed role prompt.
+
+use crate::analysis::roles::SkillImportance;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChampionClass {
+    Tank,
+    Fighter,
+    Assassin,
+    Mage,
+    Marksman,
+    /// Enchanters and utility supports; engage supports are tagged Tank.
+    Support,
+}
+
+/// Champions whose primary Data Dragon tag misrepresents how they are
+/// played, keyed by the match-v5 `championName`.
+pub const CLASS_OVERRIDES: &[(&str, ChampionClass)] = &[
+    // Tagged Support, played as a roaming executioner.
+    ("Pyke", ChampionClass::Assassin),
+    // Tagged Support, played as an engage/hook support.
+    ("Thresh", ChampionClass::Tank),
+    ("Blitzcrank", ChampionClass::Tank),
+    // Tagged Support, scales with farmed souls like a marksman.
+    ("Senna", ChampionClass::Marksman),
+    // Tagged Fighter, a ranged late-game carry from level 11.
+    ("Kayle", ChampionClass::Marksman),
+];
+
+impl ChampionClass {
+    pub const ALL: [ChampionClass; 6] = [
+        ChampionClass::Tank,
+        ChampionClass::Fighter,
+        ChampionClass::Assassin,
+        ChampionClass::Mage,
+        ChampionClass::Marksman,
+        ChampionClass::Support,
+    ];
+
+    /// Data Dragon tag (`"Tank"`, `"Mage"`, ...).
+    pub fn as_tag(self) -> &'static str {
+        match self {
+            ChampionClass::Tank => "Tank",
+            ChampionClass::Fighter => "Fighter",
+            ChampionClass::Assassin => "Assassin",
+            ChampionClass::Mage => "Mage",
+            ChampionClass::Marksman => "Marksman",
+            ChampionClass::Support => "Support",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|class| class.as_tag() == tag)
+    }
+
+    /// Class of `champion_name` given its Data Dragon tags, primary first.
+    pub fn resolve(champion_name: &str, tags: &[String]) -> Option<Self> {
+        CLASS_OVERRIDES
+            .iter()
+            .find(|(name, _)| *name == champion_name)
+            .map(|(_, class)| *class)
+            .or_else(|| tags.iter().find_map(|tag| Self::from_tag(tag)))
+    }
+
+    pub fn label_fr(self) -> &'static str {
+        match self {
+            ChampionClass::Tank => "Tank",
+            ChampionClass::Fighter => "Combattant",
+            ChampionClass::Assassin => "Assassin",
+            ChampionClass::Mage => "Mage",
+            ChampionClass::Marksman => "Tireur",
+            ChampionClass::Support => "Enchanteur / utilitaire",
+        }
+    }
+
+    /// Importance of `skill` for this class, starting from the role's.
+    /// Skills the role does not grade stay [`SkillImportance::NotApplicable`].
+    pub fn adjust(self, skill: &str, importance: SkillImportance) -> SkillImportance {
+        if importance == SkillImportance::NotApplicable {
+            return importance;
+        }
+        match (self, skill) {
+            (ChampionClass::Tank, "damage_per_minute") => SkillImportance::Low,
+            (ChampionClass::Tank, "deaths") => SkillImportance::Medium,
+            (ChampionClass::Tank, "kills_assists") => SkillImportance::High,
+            (ChampionClass::Assassin, "kills_assists") => SkillImportance::High,
+            (ChampionClass::Assassin, "deaths") => SkillImportance::Critical,
+            (ChampionClass::Mage, "damage_per_minute") => SkillImportance::Critical,
+            (ChampionClass::Marksman, "cs_per_minute") => SkillImportance::Critical,
+            (ChampionClass::Marksman, "damage_per_minute") => SkillImportance::Critical,
+            (ChampionClass::Support, "damage_per_minute") => SkillImportance::Low,
+            (ChampionClass::Support, "kills_assists") => SkillImportance::Critical,
+            _ => importance,
+        }
+    }
+
+    /// Coaching notes appended to the role prompt for this class.
+    pub fn notes_fr(self) -> &'static str {
+        match self {
+            ChampionClass::Tank => {
+                "Un tank n'est pas jugé sur ses dégâts : son rôle est d'engager, d'absorber et de protéger ses carries. Des morts en engageant un fight gagné sont acceptables ; juge plutôt la qualité des engages, la participation aux fights et la présence sur les objectifs."
+            }
+            ChampionClass::Fighter => {
+                "Un combattant doit gagner ses duels et convertir son avance en pression (split push, tourelles, Héraut). Juge s'il a su choisir entre splitter et rejoindre l'équipe au bon moment."
+            }
+            ChampionClass::Assassin => {
+                "Un assassin vit de ses picks : il doit trouver des cibles isolées et sortir vivant. Chaque mort lui coûte son avance et sa menace ; une faible participation aux kills signale qu'il a raté sa fenêtre."
+            }
+            ChampionClass::Mage => {
+                "Un mage est une source majeure de dégâts de l'équipe : ses DPM et son positionnement en teamfight priment. Juge s'il a contrôlé les vagues pour rester disponible pour les fights."
+            }
+            ChampionClass::Marksman => {
+                "Un tireur dépend de son farm pour atteindre ses objets clés : CS/min et DPM sont critiques, et chaque mort en late game peut coûter la partie. Juge son positionnement en teamfight autant que ses chiffres."
+            }
+            ChampionClass::Support => {
+                "Un enchanteur ne se juge pas à ses dégâts : boucliers, soins et peel rendent ses carries plus forts. Sa participation aux kills et sa vision sont ses vraies métriques."
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(values: &[&str]) -> Vec<String> {
+        values.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    #[test]
+    fn primary_tag_decides_the_class() {
+        assert_eq!(
+            ChampionClass::resolve("Malphite", &tags(&["Tank", "Fighter"])),
+            Some(ChampionClass::Tank)
+        );
+        assert_eq!(
+            ChampionClass::resolve("Riven", &tags(&["Fighter", "Assassin"])),
+            Some(ChampionClass::Fighter)
+        );
+        assert_eq!(ChampionClass::resolve("Unknown", &[]), None);
+    }
+
+    #[test]
+    fn overrides_win_over_tags() {
+        assert_eq!(
+            ChampionClass::resolve("Pyke", &tags(&["Support", "Assassin"])),
+            Some(ChampionClass::Assassin)
+        );
+        assert_eq!(
+            ChampionClass::resolve("Senna", &[]),
+            Some(ChampionClass::Marksman)
+        );
+    }
+
+    #[test]
+    fn adjustments_never_grade_skills_the_role_skips() {
+        for class in ChampionClass::ALL {
+            assert_eq!(
+                class.adjust("cs_per_minute", SkillImportance::NotApplicable),
+                SkillImportance::NotApplicable
+            );
+        }
+        assert_eq!(
+            ChampionClass::Tank.adjust("damage_per_minute", SkillImportance::High),
+            SkillImportance::Low
+        );
+        assert_eq!(
+            ChampionClass::Fighter.adjust("damage_per_minute", SkillImportance::High),
+            SkillImportance::High
+        );
+    }
+}
@@ -1,39 +1,252 @@
-use crate::analysis::models::AnalysisResult;
+use crate::analysis::models::{AnalysisResult, SkillVerdict};
+use crate::analysis::progression::{MOVING_AVERAGE_WINDOW, ProgressionReport, SkillTrend};
+use crate::analysis::response::partial_summary;
+use crate::analysis::scoring::skill_label;
+use crate::i18n::{Catalog, Locale, render};
+use chrono_tz::Tz;
 use serenity::builder::{CreateEmbed, CreateEmbedFooter};
-use serenity::model::Colour;
+use serenity::model::{Colour, Timestamp};
 
 const MAX_DESCRIPTION_LEN: usize = 4096;
+const MAX_FIELD_LEN: usize = 1024;
 
-pub fn format_analysis_embed(result: &AnalysisResult) -> CreateEmbed {
-    let (title, description, colour) = if let Some(error) = &result.error {
+pub fn format_analysis_embed(result: &AnalysisResult, locale: Locale) -> CreateEmbed {
+    let text = locale.catalog();
+    let title = render(text.analysis_title, &[("name", &result.champion_name)]);
+    let (description, colour) = if let Some(error) = &result.error {
         (
-            format!("📊 Analyse de partie — {}", result.champion_name),
-            format!("⚠️ Analyse indisponible : {error}"),
+            render(text.analysis_unavailable, &[("error", error)]),
             Colour::from_rgb(149, 165, 166),
         )
     } else {
         (
-            format!("📊 Analyse de partie — {}", result.champion_name),
             result.summary.clone(),
             rating_colour(result.overall_rating.as_deref()),
         )
     };
 
-    CreateEmbed::new()
+    let mut embed = CreateEmbed::new()
         .title(title)
         .description(truncate_description(&description))
-        .colour(colour)
-        .footer(CreateEmbedFooter::new("Propulsé par Gemma 4"))
+        .colour(colour);
+
+    if result.error.is_none() {
+        if !result.strengths.is_empty() {
+            embed = embed.field(text.analysis_strengths, bullets(&result.strengths), false);
+        }
+        if !result.weaknesses.is_empty() {
+            embed = embed.field(text.analysis_weaknesses, bullets(&result.weaknesses), false);
+        }
+        if !result.skill_verdicts.is_empty() {
+            embed = embed.field(
+                text.analysis_skills,
+                skill_lines(&result.skill_verdicts),
+                false,
+            );
+        }
+        if let Some(tip) = &result.key_tip {
+            embed = embed.field(text.analysis_key_tip, truncate(tip, MAX_FIELD_LEN), false);
+        }
+    }
+
+    let footer = if result.stats_based {
+        result
+            .benchmark_band
+            .as_deref()
+            .map(|band| render(text.analysis_stats_footer, &[("band", band)]))
+    } else {
+        let engine = result
+            .engine
+            .as_deref()
+            .map(|engine| render(text.analysis_footer, &[("engine", engine)]));
+        match (engine, result.cached) {
+            (Some(engine), true) => Some(format!("{engine} · {}", text.analysis_cached)),
+            (None, true) => Some(text.analysis_cached.to_string()),
+            (engine, false) => engine,
+        }
+    };
+    match footer {
+        Some(footer) => embed.footer(CreateEmbedFooter::new(footer)),
+        None => embed,
+    }
+}
+
+fn bullets(items: &[String]) -> String {
+    let lines: Vec<String> = items.iter().map(|item| format!("• {item}")).collect();
+    truncate(&lines.join("\n"), MAX_FIELD_LEN)
+}
+
+fn skill_lines(verdicts: &[SkillVerdict]) -> String {
+    let lines: Vec<String> = verdicts
+        .iter()
+        .map(|verdict| {
+            format!(
+                "{} **{}** — {}",
+                rating_marker(&verdict.rating),
+                verdict.skill,
+                verdict.comment
+            )
+        })
+        .collect();
+    truncate(&lines.join("\n"), MAX_FIELD_LEN)
+}
+
+fn rating_marker(rating: &str) -> &'static str {
+    match rating {
+        "Good" => "🟢",
+        "Average" => "🟡",
+        "Poor" => "🔴",
+        _ => "⚪",
+    }
+}
+
+/// Placeholder shown while the answer streams in: the summary generated so
+/// far, or a waiting notice before the first words arrive. `engine` is the
+/// pipeline's [`engine`](crate::analysis::pipeline::AnalysisPipeline::engine).
+pub fn format_analysis_progress_embed(
+    champion_name: &str,
+    partial_answer: &str,
+    engine: &str,
+    locale: Locale,
+) -> CreateEmbed {
+    let text = locale.catalog();
+    let preview = partial_summary(partial_answer);
+    let description = if preview.trim().is_empty() {
+        text.analysis_in_progress.to_string()
+    } else {
+        format!("{}\n\n{}", preview.trim_end(), text.analysis_in_progress)
+    };
+
+    CreateEmbed::new()
+        .title(render(text.analysis_title, &[("name", champion_name)]))
+        .description(truncate_description(&description))
+        .colour(Colour::from_rgb(149, 165, 166))
+        .footer(CreateEmbedFooter::new(render(
+            text.analysis_footer,
+            &[("engine", engine)],
+        )))
 }
 
-pub fn format_analysis_error_embed(summoner_name: &str, error_msg: &str) -> CreateEmbed {
+pub fn format_analysis_error_embed(
+    summoner_name: &str,
+    error_msg: &str,
+    locale: Locale,
+) -> CreateEmbed {
+    let text = locale.catalog();
     CreateEmbed::new()
-        .title(format!("📊 Analyse de partie — {summoner_name}"))
-        .description(truncate_description(&format!(
-            "⚠️ Analyse indisponible : {error_msg}"
+        .title(render(text.analysis_title, &[("name", summoner_name)]))
+        .description(truncate_description(&render(
+            text.analysis_unavailable,
+            &[("error", error_msg)],
         )))
         .colour(Colour::from_rgb(149, 165, 166))
-        .footer(CreateEmbedFooter::new("Propulsé par Gemma 4"))
+}
+
+/// Progression report embed, dates shown in `tz`.
+pub fn format_progression_embed(report: &ProgressionReport, tz: Tz, locale: Locale) -> CreateEmbed {
+    let text = locale.catalog();
+    let date = |at: chrono::DateTime<chrono::Utc>| {
+        at.with_timezone(&tz)
+            .format(text.digest_date_format)
+            .to_string()
+    };
+    // The range ends at its upper bound; show the last day it covers.
+    let last_day = report.end - chrono::Duration::seconds(1);
+    let ratings = &report.ratings;
+    let description = format!(
+        "{}\n{}",
+        render(
+            text.progression_period,
+            &[
+                ("start", &date(report.start)),
+                ("end", &date(last_day)),
+                ("games", &report.games.to_string()),
+                ("wins", &report.wins.to_string()),
+            ],
+        ),
+        render(
+            text.digest_coach,
+            &[
+                ("good", &ratings.good.to_string()),
+                ("average", &ratings.average.to_string()),
+                ("poor", &ratings.poor.to_string()),
+            ],
+        )
+    );
+
+    let mut embed = CreateEmbed::new()
+        .title(render(
+            text.progression_title,
+            &[("name", &report.summoner_name)],
+        ))
+        .description(truncate_description(&description))
+        .colour(Colour::from_rgb(52, 152, 219))
+        .timestamp(Timestamp::from(report.end));
+    if let Some(engine) = &report.engine {
+        embed = embed.footer(CreateEmbedFooter::new(render(
+            text.analysis_footer,
+            &[("engine", engine)],
+        )));
+    }
+
+    if !report.metrics.is_empty() {
+        let lines: Vec<String> = report
+            .metrics
+            .iter()
+            .map(|trend| {
+                render(
+                    text.progression_metric,
+                    &[
+                        ("metric", trend.metric.label(text)),
+                        ("first", &trend.metric.format(trend.first())),
+                        ("last", &trend.metric.format(trend.last())),
+                        ("average", &trend.metric.format(trend.average)),
+                    ],
+                )
+            })
+            .collect();
+        embed = embed.field(
+            render(
+                text.progression_metrics,
+                &[("window", &MOVING_AVERAGE_WINDOW.to_string())],
+            ),
+            truncate(&lines.join("\n"), MAX_FIELD_LEN),
+            false,
+        );
+    }
+    if let Some(trend) = &report.most_improved {
+        embed = embed.field(
+            text.progression_improved,
+            skill_trend_line(trend, text, locale),
+            true,
+        );
+    }
+    if let Some(trend) = &report.most_regressed {
+        embed = embed.field(
+            text.progression_regressed,
+            skill_trend_line(trend, text, locale),
+            true,
+        );
+    }
+    if let Some(coaching) = &report.coaching {
+        embed = embed.field(
+            text.progression_coaching,
+            truncate(coaching, MAX_FIELD_LEN),
+            false,
+        );
+    }
+    embed
+}
+
+fn skill_trend_line(trend: &SkillTrend, text: &Catalog, locale: Locale) -> String {
+    render(
+        text.progression_skill,
+        &[
+            ("skill", skill_label(trend.skill, locale)),
+            ("before", &format!("{:.1}", trend.before)),
+            ("after", &format!("{:.1}", trend.after)),
+        ],
+    )
 }
 
 fn rating_colour(rating: Option<&str>) -> Colour {
@@ -46,14 +259,15 @@ fn rating_colour(rating: Option<&str>) -> Colour {
 }
 
 fn truncate_description(description: &str) -> String {
-    if description.len() <= MAX_DESCRIPTION_LEN {
-        return description.to_string();
+    truncate(description, MAX_DESCRIPTION_LEN)
+}
+
+fn truncate(text: &str, max_len: usize) -> String {
+    if text.len() <= max_len {
+        return text.to_string();
     }
 
-    let mut truncated = description
-        .chars()
-        .take(MAX_DESCRIPTION_LEN - 3)
-        .collect::<String>();
+    let mut truncated = text.chars().take(max_len - 3).collect::<String>();
     truncated.push_str("...");
     truncated
 }
@@ -70,9 +284,10 @@ mod tests {
             overall_rating: Some("Good".to_string()),
             summary: "Great game".to_string(),
             error: None,
+            ..AnalysisResult::default()
         };
 
-        let embed = format_analysis_embed(&result);
+        let embed = format_analysis_embed(&result, Locale::Fr);
         let value = serde_json::to_value(embed).expect("serialize embed");
         assert_eq!(
             value.get("color"),
@@ -84,6 +299,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn format_analysis_embed_credits_the_engine() {
+        let result = AnalysisResult {
+            champion_name: "Ahri".to_string(),
+            summary: "Great game".to_string(),
+            engine: Some("qwen3 (ollama)".to_string()),
+            cached: true,
+            ..AnalysisResult::default()
+        };
+
+        let embed = format_analysis_embed(&result, Locale::En);
+        let value = serde_json::to_value(embed).expect("serialize embed");
+        assert_eq!(
+            value["footer"]["text"],
+            "Powered by qwen3 (ollama) · Cached analysis (use the force option to regenerate it)"
+        );
+    }
+
+    #[test]
+    fn stats_based_footer_names_the_benchmark_band() {
+        let result = AnalysisResult {
+            champion_name: "Ahri".to_string(),
+            summary: "Verdict".to_string(),
+            stats_based: true,
+            benchmark_band: Some("Argent / Or".to_string()),
+            ..AnalysisResult::default()
+        };
+
+        let embed = format_analysis_embed(&result, Locale::Fr);
+        let value = serde_json::to_value(embed).expect("serialize embed");
+        assert_eq!(value["footer"]["text"], "Repères Argent / Or");
+    }
+
     #[test]
     fn format_analysis_embed_sets_error_colour() {
         let result = AnalysisResult {
@@ -92,9 +340,10 @@ mod tests {
             overall_rating: None,
             summary: "Analysis unavailable: timeout".to_string(),
             error: Some("timeout".to_string()),
+            ..AnalysisResult::default()
         };
 
-        let embed = format_analysis_embed(&result);
+        let embed = format_analysis_embed(&result, Locale::Fr);
         let value = serde_json::to_value(embed).expect("serialize embed");
         assert_eq!(
             value.get("color"),
@@ -109,9 +358,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn format_analysis_embed_lists_structured_fields() {
+        let result = AnalysisResult {
+            champion_name: "Ahri".to_string(),
+            overall_rating: Some("Average".to_string()),
+            summary: "Lane perdue, bon mid game.".to_string(),
+            strengths: vec!["KP 65 %".to_string(), "600 DPM".to_string()],
+            skill_verdicts: vec![SkillVerdict {
+                skill: "CS/min".to_string(),
+                rating: "Poor".to_string(),
+                comment: "5,8 CS/min".to_string(),
+            }],
+            key_tip: Some("Reste à moins de 10 CS d'écart.".to_string()),
+            ..AnalysisResult::default()
+        };
+
+        let embed = format_analysis_embed(&result, Locale::Fr);
+        let value = serde_json::to_value(embed).expect("serialize embed");
+        let fields = value["fields"].as_array().expect("embed fields");
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields[0]["name"], "💪 Points forts");
+        assert_eq!(fields[0]["value"], "• KP 65 %\n• 600 DPM");
+        assert_eq!(fields[1]["value"], "🔴 **CS/min** — 5,8 CS/min");
+        assert_eq!(fields[2]["name"], "🎯 Conseil de coach");
+        assert_eq!(
+            value.get("description"),
+            Some(&serde_json::json!("Lane perdue, bon mid game."))
+        );
+    }
+
+    #[test]
+    fn format_analysis_progress_embed_previews_the_summary() {
+        let engine = "gemma-4-26b (llamacpp)";
+        let waiting = format_analysis_progress_embed("Ahri", r#"{"rating":"#, engine, Locale::Fr);
+        let value = serde_json::to_value(waiting).expect("serialize embed");
+        assert_eq!(value["description"], "⏳ Analyse en cours…");
+        assert_eq!(
+            value["footer"]["text"],
+            "Propulsé par gemma-4-26b (llamacpp)"
+        );
+
+        let partial = r#"{"rating":"Good","summary":"Lane gagnée"#;
+        let streaming = format_analysis_progress_embed("Ahri", partial, engine, Locale::En);
+        let value = serde_json::to_value(streaming).expect("serialize embed");
+        assert_eq!(
+            value["description"],
+            "Lane gagnée\n\n⏳ Analysis in progress…"
+        );
+    }
+
     #[test]
     fn format_analysis_error_embed_sets_title_and_colour() {
-        let embed = format_analysis_error_embed("Summoner", "rate limited");
+        let embed = format_analysis_error_embed("Summoner", "rate limited", Locale::Fr);
         let value = serde_json::to_value(embed).expect("serialize embed");
         assert_eq!(
             value.get("title"),
@@ -132,9 +431,10 @@ mod tests {
             overall_rating: Some("Good".to_string()),
             summary: long_summary,
             error: None,
+            ..AnalysisResult::default()
         };
 
-        let embed = format_analysis_embed(&result);
+        let embed = format_analysis_embed(&result, Locale::Fr);
         let value = serde_json::to_value(embed).expect("serialize embed");
         let description = value
             .get("description")
@@ -143,4 +443,18 @@ mod tests {
         assert_eq!(description.len(), MAX_DESCRIPTION_LEN);
         assert!(description.ends_with("..."));
     }
+
+    #[test]
+    fn format_analysis_error_embed_follows_locale() {
+        let embed = format_analysis_error_embed("Summoner", "rate limited", Locale::En);
+        let value = serde_json::to_value(embed).expect("serialize embed");
+        assert_eq!(
+            value.get("title"),
+            Some(&serde_json::json!("📊 Game analysis — Summoner"))
+        );
+        assert_eq!(
+            value.get("description"),
+            Some(&serde_json::json!("⚠️ Analysis unavailable: rate limited"))
+        );
+    }
 }
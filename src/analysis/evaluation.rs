@@ -0,0 +1,417 @@
+//! Offline evaluation of the analysis prompts.
+//!
+//! A corpus of recorded games (`analysis_fixtures/*.json`), each with the
+//! rating range a sound coach may give it, is run through
+//! [`AnalysisPipeline`]. Each answer is checked for rating agreement and
+//! for the response format. Two prompt directories can then be compared
+//! for regressions against an LLM server. [`replay_backend`] replays the
+//! answers recorded in the fixtures instead, so a run needs no server; it
+//! checks the parsing and format pipeline only, as the recorded answers
+//! ignore the prompts.
+//!
+//! Driven by the `evaluate-prompts` tool.
+
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::analysis::llm::{LlmError, ScriptedBackend};
+use crate::analysis::models::{AnalysisData, AnalysisResult};
+use crate::analysis::pipeline::AnalysisPipeline;
+use crate::analysis::scoring::Grade;
+use crate::i18n::Locale;
+
+/// Sentences expected in the summary of an answer.
+pub const SUMMARY_SENTENCES: RangeInclusive<usize> = 3..=5;
+/// Frequent words of each language, to tell a French answer from an
+/// English one.
+const FRENCH_WORDS: &[&str] = &[
+    "le", "la", "les", "de", "des", "du", "et", "tu", "ton", "ta", "tes", "est", "une", "pour",
+    "pas", "avec", "sur", "mais",
+];
+const ENGLISH_WORDS: &[&str] = &[
+    "the", "and", "you", "your", "is", "of", "to", "with", "for", "was", "but",
+];
+
+#[derive(Debug, Error)]
+pub enum EvaluationError {
+    #[error("Fixture directory error: {0}")]
+    FixtureDirError(String),
+    #[error("Fixture file error: {0}")]
+    FixtureFileError(#[from] std::io::Error),
+    #[error("Invalid fixture {file}: {source}")]
+    InvalidFixture {
+        file: String,
+        source: serde_json::Error,
+    },
+}
+
+/// A recorded game of the corpus, with the ratings it may receive.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Fixture {
+    /// File name without extension.
+    #[serde(skip)]
+    pub name: String,
+    pub min_rating: Grade,
+    pub max_rating: Grade,
+    pub data: AnalysisData,
+    /// Answer replayed by [`replay_backend`]: a JSON object for a
+    /// structured answer, a string for free text.
+    #[serde(default)]
+    pub recorded_answer: Option<Value>,
+}
+
+impl Fixture {
+    fn recorded_text(&self) -> Option<String> {
+        match self.recorded_answer.as_ref()? {
+            Value::String(text) => Some(text.clone()),
+            answer => Some(answer.to_string()),
+        }
+    }
+}
+
+/// Loads every `*.json` fixture of `dir`, in file name order.
+pub fn load_fixtures(dir: &Path) -> Result<Vec<Fixture>, EvaluationError> {
+    if !dir.is_dir() {
+        return Err(EvaluationError::FixtureDirError(format!(
+            "Fixtures directory not found: {}",
+            dir.display()
+        )));
+    }
+    let mut paths: Vec<_> = fs::read_dir(dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let raw = fs::read_to_string(&path)?;
+            let mut fixture: Fixture =
+                serde_json::from_str(&raw).map_err(|source| EvaluationError::InvalidFixture {
+                    file: path.display().to_string(),
+                    source,
+                })?;
+            fixture.name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            Ok(fixture)
+        })
+        .collect()
+}
+
+/// Backend answering each fixture, in order, with its recorded answer;
+/// fixtures without one get a failed analysis. Evaluate the same fixtures
+/// with it, once.
+pub fn replay_backend(fixtures: &[Fixture]) -> ScriptedBackend {
+    fixtures
+        .iter()
+        .fold(ScriptedBackend::new(), |backend, fixture| {
+            match fixture.recorded_text() {
+                Some(text) => backend.reply(text),
+                None => backend.fail(LlmError::ApiError(format!(
+                    "no recorded answer for {}",
+                    fixture.name
+                ))),
+            }
+        })
+}
+
+/// How well an answer follows the response format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatCheck {
+    /// The summary reads as French.
+    pub french: bool,
+    /// Sentences in the summary.
+    pub sentences: usize,
+    /// A rating word could be read from the answer.
+    pub rating_word: bool,
+}
+
+impl FormatCheck {
+    pub fn of(result: &AnalysisResult) -> Self {
+        Self {
+            french: is_french(&result.summary),
+            sentences: count_sentences(&result.summary),
+            rating_word: result.overall_rating.is_some(),
+        }
+    }
+
+    pub fn passes(&self) -> bool {
+        self.french && SUMMARY_SENTENCES.contains(&self.sentences) && self.rating_word
+    }
+}
+
+/// Outcome of one fixture.
+#[derive(Debug, Clone)]
+pub struct CaseOutcome {
+    pub name: String,
+    pub min_rating: Grade,
+    pub max_rating: Grade,
+    /// Rating read from the answer; `None` when there was none.
+    pub rating: Option<Grade>,
+    pub format: FormatCheck,
+    /// Why the analysis failed, if it did.
+    pub error: Option<String>,
+}
+
+impl CaseOutcome {
+    /// The rating falls within the fixture's range.
+    pub fn agrees(&self) -> bool {
+        self.rating.is_some_and(|rating| {
+            (self.min_rating.points()..=self.max_rating.points()).contains(&rating.points())
+        })
+    }
+}
+
+/// Outcomes of a corpus run, in fixture order.
+#[derive(Debug, Clone, Default)]
+pub struct EvaluationReport {
+    pub cases: Vec<CaseOutcome>,
+}
+
+impl EvaluationReport {
+    /// Cases whose rating is within the expected range.
+    pub fn agreement(&self) -> usize {
+        self.cases.iter().filter(|case| case.agrees()).count()
+    }
+
+    /// Cases whose answer follows the response format.
+    pub fn format_adherence(&self) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| case.format.passes())
+            .count()
+    }
+
+    /// One line per case, then the totals.
+    pub fn render(&self) -> String {
+        let mut lines: Vec<String> = self
+            .cases
+            .iter()
+            .map(|case| {
+                let rating = case.rating.map_or("-", Grade::as_str);
+                let verdict = if case.agrees() { "ok" } else { "MISMATCH" };
+                let mut line = format!(
+                    "{:<28} {rating:<8} expected {}..{} {verdict:<8} {}",
+                    case.name,
+                    case.min_rating.as_str(),
+                    case.max_rating.as_str(),
+                    format_notes(&case.format),
+                );
+                if let Some(error) = &case.error {
+                    line.push_str(&format!(" (error: {error})"));
+                }
+                line
+            })
+            .collect();
+        lines.push(format!(
+            "Rating agreement: {}/{} · format adherence: {}/{}",
+            self.agreement(),
+            self.cases.len(),
+            self.format_adherence(),
+            self.cases.len()
+        ));
+        lines.join("\n")
+    }
+}
+
+/// A fixture `candidate` got wrong where `baseline` got it right.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regression {
+    pub name: String,
+    /// The rating left the expected range.
+    pub rating: bool,
+    /// The answer stopped following the response format.
+    pub format: bool,
+}
+
+/// Runs every fixture through `pipeline`, one at a time and in French, the
+/// language the format checks expect.
+pub async fn evaluate(pipeline: &AnalysisPipeline, fixtures: &[Fixture]) -> EvaluationReport {
+    let mut cases = Vec::with_capacity(fixtures.len());
+    for fixture in fixtures {
+        let result = pipeline.analyze_game(&fixture.data, Locale::Fr).await;
+        cases.push(CaseOutcome {
+            name: fixture.name.clone(),
+            min_rating: fixture.min_rating,
+            max_rating: fixture.max_rating,
+            rating: result.overall_rating.as_deref().and_then(Grade::parse),
+            format: FormatCheck::of(&result),
+            error: result.error,
+        });
+    }
+    EvaluationReport { cases }
+}
+
+/// Fixtures that pass in `baseline` and fail in `candidate`, matched by
+/// name.
+pub fn regressions(baseline: &EvaluationReport, candidate: &EvaluationReport) -> Vec<Regression> {
+    candidate
+        .cases
+        .iter()
+        .filter_map(|case| {
+            let before = baseline.cases.iter().find(|b| b.name == case.name)?;
+            let regression = Regression {
+                name: case.name.clone(),
+                rating: before.agrees() && !case.agrees(),
+                format: before.format.passes() && !case.format.passes(),
+            };
+            (regression.rating || regression.format).then_some(regression)
+        })
+        .collect()
+}
+
+fn format_notes(format: &FormatCheck) -> String {
+    if format.passes() {
+        return "format ok".to_string();
+    }
+    let mut notes = Vec::new();
+    if !format.french {
+        notes.push("not French".to_string());
+    }
+    if !SUMMARY_SENTENCES.contains(&format.sentences) {
+        notes.push(format!("{} sentences", format.sentences));
+    }
+    if !format.rating_word {
+        notes.push("no rating".to_string());
+    }
+    notes.join(", ")
+}
+
+/// More frequent French words than English ones.
+fn is_french(text: &str) -> bool {
+    let lowered = text.to_lowercase();
+    let words: Vec<&str> = lowered
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .collect();
+    let count = |list: &[&str]| words.iter().filter(|word| list.contains(word)).count();
+    let french = count(FRENCH_WORDS);
+    french > 0 && french > count(ENGLISH_WORDS)
+}
+
+/// Sentences ended by `.`, `!` or `?` (or by the end of the text); a
+/// decimal point is not an end.
+fn count_sentences(text: &str) -> usize {
+    let mut count = 0;
+    let mut open = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if matches!(c, '.' | '!' | '?') {
+            if open && chars.peek().is_none_or(|next| next.is_whitespace()) {
+                count += 1;
+                open = false;
+            }
+        } else if c.is_alphanumeric() {
+            open = true;
+        }
+    }
+    count + usize::from(open)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn outcome(name: &str, rating: Option<Grade>, sentences: usize) -> CaseOutcome {
+        CaseOutcome {
+            name: name.to_string(),
+            min_rating: Grade::Average,
+            max_rating: Grade::Good,
+            rating,
+            format: FormatCheck {
+                french: true,
+                sentences,
+                rating_word: rating.is_some(),
+            },
+            error: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn repo_corpus_replays_within_range_and_format() {
+        let fixtures = load_fixtures(Path::new("analysis_fixtures")).unwrap();
+        assert!(fixtures.len() >= 4);
+        let backend = Arc::new(replay_backend(&fixtures));
+        let pipeline = AnalysisPipeline::new(backend, "analysis_prompts").unwrap();
+
+        let report = evaluate(&pipeline, &fixtures).await;
+
+        assert_eq!(report.agreement(), fixtures.len(), "{}", report.render());
+        assert_eq!(
+            report.format_adherence(),
+            fixtures.len(),
+            "{}",
+            report.render()
+        );
+    }
+
+    #[tokio::test]
+    async fn fixtures_without_a_recorded_answer_fail() {
+        let mut fixtures = load_fixtures(Path::new("analysis_fixtures")).unwrap();
+        fixtures.truncate(1);
+        fixtures[0].recorded_answer = None;
+        let backend = Arc::new(replay_backend(&fixtures));
+        let pipeline = AnalysisPipeline::new(backend, "analysis_prompts").unwrap();
+
+        let report = evaluate(&pipeline, &fixtures).await;
+
+        assert!(report.cases[0].error.is_some());
+        assert!(!report.cases[0].agrees());
+        assert!(report.render().contains("MISMATCH"));
+    }
+
+    #[test]
+    fn format_check_reads_language_and_sentences() {
+        let french = "Belle lane : +25 CS à 10 min. Tu as pris 4 tours. Continue comme ça !";
+        assert!(is_french(french));
+        assert_eq!(count_sentences(french), 3);
+        assert!(!is_french(
+            "You won the lane and your CS was great. Keep it up."
+        ));
+        assert_eq!(count_sentences("4.7 CS/min, pas mal. Mais 11 morts"), 2);
+    }
+
+    #[test]
+    fn regressions_list_cases_the_candidate_breaks() {
+        let baseline = EvaluationReport {
+            cases: vec![
+                outcome("stomp", Some(Grade::Good), 4),
+                outcome("loss", Some(Grade::Poor), 4),
+                outcome("even", Some(Grade::Average), 4),
+            ],
+        };
+        let candidate = EvaluationReport {
+            cases: vec![
+                outcome("stomp", Some(Grade::Poor), 4),
+                outcome("loss", Some(Grade::Average), 4),
+                outcome("even", Some(Grade::Average), 8),
+            ],
+        };
+
+        assert_eq!(
+            regressions(&baseline, &candidate),
+            [
+                Regression {
+                    name: "stomp".to_string(),
+                    rating: true,
+                    format: false,
+                },
+                Regression {
+                    name: "even".to_string(),
+                    rating: false,
+                    format: true,
+                },
+            ]
+        );
+    }
+}
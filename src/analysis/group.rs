@@ -0,0 +1,198 @@
+//! Group analysis: one coach report for the tracked players of a team who
+//! played the same game, instead of one analysis each that contradict each
+//! other about the same fights.
+//!
+//! The notification processor merges the teammates' analysis jobs into one
+//! (see `Repository::group_analysis_jobs`); the worker then builds the
+//! combined payload here and asks the coach for a single report.
+
+use crate::analysis::history::record_history;
+use crate::analysis::models::{AnalysisData, AnalysisResult, GroupAnalysisData, TeamMember};
+use crate::analysis::pipeline::AnalysisPipeline;
+use crate::analysis::roles::ROLE_SPECS;
+use crate::analysis::scoring::score;
+use crate::db::repository::Repository;
+use crate::i18n::Locale;
+use crate::riot::models::{MatchScoreboard, ScoreboardPlayer};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// A tracked player of a group analysis.
+#[derive(Debug, Clone)]
+pub struct GroupMember {
+    pub riot_puuid: String,
+    pub data: AnalysisData,
+}
+
+/// Combined payload for `members`, with the match scoreboard as team
+/// context. Players and both teams are listed in role order. `None` when
+/// no member is on the scoreboard.
+pub fn group_data(
+    members: &[GroupMember],
+    scoreboard: &MatchScoreboard,
+) -> Option<GroupAnalysisData> {
+    let is_tracked =
+        |player: &ScoreboardPlayer| members.iter().any(|m| m.riot_puuid == player.puuid);
+    let team_id = scoreboard.players.iter().find(|p| is_tracked(p))?.team_id;
+
+    let mut players: Vec<AnalysisData> = members.iter().map(|m| m.data.clone()).collect();
+    players.sort_by_key(|data| role_rank(&data.role));
+    // Memory is per player; the group report is about this game only.
+    for data in &mut players {
+        data.recent_games = Vec::new();
+    }
+
+    let team = |on_team: bool| {
+        let mut lines: Vec<TeamMember> = scoreboard
+            .players
+            .iter()
+            .filter(|p| (p.team_id == team_id) == on_team)
+            .map(|p| TeamMember {
+                name: p.display_name.clone(),
+                champion_name: p.champion_name.clone(),
+                role: p.position.clone(),
+                kills: p.kills,
+                deaths: p.deaths,
+                assists: p.assists,
+                total_cs: p.total_cs,
+                gold_earned: p.gold_earned,
+                total_damage: p.total_damage,
+                vision_score: p.vision_score,
+                tracked: is_tracked(p),
+            })
+            .collect();
+        lines.sort_by_key(|line| role_rank(&line.role));
+        lines
+    };
+
+    Some(GroupAnalysisData {
+        win: players.first().is_some_and(|data| data.win),
+        game_duration_secs: scoreboard.game_duration_secs,
+        game_mode: scoreboard.game_mode.clone(),
+        players,
+        allies: team(true),
+        enemies: team(false),
+    })
+}
+
+/// Runs the group report, then stores each member's snapshot in their
+/// history, rated by the stats-based verdict since the report rates the
+/// group. With `progress`, the answer text is streamed to it.
+pub async fn analyze_group<D: Repository + ?Sized>(
+    repository: &D,
+    pipeline: &AnalysisPipeline,
+    data: &GroupAnalysisData,
+    members: &[GroupMember],
+    match_id: &str,
+    locale: Locale,
+    progress: Option<UnboundedSender<String>>,
+) -> AnalysisResult {
+    let key = pipeline.group_cache_key(locale);
+    let result = pipeline.analyze_group(data, locale, progress).await;
+
+    if result.error.is_none() {
+        for member in members {
+            let rating = score(&member.data).map(|verdict| verdict.rating.as_str().to_string());
+            record_history(
+                repository,
+                &member.data,
+                &member.riot_puuid,
+                match_id,
+                rating,
+                key.as_ref(),
+                None,
+            )
+            .await;
+        }
+    }
+
+    result
+}
+
+/// Position of `role` in the usual top-to-support order; unknown roles last.
+fn role_rank(role: &str) -> usize {
+    ROLE_SPECS
+        .iter()
+        .position(|spec| spec.riot_role == role)
+        .unwrap_or(ROLE_SPECS.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(puuid: &str, champion: &str, role: &str) -> GroupMember {
+        GroupMember {
+            riot_puuid: puuid.to_string(),
+            data: AnalysisData {
+                summoner_name: format!("{puuid}#EUW"),
+                champion_name: champion.to_string(),
+                win: true,
+                ..AnalysisData::sample(role)
+            },
+        }
+    }
+
+    fn player(puuid: &str, team_id: u16, position: &str) -> ScoreboardPlayer {
+        ScoreboardPlayer {
+            puuid: puuid.to_string(),
+            display_name: format!("{puuid}#EUW"),
+            participant_id: 0,
+            team_id,
+            position: position.to_string(),
+            champion_name: "Garen".to_string(),
+            win: team_id == 200,
+            kills: 0,
+            deaths: 0,
+            assists: 0,
+            total_cs: 0,
+            gold_earned: 0,
+            total_damage: 0,
+            vision_score: 0,
+        }
+    }
+
+    fn scoreboard() -> MatchScoreboard {
+        MatchScoreboard {
+            match_id: "EUW1_1".to_string(),
+            game_mode: "CLASSIC".to_string(),
+            queue_id: Some(420),
+            game_duration_secs: 1800,
+            players: vec![
+                player("blue-mid", 100, "MIDDLE"),
+                player("red-bot", 200, "BOTTOM"),
+                player("red-top", 200, "TOP"),
+                player("red-sup", 200, "UTILITY"),
+                player("blue-top", 100, "TOP"),
+            ],
+        }
+    }
+
+    #[test]
+    fn lists_players_and_teams_in_role_order() {
+        let members = [
+            member("red-sup", "Thresh", "UTILITY"),
+            member("red-bot", "Jinx", "BOTTOM"),
+        ];
+        let data = group_data(&members, &scoreboard()).unwrap();
+
+        assert!(data.win);
+        assert_eq!(data.champion_names(), "Jinx · Thresh");
+        let allies: Vec<_> = data
+            .allies
+            .iter()
+            .map(|m| (m.role.as_str(), m.tracked))
+            .collect();
+        assert_eq!(
+            allies,
+            [("TOP", false), ("BOTTOM", true), ("UTILITY", true)]
+        );
+        let enemies: Vec<_> = data.enemies.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(enemies, ["TOP", "MIDDLE"]);
+    }
+
+    #[test]
+    fn members_missing_from_the_scoreboard_have_no_group() {
+        let members = [member("someone-else", "Jinx", "BOTTOM")];
+        assert!(group_data(&members, &scoreboard()).is_none());
+    }
+}
@@ -5,24 +5,36 @@
 //! snapshots are summarized into `AnalysisData::recent_games` so the coach
 //! can comment on progression across games ("your early CS improved since
 //! last game").
+//!
+//! The snapshot also keeps the coach's full answer with its [`CacheKey`], so
+//! an interactive rerun of the same match is served from cache.
 
+use crate::analysis::classes::ChampionClass;
 use crate::analysis::models::{AnalysisData, AnalysisResult, RecentGameSummary};
-use crate::analysis::pipeline::AnalysisPipeline;
+use crate::analysis::pipeline::{AnalysisPipeline, CacheKey};
+use crate::analysis::roles::TierBand;
+use crate::analysis::scoring::stats_result;
 use crate::db::models::{AnalysisHistoryEntry, NewAnalysisHistory};
 use crate::db::repository::Repository;
+use crate::i18n::Locale;
+use crate::riot::client::{RiotApiClient, RiotClient};
+use tokio::sync::mpsc::UnboundedSender;
 
 /// How many previous games are surfaced to the coach.
 pub const RECENT_GAMES_LIMIT: i64 = 5;
 
 /// Fetches the player's recent history, runs the analysis with it, then
 /// stores this game's snapshot. History failures are logged and degrade to
-/// a memory-less analysis — they never block the analysis itself.
+/// a memory-less analysis — they never block the analysis itself. With
+/// `progress`, the answer text is streamed to it while it is generated.
 pub async fn analyze_with_memory<D: Repository + ?Sized>(
     repository: &D,
     pipeline: &AnalysisPipeline,
     mut data: AnalysisData,
     riot_puuid: &str,
     match_id: &str,
+    locale: Locale,
+    progress: Option<UnboundedSender<String>>,
 ) -> AnalysisResult {
     // Fetch one extra row: the current match may be among the most recent
     // snapshots (e.g. /analyze-last-game reruns) and is excluded below.
@@ -37,33 +49,239 @@ pub async fn analyze_with_memory<D: Repository + ?Sized>(
         ),
     }
 
-    let result = pipeline.analyze_game(&data).await;
+    let cache_key = pipeline.cache_key(&data, locale);
+    let result = match progress {
+        Some(progress) => {
+            pipeline
+                .analyze_game_streaming(&data, locale, progress)
+                .await
+        }
+        None => pipeline.analyze_game(&data, locale).await,
+    };
 
     if result.error.is_none() {
-        match snapshot_json(&data) {
-            Ok(json) => {
-                let entry = NewAnalysisHistory {
-                    riot_puuid: riot_puuid.to_string(),
-                    match_id: match_id.to_string(),
-                    role: data.role.clone(),
-                    champion_name: data.champion_name.clone(),
-                    win: data.win,
-                    overall_rating: result.overall_rating.clone(),
-                    analysis_data: json,
-                };
-                if let Err(error) = repository.insert_analysis_history(&entry).await {
-                    tracing::warn!(error = %error, "Failed to store analysis history");
-                }
-            }
-            Err(error) => {
-                tracing::warn!(error = %error, "Failed to serialize analysis snapshot")
-            }
-        }
+        record_history(
+            repository,
+            &data,
+            riot_puuid,
+            match_id,
+            result.overall_rating.clone(),
+            Some(&cache_key),
+            Some(&result),
+        )
+        .await;
     }
 
     result
 }
 
+/// The answer stored for `match_id` when it was generated with the prompt
+/// and model `pipeline` would use now for `data`. Lookup failures and
+/// unreadable rows count as a miss.
+pub async fn cached_analysis<D: Repository + ?Sized>(
+    repository: &D,
+    pipeline: &AnalysisPipeline,
+    data: &AnalysisData,
+    riot_puuid: &str,
+    match_id: &str,
+    locale: Locale,
+) -> Option<AnalysisResult> {
+    let entry = match repository
+        .get_analysis_history_entry(riot_puuid, match_id)
+        .await
+    {
+        Ok(entry) => entry?,
+        Err(error) => {
+            tracing::warn!(error = %error, "Failed to look up the cached analysis");
+            return None;
+        }
+    };
+    let key = pipeline.cache_key(data, locale);
+    if entry.prompt_hash.as_deref() != Some(key.prompt_hash.as_str())
+        || entry.model.as_deref() != Some(key.model.as_str())
+    {
+        return None;
+    }
+    match serde_json::from_str::<AnalysisResult>(entry.analysis_result.as_deref()?) {
+        Ok(result) => Some(AnalysisResult {
+            cached: true,
+            engine: Some(pipeline.engine()),
+            ..result
+        }),
+        Err(error) => {
+            tracing::warn!(
+                match_id,
+                error = %error,
+                "Skipping unparseable cached analysis"
+            );
+            None
+        }
+    }
+}
+
+/// Fills what `data` needs from outside the match to pick the benchmarks:
+/// the player's tier band and their champion's class.
+pub async fn add_benchmark_context<D, R>(
+    repository: &D,
+    riot_client: &R,
+    data: &mut AnalysisData,
+    riot_puuid: &str,
+    region: &str,
+) where
+    D: Repository + ?Sized,
+    R: RiotApiClient + ?Sized,
+{
+    data.tier_band = resolve_tier_band(repository, riot_client, riot_puuid, region).await;
+    data.champion_class = resolve_champion_class(repository, data).await;
+}
+
+/// Class of the champion played in `data`, from the cached Data Dragon tags
+/// and the local overrides.
+pub async fn resolve_champion_class<D: Repository + ?Sized>(
+    repository: &D,
+    data: &AnalysisData,
+) -> Option<ChampionClass> {
+    let tags = match repository.get_champion_by_id(data.champion_id).await {
+        Ok(champion) => champion.map(|c| c.tags).unwrap_or_default(),
+        Err(error) => {
+            tracing::warn!(error = %error, "Failed to fetch champion tags");
+            Vec::new()
+        }
+    };
+    ChampionClass::resolve(&data.champion_name, &tags)
+}
+
+/// Tier band `riot_puuid`'s games are graded against: the tracked
+/// summoner's `/benchmark-tier` override, else the band of their Solo/Duo
+/// tier. Unranked players and failed lookups get the default band.
+pub async fn resolve_tier_band<D, R>(
+    repository: &D,
+    riot_client: &R,
+    riot_puuid: &str,
+    region: &str,
+) -> TierBand
+where
+    D: Repository + ?Sized,
+    R: RiotApiClient + ?Sized,
+{
+    let benchmark_tier = match repository.get_summoner_by_puuid(riot_puuid).await {
+        Ok(summoner) => summoner.and_then(|s| s.benchmark_tier),
+        Err(error) => {
+            tracing::warn!(error = %error, "Failed to fetch benchmark tier override");
+            None
+        }
+    };
+    if let Some(band) = benchmark_tier.as_deref().and_then(TierBand::parse) {
+        return band;
+    }
+
+    let platform = RiotClient::platform_for_region(region);
+    match riot_client
+        .get_ranked_solo_entry(riot_puuid, platform)
+        .await
+    {
+        Ok(entry) => entry
+            .and_then(|entry| TierBand::from_tier(&entry.tier))
+            .unwrap_or_default(),
+        Err(error) => {
+            tracing::warn!(error = %error, "Failed to fetch ranked tier for benchmarks");
+            TierBand::default()
+        }
+    }
+}
+
+/// Analysis requested interactively (slash command, button): runs the coach
+/// when it is configured, and answers with the stats-based verdict when it
+/// is not or when it fails — there is no later retry to wait for. Unless
+/// `force` is set, an answer cached for the same prompt and model is served
+/// instead of asking the coach again.
+pub async fn analyze_or_score<D: Repository + ?Sized>(
+    repository: &D,
+    pipeline: Option<&AnalysisPipeline>,
+    data: AnalysisData,
+    riot_puuid: &str,
+    match_id: &str,
+    locale: Locale,
+    force: bool,
+) -> AnalysisResult {
+    let fallback = stats_result(&data, locale);
+    let Some(pipeline) = pipeline else {
+        return fallback.unwrap_or_else(|| AnalysisResult {
+            summoner_name: data.summoner_name,
+            champion_name: data.champion_name,
+            error: Some("no AI coach configured and no benchmarks for this role".to_string()),
+            ..AnalysisResult::default()
+        });
+    };
+
+    if !force
+        && let Some(cached) =
+            cached_analysis(repository, pipeline, &data, riot_puuid, match_id, locale).await
+    {
+        tracing::info!(match_id, "Serving the cached analysis");
+        return cached;
+    }
+
+    let result = analyze_with_memory(
+        repository, pipeline, data, riot_puuid, match_id, locale, None,
+    )
+    .await;
+    match (&result.error, fallback) {
+        (Some(error), Some(fallback)) => {
+            tracing::warn!(
+                match_id,
+                error = error.as_str(),
+                "Analysis failed, answering with the stats-based verdict"
+            );
+            fallback
+        }
+        _ => result,
+    }
+}
+
+/// Stores this game's snapshot in the player's history, with the `key` of
+/// the prompt and model the coach answered, and the coach's `answer` when it
+/// can be served again. Best-effort: a failure is only logged.
+pub async fn record_history<D: Repository + ?Sized>(
+    repository: &D,
+    data: &AnalysisData,
+    riot_puuid: &str,
+    match_id: &str,
+    overall_rating: Option<String>,
+    key: Option<&CacheKey>,
+    answer: Option<&AnalysisResult>,
+) {
+    let analysis_result = answer.and_then(|result| match serde_json::to_string(result) {
+        Ok(json) => Some(json),
+        Err(error) => {
+            tracing::warn!(error = %error, "Failed to serialize the analysis for caching");
+            None
+        }
+    });
+    match snapshot_json(data) {
+        Ok(json) => {
+            let entry = NewAnalysisHistory {
+                riot_puuid: riot_puuid.to_string(),
+                match_id: match_id.to_string(),
+                role: data.role.clone(),
+                champion_name: data.champion_name.clone(),
+                win: data.win,
+                overall_rating,
+                analysis_data: json,
+                analysis_result,
+                prompt_hash: key.map(|key| key.prompt_hash.clone()),
+                model: key.map(|key| key.model.clone()),
+            };
+            if let Err(error) = repository.insert_analysis_history(&entry).await {
+                tracing::warn!(error = %error, "Failed to store analysis history");
+            }
+        }
+        Err(error) => {
+            tracing::warn!(error = %error, "Failed to serialize analysis snapshot")
+        }
+    }
+}
+
 /// Maps stored history rows to prompt-ready summaries, most recent first.
 /// The current match is excluded (relevant when a game is re-analyzed via
 /// `/analyze-last-game`); unparseable snapshots are skipped with a warning.
@@ -120,6 +338,7 @@ fn cs_per_minute(total_cs: i32, duration_secs: i32) -> Option<f32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analysis::roles::TierBand;
     use chrono::Utc;
     use uuid::Uuid;
 
@@ -127,6 +346,8 @@ mod tests {
         AnalysisData {
             summoner_name: "Test#EUW".to_string(),
             champion_name: "Gangplank".to_string(),
+            champion_id: 0,
+            champion_class: None,
             win: true,
             kills: 10,
             deaths: 8,
@@ -157,10 +378,16 @@ mod tests {
             cs_diff_at_10: Some(-22),
             cs_diff_at_15: Some(-38),
             cs_diff_at_20: Some(-52),
+            death_events: Vec::new(),
+            death_summary: None,
+            objective_events: Vec::new(),
+            objective_summary: None,
+            jungle: None,
             game_duration_secs: 1800,
             role: "TOP".to_string(),
             game_mode: "CLASSIC".to_string(),
             recent_games: Vec::new(),
+            tier_band: TierBand::default(),
         }
     }
 
@@ -174,6 +401,9 @@ mod tests {
             win: data.win,
             overall_rating: Some("Average".to_string()),
             analysis_data: snapshot_json(data).unwrap(),
+            analysis_result: None,
+            prompt_hash: None,
+            model: None,
             created_at: Utc::now(),
         }
     }
@@ -234,4 +464,72 @@ mod tests {
         let summaries = summaries_from_entries(&entries, "");
         assert_eq!(summaries.len(), RECENT_GAMES_LIMIT as usize);
     }
+
+    #[cfg(feature = "test-mocks")]
+    #[tokio::test]
+    async fn reruns_are_served_from_cache_unless_forced() {
+        use crate::analysis::llm::ScriptedBackend;
+        use crate::db::repository::MockRepository;
+        use std::sync::Arc;
+
+        let backend = Arc::new(ScriptedBackend::new().reply("**Note globale : Poor**"));
+        let pipeline = AnalysisPipeline::new(backend.clone(), "analysis_prompts").unwrap();
+        let data = sample_data();
+        let key = pipeline.cache_key(&data, Locale::Fr);
+        let cached = AnalysisResult {
+            summoner_name: data.summoner_name.clone(),
+            champion_name: data.champion_name.clone(),
+            overall_rating: Some("Good".to_string()),
+            summary: "Déjà analysée".to_string(),
+            ..AnalysisResult::default()
+        };
+        let mut entry = entry_for("EUW1_1", &data);
+        entry.analysis_result = Some(serde_json::to_string(&cached).unwrap());
+        entry.prompt_hash = Some(key.prompt_hash.clone());
+        entry.model = Some(key.model.clone());
+
+        let mut repository = MockRepository::new();
+        repository
+            .expect_get_analysis_history_entry()
+            .returning(move |_, _| Ok(Some(entry.clone())));
+        repository
+            .expect_get_recent_analysis_history()
+            .returning(|_, _| Ok(Vec::new()));
+        repository
+            .expect_insert_analysis_history()
+            .withf(|entry| entry.analysis_result.is_some() && entry.prompt_hash.is_some())
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let result = analyze_or_score(
+            &repository,
+            Some(&pipeline),
+            data.clone(),
+            "puuid",
+            "EUW1_1",
+            Locale::Fr,
+            false,
+        )
+        .await;
+        assert!(result.cached);
+        assert_eq!(result.summary, "Déjà analysée");
+        assert!(backend.prompts().is_empty());
+
+        // Another language is another prompt.
+        assert_ne!(pipeline.cache_key(&data, Locale::En), key);
+
+        let result = analyze_or_score(
+            &repository,
+            Some(&pipeline),
+            data,
+            "puuid",
+            "EUW1_1",
+            Locale::Fr,
+            true,
+        )
+        .await;
+        assert!(!result.cached);
+        assert_eq!(result.overall_rating.as_deref(), Some("Poor"));
+        assert_eq!(backend.prompts().len(), 1);
+    }
 }
@@ -0,0 +1,127 @@
+//! Status view of the analysis queue (`analysis_jobs`), used by
+//! `/analysis-jobs`.
+
+use crate::analysis::worker::MAX_ATTEMPTS;
+use crate::db::models::{AnalysisJob, Summoner};
+use crate::i18n::{Locale, render};
+use crate::notification::dead_letters::truncate;
+use chrono::{DateTime, Utc};
+
+/// Rows listed before the rest is collapsed into a count, keeping the
+/// reply under Discord's 2000-character message limit.
+const MAX_LISTED: usize = 12;
+const MAX_ERROR_CHARS: usize = 80;
+const SHORT_ID_LEN: usize = 8;
+
+pub fn format_analysis_jobs(
+    jobs: &[AnalysisJob],
+    summoners: &[Summoner],
+    now: DateTime<Utc>,
+    locale: Locale,
+) -> String {
+    let text = locale.catalog();
+    if jobs.is_empty() {
+        return text.analysis_jobs_empty.to_string();
+    }
+
+    let mut lines = vec![render(
+        text.analysis_jobs_header,
+        &[("count", &jobs.len().to_string())],
+    )];
+    for job in jobs.iter().take(MAX_LISTED) {
+        let name = summoners
+            .iter()
+            .find(|s| s.id == job.summoner_id)
+            .map_or(text.unknown, |s| s.game_name.as_str());
+        let status = match job.status.as_str() {
+            "running" => text.job_running,
+            "failed" => text.job_failed,
+            _ => text.job_pending,
+        };
+
+        let mut line = format!(
+            "`{}` · {} · {} · {}",
+            &job.id.to_string()[..SHORT_ID_LEN],
+            name,
+            job.match_id,
+            status
+        );
+        if job.attempts > 0 {
+            line.push_str(" · ");
+            line.push_str(&render(
+                text.job_attempts,
+                &[
+                    ("attempts", &job.attempts.to_string()),
+                    ("max", &MAX_ATTEMPTS.to_string()),
+                ],
+            ));
+        }
+        if job.status == "pending" && job.next_attempt_at > now {
+            line.push_str(&format!(" · <t:{}:R>", job.next_attempt_at.timestamp()));
+        }
+        if let Some(error) = &job.error_message {
+            line.push_str(" — ");
+            line.push_str(&truncate(error, MAX_ERROR_CHARS));
+        }
+        lines.push(line);
+    }
+    if jobs.len() > MAX_LISTED {
+        lines.push(render(
+            text.list_more,
+            &[("count", &(jobs.len() - MAX_LISTED).to_string())],
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn job(status: &str, attempts: i32, error: Option<&str>) -> AnalysisJob {
+        AnalysisJob {
+            id: Uuid::parse_str("5e6f7a8b-0000-4000-8000-000000000001").unwrap(),
+            summoner_id: Uuid::nil(),
+            match_id: "EUW1_42".to_string(),
+            created_at: Utc::now(),
+            completed_at: None,
+            status: status.to_string(),
+            attempts,
+            next_attempt_at: Utc::now() + chrono::Duration::minutes(1),
+            started_at: None,
+            error_message: error.map(str::to_string),
+            group_summoner_ids: Vec::new(),
+            group_done_summoner_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn lists_status_attempts_and_next_retry() {
+        let jobs = [
+            job("running", 1, None),
+            job("pending", 1, Some("LLM request timed out")),
+        ];
+        let listing = format_analysis_jobs(&jobs, &[], Utc::now(), Locale::En);
+
+        let mut lines = listing.lines();
+        assert_eq!(lines.next(), Some("🧠 **Analysis queue (2)**:"));
+        assert_eq!(
+            lines.next(),
+            Some("`5e6f7a8b` · Unknown · EUW1_42 · 🔄 running · attempt 1/3")
+        );
+        let retry = lines.next().unwrap();
+        assert!(
+            retry.starts_with("`5e6f7a8b` · Unknown · EUW1_42 · ⏳ waiting · attempt 1/3 · <t:")
+        );
+        assert!(retry.ends_with(":R> — LLM request timed out"));
+    }
+
+    #[test]
+    fn empty_queue_says_so() {
+        assert_eq!(
+            format_analysis_jobs(&[], &[], Utc::now(), Locale::Fr),
+            "✅ Aucune analyse en attente."
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! Placeholder message edited while the coach's answer streams in, so the
+//! channel sees progress during the minutes a local model takes instead of
+//! nothing until the analysis is done.
+
+use crate::analysis::discord::format_analysis_progress_embed;
+use crate::i18n::Locale;
+use serenity::builder::{CreateEmbed, CreateMessage, EditMessage};
+use serenity::http::Http;
+use serenity::model::id::{ChannelId, MessageId};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::time::Instant;
+
+/// Minimum time between two edits of the placeholder. Discord allows five
+/// message edits per five seconds in a channel, and several analyses can
+/// stream into the same channel at once.
+const EDIT_INTERVAL: Duration = Duration::from_secs(3);
+
+pub struct LiveAnalysisMessage {
+    http: Arc<Http>,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    champion_name: String,
+    engine: String,
+    locale: Locale,
+}
+
+impl LiveAnalysisMessage {
+    /// Posts the "analysis in progress" placeholder.
+    pub async fn post(
+        http: Arc<Http>,
+        channel_id: ChannelId,
+        champion_name: &str,
+        engine: &str,
+        locale: Locale,
+    ) -> serenity::Result<Self> {
+        let embed = format_analysis_progress_embed(champion_name, "", engine, locale);
+        let message = channel_id
+            .send_message(&http, CreateMessage::new().embed(embed))
+            .await?;
+        Ok(Self {
+            http,
+            channel_id,
+            message_id: message.id,
+            champion_name: champion_name.to_string(),
+            engine: engine.to_string(),
+            locale,
+        })
+    }
+
+    /// Shows the answer received on `chunks` until the sender is dropped,
+    /// editing at most once per [`EDIT_INTERVAL`]. Failed edits are only
+    /// logged: the final edit replaces the preview anyway.
+    pub async fn follow(&self, mut chunks: UnboundedReceiver<String>) {
+        let mut answer = String::new();
+        let mut last_edit = Instant::now();
+        while let Some(chunk) = chunks.recv().await {
+            answer.push_str(&chunk);
+            if last_edit.elapsed() < EDIT_INTERVAL {
+                continue;
+            }
+            last_edit = Instant::now();
+            let embed = format_analysis_progress_embed(
+                &self.champion_name,
+                &answer,
+                &self.engine,
+                self.locale,
+            );
+            if let Err(e) = self.edit(embed).await {
+                tracing::warn!("Failed to update live analysis message: {}", e);
+            }
+        }
+    }
+
+    /// Replaces the placeholder with the finished analysis.
+    pub async fn finish(self, embed: CreateEmbed) -> serenity::Result<()> {
+        self.edit(embed).await
+    }
+
+    /// Deletes the placeholder of an attempt that failed, so a retry does
+    /// not leave it behind. Best-effort.
+    pub async fn discard(self) {
+        if let Err(e) = self
+            .channel_id
+            .delete_message(&self.http, self.message_id)
+            .await
+        {
+            tracing::warn!("Failed to delete live analysis message: {}", e);
+        }
+    }
+
+    async fn edit(&self, embed: CreateEmbed) -> serenity::Result<()> {
+        self.channel_id
+            .edit_message(&self.http, self.message_id, EditMessage::new().embed(embed))
+            .await
+            .map(|_| ())
+    }
+}
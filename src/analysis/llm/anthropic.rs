@@ -0,0 +1,131 @@
+//! Anthropic-style `/messages` backend. `LLM_BASE_URL` is the API root
+//! including the version segment, e.g. `https://api.anthropic.com/v1`.
+
+use super::{
+    LlmBackend, LlmError, MAX_TOKENS, TEMPERATURE, endpoint, http_client, non_empty,
+    send_with_retry,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const API_VERSION: &str = "2023-06-01";
+
+#[derive(Clone)]
+pub struct AnthropicBackend {
+    client: reqwest::Client,
+    api_key: String,
+    model: String,
+    base_url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct MessagesRequest<'a> {
+    model: &'a str,
+    max_tokens: u32,
+    temperature: f32,
+    messages: Vec<Message<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct Message<'a> {
+    role: &'static str,
+    content: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessagesResponse {
+    content: Vec<ContentBlock>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContentBlock {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    text: String,
+}
+
+impl AnthropicBackend {
+    pub fn new(api_key: String, base_url: String, model: String) -> Result<Self, LlmError> {
+        Ok(Self {
+            client: http_client()?,
+            api_key,
+            model,
+            base_url,
+        })
+    }
+}
+
+#[async_trait]
+impl LlmBackend for AnthropicBackend {
+    fn name(&self) -> &'static str {
+        "anthropic"
+    }
+
+    fn model(&self) -> Option<String> {
+        Some(self.model.clone())
+    }
+
+    /// The Messages API has no schema-constrained decoding; the JSON shape
+    /// comes from the prompt alone.
+    async fn complete(
+        &self,
+        prompt: &str,
+        _schema: Option<&'static Value>,
+    ) -> Result<String, LlmError> {
+        let request_body = MessagesRequest {
+            model: &self.model,
+            max_tokens: MAX_TOKENS,
+            temperature: TEMPERATURE,
+            messages: vec![Message {
+                role: "user",
+                content: prompt,
+            }],
+        };
+
+        let url = endpoint(&self.base_url, "messages");
+        let response = send_with_retry(|| {
+            self.client
+                .post(&url)
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", API_VERSION)
+                .json(&request_body)
+        })
+        .await?;
+
+        let parsed: MessagesResponse = response.json().await.map_err(LlmError::HttpError)?;
+        extract_text(parsed)
+    }
+}
+
+/// Concatenates the text blocks; thinking and tool blocks are skipped.
+fn extract_text(parsed: MessagesResponse) -> Result<String, LlmError> {
+    let text: String = parsed
+        .content
+        .into_iter()
+        .filter(|block| block.kind == "text")
+        .map(|block| block.text)
+        .collect();
+    non_empty(Some(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_only_text_blocks() {
+        let parsed: MessagesResponse = serde_json::from_str(
+            r#"{"content":[{"type":"thinking","thinking":"..."},{"type":"text","text":"Solid game"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(extract_text(parsed).unwrap(), "Solid game");
+    }
+
+    #[test]
+    fn empty_answer_is_a_parse_error() {
+        let parsed: MessagesResponse = serde_json::from_str(r#"{"content":[]}"#).unwrap();
+        assert!(matches!(extract_text(parsed), Err(LlmError::ParseError(_))));
+    }
+}
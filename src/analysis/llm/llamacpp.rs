@@ -0,0 +1,103 @@
+//! llama.cpp's raw `/completion` backend: the prompt is sent as-is, without
+//! a chat template, for models served without one.
+
+use super::{
+    LlmBackend, LlmError, MAX_TOKENS, TEMPERATURE, endpoint, http_client, non_empty,
+    send_with_retry,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Clone)]
+pub struct LlamaCppBackend {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CompletionRequest<'a> {
+    prompt: &'a str,
+    n_predict: u32,
+    temperature: f32,
+    /// Converted by the server into a sampling grammar.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    json_schema: Option<&'a Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionResponse {
+    content: Option<String>,
+}
+
+impl LlamaCppBackend {
+    /// `base_url` is the server root (`http://host:8080`). The `/v1` suffix
+    /// of the OpenAI-compatible API is dropped, so `LLM_BASE_URL` can point
+    /// at the same server whichever backend is selected.
+    pub fn new(base_url: String) -> Result<Self, LlmError> {
+        Ok(Self {
+            client: http_client()?,
+            base_url: server_root(&base_url).to_string(),
+        })
+    }
+}
+
+/// Strips the OpenAI-compatible `/v1` suffix: `/completion` lives at the
+/// root of the llama.cpp server.
+fn server_root(base_url: &str) -> &str {
+    let base_url = base_url.trim_end_matches('/');
+    base_url.strip_suffix("/v1").unwrap_or(base_url)
+}
+
+#[async_trait]
+impl LlmBackend for LlamaCppBackend {
+    fn name(&self) -> &'static str {
+        "llamacpp"
+    }
+
+    async fn complete(
+        &self,
+        prompt: &str,
+        schema: Option<&'static Value>,
+    ) -> Result<String, LlmError> {
+        let request_body = CompletionRequest {
+            prompt,
+            n_predict: MAX_TOKENS,
+            temperature: TEMPERATURE,
+            json_schema: schema,
+        };
+
+        let url = endpoint(&self.base_url, "completion");
+        let response = send_with_retry(|| self.client.post(&url).json(&request_body)).await?;
+
+        let parsed: CompletionResponse = response.json().await.map_err(LlmError::HttpError)?;
+        non_empty(parsed.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_uses_llama_cpp_field_names() {
+        let request = CompletionRequest {
+            prompt: "Hello",
+            n_predict: 128,
+            temperature: 0.35,
+            json_schema: None,
+        };
+        let value = serde_json::to_value(&request).unwrap();
+        assert_eq!(value["prompt"], "Hello");
+        assert_eq!(value["n_predict"], 128);
+        assert!(value.get("max_tokens").is_none());
+        assert!(value.get("json_schema").is_none());
+    }
+
+    #[test]
+    fn openai_base_url_is_reduced_to_the_server_root() {
+        assert_eq!(server_root("http://jarvis:8080/v1"), "http://jarvis:8080");
+        assert_eq!(server_root("http://jarvis:8080/v1/"), "http://jarvis:8080");
+        assert_eq!(server_root("http://jarvis:8080"), "http://jarvis:8080");
+    }
+}
@@ -0,0 +1,336 @@
+//! LLM servers the analysis pipeline can talk to. Every backend takes the
+//! fully composed prompt and returns the model's answer text; the pipeline
+//! never depends on a particular wire format.
+
+pub mod anthropic;
+pub mod llamacpp;
+pub mod ollama;
+pub mod openai;
+pub mod scripted;
+
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::time::sleep;
+
+pub use anthropic::AnthropicBackend;
+pub use llamacpp::LlamaCppBackend;
+pub use ollama::OllamaBackend;
+pub use openai::OpenAiBackend;
+pub use scripted::ScriptedBackend;
+
+const MAX_ATTEMPTS: usize = 3;
+// The local Gemma 4 server generates ~30 tokens/s and spends part of the
+// budget on reasoning tokens before the visible answer, so both the token
+// budget and the HTTP timeout are much larger than a hosted-API setup.
+const MAX_TOKENS: u32 = 4096;
+const REQUEST_TIMEOUT_SECS: u64 = 300;
+// Low temperature: ratings must be stable across reruns of similar games;
+// this is evaluation, not creative writing.
+const TEMPERATURE: f32 = 0.35;
+
+#[derive(Debug, Error)]
+pub enum LlmError {
+    #[error("HTTP error: {0}")]
+    HttpError(#[from] reqwest::Error),
+    #[error("LLM API error: {0}")]
+    ApiError(String),
+    #[error("LLM parse error: {0}")]
+    ParseError(String),
+    #[error("LLM API rate limited")]
+    RateLimited,
+    #[error("LLM API request timed out")]
+    Timeout,
+    #[error("LLM_API_KEY is required by the {0} backend")]
+    MissingApiKey(&'static str),
+}
+
+#[cfg_attr(feature = "test-mocks", mockall::automock)]
+#[async_trait]
+pub trait LlmBackend: Send + Sync {
+    /// Short name used in logs.
+    fn name(&self) -> &'static str;
+
+    /// Model requested from the server, recorded with each analysis.
+    /// `None` for servers that serve a single model.
+    fn model(&self) -> Option<String> {
+        None
+    }
+
+    /// Sends `prompt` as a single user turn and returns the answer text.
+    /// With a `schema`, servers that support constrained decoding are asked
+    /// for JSON matching it; the others rely on the prompt's instructions.
+    async fn complete(
+        &self,
+        prompt: &str,
+        schema: Option<&'static Value>,
+    ) -> Result<String, LlmError>;
+
+    /// Like [`complete`](Self::complete), but forwards the answer text to
+    /// `chunks` as it is generated and returns it whole at the end.
+    /// Backends without streaming support send it in one piece.
+    async fn stream(
+        &self,
+        prompt: &str,
+        schema: Option<&'static Value>,
+        chunks: UnboundedSender<String>,
+    ) -> Result<String, LlmError> {
+        let text = self.complete(prompt, schema).await?;
+        let _ = chunks.send(text.clone());
+        Ok(text)
+    }
+}
+
+/// Wire format spoken by the configured LLM server (`LLM_BACKEND`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LlmBackendKind {
+    /// OpenAI-compatible `/chat/completions` (llama.cpp server, vLLM, ...).
+    OpenAi,
+    /// Ollama's native `/api/chat`.
+    Ollama,
+    /// Anthropic-style `/messages`.
+    Anthropic,
+    /// llama.cpp's raw `/completion`.
+    LlamaCpp,
+}
+
+impl LlmBackendKind {
+    pub const ALL: [LlmBackendKind; 4] = [
+        LlmBackendKind::OpenAi,
+        LlmBackendKind::Ollama,
+        LlmBackendKind::Anthropic,
+        LlmBackendKind::LlamaCpp,
+    ];
+
+    /// Value accepted in `LLM_BACKEND`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LlmBackendKind::OpenAi => "openai",
+            LlmBackendKind::Ollama => "ollama",
+            LlmBackendKind::Anthropic => "anthropic",
+            LlmBackendKind::LlamaCpp => "llamacpp",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_ascii_lowercase();
+        Self::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+
+    /// Whether the server authenticates requests with `LLM_API_KEY`. Ollama
+    /// and the raw llama.cpp endpoint run without one.
+    pub fn requires_api_key(self) -> bool {
+        matches!(self, LlmBackendKind::OpenAi | LlmBackendKind::Anthropic)
+    }
+}
+
+/// Builds the backend selected in the configuration. Fails with
+/// [`LlmError::MissingApiKey`] when the backend needs a key and none is set.
+pub fn create_backend(
+    kind: LlmBackendKind,
+    api_key: Option<String>,
+    base_url: String,
+    model: String,
+) -> Result<Arc<dyn LlmBackend>, LlmError> {
+    let api_key = || api_key.ok_or(LlmError::MissingApiKey(kind.as_str()));
+    Ok(match kind {
+        LlmBackendKind::OpenAi => Arc::new(OpenAiBackend::new(api_key()?, base_url, model)?),
+        LlmBackendKind::Ollama => Arc::new(OllamaBackend::new(base_url, model)?),
+        LlmBackendKind::Anthropic => Arc::new(AnthropicBackend::new(api_key()?, base_url, model)?),
+        LlmBackendKind::LlamaCpp => Arc::new(LlamaCppBackend::new(base_url)?),
+    })
+}
+
+fn http_client() -> Result<reqwest::Client, LlmError> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .map_err(LlmError::HttpError)
+}
+
+fn endpoint(base_url: &str, path: &str) -> String {
+    format!("{}/{}", base_url.trim_end_matches('/'), path)
+}
+
+/// Sends the request built by `build`, retrying rate limits, server errors
+/// and network failures with a short backoff. Returns the first successful
+/// response.
+async fn send_with_retry(
+    build: impl Fn() -> reqwest::RequestBuilder,
+) -> Result<reqwest::Response, LlmError> {
+    let mut last_error: Option<LlmError> = None;
+
+    for attempt in 0..MAX_ATTEMPTS {
+        match build().send().await {
+            Ok(resp) => {
+                let status = resp.status();
+                if status.is_success() {
+                    return Ok(resp);
+                }
+
+                if status == StatusCode::TOO_MANY_REQUESTS {
+                    last_error = Some(LlmError::RateLimited);
+                    if attempt + 1 < MAX_ATTEMPTS {
+                        sleep(Duration::from_secs(1 << attempt)).await;
+                        continue;
+                    }
+                    return Err(LlmError::RateLimited);
+                }
+
+                let body_text = resp
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| "<response body unavailable>".to_string());
+
+                if !status.is_server_error() {
+                    return Err(LlmError::ApiError(body_text));
+                }
+                last_error = Some(LlmError::ApiError(body_text));
+            }
+            Err(error) => {
+                if error.is_timeout() {
+                    last_error = Some(LlmError::Timeout);
+                } else {
+                    last_error = Some(LlmError::HttpError(error));
+                }
+            }
+        }
+
+        if attempt + 1 < MAX_ATTEMPTS {
+            sleep(Duration::from_secs(1 << attempt)).await;
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| LlmError::ApiError("Unknown LLM API error".to_string())))
+}
+
+/// Splits a server-sent events body into the payloads of its `data:` lines.
+/// Network chunks can end mid-line (or mid-character), so the incomplete
+/// tail is kept until the next chunk.
+#[derive(Default)]
+struct SseDecoder {
+    buffer: Vec<u8>,
+}
+
+impl SseDecoder {
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+        let mut payloads = Vec::new();
+        while let Some(newline) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=newline).collect();
+            let line = String::from_utf8_lossy(&line);
+            if let Some(data) = line.trim_end().strip_prefix("data:") {
+                payloads.push(data.trim_start().to_string());
+            }
+        }
+        payloads
+    }
+}
+
+/// Answer text, or a parse error when it is missing or blank. Reasoning
+/// models can exhaust the token budget before emitting any visible answer,
+/// which surfaces as an empty content field.
+fn non_empty(content: Option<String>) -> Result<String, LlmError> {
+    content
+        .filter(|content| !content.trim().is_empty())
+        .ok_or_else(|| LlmError::ParseError("Missing or empty content in LLM response".to_string()))
+}
+
+/// Substitutes the match JSON into the prompt's `{game_data}` placeholder,
+/// or appends it when the prompt has none.
+pub fn build_prompt_text(prompt: &str, data_json: &str) -> String {
+    if prompt.contains("{game_data}") {
+        prompt.replace("{game_data}", data_json)
+    } else {
+        format!("{prompt}\n\nGame Data:\n{data_json}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_prompt_text_replaces_placeholder() {
+        let prompt = "Analyze: {game_data}";
+        let data_json = "{\"foo\":1}";
+        let result = build_prompt_text(prompt, data_json);
+        assert_eq!(result, "Analyze: {\"foo\":1}");
+    }
+
+    #[test]
+    fn build_prompt_text_falls_back_when_missing_placeholder() {
+        let prompt = "Analyze this game";
+        let data_json = "{\"bar\":2}";
+        let result = build_prompt_text(prompt, data_json);
+        assert!(result.contains("Analyze this game"));
+        assert!(result.contains("Game Data:"));
+        assert!(result.contains(data_json));
+    }
+
+    #[test]
+    fn backend_kind_parses_config_values() {
+        assert_eq!(
+            LlmBackendKind::parse("openai"),
+            Some(LlmBackendKind::OpenAi)
+        );
+        assert_eq!(
+            LlmBackendKind::parse(" Ollama "),
+            Some(LlmBackendKind::Ollama)
+        );
+        assert_eq!(
+            LlmBackendKind::parse("llamacpp"),
+            Some(LlmBackendKind::LlamaCpp)
+        );
+        assert_eq!(LlmBackendKind::parse("gpt"), None);
+        for kind in LlmBackendKind::ALL {
+            assert_eq!(LlmBackendKind::parse(kind.as_str()), Some(kind));
+        }
+    }
+
+    #[test]
+    fn only_hosted_backends_require_an_api_key() {
+        let build = |kind| create_backend(kind, None, "http://localhost:8080".into(), "m".into());
+        assert!(matches!(
+            build(LlmBackendKind::OpenAi),
+            Err(LlmError::MissingApiKey("openai"))
+        ));
+        assert!(matches!(
+            build(LlmBackendKind::Anthropic),
+            Err(LlmError::MissingApiKey("anthropic"))
+        ));
+        assert!(build(LlmBackendKind::Ollama).is_ok());
+        assert!(build(LlmBackendKind::LlamaCpp).is_ok());
+    }
+
+    #[test]
+    fn sse_decoder_reassembles_split_lines() {
+        let mut decoder = SseDecoder::default();
+        assert!(decoder.push(b"data: {\"a\":").is_empty());
+        assert_eq!(
+            decoder.push(b"1}\r\n\n: keep-alive\ndata: [DONE]\n"),
+            ["{\"a\":1}", "[DONE]"]
+        );
+
+        let accent = "data: é\n".as_bytes();
+        assert!(decoder.push(&accent[..7]).is_empty());
+        assert_eq!(decoder.push(&accent[7..]), ["é"]);
+    }
+
+    #[test]
+    fn blank_content_is_a_parse_error() {
+        assert_eq!(
+            non_empty(Some("Great game".to_string())).unwrap(),
+            "Great game"
+        );
+        assert!(matches!(
+            non_empty(Some("  ".to_string())),
+            Err(LlmError::ParseError(_))
+        ));
+        assert!(matches!(non_empty(None), Err(LlmError::ParseError(_))));
+    }
+}
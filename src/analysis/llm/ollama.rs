@@ -0,0 +1,140 @@
+//! Ollama's native `/api/chat` backend, for servers run without the
+//! OpenAI compatibility layer. Ollama does not authenticate requests.
+
+use super::{
+    LlmBackend, LlmError, MAX_TOKENS, TEMPERATURE, endpoint, http_client, non_empty,
+    send_with_retry,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Clone)]
+pub struct OllamaBackend {
+    client: reqwest::Client,
+    model: String,
+    base_url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    /// One JSON body instead of newline-delimited chunks.
+    stream: bool,
+    options: ChatOptions,
+    /// JSON schema the answer must follow.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<&'a Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage<'a> {
+    role: &'static str,
+    content: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatOptions {
+    temperature: f32,
+    num_predict: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    message: Option<ResponseMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseMessage {
+    content: String,
+}
+
+impl OllamaBackend {
+    pub fn new(base_url: String, model: String) -> Result<Self, LlmError> {
+        Ok(Self {
+            client: http_client()?,
+            model,
+            base_url,
+        })
+    }
+}
+
+#[async_trait]
+impl LlmBackend for OllamaBackend {
+    fn name(&self) -> &'static str {
+        "ollama"
+    }
+
+    fn model(&self) -> Option<String> {
+        Some(self.model.clone())
+    }
+
+    async fn complete(
+        &self,
+        prompt: &str,
+        schema: Option<&'static Value>,
+    ) -> Result<String, LlmError> {
+        let request_body = build_request(&self.model, prompt, schema);
+        let url = endpoint(&self.base_url, "api/chat");
+        let response = send_with_retry(|| self.client.post(&url).json(&request_body)).await?;
+
+        let parsed: ChatResponse = response.json().await.map_err(LlmError::HttpError)?;
+        non_empty(parsed.message.map(|message| message.content))
+    }
+}
+
+fn build_request<'a>(
+    model: &'a str,
+    prompt: &'a str,
+    schema: Option<&'a Value>,
+) -> ChatRequest<'a> {
+    ChatRequest {
+        model,
+        messages: vec![ChatMessage {
+            role: "user",
+            content: prompt,
+        }],
+        stream: false,
+        options: ChatOptions {
+            temperature: TEMPERATURE,
+            num_predict: MAX_TOKENS,
+        },
+        format: schema,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_disables_streaming_and_caps_tokens() {
+        let value = serde_json::to_value(build_request("gemma3", "Hello", None)).unwrap();
+        assert_eq!(value["model"], "gemma3");
+        assert_eq!(value["stream"], false);
+        assert_eq!(value["messages"][0]["role"], "user");
+        assert_eq!(value["messages"][0]["content"], "Hello");
+        assert_eq!(value["options"]["num_predict"], MAX_TOKENS);
+        assert!(value.get("format").is_none());
+    }
+
+    #[test]
+    fn schema_is_sent_as_the_format() {
+        let schema = serde_json::json!({ "type": "object" });
+        let value = serde_json::to_value(build_request("gemma3", "Hello", Some(&schema))).unwrap();
+        assert_eq!(value["format"], schema);
+    }
+
+    #[test]
+    fn response_text_comes_from_the_message() {
+        let parsed: ChatResponse = serde_json::from_str(
+            r#"{"model":"gemma3","message":{"role":"assistant","content":"Bien joué"},"done":true}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            non_empty(parsed.message.map(|m| m.content)).unwrap(),
+            "Bien joué"
+        );
+    }
+}
@@ -0,0 +1,277 @@
+//! OpenAI-compatible `/chat/completions` backend: the local llama.cpp
+//! server's default API, also spoken by vLLM and hosted providers.
+
+use super::{
+    LlmBackend, LlmError, MAX_TOKENS, SseDecoder, TEMPERATURE, endpoint, http_client, non_empty,
+    send_with_retry,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::sync::mpsc::UnboundedSender;
+
+#[derive(Clone)]
+pub struct OpenAiBackend {
+    client: reqwest::Client,
+    api_key: String,
+    model: String,
+    base_url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct LlmRequest {
+    model: String,
+    messages: Vec<LlmMessage>,
+    temperature: Option<f32>,
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<Value>,
+    /// Answer sent as server-sent events, one `delta` per chunk.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmRole {
+    System,
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LlmMessage {
+    pub role: LlmRole,
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LlmResponse {
+    choices: Vec<LlmChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LlmChoice {
+    message: LlmMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamChunk {
+    choices: Vec<StreamChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamChoice {
+    delta: StreamDelta,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamDelta {
+    content: Option<String>,
+}
+
+impl OpenAiBackend {
+    pub fn new(api_key: String, base_url: String, model: String) -> Result<Self, LlmError> {
+        Ok(Self {
+            client: http_client()?,
+            api_key,
+            model,
+            base_url,
+        })
+    }
+
+    fn request(&self, prompt: &str, schema: Option<&Value>, stream: bool) -> LlmRequest {
+        LlmRequest {
+            model: self.model.clone(),
+            messages: vec![LlmMessage {
+                role: LlmRole::User,
+                content: prompt.to_string(),
+            }],
+            temperature: Some(TEMPERATURE),
+            max_tokens: Some(MAX_TOKENS),
+            response_format: schema.map(response_format),
+            stream,
+        }
+    }
+
+    async fn send(&self, request_body: &LlmRequest) -> Result<reqwest::Response, LlmError> {
+        let url = endpoint(&self.base_url, "chat/completions");
+        send_with_retry(|| {
+            self.client
+                .post(&url)
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .json(request_body)
+        })
+        .await
+    }
+}
+
+#[async_trait]
+impl LlmBackend for OpenAiBackend {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    fn model(&self) -> Option<String> {
+        Some(self.model.clone())
+    }
+
+    async fn complete(
+        &self,
+        prompt: &str,
+        schema: Option<&'static Value>,
+    ) -> Result<String, LlmError> {
+        let response = self.send(&self.request(prompt, schema, false)).await?;
+        let parsed: LlmResponse = response.json().await.map_err(LlmError::HttpError)?;
+        extract_response_text(parsed)
+    }
+
+    async fn stream(
+        &self,
+        prompt: &str,
+        schema: Option<&'static Value>,
+        chunks: UnboundedSender<String>,
+    ) -> Result<String, LlmError> {
+        let mut response = self.send(&self.request(prompt, schema, true)).await?;
+
+        let mut decoder = SseDecoder::default();
+        let mut text = String::new();
+        'body: while let Some(bytes) = response.chunk().await.map_err(LlmError::HttpError)? {
+            for payload in decoder.push(&bytes) {
+                if payload == "[DONE]" {
+                    break 'body;
+                }
+                if let Some(delta) = delta_content(&payload)? {
+                    text.push_str(&delta);
+                    let _ = chunks.send(delta);
+                }
+            }
+        }
+        non_empty(Some(text))
+    }
+}
+
+/// `response_format` asking for JSON matching `schema`; llama.cpp's server
+/// turns it into a grammar.
+fn response_format(schema: &Value) -> Value {
+    json!({
+        "type": "json_schema",
+        "json_schema": { "name": "analysis", "strict": true, "schema": schema },
+    })
+}
+
+/// Text added by one streamed chunk. Chunks without content (role header,
+/// final `finish_reason`) yield `None`.
+fn delta_content(payload: &str) -> Result<Option<String>, LlmError> {
+    let chunk: StreamChunk = serde_json::from_str(payload)
+        .map_err(|e| LlmError::ParseError(format!("Invalid stream chunk: {e}")))?;
+    Ok(chunk
+        .choices
+        .into_iter()
+        .next()
+        .and_then(|choice| choice.delta.content)
+        .filter(|content| !content.is_empty()))
+}
+
+fn extract_response_text(parsed: LlmResponse) -> Result<String, LlmError> {
+    non_empty(
+        parsed
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_response_text_returns_text_when_present() {
+        let parsed = LlmResponse {
+            choices: vec![LlmChoice {
+                message: LlmMessage {
+                    role: LlmRole::Assistant,
+                    content: "Great game".to_string(),
+                },
+            }],
+        };
+
+        let text = extract_response_text(parsed).expect("expected response text");
+        assert_eq!(text, "Great game");
+    }
+
+    #[test]
+    fn extract_response_text_returns_error_when_missing() {
+        let parsed = LlmResponse { choices: vec![] };
+
+        let error = extract_response_text(parsed).expect_err("expected parse error");
+        assert!(matches!(error, LlmError::ParseError(_)));
+    }
+
+    #[test]
+    fn extract_response_text_returns_error_when_content_empty() {
+        let parsed = LlmResponse {
+            choices: vec![LlmChoice {
+                message: LlmMessage {
+                    role: LlmRole::Assistant,
+                    content: "  ".to_string(),
+                },
+            }],
+        };
+
+        let error = extract_response_text(parsed).expect_err("expected parse error");
+        assert!(matches!(error, LlmError::ParseError(_)));
+    }
+
+    #[test]
+    fn llm_request_serializes_correctly() {
+        let request = LlmRequest {
+            model: "gemma-4".to_string(),
+            messages: vec![LlmMessage {
+                role: LlmRole::User,
+                content: "Hello".to_string(),
+            }],
+            temperature: Some(0.7),
+            max_tokens: Some(1024),
+            response_format: None,
+            stream: false,
+        };
+
+        let value = serde_json::to_value(&request).expect("serialize request");
+        assert_eq!(value.get("model").unwrap(), "gemma-4");
+        let temperature = value
+            .get("temperature")
+            .and_then(serde_json::Value::as_f64)
+            .expect("temperature missing or not a number");
+        assert!((temperature - 0.7).abs() < 1e-6);
+        assert_eq!(value.get("max_tokens").unwrap(), &serde_json::json!(1024));
+        let role = value["messages"][0]["role"].as_str().unwrap();
+        assert_eq!(role, "user");
+        assert!(value.get("response_format").is_none());
+        assert!(value.get("stream").is_none());
+    }
+
+    #[test]
+    fn delta_content_reads_streamed_text() {
+        let chunk = r#"{"choices":[{"index":0,"delta":{"content":"Bien"}}]}"#;
+        assert_eq!(delta_content(chunk).unwrap().as_deref(), Some("Bien"));
+
+        let done = r#"{"choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#;
+        assert_eq!(delta_content(done).unwrap(), None);
+
+        assert!(matches!(
+            delta_content("not json"),
+            Err(LlmError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn schema_is_sent_as_a_json_schema_response_format() {
+        let schema = json!({ "type": "object" });
+        let value = response_format(&schema);
+        assert_eq!(value["type"], "json_schema");
+        assert_eq!(value["json_schema"]["schema"], schema);
+    }
+}
@@ -0,0 +1,94 @@
+//! Backend answering from a script instead of a server, for tests and
+//! offline runs of the pipeline.
+
+use super::{LlmBackend, LlmError};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Replays queued answers in order and records every prompt it receives.
+/// Once the script is exhausted, each call fails with an API error.
+#[derive(Default)]
+pub struct ScriptedBackend {
+    responses: Mutex<VecDeque<Result<String, LlmError>>>,
+    prompts: Mutex<Vec<String>>,
+}
+
+impl ScriptedBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a successful answer.
+    pub fn reply(self, text: impl Into<String>) -> Self {
+        self.push(Ok(text.into()))
+    }
+
+    /// Queues a failure.
+    pub fn fail(self, error: LlmError) -> Self {
+        self.push(Err(error))
+    }
+
+    fn push(self, response: Result<String, LlmError>) -> Self {
+        self.responses
+            .lock()
+            .expect("scripted responses poisoned")
+            .push_back(response);
+        self
+    }
+
+    /// Prompts received so far, oldest first.
+    pub fn prompts(&self) -> Vec<String> {
+        self.prompts
+            .lock()
+            .expect("scripted prompts poisoned")
+            .clone()
+    }
+}
+
+#[async_trait]
+impl LlmBackend for ScriptedBackend {
+    fn name(&self) -> &'static str {
+        "scripted"
+    }
+
+    async fn complete(
+        &self,
+        prompt: &str,
+        _schema: Option<&'static Value>,
+    ) -> Result<String, LlmError> {
+        self.prompts
+            .lock()
+            .expect("scripted prompts poisoned")
+            .push(prompt.to_string());
+        self.responses
+            .lock()
+            .expect("scripted responses poisoned")
+            .pop_front()
+            .unwrap_or_else(|| Err(LlmError::ApiError("script exhausted".to_string())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn replays_the_script_in_order() {
+        let backend = ScriptedBackend::new()
+            .reply("first")
+            .fail(LlmError::Timeout);
+
+        assert_eq!(backend.complete("a", None).await.unwrap(), "first");
+        assert!(matches!(
+            backend.complete("b", None).await,
+            Err(LlmError::Timeout)
+        ));
+        assert!(matches!(
+            backend.complete("c", None).await,
+            Err(LlmError::ApiError(_))
+        ));
+        assert_eq!(backend.prompts(), ["a", "b", "c"]);
+    }
+}
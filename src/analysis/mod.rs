@@ -1,9 +1,18 @@
+pub mod classes;
 pub mod discord;
+pub mod evaluation;
+pub mod group;
 pub mod history;
+pub mod jobs;
+pub mod live;
 pub mod llm;
 pub mod models;
 pub mod pipeline;
+pub mod progression;
+pub mod response;
 pub mod roles;
+pub mod scoring;
+pub mod worker;
 
 /// Game modes the coach can meaningfully analyze. Arena ("CHERRY") has no
 /// lanes, roles, or CS economy, so the role-based prompts produce nonsense
@@ -1,9 +1,18 @@
+use crate::analysis::classes::ChampionClass;
+use crate::analysis::roles::TierBand;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisData {
     pub summoner_name: String,
     pub champion_name: String,
+    /// Riot champion ID, used to look up `champion_class`.
+    #[serde(skip)]
+    pub champion_id: i32,
+    /// Class the role benchmarks are adjusted for; `None` when the
+    /// champion's Data Dragon tags are unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub champion_class: Option<ChampionClass>,
     pub win: bool,
     pub kills: i32,
     pub deaths: i32,
@@ -34,6 +43,23 @@ pub struct AnalysisData {
     pub cs_diff_at_10: Option<i32>,
     pub cs_diff_at_15: Option<i32>,
     pub cs_diff_at_20: Option<i32>,
+    /// The player's deaths from the timeline's `CHAMPION_KILL` events,
+    /// oldest first. Empty when the timeline is unavailable.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub death_events: Vec<DeathEvent>,
+    /// Counts derived from `death_events`; `None` without a timeline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub death_summary: Option<DeathSummary>,
+    /// Epic monsters and buildings taken by either team, oldest first.
+    /// Empty when the timeline is unavailable.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub objective_events: Vec<ObjectiveEvent>,
+    /// Counts derived from `objective_events`; `None` without a timeline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub objective_summary: Option<ObjectiveSummary>,
+    /// Pathing and gank metrics, only for junglers with a timeline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jungle: Option<JungleStats>,
     pub game_duration_secs: i32,
     pub role: String,
     pub game_mode: String,
@@ -42,6 +68,232 @@ pub struct AnalysisData {
     /// when empty.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub recent_games: Vec<RecentGameSummary>,
+    /// Ranked tier band the benchmarks and the stats-based verdict are
+    /// picked for.
+    #[serde(default)]
+    pub tier_band: TierBand,
+}
+
+#[cfg(test)]
+impl AnalysisData {
+    /// A solid 30-minute ranked game on Jinx in `role`, without timeline
+    /// or lane opponent data; tests override the fields they check.
+    pub(crate) fn sample(role: &str) -> Self {
+        Self {
+            summoner_name: "Test#EUW".to_string(),
+            champion_name: "Jinx".to_string(),
+            champion_id: 0,
+            champion_class: None,
+            win: false,
+            kills: 8,
+            deaths: 2,
+            assists: 6,
+            kda: Some(7.0),
+            kill_participation: Some(0.58),
+            gold_per_minute: Some(450.0),
+            damage_per_minute: Some(850.0),
+            vision_score_per_minute: Some(0.9),
+            team_damage_percentage: Some(0.3),
+            max_cs_advantage_on_lane_opponent: None,
+            early_laning_phase_gold_exp_advantage: None,
+            laning_phase_gold_exp_advantage: None,
+            turret_kills: 1,
+            inhibitor_kills: 0,
+            objectives_stolen: 0,
+            damage_dealt_to_objectives: 5000,
+            total_damage_dealt_to_champions: 25500,
+            gold_earned: 13500,
+            total_cs: 255,
+            enemy_champion_name: None,
+            enemy_cs: None,
+            enemy_gold: None,
+            enemy_damage: None,
+            gold_diff_at_10: None,
+            gold_diff_at_15: None,
+            gold_diff_at_20: None,
+            cs_diff_at_10: None,
+            cs_diff_at_15: None,
+            cs_diff_at_20: None,
+            death_events: Vec::new(),
+            death_summary: None,
+            objective_events: Vec::new(),
+            objective_summary: None,
+            jungle: None,
+            game_duration_secs: 1800,
+            role: role.to_string(),
+            game_mode: "CLASSIC".to_string(),
+            recent_games: Vec::new(),
+            tier_band: TierBand::default(),
+        }
+    }
+}
+
+/// One death of the player, read from the match timeline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeathEvent {
+    /// Game time of the death.
+    pub timestamp_secs: i32,
+    /// Map coordinates (0-15000, blue base bottom-left).
+    pub x: i32,
+    pub y: i32,
+    /// Champion credited with the kill; `None` for executions by a turret,
+    /// minions or a monster.
+    pub killer: Option<String>,
+    #[serde(default)]
+    pub assisters: Vec<String>,
+    /// No ally fought nearby: picked off rather than traded in a fight.
+    pub alone: bool,
+    /// Died within range of a standing enemy turret.
+    pub near_enemy_tower: bool,
+    /// `DRAGON` or `BARON_NASHOR` taken by the enemy team right after.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before_objective: Option<String>,
+}
+
+/// How the player's deaths break down; a death can fall in several counts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeathSummary {
+    pub before_10_min: i32,
+    pub alone: i32,
+    pub in_fights: i32,
+    pub near_enemy_tower: i32,
+    pub before_objective: i32,
+    /// Deaths alone, under an enemy turret or before an enemy objective —
+    /// the ones that cost the most. Graded by the `death_timing` skill.
+    pub costly: i32,
+}
+
+/// An epic monster or building taken during the game, read from the
+/// match timeline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObjectiveEvent {
+    pub timestamp_secs: i32,
+    /// `DRAGON`, `HORDE` (void grubs), `RIFTHERALD`, `BARON_NASHOR`,
+    /// `TOWER_BUILDING` or `INHIBITOR_BUILDING`.
+    pub objective: String,
+    /// Dragon element or building lane.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Taken by the player's team.
+    pub ally: bool,
+    /// The player got credit for it or stood nearby when it fell.
+    pub player_present: bool,
+}
+
+/// Objectives taken and given up by the player's team.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ObjectiveTally {
+    pub taken: i32,
+    pub lost: i32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ObjectiveSummary {
+    pub dragons: ObjectiveTally,
+    pub void_grubs: ObjectiveTally,
+    pub heralds: ObjectiveTally,
+    pub barons: ObjectiveTally,
+    pub towers: ObjectiveTally,
+    /// Epic monsters (either team's) the player was present for; a void
+    /// grub camp counts once.
+    pub epic_monsters_present: i32,
+    pub epic_monsters_total: i32,
+    /// `epic_monsters_present / epic_monsters_total`; `None` when no epic
+    /// monster died. Graded by the `objectives` skill.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presence: Option<f32>,
+    /// Player deaths that gave a dragon or Baron away, as counted by
+    /// `DeathSummary::before_objective`.
+    pub setup_deaths: i32,
+}
+
+/// How a jungler spent the game, read from the match timeline and the
+/// participant's challenges.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct JungleStats {
+    /// Game time the first full clear was done, interpolated between the
+    /// minute frames; `None` when the jungler did not full clear first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_clear_secs: Option<i32>,
+    /// Jungle camps taken before 14:00, until 25:00 and after, counted
+    /// from the jungle CS at 4 CS a camp.
+    #[serde(default)]
+    pub jungle_camps_by_phase: PhaseCounts,
+    /// Takedowns in each lane before 14:00.
+    pub ganks: LaneCounts,
+    /// Monsters taken in the enemy jungle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counter_jungle_monsters: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scuttle_crabs: Option<i32>,
+    /// Scuttle crabs taken at their first spawn (0-2).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_scuttles: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PhaseCounts {
+    pub early: i32,
+    pub mid: i32,
+    pub late: i32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LaneCounts {
+    pub top: i32,
+    pub mid: i32,
+    pub bot: i32,
+}
+
+/// Combined payload of a group analysis: the tracked players of one team
+/// who played the same game, with both teams' scoreboard as context.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupAnalysisData {
+    pub win: bool,
+    pub game_duration_secs: i32,
+    pub game_mode: String,
+    /// Full data of each tracked player, in role order.
+    pub players: Vec<AnalysisData>,
+    pub allies: Vec<TeamMember>,
+    pub enemies: Vec<TeamMember>,
+}
+
+impl GroupAnalysisData {
+    /// Riot IDs of the analyzed players, for the report and the logs.
+    pub fn summoner_names(&self) -> String {
+        self.players
+            .iter()
+            .map(|p| p.summoner_name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Champions of the analyzed players, for the report title.
+    pub fn champion_names(&self) -> String {
+        self.players
+            .iter()
+            .map(|p| p.champion_name.as_str())
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+/// One participant's scoreboard line in a group analysis.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TeamMember {
+    pub name: String,
+    pub champion_name: String,
+    pub role: String,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub total_cs: i32,
+    pub gold_earned: i32,
+    pub total_damage: i32,
+    pub vision_score: i32,
+    /// One of the analyzed players.
+    #[serde(default)]
+    pub tracked: bool,
 }
 
 /// Compact summary of a previously analyzed game, injected into the prompt
@@ -62,11 +314,44 @@ pub struct RecentGameSummary {
     pub vision_score_per_minute: Option<f32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AnalysisResult {
     pub summoner_name: String,
     pub champion_name: String,
     pub overall_rating: Option<String>,
     pub summary: String,
+    /// The fields below are only filled when the coach answered in the
+    /// structured JSON format; a free-text answer leaves them empty.
+    #[serde(default)]
+    pub strengths: Vec<String>,
+    #[serde(default)]
+    pub weaknesses: Vec<String>,
+    #[serde(default)]
+    pub skill_verdicts: Vec<SkillVerdict>,
+    #[serde(default)]
+    pub key_tip: Option<String>,
+    /// Graded from the stats alone, without the coach.
+    #[serde(default)]
+    pub stats_based: bool,
+    /// Label of the tier band whose benchmarks graded a stats-based verdict,
+    /// in the result's language.
+    #[serde(default)]
+    pub benchmark_band: Option<String>,
+    /// Served from the analysis cache instead of a new coach answer.
+    #[serde(skip)]
+    pub cached: bool,
+    /// Model and backend that wrote the answer, shown in the footer; `None`
+    /// without a coach answer.
+    #[serde(skip)]
+    pub engine: Option<String>,
     pub error: Option<String>,
 }
+
+/// The coach's verdict on one skill of the role's referential.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillVerdict {
+    pub skill: String,
+    /// `Good`, `Average` or `Poor`.
+    pub rating: String,
+    pub comment: String,
+}
@@ -1,12 +1,21 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
 
 use thiserror::Error;
-
-use crate::analysis::llm::{LlmClient, LlmError};
-use crate::analysis::models::{AnalysisData, AnalysisResult};
-use crate::analysis::roles::{ROLE_SPECS, RoleSpec, SkillImportance, spec_for};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::analysis::classes::ChampionClass;
+use crate::analysis::llm::{LlmBackend, LlmError, build_prompt_text};
+use crate::analysis::models::{AnalysisData, AnalysisResult, GroupAnalysisData};
+use crate::analysis::progression::ProgressionReport;
+use crate::analysis::response::{StructuredAnalysis, parse_structured, response_schema};
+use crate::analysis::roles::{ROLE_SPECS, RoleSpec, SkillImportance, TierBand, spec_for};
+use crate::analysis::scoring::score;
+use crate::i18n::{Locale, render};
 
 /// Maps Riot API team_position values to prompt file names.
 const ROLE_PROMPT_FILES: &[(&str, &str)] = &[
@@ -18,20 +27,30 @@ const ROLE_PROMPT_FILES: &[(&str, &str)] = &[
 ];
 
 const DEFAULT_PROMPT_FILE: &str = "default.md";
+/// Prompt of the whole-team report. Self-contained: the role referential and
+/// the shared sections are about one player.
+const GROUP_PROMPT_FILE: &str = "group.md";
+/// Prompt of the coaching paragraph of a progression report; self-contained
+/// too, and answered in free text.
+const PROGRESSION_PROMPT_FILE: &str = "progression.md";
 const SKILLS_SUBDIR: &str = "skills";
 const SHARED_SUBDIR: &str = "shared";
 /// Shared prompt sections appended to every composed prompt, in this order.
 const SHARED_SECTION_FILES: &[&str] = &["rating_rubric", "response_format"];
 /// Trailing section holding the match JSON; `{game_data}` is substituted by
-/// `LlmClient::analyze` at request time.
+/// [`build_prompt_text`] at request time.
 const GAME_DATA_SECTION: &str = "---\n\n## Données de la partie (JSON)\n\n{game_data}";
 const SKILL_NAMES: &[&str] = &[
     "cs_per_minute",
     "damage_per_minute",
     "kills_assists",
     "deaths",
+    "death_timing",
+    "objectives",
     "vision_score",
 ];
+/// Placeholders a composed prompt may keep; they are filled per request.
+const REQUEST_PLACEHOLDERS: &[&str] = &["game_data", "response_language", "tier_band"];
 
 #[derive(Debug, Error)]
 pub enum AnalysisError {
@@ -43,28 +62,58 @@ pub enum AnalysisError {
     PromptDirError(String),
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
+    #[error("Invalid prompts: {0}")]
+    InvalidPrompts(String),
 }
 
-#[derive(Clone)]
-pub struct AnalysisPipeline {
-    llm_client: LlmClient,
-    /// Final composed prompts (role intro + skill blocks) keyed by Riot role.
-    role_prompts: HashMap<String, String>,
+/// What a coach answer depends on besides the game itself: a cached answer
+/// is only served while both are unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKey {
+    /// Hash of the prompt sent with the game data.
+    pub prompt_hash: String,
+    /// Backend and model that answered (`openai/gemma-4`).
+    pub model: String,
+}
+
+/// Variant of a composed role prompt.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct PromptKey {
+    role: String,
+    band: TierBand,
+    class: Option<ChampionClass>,
+}
+
+/// The prompts composed from one state of the prompts directory; a reload
+/// swaps the whole set.
+struct PromptSet {
+    /// Final composed prompts (role intro + skill blocks), one per Riot
+    /// role, tier band and champion class.
+    role_prompts: HashMap<PromptKey, String>,
     /// Fallback used when no role-specific composed prompt exists.
     default_prompt: String,
+    /// `None` when the prompts directory has no group prompt.
+    group_prompt: Option<String>,
+    /// `None` when the prompts directory has no progression prompt.
+    progression_prompt: Option<String>,
+    /// Hash of the files the set was composed from.
+    fingerprint: String,
 }
 
-impl AnalysisPipeline {
-    pub fn new(llm_client: LlmClient, prompts_dir: &str) -> Result<Self, AnalysisError> {
-        let dir_path = Path::new(prompts_dir);
-
-        if !dir_path.is_dir() {
+impl PromptSet {
+    /// Composes the prompts of `prompts_dir`, along with what makes them
+    /// unfit to replace a working set (see [`prompt_problems`]). Only a
+    /// missing directory or default prompt is an error.
+    fn load(prompts_dir: &Path) -> Result<(Self, Vec<String>), AnalysisError> {
+        if !prompts_dir.is_dir() {
             return Err(AnalysisError::PromptDirError(format!(
-                "Prompts directory not found: {prompts_dir}"
+                "Prompts directory not found: {}",
+                prompts_dir.display()
             )));
         }
+        let fingerprint = prompts_fingerprint(prompts_dir);
 
-        let default_prompt_path = dir_path.join(DEFAULT_PROMPT_FILE);
+        let default_prompt_path = prompts_dir.join(DEFAULT_PROMPT_FILE);
         let default_intro = fs::read_to_string(&default_prompt_path)
             .map(|raw| strip_frontmatter(&raw).to_string())
             .map_err(|e| {
@@ -74,14 +123,22 @@ impl AnalysisPipeline {
                 ))
             })?;
 
-        let skills = load_skills(dir_path);
-        let shared = load_shared_sections(dir_path);
+        let skills = load_skills(prompts_dir);
+        let shared = load_shared_sections(prompts_dir);
 
         let default_prompt = finalize_prompt(compose_base(&default_intro, &shared));
 
+        let mut problems = BTreeSet::new();
+        for name in SKILL_NAMES {
+            if !skills.contains_key(name) {
+                problems.insert(format!("{SKILLS_SUBDIR}/{name}.md is missing"));
+            }
+        }
+        prompt_problems(DEFAULT_PROMPT_FILE, &default_prompt, &mut problems);
+
         let mut role_prompts = HashMap::new();
         for (role, filename) in ROLE_PROMPT_FILES {
-            let file_path = dir_path.join(filename);
+            let file_path = prompts_dir.join(filename);
             let intro = match fs::read_to_string(&file_path) {
                 Ok(content) => strip_frontmatter(&content).to_string(),
                 Err(e) => {
@@ -95,57 +152,270 @@ impl AnalysisPipeline {
                 }
             };
 
-            let composed = match spec_for(role) {
-                Some(spec) => compose_prompt(&intro, spec, &skills, &shared),
-                None => compose_base(&intro, &shared),
-            };
-            role_prompts.insert((*role).to_string(), finalize_prompt(composed));
+            let classes = std::iter::once(None).chain(ChampionClass::ALL.map(Some));
+            for class in classes {
+                for band in TierBand::ALL {
+                    let composed = match spec_for(role) {
+                        Some(spec) => compose_prompt(&intro, spec, band, class, &skills, &shared),
+                        None => compose_base(&intro, &shared),
+                    };
+                    let composed = finalize_prompt(composed);
+                    prompt_problems(filename, &composed, &mut problems);
+                    let key = PromptKey {
+                        role: (*role).to_string(),
+                        band,
+                        class,
+                    };
+                    role_prompts.insert(key, composed);
+                }
+            }
+        }
+
+        let group_prompt = load_optional_prompt(
+            prompts_dir,
+            GROUP_PROMPT_FILE,
+            "Group prompt not found, teammates will be analyzed separately",
+        );
+        let progression_prompt = load_optional_prompt(
+            prompts_dir,
+            PROGRESSION_PROMPT_FILE,
+            "Progression prompt not found, progression reports will have no coaching",
+        );
+        for (file, prompt) in [
+            (GROUP_PROMPT_FILE, &group_prompt),
+            (PROGRESSION_PROMPT_FILE, &progression_prompt),
+        ] {
+            if let Some(prompt) = prompt {
+                prompt_problems(file, prompt, &mut problems);
+            }
         }
 
         tracing::info!(
-            loaded_roles = role_prompts.len(),
+            loaded_roles =
+                role_prompts.len() / (TierBand::ALL.len() * (ChampionClass::ALL.len() + 1)),
             total_roles = ROLE_PROMPT_FILES.len(),
             loaded_skills = skills.len(),
             specs = ROLE_SPECS.len(),
+            version = fingerprint.as_str(),
             "Analysis prompts loaded"
         );
 
-        Ok(Self {
-            llm_client,
+        let prompts = Self {
             role_prompts,
             default_prompt,
+            group_prompt,
+            progression_prompt,
+            fingerprint,
+        };
+        Ok((prompts, problems.into_iter().collect()))
+    }
+}
+
+#[derive(Clone)]
+pub struct AnalysisPipeline {
+    llm: Arc<dyn LlmBackend>,
+    prompts_dir: PathBuf,
+    /// Current prompts, shared with the clones so a reload reaches them all.
+    prompts: Arc<RwLock<Arc<PromptSet>>>,
+    /// Adds the stats-based verdict to the game data sent to the coach.
+    stats_anchor: bool,
+    /// Requests the LLM server may serve at once, shared with the clones so
+    /// queued jobs and commands wait for the same slots.
+    llm_slots: Arc<Semaphore>,
+}
+
+impl AnalysisPipeline {
+    /// Loads the prompts of `prompts_dir`. Problems a reload would reject
+    /// are only logged here, as there are no previous prompts to keep.
+    pub fn new(llm: Arc<dyn LlmBackend>, prompts_dir: &str) -> Result<Self, AnalysisError> {
+        let prompts_dir = PathBuf::from(prompts_dir);
+        let (prompts, problems) = PromptSet::load(&prompts_dir)?;
+        for problem in problems {
+            tracing::warn!(problem = problem.as_str(), "Analysis prompt problem");
+        }
+
+        Ok(Self {
+            llm,
+            prompts_dir,
+            prompts: Arc::new(RwLock::new(Arc::new(prompts))),
+            stats_anchor: false,
+            llm_slots: Arc::new(Semaphore::new(1)),
+        })
+    }
+
+    /// Recomposes the prompts from the prompts directory. On any problem
+    /// (a prompt without exactly one `{game_data}`, an unknown placeholder,
+    /// a missing skill file) the current prompts are kept.
+    pub fn reload(&self) -> Result<(), AnalysisError> {
+        let (prompts, problems) = PromptSet::load(&self.prompts_dir)?;
+        if !problems.is_empty() {
+            return Err(AnalysisError::InvalidPrompts(problems.join("; ")));
+        }
+        *self.prompts.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(prompts);
+        Ok(())
+    }
+
+    /// Polls the prompts directory every `every` and [reloads](Self::reload)
+    /// the prompts when a file changed. Runs forever.
+    pub async fn watch_prompts(self: Arc<Self>, every: Duration) {
+        let mut interval = tokio::time::interval(every);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut seen = self.prompts().fingerprint.clone();
+        loop {
+            interval.tick().await;
+            let fingerprint = prompts_fingerprint(&self.prompts_dir);
+            if fingerprint == seen {
+                continue;
+            }
+            // A rejected change is not retried until the files change again.
+            seen = fingerprint;
+            match self.reload() {
+                Ok(()) => tracing::info!("Analysis prompts reloaded"),
+                Err(error) => tracing::warn!(
+                    error = %error,
+                    "Analysis prompts changed but were rejected, keeping the previous ones"
+                ),
+            }
+        }
+    }
+
+    fn prompts(&self) -> Arc<PromptSet> {
+        self.prompts
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Sends the stats-based verdict along with the game data, as an anchor
+    /// against ratings that contradict the numbers.
+    pub fn with_stats_anchor(mut self, enabled: bool) -> Self {
+        self.stats_anchor = enabled;
+        self
+    }
+
+    /// Lets `concurrency` requests reach the LLM server at once (1 by
+    /// default); further analyses wait for a free slot.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.llm_slots = Arc::new(Semaphore::new(concurrency.max(1)));
+        self
+    }
+
+    fn data_json(&self, data: &AnalysisData) -> Result<String, serde_json::Error> {
+        if !self.stats_anchor {
+            return serde_json::to_string_pretty(data);
+        }
+        let mut value = serde_json::to_value(data)?;
+        if let (Some(verdict), Some(object)) = (score(data), value.as_object_mut()) {
+            object.insert("stats_verdict".to_string(), serde_json::to_value(verdict)?);
+        }
+        serde_json::to_string_pretty(&value)
+    }
+
+    fn get_prompt_for_role(
+        &self,
+        role: &str,
+        band: TierBand,
+        class: Option<ChampionClass>,
+    ) -> String {
+        let key = PromptKey {
+            role: role.to_string(),
+            band,
+            class,
+        };
+        let prompts = self.prompts();
+        prompts
+            .role_prompts
+            .get(&key)
+            .unwrap_or(&prompts.default_prompt)
+            .clone()
+    }
+
+    /// Runs the role prompt for `data`, asking the coach to answer in
+    /// `locale`'s language.
+    pub async fn analyze_game(&self, data: &AnalysisData, locale: Locale) -> AnalysisResult {
+        self.analyze(data, locale, None).await
+    }
+
+    /// Same as [`analyze_game`](Self::analyze_game), forwarding the answer
+    /// text to `progress` as the model generates it.
+    pub async fn analyze_game_streaming(
+        &self,
+        data: &AnalysisData,
+        locale: Locale,
+        progress: UnboundedSender<String>,
+    ) -> AnalysisResult {
+        self.analyze(data, locale, Some(progress)).await
+    }
+
+    /// Cache key of the answer [`analyze_game`](Self::analyze_game) would
+    /// give for `data` in `locale`.
+    pub fn cache_key(&self, data: &AnalysisData, locale: Locale) -> CacheKey {
+        let mut prompt = self.prompt_for(data, locale);
+        if self.stats_anchor {
+            prompt.push_str("\nstats_anchor");
+        }
+        CacheKey {
+            prompt_hash: content_hash(&prompt),
+            model: self.model_key(),
+        }
+    }
+
+    /// Key of the prompt and model [`analyze_group`](Self::analyze_group)
+    /// answers with in `locale`; `None` without a group prompt.
+    pub fn group_cache_key(&self, locale: Locale) -> Option<CacheKey> {
+        let prompts = self.prompts();
+        let group_prompt = prompts.group_prompt.as_deref()?;
+        Some(CacheKey {
+            prompt_hash: content_hash(&localize_prompt(group_prompt, locale)),
+            model: self.model_key(),
         })
     }
 
-    fn get_prompt_for_role(&self, role: &str) -> &str {
-        self.role_prompts
-            .get(role)
-            .map(String::as_str)
-            .unwrap_or(&self.default_prompt)
+    fn model_key(&self) -> String {
+        match self.llm.model() {
+            Some(model) => format!("{}/{model}", self.llm.name()),
+            None => self.llm.name().to_string(),
+        }
+    }
+
+    /// Model and backend answering, as shown in the analysis footers.
+    pub fn engine(&self) -> String {
+        match self.llm.model() {
+            Some(model) => format!("{model} ({})", self.llm.name()),
+            None => self.llm.name().to_string(),
+        }
     }
 
-    pub async fn analyze_game(&self, data: &AnalysisData) -> AnalysisResult {
-        let prompt = self.get_prompt_for_role(&data.role);
+    /// The role prompt for `data`, localized, before the game data is added.
+    fn prompt_for(&self, data: &AnalysisData, locale: Locale) -> String {
+        let role_prompt = self.get_prompt_for_role(&data.role, data.tier_band, data.champion_class);
+        localize_role_prompt(&role_prompt, data, locale)
+    }
 
-        let error_message = match serde_json::to_string_pretty(data) {
-            Ok(data_json) => {
-                let result = self.llm_client.analyze(prompt, &data_json).await;
+    async fn analyze(
+        &self,
+        data: &AnalysisData,
+        locale: Locale,
+        progress: Option<UnboundedSender<String>>,
+    ) -> AnalysisResult {
+        let role_prompt = self.get_prompt_for_role(&data.role, data.tier_band, data.champion_class);
+        let prompt = localize_role_prompt(&role_prompt, data, locale);
 
-                match result {
+        let error_message = match self.data_json(data) {
+            Ok(data_json) => {
+                let prompt_text = build_prompt_text(&prompt, &data_json);
+                match self.ask(&prompt_text, progress).await {
                     Ok(text) => {
-                        let overall_rating = extract_overall_rating(&text);
                         return AnalysisResult {
-                            summoner_name: data.summoner_name.clone(),
-                            champion_name: data.champion_name.clone(),
-                            overall_rating,
-                            summary: text,
-                            error: None,
+                            engine: Some(self.engine()),
+                            ..result_from_answer(&data.summoner_name, &data.champion_name, text)
                         };
                     }
                     Err(error) => {
                         tracing::warn!(
                             summoner = data.summoner_name.as_str(),
                             role = data.role.as_str(),
+                            backend = self.llm.name(),
                             error = %error,
                             "LLM analysis failed"
                         );
@@ -163,14 +433,286 @@ impl AnalysisPipeline {
             }
         };
 
-        AnalysisResult {
-            summoner_name: data.summoner_name.clone(),
-            champion_name: data.champion_name.clone(),
-            overall_rating: None,
-            summary: format!("Analyse indisponible : {error_message}"),
-            error: Some(error_message),
+        unavailable_result(
+            &data.summoner_name,
+            &data.champion_name,
+            error_message,
+            locale,
+        )
+    }
+
+    /// Whether a group prompt was loaded; without it, teammates are
+    /// analyzed separately.
+    pub fn supports_group(&self) -> bool {
+        self.prompts().group_prompt.is_some()
+    }
+
+    /// Runs the group prompt for the tracked players of one team, asking
+    /// for a single report on how they played together. With `progress`,
+    /// the answer text is streamed to it while it is generated.
+    pub async fn analyze_group(
+        &self,
+        data: &GroupAnalysisData,
+        locale: Locale,
+        progress: Option<UnboundedSender<String>>,
+    ) -> AnalysisResult {
+        let summoner_names = data.summoner_names();
+        let champion_names = data.champion_names();
+        let prompts = self.prompts();
+        let Some(group_prompt) = &prompts.group_prompt else {
+            return unavailable_result(
+                &summoner_names,
+                &champion_names,
+                "no group prompt loaded".to_string(),
+                locale,
+            );
+        };
+
+        let error_message = match serde_json::to_string_pretty(data) {
+            Ok(data_json) => {
+                let prompt = localize_group_prompt(group_prompt, data, locale);
+                let prompt_text = build_prompt_text(&prompt, &data_json);
+                match self.ask(&prompt_text, progress).await {
+                    Ok(text) => {
+                        return AnalysisResult {
+                            engine: Some(self.engine()),
+                            ..result_from_answer(&summoner_names, &champion_names, text)
+                        };
+                    }
+                    Err(error) => {
+                        tracing::warn!(
+                            summoners = summoner_names.as_str(),
+                            backend = self.llm.name(),
+                            error = %error,
+                            "LLM group analysis failed"
+                        );
+                        error.to_string()
+                    }
+                }
+            }
+            Err(error) => {
+                tracing::warn!(
+                    summoners = summoner_names.as_str(),
+                    error = %error,
+                    "Failed to serialize group analysis data"
+                );
+                error.to_string()
+            }
+        };
+
+        unavailable_result(&summoner_names, &champion_names, error_message, locale)
+    }
+
+    /// The coach's paragraph on the trend of `report`, in `locale`'s
+    /// language. `None` without a progression prompt or when the coach
+    /// failed; the report is posted without it.
+    pub async fn coach_progression(
+        &self,
+        report: &ProgressionReport,
+        locale: Locale,
+    ) -> Option<String> {
+        let prompt = localize_prompt(self.prompts().progression_prompt.as_ref()?, locale);
+        let report_json = match serde_json::to_string_pretty(report) {
+            Ok(json) => json,
+            Err(error) => {
+                tracing::warn!(error = %error, "Failed to serialize progression report");
+                return None;
+            }
+        };
+        let _slot = self.llm_slot().await;
+        match self
+            .llm
+            .complete(&build_prompt_text(&prompt, &report_json), None)
+            .await
+        {
+            Ok(text) => Some(text.trim().to_string()).filter(|text| !text.is_empty()),
+            Err(error) => {
+                tracing::warn!(
+                    summoner = report.summoner_name.as_str(),
+                    backend = self.llm.name(),
+                    error = %error,
+                    "LLM progression coaching failed"
+                );
+                None
+            }
         }
     }
+
+    async fn ask(
+        &self,
+        prompt_text: &str,
+        progress: Option<UnboundedSender<String>>,
+    ) -> Result<String, LlmError> {
+        let _slot = self.llm_slot().await;
+        match progress {
+            Some(progress) => {
+                self.llm
+                    .stream(prompt_text, Some(response_schema()), progress)
+                    .await
+            }
+            None => {
+                self.llm
+                    .complete(prompt_text, Some(response_schema()))
+                    .await
+            }
+        }
+    }
+
+    /// Waits for a free slot on the LLM server.
+    async fn llm_slot(&self) -> SemaphorePermit<'_> {
+        self.llm_slots
+            .acquire()
+            .await
+            .expect("LLM slots are never closed")
+    }
+}
+
+/// Result posted when the coach could not answer.
+fn unavailable_result(
+    summoner_name: &str,
+    champion_name: &str,
+    error_message: String,
+    locale: Locale,
+) -> AnalysisResult {
+    AnalysisResult {
+        summoner_name: summoner_name.to_string(),
+        champion_name: champion_name.to_string(),
+        overall_rating: None,
+        summary: render(
+            locale.catalog().analysis_unavailable,
+            &[("error", &error_message)],
+        ),
+        error: Some(error_message),
+        ..AnalysisResult::default()
+    }
+}
+
+/// Reads the structured JSON answer, or falls back to free-text parsing when
+/// the model ignored the format.
+fn result_from_answer(summoner_name: &str, champion_name: &str, text: String) -> AnalysisResult {
+    let base = AnalysisResult {
+        summoner_name: summoner_name.to_string(),
+        champion_name: champion_name.to_string(),
+        ..AnalysisResult::default()
+    };
+
+    match parse_structured(&text) {
+        Some(StructuredAnalysis {
+            rating,
+            summary,
+            strengths,
+            weaknesses,
+            skills,
+            key_tip,
+        }) => AnalysisResult {
+            overall_rating: Some(rating),
+            summary,
+            strengths,
+            weaknesses,
+            skill_verdicts: skills,
+            key_tip: Some(key_tip).filter(|tip| !tip.trim().is_empty()),
+            ..base
+        },
+        None => {
+            tracing::warn!(
+                summoner = summoner_name,
+                "LLM answer is not structured JSON, falling back to text parsing"
+            );
+            AnalysisResult {
+                overall_rating: extract_overall_rating(&text),
+                summary: text,
+                ..base
+            }
+        }
+    }
+}
+
+/// FNV-1a hash of `content`, in hex: stable across builds and platforms,
+/// unlike the standard library hashers.
+fn content_hash(content: &str) -> String {
+    let hash = content
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
+/// Hash of every file of the prompts directory, to notice edits without
+/// recomposing the prompts.
+fn prompts_fingerprint(prompts_dir: &Path) -> String {
+    let mut files: Vec<PathBuf> = [
+        prompts_dir.to_path_buf(),
+        prompts_dir.join(SKILLS_SUBDIR),
+        prompts_dir.join(SHARED_SUBDIR),
+    ]
+    .iter()
+    .filter_map(|dir| fs::read_dir(dir).ok())
+    .flat_map(|entries| entries.flatten().map(|entry| entry.path()))
+    .filter(|path| path.is_file())
+    .collect();
+    files.sort();
+
+    let mut content = String::new();
+    for path in files {
+        content.push_str(&path.display().to_string());
+        content.push('\0');
+        content.push_str(&fs::read_to_string(&path).unwrap_or_default());
+        content.push('\0');
+    }
+    content_hash(&content)
+}
+
+/// Records in `problems` what is wrong with the composed `prompt` of
+/// `file`: the game data must appear exactly once, and every placeholder
+/// left must be one filled per request.
+fn prompt_problems(file: &str, prompt: &str, problems: &mut BTreeSet<String>) {
+    let game_data = placeholders(prompt)
+        .filter(|name| *name == "game_data")
+        .count();
+    if game_data != 1 {
+        problems.insert(format!(
+            "{file}: {{game_data}} appears {game_data} times instead of once"
+        ));
+    }
+    for name in placeholders(prompt) {
+        if !REQUEST_PLACEHOLDERS.contains(&name) {
+            problems.insert(format!("{file}: unknown placeholder {{{name}}}"));
+        }
+    }
+}
+
+/// Names of the `{name}` placeholders of `prompt`. Only lowercase
+/// identifiers count, so JSON examples are not taken for placeholders.
+fn placeholders(prompt: &str) -> impl Iterator<Item = &str> {
+    prompt.split('{').skip(1).filter_map(|rest| {
+        let name = &rest[..rest.find('}')?];
+        (!name.is_empty() && name.bytes().all(|b| b.is_ascii_lowercase() || b == b'_'))
+            .then_some(name)
+    })
+}
+
+/// Localizes a composed role prompt for `data`'s tier band.
+fn localize_role_prompt(role_prompt: &str, data: &AnalysisData, locale: Locale) -> String {
+    localize_prompt(role_prompt, locale).replace("{tier_band}", data.tier_band.label_fr())
+}
+
+/// Localizes the group prompt for the tier bands of its players.
+fn localize_group_prompt(group_prompt: &str, data: &GroupAnalysisData, locale: Locale) -> String {
+    let mut bands: Vec<&str> = Vec::new();
+    for player in &data.players {
+        let band = player.tier_band.label_fr();
+        if !bands.contains(&band) {
+            bands.push(band);
+        }
+    }
+    localize_prompt(group_prompt, locale).replace("{tier_band}", &bands.join(", "))
+}
+
+/// Fills the `{response_language}` placeholder of the shared response format.
+/// Prompt directories without it keep their hard-coded language.
+fn localize_prompt(prompt: &str, locale: Locale) -> String {
+    prompt.replace("{response_language}", locale.catalog().prompt_language)
 }
 
 /// Loads every shared skill template into a `name -> body` map. Missing
@@ -205,6 +747,21 @@ fn load_skills(prompts_dir: &Path) -> HashMap<&'static str, String> {
     out
 }
 
+/// Loads a self-contained prompt; `None`, with `missing` logged, when the
+/// file cannot be read.
+fn load_optional_prompt(prompts_dir: &Path, file: &str, missing: &str) -> Option<String> {
+    let path = prompts_dir.join(file);
+    match fs::read_to_string(&path) {
+        Ok(raw) => Some(finalize_prompt(
+            strip_frontmatter(&raw).trim_end().to_string(),
+        )),
+        Err(e) => {
+            tracing::warn!(file = %path.display(), error = %e, "{missing}");
+            None
+        }
+    }
+}
+
 /// Loads the shared prompt sections (rating rubric, response format) that are
 /// appended to every composed prompt. Missing files are skipped with a warning
 /// rather than failing startup, mirroring the skills loading behaviour.
@@ -254,33 +811,45 @@ fn finalize_prompt(composed: String) -> String {
     }
 }
 
-/// Composes the final per-role prompt: role intro + each applicable skill
-/// block with role-specific thresholds substituted in, followed by the shared
-/// sections.
+/// Composes the final per-role prompt: role intro + the champion class
+/// notes + each applicable skill block with the role's benchmarks for `band`
+/// substituted in, followed by the shared sections.
 fn compose_prompt(
     intro: &str,
     spec: &RoleSpec,
+    band: TierBand,
+    class: Option<ChampionClass>,
     skills: &HashMap<&'static str, String>,
     shared: &[String],
 ) -> String {
     let mut sections: Vec<String> = vec![intro.trim_end().to_string()];
 
-    sections.push(String::from(
-        "\n---\n\n## Référentiel par compétence (Platine / Émeraude)\n",
+    sections.push(format!(
+        "\n---\n\n## Référentiel par compétence ({})\n",
+        band.label_fr()
     ));
 
+    if let Some(class) = class {
+        sections.push(format!(
+            "### Profil du champion : {}\n\n{}",
+            class.label_fr(),
+            class.notes_fr()
+        ));
+    }
+
     for binding in spec.bindings {
-        if binding.importance == SkillImportance::NotApplicable {
+        let importance = binding.importance_for(class);
+        if importance == SkillImportance::NotApplicable {
             continue;
         }
         let Some(template) = skills.get(binding.skill) else {
             continue;
         };
         let body = template
-            .replace("{benchmarks}", binding.benchmarks)
+            .replace("{benchmarks}", &binding.benchmarks_for(band, spec.label_fr))
             .replace("{role_notes}", binding.role_notes);
 
-        if let Some(label) = binding.importance.label_fr() {
+        if let Some(label) = importance.label_fr() {
             sections.push(format!("{label}\n\n{body}"));
         } else {
             sections.push(body);
@@ -363,6 +932,7 @@ fn extract_overall_rating(text: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analysis::llm::ScriptedBackend;
     use crate::analysis::models::AnalysisData;
     use std::fs;
     use tempfile::TempDir;
@@ -371,6 +941,8 @@ mod tests {
         AnalysisData {
             summoner_name: "TestSummoner".to_string(),
             champion_name: "Ahri".to_string(),
+            champion_id: 0,
+            champion_class: None,
             win: true,
             kills: 10,
             deaths: 2,
@@ -401,10 +973,16 @@ mod tests {
             cs_diff_at_10: Some(10),
             cs_diff_at_15: Some(15),
             cs_diff_at_20: Some(20),
+            death_events: Vec::new(),
+            death_summary: None,
+            objective_events: Vec::new(),
+            objective_summary: None,
+            jungle: None,
             game_duration_secs: 2100,
             role: "MIDDLE".to_string(),
             game_mode: "CLASSIC".to_string(),
             recent_games: Vec::new(),
+            tier_band: TierBand::default(),
         }
     }
 
@@ -429,13 +1007,8 @@ mod tests {
         }
     }
 
-    fn make_client() -> LlmClient {
-        LlmClient::new(
-            "fake-key".to_string(),
-            "http://localhost:8080/v1".to_string(),
-            "gemma-4-26b".to_string(),
-        )
-        .unwrap()
+    fn make_client() -> Arc<dyn LlmBackend> {
+        Arc::new(ScriptedBackend::new())
     }
 
     fn make_pipeline(dir: &TempDir) -> AnalysisPipeline {
@@ -519,11 +1092,11 @@ mod tests {
 
         let pipeline = make_pipeline(&dir);
 
-        let top = pipeline.get_prompt_for_role("TOP");
+        let top = pipeline.get_prompt_for_role("TOP", TierBand::PlatinumEmerald, None);
         assert!(!top.contains("name: lol-coach-top"));
         assert!(top.starts_with("Top lane prompt:"));
 
-        let fallback = pipeline.get_prompt_for_role("UNKNOWN");
+        let fallback = pipeline.get_prompt_for_role("UNKNOWN", TierBand::PlatinumEmerald, None);
         assert!(!fallback.contains("name: lol-coach-default"));
         assert!(fallback.starts_with("Default prompt:"));
     }
@@ -543,11 +1116,44 @@ mod tests {
         write_role_files(&dir);
         let pipeline = make_pipeline(&dir);
 
-        assert!(pipeline.get_prompt_for_role("TOP").contains("Top lane"));
-        assert!(pipeline.get_prompt_for_role("JUNGLE").contains("Jungle"));
-        assert!(pipeline.get_prompt_for_role("MIDDLE").contains("Mid lane"));
-        assert!(pipeline.get_prompt_for_role("BOTTOM").contains("ADC"));
-        assert!(pipeline.get_prompt_for_role("UTILITY").contains("Support"));
+        assert!(
+            pipeline
+                .get_prompt_for_role("TOP", TierBand::PlatinumEmerald, None)
+                .contains("Top lane")
+        );
+        assert!(
+            pipeline
+                .get_prompt_for_role("JUNGLE", TierBand::PlatinumEmerald, None)
+                .contains("Jungle")
+        );
+        assert!(
+            pipeline
+                .get_prompt_for_role("MIDDLE", TierBand::PlatinumEmerald, None)
+                .contains("Mid lane")
+        );
+        assert!(
+            pipeline
+                .get_prompt_for_role("BOTTOM", TierBand::PlatinumEmerald, None)
+                .contains("ADC")
+        );
+        assert!(
+            pipeline
+                .get_prompt_for_role("UTILITY", TierBand::PlatinumEmerald, None)
+                .contains("Support")
+        );
+    }
+
+    #[test]
+    fn repo_response_format_follows_locale() {
+        let pipeline = AnalysisPipeline::new(make_client(), "analysis_prompts").unwrap();
+        let prompt = pipeline.get_prompt_for_role("MIDDLE", TierBand::PlatinumEmerald, None);
+        assert!(prompt.contains("{response_language}"));
+
+        let fr = localize_prompt(&prompt, Locale::Fr);
+        assert!(fr.contains("Réponds entièrement en français"));
+        let en = localize_prompt(&prompt, Locale::En);
+        assert!(en.contains("Réponds entièrement en anglais"));
+        assert!(!en.contains("{response_language}"));
     }
 
     #[test]
@@ -556,8 +1162,16 @@ mod tests {
         write_role_files(&dir);
         let pipeline = make_pipeline(&dir);
 
-        assert!(pipeline.get_prompt_for_role("").contains("Default"));
-        assert!(pipeline.get_prompt_for_role("UNKNOWN").contains("Default"));
+        assert!(
+            pipeline
+                .get_prompt_for_role("", TierBand::PlatinumEmerald, None)
+                .contains("Default")
+        );
+        assert!(
+            pipeline
+                .get_prompt_for_role("UNKNOWN", TierBand::PlatinumEmerald, None)
+                .contains("Default")
+        );
     }
 
     #[test]
@@ -584,7 +1198,7 @@ mod tests {
         for role in [
             "TOP", "JUNGLE", "MIDDLE", "BOTTOM", "UTILITY", "", "UNKNOWN",
         ] {
-            let prompt = pipeline.get_prompt_for_role(role);
+            let prompt = pipeline.get_prompt_for_role(role, TierBand::PlatinumEmerald, None);
             assert!(
                 prompt.contains("## Barème de notation"),
                 "rating rubric missing for role {role:?}"
@@ -604,7 +1218,7 @@ mod tests {
             );
         }
 
-        let top = pipeline.get_prompt_for_role("TOP");
+        let top = pipeline.get_prompt_for_role("TOP", TierBand::PlatinumEmerald, None);
         assert!(top.contains("Top Lane"));
         assert!(top.contains("Référentiel par compétence"));
         let referential = top.find("Référentiel par compétence").unwrap();
@@ -615,7 +1229,11 @@ mod tests {
             "sections out of order: referential={referential} rubric={rubric} format={format}"
         );
 
-        assert!(pipeline.get_prompt_for_role("UTILITY").contains("Support"));
+        assert!(
+            pipeline
+                .get_prompt_for_role("UTILITY", TierBand::PlatinumEmerald, None)
+                .contains("Support")
+        );
     }
 
     #[test]
@@ -624,8 +1242,16 @@ mod tests {
         fs::write(dir.path().join("default.md"), "Default only: {game_data}").unwrap();
         let pipeline = make_pipeline(&dir);
 
-        assert!(pipeline.get_prompt_for_role("TOP").contains("Default"));
-        assert!(pipeline.get_prompt_for_role("MIDDLE").contains("Default"));
+        assert!(
+            pipeline
+                .get_prompt_for_role("TOP", TierBand::PlatinumEmerald, None)
+                .contains("Default")
+        );
+        assert!(
+            pipeline
+                .get_prompt_for_role("MIDDLE", TierBand::PlatinumEmerald, None)
+                .contains("Default")
+        );
     }
 
     #[test]
@@ -635,7 +1261,7 @@ mod tests {
         write_skill_files(&dir);
         let pipeline = make_pipeline(&dir);
 
-        let top = pipeline.get_prompt_for_role("TOP");
+        let top = pipeline.get_prompt_for_role("TOP", TierBand::PlatinumEmerald, None);
         assert!(top.contains("Top lane prompt"));
         assert!(top.contains("Référentiel par compétence"));
         assert!(top.contains("cs_per_minute skill"));
@@ -654,7 +1280,7 @@ mod tests {
         write_skill_files(&dir);
         let pipeline = make_pipeline(&dir);
 
-        let support = pipeline.get_prompt_for_role("UTILITY");
+        let support = pipeline.get_prompt_for_role("UTILITY", TierBand::PlatinumEmerald, None);
         assert!(support.contains("Support prompt"));
         // Support config marks CS as NotApplicable → its block must NOT appear.
         assert!(!support.contains("cs_per_minute skill"));
@@ -670,13 +1296,105 @@ mod tests {
         write_skill_files(&dir);
         let pipeline = make_pipeline(&dir);
 
-        let adc = pipeline.get_prompt_for_role("BOTTOM");
+        let adc = pipeline.get_prompt_for_role("BOTTOM", TierBand::PlatinumEmerald, None);
         // BOTTOM spec has concrete CS benchmarks like "8-9 CS/min".
         assert!(adc.contains("8-9 CS/min"));
         // It also has concrete DPM benchmarks like "800-1100 DPM".
         assert!(adc.contains("800-1100 DPM"));
     }
 
+    #[test]
+    fn composed_prompt_carries_the_benchmarks_of_each_tier_band() {
+        let dir = TempDir::new().unwrap();
+        write_role_files(&dir);
+        write_skill_files(&dir);
+        let pipeline = make_pipeline(&dir);
+
+        let iron = pipeline.get_prompt_for_role("BOTTOM", TierBand::IronBronze, None);
+        assert!(iron.contains("## Référentiel par compétence (Fer / Bronze)"));
+        assert!(iron.contains("Repères ADC en Fer / Bronze :"));
+        assert!(!iron.contains("8-9 CS/min"));
+
+        let silver = pipeline.get_prompt_for_role("BOTTOM", TierBand::SilverGold, None);
+        assert!(silver.contains("Repères ADC en Argent / Or :"));
+
+        let plat = pipeline.get_prompt_for_role("BOTTOM", TierBand::PlatinumEmerald, None);
+        assert!(plat.contains("## Référentiel par compétence (Platine / Émeraude)"));
+        assert!(plat.contains("8-9 CS/min"));
+
+        let diamond = pipeline.get_prompt_for_role("BOTTOM", TierBand::DiamondPlus, None);
+        assert!(diamond.contains("Repères ADC en Diamant et plus :"));
+        assert!(diamond.contains("- Good : 8,8 CS/min et plus"));
+
+        for band in TierBand::ALL {
+            assert!(
+                pipeline
+                    .get_prompt_for_role("", band, None)
+                    .contains("Default prompt")
+            );
+        }
+
+        // The shipped role prompts name the player's band, not Platine / Émeraude.
+        let shipped = AnalysisPipeline::new(make_client(), "analysis_prompts").unwrap();
+        let mut data = sample_analysis_data();
+        for band in TierBand::ALL {
+            if band == TierBand::PlatinumEmerald {
+                continue;
+            }
+            data.tier_band = band;
+            for role in ["TOP", "JUNGLE", "MIDDLE", "BOTTOM", "UTILITY", ""] {
+                let prompt = localize_role_prompt(
+                    &shipped.get_prompt_for_role(role, band, None),
+                    &data,
+                    Locale::Fr,
+                );
+                assert!(prompt.contains(band.label_fr()), "{role} in {band:?}");
+                assert!(!prompt.contains("Platine / Émeraude"), "{role} in {band:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn composed_prompt_follows_the_champion_class() {
+        let dir = TempDir::new().unwrap();
+        write_role_files(&dir);
+        write_skill_files(&dir);
+        let pipeline = make_pipeline(&dir);
+        let band = TierBand::PlatinumEmerald;
+        let low_damage = "**À mentionner si pertinent**\n\n### damage_per_minute skill";
+
+        let generic = pipeline.get_prompt_for_role("TOP", band, None);
+        assert!(!generic.contains("Profil du champion"));
+        assert!(!generic.contains(low_damage));
+
+        let tank = pipeline.get_prompt_for_role("TOP", band, Some(ChampionClass::Tank));
+        assert!(tank.contains("### Profil du champion : Tank"));
+        assert!(tank.contains(low_damage));
+
+        let enchanter = pipeline.get_prompt_for_role("UTILITY", band, Some(ChampionClass::Support));
+        assert!(enchanter.contains("### Profil du champion : Enchanteur / utilitaire"));
+        assert!(enchanter.contains(low_damage));
+        assert!(!enchanter.contains("cs_per_minute skill"));
+
+        let engage = pipeline.get_prompt_for_role("UTILITY", band, Some(ChampionClass::Tank));
+        assert_ne!(enchanter, engage);
+    }
+
+    #[tokio::test]
+    async fn analyze_game_names_the_tier_band_in_the_rubric() {
+        let backend = Arc::new(ScriptedBackend::new().reply("Good"));
+        let pipeline = AnalysisPipeline::new(backend.clone(), "analysis_prompts").unwrap();
+        let mut data = sample_analysis_data();
+        data.tier_band = TierBand::SilverGold;
+
+        pipeline.analyze_game(&data, Locale::Fr).await;
+
+        let prompt = &backend.prompts()[0];
+        assert!(prompt.contains("## Référentiel par compétence (Argent / Or)"));
+        assert!(prompt.contains("niveau du joueur (Argent / Or)"));
+        assert!(!prompt.contains("{tier_band}"));
+    }
+
     #[test]
     fn pipeline_works_without_skills_directory() {
         let dir = TempDir::new().unwrap();
@@ -684,9 +1402,306 @@ mod tests {
         // No skills dir written.
         let pipeline = make_pipeline(&dir);
 
-        let top = pipeline.get_prompt_for_role("TOP");
+        let top = pipeline.get_prompt_for_role("TOP", TierBand::PlatinumEmerald, None);
         // Still composes the header but no skill bodies.
         assert!(top.contains("Top lane prompt"));
         assert!(!top.contains("cs_per_minute skill"));
     }
+
+    #[tokio::test]
+    async fn analyze_game_sends_the_role_prompt_to_the_backend() {
+        let dir = TempDir::new().unwrap();
+        write_role_files(&dir);
+        let backend = Arc::new(ScriptedBackend::new().reply("**Note globale : Good**"));
+        let pipeline =
+            AnalysisPipeline::new(backend.clone(), dir.path().to_str().unwrap()).unwrap();
+
+        let result = pipeline
+            .analyze_game(&sample_analysis_data(), Locale::Fr)
+            .await;
+
+        assert!(result.error.is_none());
+        assert_eq!(result.summary, "**Note globale : Good**");
+        assert_eq!(result.overall_rating.as_deref(), Some("Good"));
+        assert!(result.strengths.is_empty());
+        let prompts = backend.prompts();
+        assert_eq!(prompts.len(), 1);
+        assert!(prompts[0].starts_with("Mid lane prompt: "));
+        assert!(!prompts[0].contains("{game_data}"));
+    }
+
+    #[tokio::test]
+    async fn interactive_analysis_waits_while_a_queued_job_holds_the_only_slot() {
+        let backend = Arc::new(ScriptedBackend::new().reply("Good"));
+        let pipeline = AnalysisPipeline::new(backend.clone(), "analysis_prompts").unwrap();
+        // The worker's clone is asking the LLM server with the only slot.
+        let worker_pipeline = pipeline.clone();
+        let worker_job = worker_pipeline.llm_slot().await;
+
+        let data = sample_analysis_data();
+        let interactive =
+            tokio::spawn(async move { pipeline.analyze_game(&data, Locale::Fr).await });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!interactive.is_finished());
+        assert!(backend.prompts().is_empty());
+
+        drop(worker_job);
+        let result = interactive.await.unwrap();
+        assert!(result.error.is_none());
+        assert_eq!(backend.prompts().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn analyze_group_uses_the_group_prompt() {
+        let dir = TempDir::new().unwrap();
+        write_role_files(&dir);
+        fs::write(
+            dir.path().join("group.md"),
+            "Group prompt ({tier_band}): {game_data}",
+        )
+        .unwrap();
+        let backend = Arc::new(ScriptedBackend::new().reply("**Note globale : Average**"));
+        let pipeline =
+            AnalysisPipeline::new(backend.clone(), dir.path().to_str().unwrap()).unwrap();
+        assert!(pipeline.supports_group());
+
+        let mut jungler = sample_analysis_data();
+        jungler.champion_name = "Vi".to_string();
+        jungler.tier_band = TierBand::SilverGold;
+        let data = GroupAnalysisData {
+            win: true,
+            game_duration_secs: 2100,
+            game_mode: "CLASSIC".to_string(),
+            players: vec![jungler, sample_analysis_data()],
+            allies: Vec::new(),
+            enemies: Vec::new(),
+        };
+        let result = pipeline.analyze_group(&data, Locale::Fr, None).await;
+
+        assert!(result.error.is_none());
+        assert_eq!(result.champion_name, "Vi · Ahri");
+        assert_eq!(result.overall_rating.as_deref(), Some("Average"));
+        let prompts = backend.prompts();
+        assert!(prompts[0].starts_with("Group prompt (Argent / Or, Platine / Émeraude): "));
+        assert!(prompts[0].contains("\"Vi\""));
+    }
+
+    #[tokio::test]
+    async fn coach_progression_sends_the_report_to_the_progression_prompt() {
+        let dir = TempDir::new().unwrap();
+        write_role_files(&dir);
+        fs::write(
+            dir.path().join("progression.md"),
+            "Progression prompt in {response_language}: {game_data}",
+        )
+        .unwrap();
+        let backend = Arc::new(ScriptedBackend::new().reply("  Tu progresses.  "));
+        let pipeline =
+            AnalysisPipeline::new(backend.clone(), dir.path().to_str().unwrap()).unwrap();
+        let report = ProgressionReport {
+            summoner_name: "Test#EUW".to_string(),
+            start: chrono::Utc::now(),
+            end: chrono::Utc::now(),
+            games: 4,
+            wins: 3,
+            ratings: Default::default(),
+            metrics: Vec::new(),
+            most_improved: None,
+            most_regressed: None,
+            coaching: None,
+            engine: None,
+        };
+
+        let coaching = pipeline.coach_progression(&report, Locale::En).await;
+
+        assert_eq!(coaching.as_deref(), Some("Tu progresses."));
+        let prompt = &backend.prompts()[0];
+        assert!(prompt.starts_with("Progression prompt in anglais: "));
+        assert!(prompt.contains("\"wins\": 3"));
+
+        fs::remove_file(dir.path().join("progression.md")).unwrap();
+        let without_prompt =
+            AnalysisPipeline::new(backend.clone(), dir.path().to_str().unwrap()).unwrap();
+        assert!(
+            without_prompt
+                .coach_progression(&report, Locale::En)
+                .await
+                .is_none()
+        );
+        assert_eq!(backend.prompts().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn reload_swaps_in_edited_prompts_with_a_new_version() {
+        let dir = TempDir::new().unwrap();
+        write_role_files(&dir);
+        write_skill_files(&dir);
+        let backend = Arc::new(ScriptedBackend::new().reply("Good").reply("Good"));
+        let pipeline =
+            AnalysisPipeline::new(backend.clone(), dir.path().to_str().unwrap()).unwrap();
+        let worker_copy = pipeline.clone();
+        let data = sample_analysis_data();
+
+        let before = pipeline.cache_key(&data, Locale::Fr);
+        pipeline.analyze_game(&data, Locale::Fr).await;
+        fs::write(dir.path().join("middle.md"), "Mid lane v2: {game_data}").unwrap();
+        pipeline.reload().unwrap();
+        worker_copy.analyze_game(&data, Locale::Fr).await;
+
+        let prompts = backend.prompts();
+        assert!(prompts[1].starts_with("Mid lane v2: "));
+        assert_ne!(
+            before.prompt_hash,
+            worker_copy.cache_key(&data, Locale::Fr).prompt_hash
+        );
+    }
+
+    #[test]
+    fn reload_keeps_the_prompts_when_the_edit_is_invalid() {
+        let dir = TempDir::new().unwrap();
+        write_role_files(&dir);
+        write_skill_files(&dir);
+        let pipeline = make_pipeline(&dir);
+        let middle = || pipeline.get_prompt_for_role("MIDDLE", TierBand::PlatinumEmerald, None);
+        let original = middle();
+        let skill = dir.path().join("skills").join("deaths.md");
+
+        let rejected = |expected: &str| match pipeline.reload() {
+            Err(AnalysisError::InvalidPrompts(problems)) => {
+                assert!(problems.contains(expected), "{problems}")
+            }
+            other => panic!("expected rejected prompts, got {other:?}"),
+        };
+
+        fs::write(&skill, "### deaths\n\n{benchmark}\n").unwrap();
+        rejected("unknown placeholder {benchmark}");
+        assert_eq!(middle(), original);
+
+        fs::remove_file(&skill).unwrap();
+        rejected("skills/deaths.md is missing");
+        assert_eq!(middle(), original);
+
+        write_skill_files(&dir);
+        fs::write(
+            dir.path().join("middle.md"),
+            "Mid lane: {game_data}\n\nAgain: {game_data}",
+        )
+        .unwrap();
+        rejected("middle.md: {game_data} appears 2 times");
+        assert_eq!(middle(), original);
+    }
+
+    #[test]
+    fn repo_prompts_pass_the_reload_checks() {
+        let (_, problems) = PromptSet::load(Path::new("analysis_prompts")).unwrap();
+        assert!(problems.is_empty(), "{problems:?}");
+    }
+
+    #[test]
+    fn placeholders_skip_json_braces() {
+        let prompt = r#"{game_data} {"rating": "Good"} {} {Tier} {tier_band}"#;
+        assert_eq!(
+            placeholders(prompt).collect::<Vec<_>>(),
+            ["game_data", "tier_band"]
+        );
+    }
+
+    #[test]
+    fn cache_key_follows_the_prompt_files() {
+        let dir = TempDir::new().unwrap();
+        write_role_files(&dir);
+        let data = sample_analysis_data();
+        let key = make_pipeline(&dir).cache_key(&data, Locale::Fr);
+        assert_eq!(make_pipeline(&dir).cache_key(&data, Locale::Fr), key);
+        assert_eq!(key.model, "scripted");
+        assert_eq!(key.prompt_hash.len(), 16);
+
+        fs::write(dir.path().join("middle.md"), "Mid lane v2: {game_data}").unwrap();
+        let edited = make_pipeline(&dir).cache_key(&data, Locale::Fr);
+        assert_ne!(edited.prompt_hash, key.prompt_hash);
+
+        let anchored = make_pipeline(&dir).with_stats_anchor(true);
+        assert_ne!(anchored.cache_key(&data, Locale::Fr), edited);
+    }
+
+    #[test]
+    fn group_analysis_needs_a_group_prompt() {
+        let dir = TempDir::new().unwrap();
+        write_role_files(&dir);
+        assert!(!make_pipeline(&dir).supports_group());
+    }
+
+    #[tokio::test]
+    async fn analyze_game_reports_backend_errors() {
+        let dir = TempDir::new().unwrap();
+        write_role_files(&dir);
+        let backend = Arc::new(ScriptedBackend::new().fail(LlmError::Timeout));
+        let pipeline = AnalysisPipeline::new(backend, dir.path().to_str().unwrap()).unwrap();
+
+        let result = pipeline
+            .analyze_game(&sample_analysis_data(), Locale::En)
+            .await;
+
+        assert_eq!(result.error.as_deref(), Some("LLM API request timed out"));
+        assert!(result.overall_rating.is_none());
+    }
+
+    #[tokio::test]
+    async fn analyze_game_streaming_forwards_the_answer() {
+        let dir = TempDir::new().unwrap();
+        write_role_files(&dir);
+        let backend = Arc::new(ScriptedBackend::new().reply("Good. Belle partie."));
+        let pipeline = AnalysisPipeline::new(backend, dir.path().to_str().unwrap()).unwrap();
+        let (progress, mut chunks) = tokio::sync::mpsc::unbounded_channel();
+
+        let result = pipeline
+            .analyze_game_streaming(&sample_analysis_data(), Locale::Fr, progress)
+            .await;
+
+        assert_eq!(result.overall_rating.as_deref(), Some("Good"));
+        assert_eq!(chunks.recv().await.as_deref(), Some("Good. Belle partie."));
+        assert_eq!(chunks.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn stats_anchor_adds_the_verdict_to_the_game_data() {
+        let dir = TempDir::new().unwrap();
+        write_role_files(&dir);
+        let backend = Arc::new(ScriptedBackend::new().reply("Good.").reply("Good."));
+        let plain = AnalysisPipeline::new(backend.clone(), dir.path().to_str().unwrap()).unwrap();
+        let anchored = plain.clone().with_stats_anchor(true);
+
+        plain
+            .analyze_game(&sample_analysis_data(), Locale::Fr)
+            .await;
+        anchored
+            .analyze_game(&sample_analysis_data(), Locale::Fr)
+            .await;
+
+        let prompts = backend.prompts();
+        assert!(!prompts[0].contains("stats_verdict"));
+        assert!(prompts[1].contains("\"stats_verdict\""));
+    }
+
+    #[tokio::test]
+    async fn analyze_game_reads_structured_answers() {
+        let dir = TempDir::new().unwrap();
+        write_role_files(&dir);
+        let answer = r#"{"rating":"Average","summary":"Lane perdue, bon mid game.","strengths":["KP 65 %"],"weaknesses":["-10 CS à 10 min"],"skills":[],"key_tip":"Reste à moins de 10 CS d'écart à 10 min."}"#;
+        let backend = Arc::new(ScriptedBackend::new().reply(answer));
+        let pipeline = AnalysisPipeline::new(backend, dir.path().to_str().unwrap()).unwrap();
+
+        let result = pipeline
+            .analyze_game(&sample_analysis_data(), Locale::Fr)
+            .await;
+
+        assert_eq!(result.overall_rating.as_deref(), Some("Average"));
+        assert_eq!(result.summary, "Lane perdue, bon mid game.");
+        assert_eq!(result.strengths, ["KP 65 %"]);
+        assert_eq!(result.weaknesses, ["-10 CS à 10 min"]);
+        assert_eq!(
+            result.key_tip.as_deref(),
+            Some("Reste à moins de 10 CS d'écart à 10 min.")
+        );
+    }
 }
@@ -0,0 +1,398 @@
+//! Progression report: a player's trend over a date range, read from their
+//! `analysis_history` snapshots — rating distribution, moving averages of a
+//! few key stats, the skills that moved the most and, with a coach, a
+//! paragraph on the trend.
+//!
+//! Requested with `/progression` and posted with the weekly digest.
+
+use crate::analysis::models::AnalysisData;
+use crate::analysis::pipeline::AnalysisPipeline;
+use crate::analysis::scoring::score;
+use crate::db::models::AnalysisHistoryEntry;
+use crate::db::repository::{Repository, RepositoryError};
+use crate::i18n::{Catalog, Locale};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Games averaged by each point of a moving average.
+pub const MOVING_AVERAGE_WINDOW: usize = 3;
+/// Fewer analyzed games than this make no trend.
+pub const MIN_GAMES: usize = 3;
+/// Longest range a report covers; older snapshots may have been pruned.
+pub const MAX_DAYS: i64 = 90;
+
+/// Stats followed game after game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Metric {
+    CsPerMinute,
+    Deaths,
+    VisionScorePerMinute,
+    CsDiffAt10,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 4] = [
+        Metric::CsPerMinute,
+        Metric::Deaths,
+        Metric::VisionScorePerMinute,
+        Metric::CsDiffAt10,
+    ];
+
+    fn value(self, data: &AnalysisData) -> Option<f32> {
+        match self {
+            Metric::CsPerMinute => (data.game_duration_secs > 0)
+                .then(|| data.total_cs as f32 / (data.game_duration_secs as f32 / 60.0)),
+            Metric::Deaths => Some(data.deaths as f32),
+            Metric::VisionScorePerMinute => data.vision_score_per_minute,
+            Metric::CsDiffAt10 => data.cs_diff_at_10.map(|diff| diff as f32),
+        }
+    }
+
+    pub fn label(self, text: &Catalog) -> &'static str {
+        match self {
+            Metric::CsPerMinute => text.skill_cs_per_minute,
+            Metric::Deaths => text.skill_deaths,
+            Metric::VisionScorePerMinute => text.skill_vision_score,
+            Metric::CsDiffAt10 => text.metric_cs_diff_at_10,
+        }
+    }
+
+    pub fn format(self, value: f32) -> String {
+        match self {
+            Metric::CsDiffAt10 => format!("{value:+.0}"),
+            _ => format!("{value:.1}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricTrend {
+    pub metric: Metric,
+    pub average: f32,
+    /// Average of the last [`MOVING_AVERAGE_WINDOW`] games at each game
+    /// where the stat is known, oldest first.
+    pub moving_averages: Vec<f32>,
+}
+
+impl MetricTrend {
+    pub fn first(&self) -> f32 {
+        self.moving_averages
+            .first()
+            .copied()
+            .unwrap_or(self.average)
+    }
+
+    pub fn last(&self) -> f32 {
+        self.moving_averages.last().copied().unwrap_or(self.average)
+    }
+}
+
+/// Change of a graded skill between the first and the second half of the
+/// period, in grade points (Good = 2, Average = 1, Poor = 0).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkillTrend {
+    pub skill: &'static str,
+    pub before: f32,
+    pub after: f32,
+}
+
+impl SkillTrend {
+    pub fn change(&self) -> f32 {
+        self.after - self.before
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RatingDistribution {
+    pub good: u32,
+    pub average: u32,
+    pub poor: u32,
+    /// Games stored without a rating (coach answer without one).
+    pub unrated: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProgressionReport {
+    pub summoner_name: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub games: u32,
+    pub wins: u32,
+    pub ratings: RatingDistribution,
+    pub metrics: Vec<MetricTrend>,
+    pub most_improved: Option<SkillTrend>,
+    pub most_regressed: Option<SkillTrend>,
+    /// The coach's paragraph on the trend; `None` without a coach or when
+    /// it failed.
+    #[serde(skip)]
+    pub coaching: Option<String>,
+    /// Model and backend that wrote `coaching`.
+    #[serde(skip)]
+    pub engine: Option<String>,
+}
+
+/// Builds the report of the games stored in `entries` (any order). `None`
+/// with fewer than [`MIN_GAMES`] readable snapshots.
+pub fn build_report(
+    summoner_name: &str,
+    entries: &[AnalysisHistoryEntry],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Option<ProgressionReport> {
+    let mut entries: Vec<&AnalysisHistoryEntry> = entries.iter().collect();
+    entries.sort_by_key(|entry| entry.created_at);
+
+    let games: Vec<(&AnalysisHistoryEntry, AnalysisData)> = entries
+        .into_iter()
+        .filter_map(|entry| match serde_json::from_str(&entry.analysis_data) {
+            Ok(data) => Some((entry, data)),
+            Err(error) => {
+                tracing::warn!(
+                    match_id = entry.match_id.as_str(),
+                    error = %error,
+                    "Skipping unparseable analysis history snapshot"
+                );
+                None
+            }
+        })
+        .collect();
+    if games.len() < MIN_GAMES {
+        return None;
+    }
+
+    let mut ratings = RatingDistribution::default();
+    for (entry, _) in &games {
+        match entry.overall_rating.as_deref() {
+            Some(r) if r.eq_ignore_ascii_case("good") => ratings.good += 1,
+            Some(r) if r.eq_ignore_ascii_case("average") => ratings.average += 1,
+            Some(r) if r.eq_ignore_ascii_case("poor") => ratings.poor += 1,
+            _ => ratings.unrated += 1,
+        }
+    }
+
+    let snapshots: Vec<&AnalysisData> = games.iter().map(|(_, data)| data).collect();
+    let (most_improved, most_regressed) = skill_extremes(&snapshots);
+
+    Some(ProgressionReport {
+        summoner_name: summoner_name.to_string(),
+        start,
+        end,
+        games: games.len() as u32,
+        wins: games.iter().filter(|(entry, _)| entry.win).count() as u32,
+        ratings,
+        metrics: Metric::ALL
+            .into_iter()
+            .filter_map(|metric| metric_trend(metric, &snapshots))
+            .collect(),
+        most_improved,
+        most_regressed,
+        coaching: None,
+        engine: None,
+    })
+}
+
+/// Builds `riot_puuid`'s report over `[start, end)` and asks the coach for
+/// its paragraph when there is one. `Ok(None)` when there are not enough
+/// analyzed games.
+pub async fn progression_report<D: Repository + ?Sized>(
+    repository: &D,
+    pipeline: Option<&AnalysisPipeline>,
+    summoner_name: &str,
+    riot_puuid: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    locale: Locale,
+) -> Result<Option<ProgressionReport>, RepositoryError> {
+    let entries = repository
+        .get_analysis_history_between(riot_puuid, start, end)
+        .await?;
+    let Some(mut report) = build_report(summoner_name, &entries, start, end) else {
+        return Ok(None);
+    };
+    if let Some(pipeline) = pipeline {
+        report.coaching = pipeline.coach_progression(&report, locale).await;
+        report.engine = report.coaching.is_some().then(|| pipeline.engine());
+    }
+    Ok(Some(report))
+}
+
+fn metric_trend(metric: Metric, games: &[&AnalysisData]) -> Option<MetricTrend> {
+    let values: Vec<f32> = games.iter().filter_map(|data| metric.value(data)).collect();
+    if values.is_empty() {
+        return None;
+    }
+    let window = MOVING_AVERAGE_WINDOW.min(values.len());
+    let moving_averages = values.windows(window).map(mean).collect();
+    Some(MetricTrend {
+        metric,
+        average: mean(&values),
+        moving_averages,
+    })
+}
+
+/// The skills whose grade rose and fell the most between the two halves of
+/// the period. Skills graded in fewer than two games are left out.
+fn skill_extremes(games: &[&AnalysisData]) -> (Option<SkillTrend>, Option<SkillTrend>) {
+    let mut points: BTreeMap<&'static str, Vec<f32>> = BTreeMap::new();
+    for data in games {
+        for skill in score(data)
+            .map(|verdict| verdict.skills)
+            .unwrap_or_default()
+        {
+            points
+                .entry(skill.skill)
+                .or_default()
+                .push(skill.grade.points());
+        }
+    }
+
+    let trends: Vec<SkillTrend> = points
+        .into_iter()
+        .filter(|(_, points)| points.len() >= 2)
+        .map(|(skill, points)| {
+            let (before, after) = points.split_at(points.len() / 2);
+            SkillTrend {
+                skill,
+                before: mean(before),
+                after: mean(after),
+            }
+        })
+        .collect();
+
+    let improved = trends
+        .iter()
+        .filter(|trend| trend.change() > 0.0)
+        .max_by(|a, b| a.change().total_cmp(&b.change()))
+        .cloned();
+    let regressed = trends
+        .iter()
+        .filter(|trend| trend.change() < 0.0)
+        .min_by(|a, b| a.change().total_cmp(&b.change()))
+        .cloned();
+    (improved, regressed)
+}
+
+fn mean(values: &[f32]) -> f32 {
+    values.iter().sum::<f32>() / values.len().max(1) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn game(total_cs: i32, kill_participation: f32) -> AnalysisData {
+        AnalysisData {
+            total_cs,
+            kill_participation: Some(kill_participation),
+            ..AnalysisData::sample("BOTTOM")
+        }
+    }
+
+    fn entry(day: u32, rating: Option<&str>, data: &AnalysisData) -> AnalysisHistoryEntry {
+        AnalysisHistoryEntry {
+            id: Uuid::new_v4(),
+            riot_puuid: "puuid".to_string(),
+            match_id: format!("EUW1_{day}"),
+            role: data.role.clone(),
+            champion_name: data.champion_name.clone(),
+            win: day.is_multiple_of(2),
+            overall_rating: rating.map(str::to_string),
+            analysis_data: serde_json::to_string(data).unwrap(),
+            analysis_result: None,
+            prompt_hash: None,
+            model: None,
+            created_at: Utc.with_ymd_and_hms(2026, 10, day, 20, 0, 0).unwrap(),
+        }
+    }
+
+    fn report(entries: &[AnalysisHistoryEntry]) -> Option<ProgressionReport> {
+        build_report(
+            "Test#EUW",
+            entries,
+            Utc.with_ymd_and_hms(2026, 10, 12, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 10, 19, 0, 0, 0).unwrap(),
+        )
+    }
+
+    #[test]
+    fn counts_ratings_and_wins() {
+        let data = game(200, 0.5);
+        let report = report(&[
+            entry(12, Some("Good"), &data),
+            entry(13, Some("poor"), &data),
+            entry(14, None, &data),
+            entry(15, Some("Average"), &data),
+        ])
+        .unwrap();
+
+        assert_eq!(report.games, 4);
+        assert_eq!(report.wins, 2);
+        assert_eq!(
+            report.ratings,
+            RatingDistribution {
+                good: 1,
+                average: 1,
+                poor: 1,
+                unrated: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn moving_averages_follow_the_games_in_order() {
+        // Stored out of order: 150 CS (5/min) twice, then 255 (8.5/min) twice.
+        let report = report(&[
+            entry(16, None, &game(255, 0.5)),
+            entry(12, None, &game(150, 0.5)),
+            entry(15, None, &game(255, 0.5)),
+            entry(13, None, &game(150, 0.5)),
+        ])
+        .unwrap();
+
+        let cs = &report.metrics[0];
+        assert_eq!(cs.metric, Metric::CsPerMinute);
+        assert_eq!(cs.moving_averages.len(), 2);
+        assert!((cs.first() - 6.17).abs() < 0.01);
+        assert!((cs.last() - 7.33).abs() < 0.01);
+        assert!((cs.average - 6.75).abs() < 0.01);
+        // No lane opponent data: no CS diff trend.
+        assert!(
+            report
+                .metrics
+                .iter()
+                .all(|m| m.metric != Metric::CsDiffAt10)
+        );
+    }
+
+    #[test]
+    fn finds_the_most_improved_and_regressed_skills() {
+        let report = report(&[
+            entry(12, None, &game(150, 0.7)),
+            entry(13, None, &game(150, 0.7)),
+            entry(14, None, &game(255, 0.3)),
+            entry(15, None, &game(255, 0.3)),
+        ])
+        .unwrap();
+
+        let improved = report.most_improved.unwrap();
+        assert_eq!(improved.skill, "cs_per_minute");
+        assert_eq!((improved.before, improved.after), (0.0, 2.0));
+        let regressed = report.most_regressed.unwrap();
+        assert_eq!(regressed.skill, "kills_assists");
+        assert_eq!(regressed.change(), -2.0);
+    }
+
+    #[test]
+    fn too_few_games_make_no_report() {
+        let data = game(200, 0.5);
+        assert!(report(&[entry(12, None, &data), entry(13, None, &data)]).is_none());
+
+        let mut unreadable = entry(14, None, &data);
+        unreadable.analysis_data = "{".to_string();
+        assert!(report(&[entry(12, None, &data), entry(13, None, &data), unreadable]).is_none());
+    }
+}
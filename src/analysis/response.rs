@@ -0,0 +1,230 @@
+//! Structured answer of the coach: the JSON schema the LLM server is asked
+//! to follow, and the parser turning an answer into `AnalysisResult` fields.
+
+use crate::analysis::models::SkillVerdict;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::sync::LazyLock;
+
+const RATINGS: [&str; 3] = ["Good", "Average", "Poor"];
+
+/// Typed form of an answer that follows [`response_schema`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StructuredAnalysis {
+    pub rating: String,
+    pub summary: String,
+    #[serde(default)]
+    pub strengths: Vec<String>,
+    #[serde(default)]
+    pub weaknesses: Vec<String>,
+    #[serde(default)]
+    pub skills: Vec<SkillVerdict>,
+    pub key_tip: String,
+}
+
+/// JSON schema of the answer, sent along with the prompt so servers with
+/// constrained decoding (llama.cpp grammars, Ollama `format`) cannot drift
+/// from it.
+pub fn response_schema() -> &'static Value {
+    &RESPONSE_SCHEMA
+}
+
+static RESPONSE_SCHEMA: LazyLock<Value> = LazyLock::new(|| {
+    json!({
+        "type": "object",
+        "properties": {
+            "rating": { "type": "string", "enum": RATINGS },
+            "summary": { "type": "string" },
+            "strengths": {
+                "type": "array",
+                "items": { "type": "string" },
+                "maxItems": 3
+            },
+            "weaknesses": {
+                "type": "array",
+                "items": { "type": "string" },
+                "maxItems": 3
+            },
+            "skills": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "skill": { "type": "string" },
+                        "rating": { "type": "string", "enum": RATINGS },
+                        "comment": { "type": "string" }
+                    },
+                    "required": ["skill", "rating", "comment"],
+                    "additionalProperties": false
+                }
+            },
+            "key_tip": { "type": "string" }
+        },
+        "required": ["rating", "summary", "strengths", "weaknesses", "skills", "key_tip"],
+        "additionalProperties": false
+    })
+});
+
+/// Parses a structured answer. Models without constrained decoding tend to
+/// wrap the object in a Markdown fence or a sentence, so only the outermost
+/// `{...}` is read. Returns `None` when the answer is not valid JSON, has an
+/// unknown rating or an empty summary — the caller then falls back to
+/// reading it as free text.
+pub fn parse_structured(text: &str) -> Option<StructuredAnalysis> {
+    let start = text.find('{')?;
+    let end = text.rfind('}')?;
+    if end < start {
+        return None;
+    }
+
+    let mut parsed: StructuredAnalysis = serde_json::from_str(&text[start..=end]).ok()?;
+    parsed.rating = normalize_rating(&parsed.rating)?;
+    if parsed.summary.trim().is_empty() {
+        return None;
+    }
+    for verdict in &mut parsed.skills {
+        verdict.rating = normalize_rating(&verdict.rating)?;
+    }
+    parsed.strengths.retain(|s| !s.trim().is_empty());
+    parsed.weaknesses.retain(|s| !s.trim().is_empty());
+    Some(parsed)
+}
+
+/// Readable part of an answer still being generated: the `summary` value
+/// decoded so far for a JSON answer, or the text itself for a free-text one.
+/// Like [`parse_structured`], the object may follow a sentence or a Markdown
+/// fence; an opened fence hides the text until the object arrives.
+pub fn partial_summary(text: &str) -> String {
+    let Some(start) = text.find('{') else {
+        if text.trim_start().starts_with('`') {
+            return String::new();
+        }
+        return text.to_string();
+    };
+    let text = &text[start..];
+    let Some(key) = text.find("\"summary\"") else {
+        return String::new();
+    };
+    let rest = text[key + "\"summary\"".len()..].trim_start();
+    let Some(rest) = rest.strip_prefix(':') else {
+        return String::new();
+    };
+    let Some(value) = rest.trim_start().strip_prefix('"') else {
+        return String::new();
+    };
+
+    let mut out = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' => match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                        Some(decoded) if hex.len() == 4 => out.push(decoded),
+                        _ => break,
+                    }
+                }
+                Some(other) => out.push(other),
+                None => break,
+            },
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn normalize_rating(rating: &str) -> Option<String> {
+    RATINGS
+        .iter()
+        .find(|known| known.eq_ignore_ascii_case(rating.trim()))
+        .map(|known| known.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWER: &str = r#"{
+        "rating": "good",
+        "summary": "Lane gagnée et convertie.",
+        "strengths": ["+15 CS à 10 min", " "],
+        "weaknesses": ["3 morts isolées"],
+        "skills": [{"skill": "CS/min", "rating": "Average", "comment": "7,1 CS/min"}],
+        "key_tip": "Garde une ward dans la rivière avant de push."
+    }"#;
+
+    #[test]
+    fn parses_a_schema_answer() {
+        let parsed = parse_structured(ANSWER).expect("structured answer");
+        assert_eq!(parsed.rating, "Good");
+        assert_eq!(parsed.summary, "Lane gagnée et convertie.");
+        assert_eq!(parsed.strengths, ["+15 CS à 10 min"]);
+        assert_eq!(parsed.weaknesses, ["3 morts isolées"]);
+        assert_eq!(
+            parsed.skills,
+            [SkillVerdict {
+                skill: "CS/min".to_string(),
+                rating: "Average".to_string(),
+                comment: "7,1 CS/min".to_string(),
+            }]
+        );
+        assert_eq!(
+            parsed.key_tip,
+            "Garde une ward dans la rivière avant de push."
+        );
+    }
+
+    #[test]
+    fn reads_the_object_inside_a_markdown_fence() {
+        let fenced = format!("Voici l'analyse :\n```json\n{ANSWER}\n```");
+        assert!(parse_structured(&fenced).is_some());
+    }
+
+    #[test]
+    fn rejects_free_text_and_unknown_ratings() {
+        assert!(parse_structured("Good. Belle partie, continue comme ça.").is_none());
+        assert!(parse_structured(&ANSWER.replace("\"good\"", "\"Great\"")).is_none());
+        assert!(parse_structured(r#"{"rating": "Poor", "summary": "x"}"#).is_none());
+    }
+
+    #[test]
+    fn partial_summary_decodes_the_summary_so_far() {
+        assert_eq!(
+            partial_summary(r#"{"rating": "Good", "summary": "Lane \"propre\",\nmais"#),
+            "Lane \"propre\",\nmais"
+        );
+        assert_eq!(
+            partial_summary(r#"{"rating":"Good","summary":"fini","strengths":["#),
+            "fini"
+        );
+        assert_eq!(partial_summary(r#"{"rating": "Go"#), "");
+        assert_eq!(partial_summary(r#"{"summary": "caf\u00e9 \u00"#), "café ");
+        assert_eq!(partial_summary("Good. Belle partie"), "Good. Belle partie");
+    }
+
+    #[test]
+    fn partial_summary_reads_a_fenced_answer() {
+        assert_eq!(partial_summary("```json\n"), "");
+        assert_eq!(
+            partial_summary("```json\n{\"rating\": \"Good\", \"summary\": \"Lane gag"),
+            "Lane gag"
+        );
+        assert_eq!(
+            partial_summary("Voici l'analyse :\n```json\n{\"rating\": \"Go"),
+            ""
+        );
+    }
+
+    #[test]
+    fn schema_requires_every_field() {
+        let schema = response_schema();
+        let required = schema["required"].as_array().unwrap();
+        for field in schema["properties"].as_object().unwrap().keys() {
+            assert!(required.contains(&json!(field)), "{field} is not required");
+        }
+    }
+}
@@ -8,7 +8,17 @@
 //!
 //! The benchmarks below target the Platine/Émeraude range; they are
 //! intentionally narrative (paragraph-style French) because they're spliced
-//! verbatim into the prompt body sent to the LLM.
+//! verbatim into the prompt body sent to the LLM. Other [`TierBand`]s get
+//! their thresholds shifted by [`BAND_ADJUSTMENTS`] and a generated
+//! benchmark table instead.
+//!
+//! The importances are the role's defaults; the champion's [`ChampionClass`]
+//! can shift them (see [`SkillBinding::importance_for`]).
+
+use crate::analysis::classes::ChampionClass;
+use crate::i18n::Catalog;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 /// How much weight a role places on a given metric. Drives both
 /// inclusion (`NotApplicable` skips the skill entirely) and framing in
@@ -35,18 +45,224 @@ impl SkillImportance {
     }
 }
 
+/// Ranked tier band a player is graded against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TierBand {
+    IronBronze,
+    SilverGold,
+    /// Band the narrative benchmarks were written for; also used for
+    /// unranked players.
+    #[default]
+    PlatinumEmerald,
+    DiamondPlus,
+}
+
+impl TierBand {
+    pub const ALL: [TierBand; 4] = [
+        TierBand::IronBronze,
+        TierBand::SilverGold,
+        TierBand::PlatinumEmerald,
+        TierBand::DiamondPlus,
+    ];
+
+    /// Stored value of a per-summoner override.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TierBand::IronBronze => "iron_bronze",
+            TierBand::SilverGold => "silver_gold",
+            TierBand::PlatinumEmerald => "platinum_emerald",
+            TierBand::DiamondPlus => "diamond_plus",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|band| band.as_str() == value)
+    }
+
+    /// Band of a Riot tier (`IRON` … `CHALLENGER`).
+    pub fn from_tier(tier: &str) -> Option<Self> {
+        match tier.to_ascii_uppercase().as_str() {
+            "IRON" | "BRONZE" => Some(TierBand::IronBronze),
+            "SILVER" | "GOLD" => Some(TierBand::SilverGold),
+            "PLATINUM" | "EMERALD" => Some(TierBand::PlatinumEmerald),
+            "DIAMOND" | "MASTER" | "GRANDMASTER" | "CHALLENGER" => Some(TierBand::DiamondPlus),
+            _ => None,
+        }
+    }
+
+    /// French label used in the composed prompt.
+    pub fn label_fr(self) -> &'static str {
+        match self {
+            TierBand::IronBronze => "Fer / Bronze",
+            TierBand::SilverGold => "Argent / Or",
+            TierBand::PlatinumEmerald => "Platine / Émeraude",
+            TierBand::DiamondPlus => "Diamant et plus",
+        }
+    }
+
+    /// Name shown in `/benchmark-tier` choices and replies.
+    pub fn label(self, text: &Catalog) -> &'static str {
+        match self {
+            TierBand::IronBronze => text.tier_band_iron_bronze,
+            TierBand::SilverGold => text.tier_band_silver_gold,
+            TierBand::PlatinumEmerald => text.tier_band_platinum_emerald,
+            TierBand::DiamondPlus => text.tier_band_diamond_plus,
+        }
+    }
+
+    fn adjustment(self) -> &'static BandAdjustment {
+        &BAND_ADJUSTMENTS[self as usize]
+    }
+}
+
+/// How a band shifts the Platine/Émeraude thresholds: a ratio for volume
+/// stats, an offset for kill participation and deaths.
+pub struct BandAdjustment {
+    /// Applied to CS/min and damage/min.
+    pub volume: f32,
+    /// Applied to vision score/min, which varies the most across ranks.
+    pub vision: f32,
+    /// Added to kill participation (0-1).
+    pub kill_participation: f32,
+    /// Added to the tolerated deaths.
+    pub deaths: f32,
+}
+
+/// Indexed by [`TierBand`], lowest band first.
+pub const BAND_ADJUSTMENTS: [BandAdjustment; 4] = [
+    BandAdjustment {
+        volume: 0.75,
+        vision: 0.6,
+        kill_participation: -0.05,
+        deaths: 2.0,
+    },
+    BandAdjustment {
+        volume: 0.88,
+        vision: 0.8,
+        kill_participation: -0.03,
+        deaths: 1.0,
+    },
+    BandAdjustment {
+        volume: 1.0,
+        vision: 1.0,
+        kill_participation: 0.0,
+        deaths: 0.0,
+    },
+    BandAdjustment {
+        volume: 1.1,
+        vision: 1.2,
+        kill_participation: 0.03,
+        deaths: -1.0,
+    },
+];
+
+/// Numeric grading bounds of a skill, taken from its narrative benchmarks.
+/// Used by the stats-based verdict; the LLM only sees the narrative.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Threshold {
+    /// Good from `good`, Average from `average`, Poor below.
+    AtLeast { average: f32, good: f32 },
+    /// Good up to `good`, Average up to `average`, Poor above.
+    AtMost { good: f32, average: f32 },
+    /// The role does not grade this skill.
+    Ungraded,
+}
+
 /// Binding of a shared skill to a role-specific threshold + notes block.
 pub struct SkillBinding {
     pub skill: &'static str,
     pub importance: SkillImportance,
-    /// Substituted into the skill's `{benchmarks}` placeholder.
+    /// Platine/Émeraude bounds; see [`SkillBinding::threshold_for`].
+    pub threshold: Threshold,
+    /// Platine/Émeraude narrative, substituted into the skill's
+    /// `{benchmarks}` placeholder; see [`SkillBinding::benchmarks_for`].
     pub benchmarks: &'static str,
     /// Substituted into the skill's `{role_notes}` placeholder.
     pub role_notes: &'static str,
 }
 
+impl SkillBinding {
+    /// Importance for a champion of `class`; the role's own importance when
+    /// the class is unknown.
+    pub fn importance_for(&self, class: Option<ChampionClass>) -> SkillImportance {
+        match class {
+            Some(class) => class.adjust(self.skill, self.importance),
+            None => self.importance,
+        }
+    }
+
+    /// Grading bounds for players of `band`.
+    pub fn threshold_for(&self, band: TierBand) -> Threshold {
+        let adjustment = band.adjustment();
+        let (scale, offset) = match self.skill {
+            "cs_per_minute" | "damage_per_minute" => (adjustment.volume, 0.0),
+            "vision_score" => (adjustment.vision, 0.0),
+            "kills_assists" | "objectives" => (1.0, adjustment.kill_participation),
+            "deaths" | "death_timing" => (1.0, adjustment.deaths),
+            _ => (1.0, 0.0),
+        };
+        let shift = |value: f32| value * scale + offset;
+        match self.threshold {
+            Threshold::AtLeast { average, good } => Threshold::AtLeast {
+                average: shift(average),
+                good: shift(good),
+            },
+            Threshold::AtMost { good, average } => Threshold::AtMost {
+                good: shift(good),
+                average: shift(average),
+            },
+            Threshold::Ungraded => Threshold::Ungraded,
+        }
+    }
+
+    /// Benchmark block for players of `band`: the hand-written narrative
+    /// for Platine/Émeraude, a table generated from
+    /// [`threshold_for`](Self::threshold_for) for the other bands.
+    pub fn benchmarks_for(&self, band: TierBand, role_label: &str) -> Cow<'static, str> {
+        if band == TierBand::PlatinumEmerald {
+            return Cow::Borrowed(self.benchmarks);
+        }
+        let format = |value: f32| format_benchmark(self.skill, value);
+        let header = format!("Repères {role_label} en {} :", band.label_fr());
+        let table = match self.threshold_for(band) {
+            Threshold::AtLeast { average, good } => format!(
+                "- Poor : < {}\n- Average : {} à {}\n- Good : {} et plus",
+                format(average),
+                format(average),
+                format(good),
+                format(good)
+            ),
+            Threshold::AtMost { good, average } => format!(
+                "- Good : {} ou moins\n- Average : {} à {}\n- Poor : plus de {}",
+                format(good),
+                format(good),
+                format(average),
+                format(average)
+            ),
+            Threshold::Ungraded => return Cow::Borrowed(self.benchmarks),
+        };
+        Cow::Owned(format!("{header}\n{table}"))
+    }
+}
+
+/// French-formatted value of a skill, with its unit.
+fn format_benchmark(skill: &str, value: f32) -> String {
+    match skill {
+        "cs_per_minute" => format!("{value:.1} CS/min").replace('.', ","),
+        "damage_per_minute" => format!("{:.0} DPM", (value / 10.0).round() * 10.0),
+        "kills_assists" => format!("{:.0}% de KP", value * 100.0),
+        "deaths" => format!("{:.0} morts", value.max(0.0)),
+        "death_timing" => format!("{:.0} morts évitables", value.max(0.0)),
+        "objectives" => format!("{:.0}% de présence aux objectifs", value * 100.0),
+        _ => format!("{value:.1} vision score/min").replace('.', ","),
+    }
+}
+
 pub struct RoleSpec {
     pub riot_role: &'static str,
+    /// Name of the role in generated benchmark tables.
+    pub label_fr: &'static str,
     pub bindings: &'static [SkillBinding],
 }
 
@@ -54,34 +270,75 @@ pub const ROLE_SPECS: &[RoleSpec] = &[TOP, JUNGLE, MIDDLE, BOTTOM, UTILITY];
 
 pub const TOP: RoleSpec = RoleSpec {
     riot_role: "TOP",
+    label_fr: "Top",
     bindings: &[
         SkillBinding {
             skill: "cs_per_minute",
             importance: SkillImportance::Critical,
+            threshold: Threshold::AtLeast {
+                average: 6.0,
+                good: 7.5,
+            },
             benchmarks: "Repères Top en Platine/Émeraude :\n- Poor : < 6 CS/min sur la game\n- Average : 6-7,5 CS/min\n- Good : 7,5-9 CS/min (objectif solide)\n- Excellent : 9+ CS/min\n\nLane phase : 70-80 CS à 10 min, 130-150 CS à 20 min sont les repères pour bien jouer la lane.",
             role_notes: "En top, le CS est LA métrique de pression. Garder +10 CS sur l'adversaire à 10 min ouvre tout : roam jungle, prise de plaques, freeze gagnant.",
         },
         SkillBinding {
             skill: "damage_per_minute",
             importance: SkillImportance::Medium,
+            threshold: Threshold::AtLeast {
+                average: 450.0,
+                good: 600.0,
+            },
             benchmarks: "Repères Top en Platine/Émeraude :\n- Bruiser/fighter : 450-700 DPM attendu\n- Tank : 250-450 DPM (jugé surtout sur la KP et le frontline, pas sur le DPM)\n- Carry top (Jax, Fiora, Camille) : 600-900 DPM attendu",
             role_notes: "Un haut DPM en top sans pression de tourelle est souvent du carpet bombing inutile. Convertir = plaques, kills, roam.",
         },
         SkillBinding {
             skill: "kills_assists",
             importance: SkillImportance::Medium,
+            threshold: Threshold::AtLeast {
+                average: 0.40,
+                good: 0.45,
+            },
             benchmarks: "Repères Top en Platine/Émeraude :\n- KP attendue : 45-55% (top est la lane la plus isolée, KP plus basse que les autres rôles)\n- Une KP < 40% = jeu trop solo, lane non convertie en map pressure.\n- Une KP > 65% = sur-grouping qui coûte du CS de side.",
             role_notes: "La conversion d'avance solo lane vers la map se mesure en KP + tourelles + jungle invade. Pas juste en kills.",
         },
         SkillBinding {
             skill: "deaths",
             importance: SkillImportance::High,
+            threshold: Threshold::AtMost {
+                good: 3.0,
+                average: 6.0,
+            },
             benchmarks: "Repères Top en Platine/Émeraude :\n- 0-3 morts : excellent contrôle\n- 4-6 morts : moyen, à surveiller\n- 7+ morts : trop. Souvent dû à un overextend en lane sans vision river.",
             role_notes: "Le top mourant donne directement Drake/Herald à l'ennemi car le jungler perd son back-up. Le ward 2:50-3:00 au river est obligatoire.",
         },
+        SkillBinding {
+            skill: "death_timing",
+            importance: SkillImportance::Medium,
+            threshold: Threshold::AtMost {
+                good: 1.0,
+                average: 3.0,
+            },
+            benchmarks: "Repères Top en Platine/Émeraude :\n- 0-1 mort évitable : bonne gestion du risque en side\n- 2-3 : à surveiller, souvent des push sans vision river\n- 4+ : le top offre des kills gratuits et libère le Herald/Drake adverse",
+            role_notes: "Pour le top, les morts évitables typiques sont les split push trop profonds sans info sur le jungler adverse et les morts sous tourelle ennemie en essayant de finir un kill.",
+        },
+        SkillBinding {
+            skill: "objectives",
+            importance: SkillImportance::Medium,
+            threshold: Threshold::AtLeast {
+                average: 0.25,
+                good: 0.4,
+            },
+            benchmarks: "Repères Top en Platine/Émeraude :\n- 40 % de présence sur les monstres épiques et plus : bonnes rotations (larves, Héraut, Baron)\n- 25-40 % : correct pour un top isolé en side\n- < 25 % : le top ne participe à aucun objectif",
+            role_notes: "Le top doit jouer les larves et le Héraut avec son jungler, et arriver au Baron à temps. Ne pas critiquer une absence au dragon si le top a converti la pression en tourelles.",
+        },
         SkillBinding {
             skill: "vision_score",
             importance: SkillImportance::Low,
+            threshold: Threshold::AtLeast {
+                average: 0.5,
+                good: 0.8,
+            },
             benchmarks: "Repères Top en Platine/Émeraude :\n- Objectif : 0,8-1,2 vision score/min\n- Ne pas critiquer un faible score si le contrôle de wave et le CS sont bons.",
             role_notes: "Pour le top, la vision est secondaire derrière le tempo de lane. Mais un control ward au river après push est gratuit.",
         },
@@ -90,34 +347,75 @@ pub const TOP: RoleSpec = RoleSpec {
 
 pub const JUNGLE: RoleSpec = RoleSpec {
     riot_role: "JUNGLE",
+    label_fr: "Jungle",
     bindings: &[
         SkillBinding {
             skill: "cs_per_minute",
             importance: SkillImportance::Low,
+            threshold: Threshold::AtLeast {
+                average: 4.0,
+                good: 4.5,
+            },
             benchmarks: "Repères Jungle en Platine/Émeraude :\n- Le CS jungle vaut plus de gold/CS que la lane, mais le total est plus faible.\n- 4,5-5,5 CS/min (camps + minions) = correct.\n- Le tempo de clear (≤2:55 full clear) compte plus que le total brut.",
             role_notes: "Ne pas critiquer un faible CS pour un jungler qui a très bien gank. La métrique critique pour le jungler est la KP et le contrôle d'objectifs.",
         },
         SkillBinding {
             skill: "damage_per_minute",
             importance: SkillImportance::Medium,
+            threshold: Threshold::AtLeast {
+                average: 300.0,
+                good: 500.0,
+            },
             benchmarks: "Repères Jungle en Platine/Émeraude :\n- Carry jungle (Lee Sin, Graves, Kha'Zix) : 500-800 DPM\n- Tank/utilitaire (Amumu, Sejuani) : 250-400 DPM, jugé sur KP et présence aux objectifs",
             role_notes: "Le DPM jungle inclut les dégâts aux camps si on n'utilise pas total_damage_dealt_to_champions strictement. Toujours évaluer dans le contexte du type de champion.",
         },
         SkillBinding {
             skill: "kills_assists",
             importance: SkillImportance::Critical,
+            threshold: Threshold::AtLeast {
+                average: 0.45,
+                good: 0.55,
+            },
             benchmarks: "Repères Jungle en Platine/Émeraude :\n- KP attendue : 52-58% (le jungler doit avoir l'une des plus hautes KP de l'équipe).\n- Poor : < 45% (pathing trop passif, trop de full clear sans gank).\n- Good : 55-65%.\n- Au-delà de 70% = over-grouping et négligence du farm.",
             role_notes: "Le jungler vit et meurt par sa KP. Une KP basse + équipe qui perd la lane = pathing à reprendre depuis zéro.",
         },
         SkillBinding {
             skill: "deaths",
             importance: SkillImportance::High,
+            threshold: Threshold::AtMost {
+                good: 3.0,
+                average: 6.0,
+            },
             benchmarks: "Repères Jungle en Platine/Émeraude :\n- 0-3 morts : excellent\n- 4-6 morts : moyen, surveiller les ganks ratés et les invades sans vision\n- 7+ : pathing brisé, ennemi contre-track avec succès",
             role_notes: "Chaque mort jungle = double info : ta position est confirmée + l'ennemi peut prendre l'objectif opposé. Une mort à 3 min = Drake quasi assuré.",
         },
+        SkillBinding {
+            skill: "death_timing",
+            importance: SkillImportance::High,
+            threshold: Threshold::AtMost {
+                good: 1.0,
+                average: 3.0,
+            },
+            benchmarks: "Repères Jungle en Platine/Émeraude :\n- 0-1 mort évitable : pathing lisible seulement par ses alliés\n- 2-3 : invades ou ganks mal timés\n- 4+ : objectifs donnés à répétition",
+            role_notes: "Une mort jungle juste avant un Drake ou un Baron est la plus coûteuse du jeu : l'ennemi prend l'objectif quasiment sans contestation.",
+        },
+        SkillBinding {
+            skill: "objectives",
+            importance: SkillImportance::Critical,
+            threshold: Threshold::AtLeast {
+                average: 0.6,
+                good: 0.8,
+            },
+            benchmarks: "Repères Jungle en Platine/Émeraude :\n- 80 % de présence sur les monstres épiques et plus : contrôle exemplaire\n- 60-80 % : correct, quelques objectifs abandonnés\n- < 60 % : le jungler laisse les objectifs à l'ennemi",
+            role_notes: "Le jungler est responsable du timing de chaque objectif : chaque dragon ou Baron perdu sans lui est une question à poser. Une mort de setup d'un jungler offre presque toujours l'objectif.",
+        },
         SkillBinding {
             skill: "vision_score",
             importance: SkillImportance::High,
+            threshold: Threshold::AtLeast {
+                average: 1.0,
+                good: 1.3,
+            },
             benchmarks: "Repères Jungle en Platine/Émeraude :\n- Objectif : 1,3-1,8 vision score/min\n- < 1,0 = invisible jungle pour son équipe.\n- Le contrôle de vision autour des objectifs (90s avant spawn) > le total brut.",
             role_notes: "La vision jungle prépare les objectifs. Sweeper le pit + control ward + ward à l'entrée river ennemie = setup standard à faire 60-90s avant chaque Drake/Herald/Baron.",
         },
@@ -126,34 +424,75 @@ pub const JUNGLE: RoleSpec = RoleSpec {
 
 pub const MIDDLE: RoleSpec = RoleSpec {
     riot_role: "MIDDLE",
+    label_fr: "Mid",
     bindings: &[
         SkillBinding {
             skill: "cs_per_minute",
             importance: SkillImportance::High,
+            threshold: Threshold::AtLeast {
+                average: 6.5,
+                good: 8.0,
+            },
             benchmarks: "Repères Mid en Platine/Émeraude :\n- Poor : < 6,5 CS/min\n- Average : 6,5-8 CS/min\n- Good : 8-9 CS/min (objectif standard)\n- Excellent : 9+ CS/min\n\nLane phase : 80-90 CS à 10 min (mage de farm) ; 70-80 CS à 10 min (assassin/roamer).",
             role_notes: "Le mid est la lane la plus connectée à la map. Une avance de CS + un roam réussi = snowball maximal. Mais perdre du CS pour un roam raté = double pénalité.",
         },
         SkillBinding {
             skill: "damage_per_minute",
             importance: SkillImportance::Critical,
+            threshold: Threshold::AtLeast {
+                average: 450.0,
+                good: 650.0,
+            },
             benchmarks: "Repères Mid en Platine/Émeraude :\n- Control mage (Orianna, Viktor, Syndra) : 650-900 DPM\n- Burst mage (Syndra, Veigar, Lux) : 600-800 DPM\n- Assassin (Zed, Akali, Talon) : 450-650 DPM\n- Sous 450 DPM en tant que mid damage-oriented = trop de morts ou trop passif.",
             role_notes: "Pour le mid, le DPM EST la métrique de carry. Couplé au team_damage_percentage : un mid devrait être 1er ou 2e en dégâts d'équipe (25-32%).",
         },
         SkillBinding {
             skill: "kills_assists",
             importance: SkillImportance::High,
+            threshold: Threshold::AtLeast {
+                average: 0.45,
+                good: 0.55,
+            },
             benchmarks: "Repères Mid en Platine/Émeraude :\n- KP attendue : 50-58%\n- Poor : < 45% (mid trop passif, pas de roam)\n- Good : 55-65%\n- > 70% = over-grouping qui coûte des vagues mid",
             role_notes: "La KP mid se construit avec les roams. Un mid avec 60% KP et beaucoup d'assists = roamer efficace. Avec 60% KP et beaucoup de kills = carry qui finit les fights.",
         },
         SkillBinding {
             skill: "deaths",
             importance: SkillImportance::High,
+            threshold: Threshold::AtMost {
+                good: 3.0,
+                average: 6.0,
+            },
             benchmarks: "Repères Mid en Platine/Émeraude :\n- 0-3 morts : excellent\n- 4-6 : moyen\n- 7+ : trop, souvent dû à des all-in mal calculés ou des roams sans vision",
             role_notes: "Pour un assassin, mourir après un pick raté = double pénalité (pas de kill + shutdown donné). Pour un mage, mourir en lane à l'ennemi assassin = lane perdue pour le reste de la game.",
         },
+        SkillBinding {
+            skill: "death_timing",
+            importance: SkillImportance::Medium,
+            threshold: Threshold::AtMost {
+                good: 1.0,
+                average: 3.0,
+            },
+            benchmarks: "Repères Mid en Platine/Émeraude :\n- 0-1 mort évitable : bon placement en roam et en fin de wave\n- 2-3 : morts seules en side en mid game à surveiller\n- 4+ : le mid perd sa priorité et son équipe perd les fights de river",
+            role_notes: "Le mid doit rester disponible pour les fights de river : mourir seul en side avant un objectif retire la source principale de dégâts de l'équipe.",
+        },
+        SkillBinding {
+            skill: "objectives",
+            importance: SkillImportance::Medium,
+            threshold: Threshold::AtLeast {
+                average: 0.4,
+                good: 0.6,
+            },
+            benchmarks: "Repères Mid en Platine/Émeraude :\n- 60 % de présence sur les monstres épiques et plus : le mid rejoint ses objectifs\n- 40-60 % : correct\n- < 40 % : le mid ne push pas sa wave pour bouger",
+            role_notes: "La priorité mid est ce qui rend les objectifs possibles : pousser la wave puis rejoindre le jungler au dragon ou aux larves.",
+        },
         SkillBinding {
             skill: "vision_score",
             importance: SkillImportance::Medium,
+            threshold: Threshold::AtLeast {
+                average: 0.8,
+                good: 1.0,
+            },
             benchmarks: "Repères Mid en Platine/Émeraude :\n- Objectif : 1,0-1,5 vision score/min\n- < 0,8 = pas de wards sur les approches Drake/Herald, ce qui coûte des objectifs.",
             role_notes: "Le mid est responsable de la vision river offensive (côté Drake et Herald). Un control ward au river après un push de vague est obligatoire.",
         },
@@ -162,34 +501,75 @@ pub const MIDDLE: RoleSpec = RoleSpec {
 
 pub const BOTTOM: RoleSpec = RoleSpec {
     riot_role: "BOTTOM",
+    label_fr: "ADC",
     bindings: &[
         SkillBinding {
             skill: "cs_per_minute",
             importance: SkillImportance::Critical,
+            threshold: Threshold::AtLeast {
+                average: 6.5,
+                good: 8.0,
+            },
             benchmarks: "Repères ADC en Platine/Émeraude :\n- Poor : < 6,5 CS/min\n- Average : 6,5-8 CS/min\n- Good : 8-9 CS/min (objectif standard)\n- Excellent : 9-10+ CS/min\n\nLane phase : 80-90 CS à 10 min = correct ; 90+ = bon.",
             role_notes: "Pour l'ADC, le CS = items = scaling = late game. Chaque mort coûte ~25 CS minimum = un composant d'item. Un ADC qui reste en bot après chute T1 sans raison perd du CS comparé à un ADC qui swap mid après T1.",
         },
         SkillBinding {
             skill: "damage_per_minute",
             importance: SkillImportance::Critical,
+            threshold: Threshold::AtLeast {
+                average: 600.0,
+                good: 800.0,
+            },
             benchmarks: "Repères ADC en Platine/Émeraude :\n- Poor : < 600 DPM\n- Average : 600-800 DPM\n- Good : 800-1100 DPM\n- Excellent : 1100+ DPM\n\nL'ADC devrait être 1er en team_damage_percentage (25-35% des dégâts d'équipe).",
             role_notes: "Le DPM ADC est la métrique de succès numéro 2 après la survie. Un ADC qui inflige peu de dégâts = ADC qui est mort tôt en teamfight ou qui est resté trop à l'arrière.",
         },
         SkillBinding {
             skill: "kills_assists",
             importance: SkillImportance::High,
+            threshold: Threshold::AtLeast {
+                average: 0.45,
+                good: 0.55,
+            },
             benchmarks: "Repères ADC en Platine/Émeraude :\n- KP attendue : 50-60%\n- Poor : < 45%\n- Good : 55-65%",
             role_notes: "L'ADC se mesure plus en dégâts qu'en KP, mais une KP < 45% indique un ADC absent des fights — soit mort trop tôt, soit en train de farmer pendant les objectifs.",
         },
         SkillBinding {
             skill: "deaths",
             importance: SkillImportance::Critical,
+            threshold: Threshold::AtMost {
+                good: 3.0,
+                average: 6.0,
+            },
             benchmarks: "Repères ADC en Platine/Émeraude :\n- 0-3 morts : excellent positionnement\n- 4-6 : moyen, à surveiller (les pros font 2-3)\n- 7+ : positionnement défaillant, ADC qui ne peut pas tenir le late game",
             role_notes: "Règle #1 ADC : 0 dégâts mort > beaucoup de dégâts en mourant. Chaque mort = l'équipe perd 100% de ses dégâts soutenus pendant le respawn.",
         },
+        SkillBinding {
+            skill: "death_timing",
+            importance: SkillImportance::High,
+            threshold: Threshold::AtMost {
+                good: 1.0,
+                average: 2.0,
+            },
+            benchmarks: "Repères ADC en Platine/Émeraude :\n- 0-1 mort évitable : positionnement maîtrisé\n- 2 : à corriger, surtout en mid/late game\n- 3+ : chaque mort seule d'un ADC en late game coûte un objectif",
+            role_notes: "Un ADC qui meurt seul (face-check, farm en side sans vision) prive l'équipe de ses dégâts ; en late game c'est souvent un Baron ou la partie.",
+        },
+        SkillBinding {
+            skill: "objectives",
+            importance: SkillImportance::Medium,
+            threshold: Threshold::AtLeast {
+                average: 0.35,
+                good: 0.55,
+            },
+            benchmarks: "Repères ADC en Platine/Émeraude :\n- 55 % de présence sur les monstres épiques et plus : bonne conversion de la pression bot\n- 35-55 % : correct\n- < 35 % : l'ADC farme pendant que l'équipe combat",
+            role_notes: "L'ADC fait tomber les dragons et le Baron plus vite que personne. Son absence sur un objectif contesté se paie en fight perdu.",
+        },
         SkillBinding {
             skill: "vision_score",
             importance: SkillImportance::Low,
+            threshold: Threshold::AtLeast {
+                average: 0.6,
+                good: 0.8,
+            },
             benchmarks: "Repères ADC en Platine/Émeraude :\n- Objectif : 0,8-1,2 vision score/min\n- Ne pas critiquer un faible score, c'est le rôle du support.",
             role_notes: "L'ADC achète un control ward par recall en bot lane phase. Après ça, la vision est portée par le support et le jungler.",
         },
@@ -198,34 +578,72 @@ pub const BOTTOM: RoleSpec = RoleSpec {
 
 pub const UTILITY: RoleSpec = RoleSpec {
     riot_role: "UTILITY",
+    label_fr: "Support",
     bindings: &[
         SkillBinding {
             skill: "cs_per_minute",
             importance: SkillImportance::NotApplicable,
+            threshold: Threshold::Ungraded,
             benchmarks: "",
             role_notes: "",
         },
         SkillBinding {
             skill: "damage_per_minute",
             importance: SkillImportance::Low,
+            threshold: Threshold::AtLeast {
+                average: 150.0,
+                good: 350.0,
+            },
             benchmarks: "Repères Support en Platine/Émeraude :\n- Mage support (Brand, Zyra, Xerath) : 400-650 DPM attendu\n- Enchanteur (Lulu, Nami, Soraka) : 150-350 DPM (juge sur les heals/buffs/KP, pas sur le DPM)\n- Tank/engage (Leona, Nautilus) : 200-400 DPM",
             role_notes: "Pour la plupart des supports, le DPM est une métrique secondaire. Ne le mentionne que pour les mages supports ou si le support a porté toute l'équipe.",
         },
         SkillBinding {
             skill: "kills_assists",
             importance: SkillImportance::Critical,
+            threshold: Threshold::AtLeast {
+                average: 0.50,
+                good: 0.60,
+            },
             benchmarks: "Repères Support en Platine/Émeraude :\n- KP attendue : 60-75% (la plus haute de l'équipe).\n- Engage (Leona, Nautilus) : 65-80% attendu.\n- Enchanteur (Nami, Lulu) : 55-70%.\n- Roamer (Bard, Pyke) : 70%+ attendu.\n- < 50% KP = autopilot en lane pendant que l'équipe fight sans toi.",
             role_notes: "La KP est LA métrique du support. Les assists comptent autant que les kills — un support qui peel et permet à son ADC de carry est un excellent support.",
         },
         SkillBinding {
             skill: "deaths",
             importance: SkillImportance::High,
+            threshold: Threshold::AtMost {
+                good: 4.0,
+                average: 7.0,
+            },
             benchmarks: "Repères Support en Platine/Émeraude :\n- 0-4 morts : excellent\n- 5-7 : moyen (le support engage et meurt parfois pour sauver l'ADC — c'est attendu)\n- 8+ : trop, souvent dû à des engages 2v5 ou à chasing un kill quand l'ennemi a un escape",
             role_notes: "Une mort \"pour sauver l'ADC\" est attendue. Une mort \"pour rien\" (chase, engage 2v5) est le piège classique du support Platine.",
         },
+        SkillBinding {
+            skill: "death_timing",
+            importance: SkillImportance::Medium,
+            threshold: Threshold::AtMost {
+                good: 2.0,
+                average: 4.0,
+            },
+            benchmarks: "Repères Support en Platine/Émeraude :\n- 0-2 morts évitables : normal, le support pose la vision en premier\n- 3-4 : wards posées sans couverture ni info\n- 5+ : le support meurt seul en warding de façon répétée",
+            role_notes: "Un support qui meurt en posant un ward avant un objectif donne l'info et le numbers advantage à l'ennemi : il doit warder avec son jungler ou plus tôt.",
+        },
+        SkillBinding {
+            skill: "objectives",
+            importance: SkillImportance::High,
+            threshold: Threshold::AtLeast {
+                average: 0.55,
+                good: 0.75,
+            },
+            benchmarks: "Repères Support en Platine/Émeraude :\n- 75 % de présence sur les monstres épiques et plus : setup vision et présence exemplaires\n- 55-75 % : correct\n- < 55 % : le support n'accompagne pas son jungler",
+            role_notes: "Le support prépare les objectifs avec son jungler : vision 60-90 s avant le spawn, puis présence au pit. Une mort de setup en warding offre l'objectif.",
+        },
         SkillBinding {
             skill: "vision_score",
             importance: SkillImportance::Critical,
+            threshold: Threshold::AtLeast {
+                average: 2.0,
+                good: 2.5,
+            },
             benchmarks: "Repères Support en Platine/Émeraude :\n- Poor : < 2,0 vision score/min\n- Average : 2,0-2,5\n- Good : 2,5-3,0 (Platine), 3,0+ (Émeraude)\n- Excellent : 3,5+\n\nObjectif concret : 10-15 wards posées par partie, 2 pièces de vision à chaque objectif majeur 90s avant spawn.",
             role_notes: "Le score brut est trompeur — c'est l'emplacement qui gagne les games. Un control ward acheté à CHAQUE recall (75g) est obligatoire. Switch vers Oracle Lens (sweeper) après la complétion de la quête de rôle.",
         },
@@ -242,7 +660,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn every_role_has_all_five_skills() {
+    fn every_role_binds_every_skill() {
         for spec in ROLE_SPECS {
             let skills: Vec<&str> = spec.bindings.iter().map(|b| b.skill).collect();
             for required in &[
@@ -250,6 +668,8 @@ mod tests {
                 "damage_per_minute",
                 "kills_assists",
                 "deaths",
+                "death_timing",
+                "objectives",
                 "vision_score",
             ] {
                 assert!(
@@ -273,6 +693,118 @@ mod tests {
         assert!(spec_for("INVALID").is_none());
     }
 
+    #[test]
+    fn thresholds_are_ordered_and_match_importance() {
+        for spec in ROLE_SPECS {
+            for binding in spec.bindings {
+                match binding.threshold {
+                    Threshold::AtLeast { average, good } => assert!(average < good),
+                    Threshold::AtMost { good, average } => assert!(good < average),
+                    Threshold::Ungraded => assert_eq!(
+                        binding.importance,
+                        SkillImportance::NotApplicable,
+                        "{} {} is applicable but ungraded",
+                        spec.riot_role,
+                        binding.skill
+                    ),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tier_bands_cover_every_ranked_tier() {
+        assert_eq!(TierBand::from_tier("IRON"), Some(TierBand::IronBronze));
+        assert_eq!(TierBand::from_tier("gold"), Some(TierBand::SilverGold));
+        assert_eq!(
+            TierBand::from_tier("EMERALD"),
+            Some(TierBand::PlatinumEmerald)
+        );
+        assert_eq!(
+            TierBand::from_tier("CHALLENGER"),
+            Some(TierBand::DiamondPlus)
+        );
+        assert_eq!(TierBand::from_tier("UNRANKED"), None);
+        for band in TierBand::ALL {
+            assert_eq!(TierBand::parse(band.as_str()), Some(band));
+        }
+    }
+
+    #[test]
+    fn thresholds_rise_with_the_tier_band() {
+        let cs = TOP
+            .bindings
+            .iter()
+            .find(|b| b.skill == "cs_per_minute")
+            .unwrap();
+        let deaths = TOP.bindings.iter().find(|b| b.skill == "deaths").unwrap();
+
+        let good_cs: Vec<f32> = TierBand::ALL
+            .iter()
+            .map(|band| match cs.threshold_for(*band) {
+                Threshold::AtLeast { good, .. } => good,
+                other => panic!("unexpected threshold {other:?}"),
+            })
+            .collect();
+        assert!(good_cs.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(cs.threshold_for(TierBand::PlatinumEmerald), cs.threshold);
+
+        let tolerated_deaths: Vec<f32> = TierBand::ALL
+            .iter()
+            .map(|band| match deaths.threshold_for(*band) {
+                Threshold::AtMost { good, .. } => good,
+                other => panic!("unexpected threshold {other:?}"),
+            })
+            .collect();
+        assert!(tolerated_deaths.windows(2).all(|pair| pair[0] > pair[1]));
+    }
+
+    #[test]
+    fn every_band_keeps_thresholds_ordered() {
+        for band in TierBand::ALL {
+            for spec in ROLE_SPECS {
+                for binding in spec.bindings {
+                    match binding.threshold_for(band) {
+                        Threshold::AtLeast { average, good } => assert!(average < good),
+                        Threshold::AtMost { good, average } => {
+                            assert!(good >= 0.0 && good < average)
+                        }
+                        Threshold::Ungraded => {}
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn benchmarks_follow_the_tier_band() {
+        let cs = BOTTOM
+            .bindings
+            .iter()
+            .find(|b| b.skill == "cs_per_minute")
+            .unwrap();
+
+        assert_eq!(
+            cs.benchmarks_for(TierBand::PlatinumEmerald, BOTTOM.label_fr),
+            cs.benchmarks
+        );
+        assert_eq!(
+            cs.benchmarks_for(TierBand::IronBronze, BOTTOM.label_fr),
+            "Repères ADC en Fer / Bronze :\n- Poor : < 4,9 CS/min\n- Average : 4,9 CS/min à 6,0 CS/min\n- Good : 6,0 CS/min et plus"
+        );
+        assert!(
+            cs.benchmarks_for(TierBand::SilverGold, BOTTOM.label_fr)
+                .starts_with("Repères ADC en Argent / Or :")
+        );
+        let diamond = UTILITY
+            .bindings
+            .iter()
+            .find(|b| b.skill == "deaths")
+            .unwrap()
+            .benchmarks_for(TierBand::DiamondPlus, UTILITY.label_fr);
+        assert!(diamond.contains("- Good : 3 morts ou moins"));
+    }
+
     #[test]
     fn support_does_not_grade_cs_per_minute() {
         let support = spec_for("UTILITY").unwrap();
@@ -317,4 +849,19 @@ mod tests {
         assert_eq!(vision.importance, SkillImportance::Critical);
         assert_eq!(ka.importance, SkillImportance::Critical);
     }
+
+    #[test]
+    fn objectives_matter_most_to_jungle_and_support() {
+        let importance = |role: &str| {
+            spec_for(role)
+                .unwrap()
+                .bindings
+                .iter()
+                .find(|b| b.skill == "objectives")
+                .unwrap()
+                .importance
+        };
+        assert_eq!(importance("JUNGLE"), SkillImportance::Critical);
+        assert_eq!(importance("UTILITY"), SkillImportance::High);
+    }
 }
@@ -0,0 +1,320 @@
+//! Stats-based verdict: grades each skill of the role against the numeric
+//! [`Threshold`]s of its [`RoleSpec`] and derives an overall rating without
+//! the LLM. Posted when the coach is not configured or keeps failing, and
+//! optionally handed to the coach as a sanity anchor.
+
+use crate::analysis::models::{AnalysisData, AnalysisResult, SkillVerdict};
+use crate::analysis::roles::{SkillImportance, Threshold, spec_for};
+use crate::i18n::Locale;
+use serde::{Deserialize, Serialize};
+
+/// Average grade points (Good = 2, Average = 1, Poor = 0) from which the
+/// overall rating is Good.
+const GOOD_FROM: f32 = 1.4;
+/// Average grade points below which the overall rating is Poor.
+const POOR_BELOW: f32 = 0.6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Grade {
+    Good,
+    Average,
+    Poor,
+}
+
+impl Grade {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Grade::Good => "Good",
+            Grade::Average => "Average",
+            Grade::Poor => "Poor",
+        }
+    }
+
+    /// Reads a rating word as the coach writes it (`Good`, `Average`,
+    /// `Poor`).
+    pub fn parse(value: &str) -> Option<Self> {
+        [Grade::Good, Grade::Average, Grade::Poor]
+            .into_iter()
+            .find(|grade| grade.as_str().eq_ignore_ascii_case(value.trim()))
+    }
+
+    pub(crate) fn points(self) -> f32 {
+        match self {
+            Grade::Good => 2.0,
+            Grade::Average => 1.0,
+            Grade::Poor => 0.0,
+        }
+    }
+}
+
+/// One graded skill.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkillScore {
+    pub skill: &'static str,
+    pub value: f32,
+    pub grade: Grade,
+    #[serde(skip)]
+    pub importance: SkillImportance,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatsVerdict {
+    pub rating: Grade,
+    pub skills: Vec<SkillScore>,
+}
+
+/// Grades `data` against its role's benchmarks. `None` when the role has
+/// no spec (ARAM, unknown position) or no skill could be measured.
+pub fn score(data: &AnalysisData) -> Option<StatsVerdict> {
+    let spec = spec_for(&data.role)?;
+
+    let skills: Vec<SkillScore> = spec
+        .bindings
+        .iter()
+        .filter_map(|binding| {
+            let value = skill_value(binding.skill, data)?;
+            let grade = grade(value, binding.threshold_for(data.tier_band))?;
+            Some(SkillScore {
+                skill: binding.skill,
+                value,
+                grade,
+                importance: binding.importance_for(data.champion_class),
+            })
+        })
+        .collect();
+
+    let total_weight: f32 = skills.iter().map(|s| weight(s.importance)).sum();
+    if total_weight == 0.0 {
+        return None;
+    }
+    let points = skills
+        .iter()
+        .map(|s| weight(s.importance) * s.grade.points())
+        .sum::<f32>()
+        / total_weight;
+
+    let mut rating = if points >= GOOD_FROM {
+        Grade::Good
+    } else if points < POOR_BELOW {
+        Grade::Poor
+    } else {
+        Grade::Average
+    };
+    // Same rule as the coach's rubric: a won game is never Poor.
+    if data.win && rating == Grade::Poor {
+        rating = Grade::Average;
+    }
+
+    Some(StatsVerdict { rating, skills })
+}
+
+/// Analysis posted in place of the coach's: the verdict, one line per
+/// graded skill, no narrative.
+pub fn stats_result(data: &AnalysisData, locale: Locale) -> Option<AnalysisResult> {
+    let verdict = score(data)?;
+    let text = locale.catalog();
+    Some(AnalysisResult {
+        summoner_name: data.summoner_name.clone(),
+        champion_name: data.champion_name.clone(),
+        overall_rating: Some(verdict.rating.as_str().to_string()),
+        summary: text.analysis_stats_summary.to_string(),
+        skill_verdicts: verdict
+            .skills
+            .iter()
+            .map(|score| SkillVerdict {
+                skill: skill_label(score.skill, locale).to_string(),
+                rating: score.grade.as_str().to_string(),
+                comment: format_value(score.skill, score.value),
+            })
+            .collect(),
+        stats_based: true,
+        benchmark_band: Some(data.tier_band.label(text).to_string()),
+        ..AnalysisResult::default()
+    })
+}
+
+fn skill_value(skill: &str, data: &AnalysisData) -> Option<f32> {
+    match skill {
+        "cs_per_minute" => {
+            (data.game_duration_secs > 0).then(|| data.total_cs as f32 / minutes(data))
+        }
+        "damage_per_minute" => data.damage_per_minute,
+        "kills_assists" => data.kill_participation,
+        "deaths" => Some(data.deaths as f32),
+        "death_timing" => data.death_summary.map(|summary| summary.costly as f32),
+        "objectives" => data.objective_summary.and_then(|summary| summary.presence),
+        "vision_score" => data.vision_score_per_minute,
+        _ => None,
+    }
+}
+
+fn minutes(data: &AnalysisData) -> f32 {
+    data.game_duration_secs as f32 / 60.0
+}
+
+fn grade(value: f32, threshold: Threshold) -> Option<Grade> {
+    match threshold {
+        Threshold::AtLeast { average, good } => Some(if value >= good {
+            Grade::Good
+        } else if value >= average {
+            Grade::Average
+        } else {
+            Grade::Poor
+        }),
+        Threshold::AtMost { good, average } => Some(if value <= good {
+            Grade::Good
+        } else if value <= average {
+            Grade::Average
+        } else {
+            Grade::Poor
+        }),
+        Threshold::Ungraded => None,
+    }
+}
+
+fn weight(importance: SkillImportance) -> f32 {
+    match importance {
+        SkillImportance::Critical => 4.0,
+        SkillImportance::High => 3.0,
+        SkillImportance::Medium => 2.0,
+        SkillImportance::Low => 1.0,
+        SkillImportance::NotApplicable => 0.0,
+    }
+}
+
+pub(crate) fn skill_label(skill: &str, locale: Locale) -> &'static str {
+    let text = locale.catalog();
+    match skill {
+        "cs_per_minute" => text.skill_cs_per_minute,
+        "damage_per_minute" => text.skill_damage_per_minute,
+        "kills_assists" => text.skill_kills_assists,
+        "deaths" => text.skill_deaths,
+        "death_timing" => text.skill_death_timing,
+        "objectives" => text.skill_objectives,
+        _ => text.skill_vision_score,
+    }
+}
+
+fn format_value(skill: &str, value: f32) -> String {
+    match skill {
+        "cs_per_minute" => format!("{value:.1} CS/min"),
+        "damage_per_minute" => format!("{value:.0} DPM"),
+        "kills_assists" => format!("{:.0}% KP", value * 100.0),
+        "objectives" => format!("{:.0}%", value * 100.0),
+        "deaths" | "death_timing" => format!("{value:.0}"),
+        _ => format!("{value:.1}/min"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::classes::ChampionClass;
+    use crate::analysis::models::DeathSummary;
+    use crate::analysis::roles::TierBand;
+
+    #[test]
+    fn strong_adc_game_is_good() {
+        let verdict = score(&AnalysisData::sample("BOTTOM")).unwrap();
+        assert_eq!(verdict.rating, Grade::Good);
+        let cs = verdict
+            .skills
+            .iter()
+            .find(|s| s.skill == "cs_per_minute")
+            .unwrap();
+        assert!((cs.value - 8.5).abs() < 1e-3);
+        assert_eq!(cs.grade, Grade::Good);
+    }
+
+    #[test]
+    fn feeding_with_low_farm_is_poor_unless_won() {
+        let mut game = AnalysisData::sample("BOTTOM");
+        game.deaths = 11;
+        game.total_cs = 150;
+        game.damage_per_minute = Some(400.0);
+        game.kill_participation = Some(0.3);
+        assert_eq!(score(&game).unwrap().rating, Grade::Poor);
+
+        game.win = true;
+        assert_eq!(score(&game).unwrap().rating, Grade::Average);
+    }
+
+    #[test]
+    fn grades_against_the_tier_band() {
+        let mut game = AnalysisData::sample("BOTTOM");
+        game.total_cs = 183;
+        game.damage_per_minute = Some(550.0);
+        game.kill_participation = Some(0.51);
+        game.deaths = 6;
+        game.vision_score_per_minute = Some(0.4);
+
+        let grades = |band: TierBand| {
+            let mut game = game.clone();
+            game.tier_band = band;
+            score(&game).unwrap()
+        };
+        assert_eq!(grades(TierBand::IronBronze).rating, Grade::Good);
+        assert_eq!(grades(TierBand::SilverGold).rating, Grade::Average);
+        assert_eq!(grades(TierBand::PlatinumEmerald).rating, Grade::Poor);
+        assert_eq!(grades(TierBand::DiamondPlus).rating, Grade::Poor);
+    }
+
+    #[test]
+    fn champion_class_reweights_the_skills() {
+        let mut game = AnalysisData::sample("TOP");
+        game.total_cs = 228;
+        game.damage_per_minute = Some(300.0);
+        game.kill_participation = Some(0.5);
+        game.deaths = 7;
+        assert_eq!(score(&game).unwrap().rating, Grade::Average);
+
+        game.champion_class = Some(ChampionClass::Tank);
+        assert_eq!(score(&game).unwrap().rating, Grade::Good);
+    }
+
+    #[test]
+    fn costly_deaths_are_graded_only_with_a_timeline() {
+        let mut game = AnalysisData::sample("BOTTOM");
+        assert!(
+            score(&game)
+                .unwrap()
+                .skills
+                .iter()
+                .all(|s| s.skill != "death_timing")
+        );
+
+        game.death_summary = Some(DeathSummary {
+            costly: 3,
+            ..DeathSummary::default()
+        });
+        let verdict = score(&game).unwrap();
+        let timing = verdict
+            .skills
+            .iter()
+            .find(|s| s.skill == "death_timing")
+            .unwrap();
+        assert_eq!(timing.grade, Grade::Poor);
+    }
+
+    #[test]
+    fn support_cs_is_not_graded() {
+        let verdict = score(&AnalysisData::sample("UTILITY")).unwrap();
+        assert!(verdict.skills.iter().all(|s| s.skill != "cs_per_minute"));
+    }
+
+    #[test]
+    fn roles_without_a_spec_are_not_scored() {
+        assert!(score(&AnalysisData::sample("")).is_none());
+    }
+
+    #[test]
+    fn stats_result_lists_each_graded_skill() {
+        let result = stats_result(&AnalysisData::sample("BOTTOM"), Locale::En).unwrap();
+        assert!(result.stats_based);
+        assert_eq!(result.benchmark_band.as_deref(), Some("Platinum / Emerald"));
+        assert_eq!(result.overall_rating.as_deref(), Some("Good"));
+        assert_eq!(result.skill_verdicts.len(), 5);
+        assert_eq!(result.skill_verdicts[0].comment, "8.5 CS/min");
+        assert_eq!(result.skill_verdicts[2].comment, "58% KP");
+    }
+}
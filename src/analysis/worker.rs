@@ -0,0 +1,504 @@
+//! Runs the post-game analyses queued in `analysis_jobs` with a bounded
+//! number of concurrent jobs, so games finishing together do not all hit
+//! the LLM server at once. Failed attempts are retried with a backoff and
+//! jobs interrupted by a restart are picked up again. Without a coach, or
+//! once it has failed every attempt, the stats-based verdict is posted.
+//! A job merged with its teammates' jobs posts one group report. During the
+//! guild's quiet hours jobs wait for the window to close, unless analyses
+//! bypass it.
+
+use crate::analysis::discord::{format_analysis_embed, format_analysis_error_embed};
+use crate::analysis::group::{GroupMember, analyze_group, group_data};
+use crate::analysis::history::{add_benchmark_context, analyze_with_memory};
+use crate::analysis::live::LiveAnalysisMessage;
+use crate::analysis::models::{AnalysisData, AnalysisResult};
+use crate::analysis::pipeline::AnalysisPipeline;
+use crate::analysis::scoring::stats_result;
+use crate::db::models::{AnalysisJob, Summoner};
+use crate::db::repository::{Repository, RepositoryError};
+use crate::i18n::Locale;
+use crate::notification::processor::ANALYSIS_GROUPING_DELAY;
+use crate::notification::quiet_hours::{ANALYSIS_EVENT_TYPE, QuietWindow};
+use crate::notification::retry::{backoff_delay, random_jitter};
+use crate::riot::client::{RiotApiClient, RiotClient};
+use chrono::{DateTime, Utc};
+use serenity::builder::{CreateEmbed, CreateMessage};
+use serenity::model::id::ChannelId;
+use serenity::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Notify, Semaphore, mpsc};
+use uuid::Uuid;
+
+/// Attempts before a job is given up and its error posted.
+pub const MAX_ATTEMPTS: i32 = 3;
+
+/// Longest sleep between two claims, in case a wake-up was missed.
+const SAFETY_SWEEP: Duration = Duration::from_secs(60);
+
+/// Where a job's analysis is posted.
+struct JobTarget {
+    summoner: Summoner,
+    channel_id: ChannelId,
+    locale: Locale,
+}
+
+pub struct AnalysisWorker {
+    repository: Arc<dyn Repository>,
+    riot_client: Arc<dyn RiotApiClient>,
+    /// `None` when no LLM is configured.
+    pipeline: Option<Arc<AnalysisPipeline>>,
+    ctx: Context,
+    region: String,
+    concurrency: usize,
+    /// Signalled when a job is queued.
+    wake: Arc<Notify>,
+}
+
+impl AnalysisWorker {
+    pub fn new(
+        repository: Arc<dyn Repository>,
+        riot_client: Arc<dyn RiotApiClient>,
+        pipeline: Option<Arc<AnalysisPipeline>>,
+        ctx: Context,
+        region: String,
+        concurrency: usize,
+        wake: Arc<Notify>,
+    ) -> Self {
+        Self {
+            repository,
+            riot_client,
+            pipeline,
+            ctx,
+            region,
+            concurrency: concurrency.max(1),
+            wake,
+        }
+    }
+
+    /// Claims due jobs as long as a slot is free, then sleeps until a job is
+    /// queued or the next retry is due.
+    pub async fn start(self) {
+        match self.repository.requeue_running_analysis_jobs().await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Requeued {} interrupted analysis jobs", count),
+            Err(e) => tracing::error!("Failed to requeue interrupted analysis jobs: {}", e),
+        }
+
+        let slots = Arc::new(Semaphore::new(self.concurrency));
+        let worker = Arc::new(self);
+        loop {
+            let slot = slots
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("analysis slots are never closed");
+
+            match worker.repository.claim_analysis_job().await {
+                Ok(Some(job)) => {
+                    let worker = worker.clone();
+                    tokio::spawn(async move {
+                        worker.run(job).await;
+                        drop(slot);
+                    });
+                    continue;
+                }
+                Ok(None) => {}
+                Err(e) => tracing::error!("Failed to claim analysis job: {}", e),
+            }
+            drop(slot);
+
+            let delay = match worker.repository.get_next_analysis_attempt().await {
+                Ok(Some(at)) => (at - Utc::now())
+                    .to_std()
+                    .unwrap_or(Duration::ZERO)
+                    .min(SAFETY_SWEEP),
+                Ok(None) => SAFETY_SWEEP,
+                Err(e) => {
+                    tracing::error!("Failed to get next analysis attempt: {}", e);
+                    SAFETY_SWEEP
+                }
+            };
+            tokio::select! {
+                _ = worker.wake.notified() => {}
+                _ = tokio::time::sleep(delay) => {}
+            }
+        }
+    }
+
+    async fn run(&self, job: AnalysisJob) {
+        if let Some(until) = self.quiet_until().await {
+            tracing::info!(
+                match_id = job.match_id.as_str(),
+                "Quiet hours, postponing analysis until {}",
+                until
+            );
+            if let Err(e) = self.repository.postpone_analysis_job(job.id, until).await {
+                tracing::error!("Failed to postpone analysis job {}: {}", job.id, e);
+            }
+            return;
+        }
+
+        let result = match self.attempt(&job).await {
+            Ok(()) => self.repository.complete_analysis_job(job.id).await,
+            Err(error) if job.attempts < MAX_ATTEMPTS => {
+                let next_attempt_at = Utc::now() + backoff_delay(job.attempts - 1, random_jitter());
+                tracing::warn!(
+                    match_id = job.match_id.as_str(),
+                    attempt = job.attempts,
+                    error = error.as_str(),
+                    "Analysis failed, retrying at {}",
+                    next_attempt_at
+                );
+                self.repository
+                    .retry_analysis_job(job.id, &error, next_attempt_at)
+                    .await
+            }
+            Err(error) => {
+                tracing::error!(
+                    match_id = job.match_id.as_str(),
+                    attempts = job.attempts,
+                    error = error.as_str(),
+                    "Analysis failed, giving up"
+                );
+                self.report_failure(&job, &error).await;
+                self.repository.fail_analysis_job(job.id, &error).await
+            }
+        };
+
+        if let Err(e) = result {
+            tracing::error!("Failed to update analysis job {}: {}", job.id, e);
+        }
+    }
+
+    /// When a job postponed by the guild's current quiet hours may run,
+    /// unless analyses bypass them: the grouping delay after the window
+    /// closes, so the quiet-hours summary merges teammates' jobs first.
+    /// Lookup failures are logged and let the analysis through.
+    async fn quiet_until(&self) -> Option<DateTime<Utc>> {
+        let lookup = async {
+            let Some(config) = self
+                .repository
+                .get_all_bot_configs()
+                .await?
+                .into_iter()
+                .next()
+            else {
+                return Ok(None);
+            };
+            self.repository.get_quiet_hours(config.guild_id).await
+        };
+        let settings = match lookup.await {
+            Ok(settings) => settings?,
+            Err(e) => {
+                tracing::error!("Failed to fetch quiet hours for analyses: {}", e);
+                return None;
+            }
+        };
+        let window = QuietWindow::from_settings(&settings)?;
+        let now = Utc::now();
+        (!window.bypasses(ANALYSIS_EVENT_TYPE) && window.contains(now)).then(|| {
+            window.next_end(now)
+                + chrono::Duration::from_std(ANALYSIS_GROUPING_DELAY).unwrap_or_default()
+        })
+    }
+
+    /// One attempt at a job. A job with nowhere to post (summoner removed,
+    /// no notification channel) succeeds without doing anything.
+    async fn attempt(&self, job: &AnalysisJob) -> Result<(), String> {
+        if job.group_summoner_ids.len() >= 2 {
+            return self.attempt_group(job).await;
+        }
+        let Some(target) = self
+            .target(job, job.summoner_id)
+            .await
+            .map_err(|e| e.to_string())?
+        else {
+            return Ok(());
+        };
+        self.attempt_single(job, &target).await
+    }
+
+    /// Analyzes one player. The answer is streamed into a placeholder
+    /// message that becomes the analysis.
+    async fn attempt_single(&self, job: &AnalysisJob, target: &JobTarget) -> Result<(), String> {
+        let summoner = &target.summoner;
+        let data = self.fetch_data(target, &job.match_id).await?;
+
+        let Some(pipeline) = &self.pipeline else {
+            let Some(result) = stats_result(&data, target.locale) else {
+                tracing::info!(
+                    "No stats benchmarks for {} in match {}, skipping analysis",
+                    data.role,
+                    job.match_id
+                );
+                return Ok(());
+            };
+            return self
+                .send(target, format_analysis_embed(&result, target.locale))
+                .await;
+        };
+        let fallback = (job.attempts >= MAX_ATTEMPTS)
+            .then(|| stats_result(&data, target.locale))
+            .flatten();
+
+        let live = LiveAnalysisMessage::post(
+            self.ctx.http.clone(),
+            target.channel_id,
+            &data.champion_name,
+            &pipeline.engine(),
+            target.locale,
+        )
+        .await
+        .map_err(|e| format!("could not send the analysis: {e}"))?;
+
+        let (progress, chunks) = mpsc::unbounded_channel();
+        let (result, ()) = tokio::join!(
+            analyze_with_memory(
+                self.repository.as_ref(),
+                pipeline,
+                data,
+                &summoner.riot_puuid,
+                &job.match_id,
+                target.locale,
+                Some(progress),
+            ),
+            live.follow(chunks)
+        );
+        let result = match (result.error.clone(), fallback) {
+            (None, _) => result,
+            (Some(error), Some(fallback)) => {
+                tracing::warn!(
+                    match_id = job.match_id.as_str(),
+                    error = error.as_str(),
+                    "Analysis failed on its last attempt, posting the stats-based verdict"
+                );
+                fallback
+            }
+            (Some(error), None) => {
+                live.discard().await;
+                return Err(error);
+            }
+        };
+
+        live.finish(format_analysis_embed(&result, target.locale))
+            .await
+            .map_err(|e| format!("could not send the analysis: {e}"))
+    }
+
+    /// Analyzes the teammates of a merged job in one report. Without a
+    /// coach able to write it, or with a single member still tracked, each
+    /// member gets their own analysis instead; members already posted by an
+    /// earlier attempt are skipped.
+    async fn attempt_group(&self, job: &AnalysisJob) -> Result<(), String> {
+        let mut targets = Vec::new();
+        for summoner_id in &job.group_summoner_ids {
+            if let Some(target) = self
+                .target(job, *summoner_id)
+                .await
+                .map_err(|e| e.to_string())?
+            {
+                targets.push(target);
+            }
+        }
+
+        let pipeline = self
+            .pipeline
+            .as_ref()
+            .filter(|pipeline| pipeline.supports_group());
+        let (Some(pipeline), [first, _, ..]) = (pipeline, targets.as_slice()) else {
+            for target in &targets {
+                let summoner_id = target.summoner.id;
+                if job.group_done_summoner_ids.contains(&summoner_id) {
+                    continue;
+                }
+                self.attempt_single(job, target).await?;
+                if let Err(e) = self
+                    .repository
+                    .mark_analysis_group_member_done(job.id, summoner_id)
+                    .await
+                {
+                    tracing::error!(
+                        "Failed to record the analysis of {} for job {}: {}",
+                        summoner_id,
+                        job.id,
+                        e
+                    );
+                }
+            }
+            return Ok(());
+        };
+        let locale = first.locale;
+
+        let mut members = Vec::new();
+        for target in &targets {
+            members.push(GroupMember {
+                riot_puuid: target.summoner.riot_puuid.clone(),
+                data: self.fetch_data(target, &job.match_id).await?,
+            });
+        }
+        let scoreboard = self
+            .riot_client
+            .get_match_scoreboard(&job.match_id, RiotClient::regional_for_region(&self.region))
+            .await
+            .map_err(|error| {
+                tracing::warn!(error = %error, "Failed to fetch match scoreboard");
+                "could not retrieve match data".to_string()
+            })?
+            .ok_or_else(|| "match data not found".to_string())?;
+        let data = group_data(&members, &scoreboard)
+            .ok_or_else(|| "players not found in the match".to_string())?;
+
+        let fallback: Vec<AnalysisResult> = if job.attempts >= MAX_ATTEMPTS {
+            members
+                .iter()
+                .filter_map(|member| stats_result(&member.data, locale))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let live = LiveAnalysisMessage::post(
+            self.ctx.http.clone(),
+            first.channel_id,
+            &data.champion_names(),
+            &pipeline.engine(),
+            locale,
+        )
+        .await
+        .map_err(|e| format!("could not send the analysis: {e}"))?;
+
+        let (progress, chunks) = mpsc::unbounded_channel();
+        let (result, ()) = tokio::join!(
+            analyze_group(
+                self.repository.as_ref(),
+                pipeline,
+                &data,
+                &members,
+                &job.match_id,
+                locale,
+                Some(progress),
+            ),
+            live.follow(chunks)
+        );
+        match result.error {
+            None => live
+                .finish(format_analysis_embed(&result, locale))
+                .await
+                .map_err(|e| format!("could not send the analysis: {e}")),
+            Some(error) if !fallback.is_empty() => {
+                tracing::warn!(
+                    match_id = job.match_id.as_str(),
+                    error = error.as_str(),
+                    "Group analysis failed on its last attempt, posting the stats-based verdicts"
+                );
+                live.discard().await;
+                for result in &fallback {
+                    self.send(first, format_analysis_embed(result, locale))
+                        .await?;
+                }
+                Ok(())
+            }
+            Some(error) => {
+                live.discard().await;
+                Err(error)
+            }
+        }
+    }
+
+    /// The target player's data for the match, with the benchmark context.
+    async fn fetch_data(&self, target: &JobTarget, match_id: &str) -> Result<AnalysisData, String> {
+        let summoner = &target.summoner;
+        let summoner_name = format!("{}#{}", summoner.game_name, summoner.tag_line);
+
+        let mut data = self
+            .riot_client
+            .get_match_analysis_data(
+                match_id,
+                &summoner.riot_puuid,
+                &summoner_name,
+                RiotClient::regional_for_region(&self.region),
+            )
+            .await
+            .map_err(|error| {
+                tracing::warn!(
+                    summoner = summoner_name.as_str(),
+                    error = %error,
+                    "Failed to fetch match analysis data"
+                );
+                "could not retrieve match data".to_string()
+            })?
+            .ok_or_else(|| "match data not found".to_string())?;
+        add_benchmark_context(
+            self.repository.as_ref(),
+            self.riot_client.as_ref(),
+            &mut data,
+            &summoner.riot_puuid,
+            &self.region,
+        )
+        .await;
+        Ok(data)
+    }
+
+    /// Posts the error of a job that ran out of attempts. Best-effort.
+    async fn report_failure(&self, job: &AnalysisJob, error: &str) {
+        let target = match self.target(job, job.summoner_id).await {
+            Ok(Some(target)) => target,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::error!("Failed to load analysis job target: {}", e);
+                return;
+            }
+        };
+        let summoner_name = format!("{}#{}", target.summoner.game_name, target.summoner.tag_line);
+        let embed = format_analysis_error_embed(&summoner_name, error, target.locale);
+        if let Err(error) = self.send(&target, embed).await {
+            tracing::error!(
+                summoner = summoner_name.as_str(),
+                error = error.as_str(),
+                "Failed to send analysis error message"
+            );
+        }
+    }
+
+    async fn send(&self, target: &JobTarget, embed: CreateEmbed) -> Result<(), String> {
+        target
+            .channel_id
+            .send_message(&self.ctx.http, CreateMessage::new().embed(embed))
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("could not send the analysis: {e}"))
+    }
+
+    /// Where the analysis of `summoner_id`, one of `job`'s players, is
+    /// posted.
+    async fn target(
+        &self,
+        job: &AnalysisJob,
+        summoner_id: Uuid,
+    ) -> Result<Option<JobTarget>, RepositoryError> {
+        let Some(summoner) = self.repository.get_summoner_by_id(summoner_id).await? else {
+            tracing::info!(
+                "Summoner of analysis job {} is no longer tracked, skipping",
+                job.id
+            );
+            return Ok(None);
+        };
+
+        let configs = self.repository.get_all_bot_configs().await?;
+        let Some(config) = configs.first() else {
+            tracing::warn!(
+                "No notification channel configured, skipping analysis for {}#{}",
+                summoner.game_name,
+                summoner.tag_line
+            );
+            return Ok(None);
+        };
+
+        Ok(Some(JobTarget {
+            summoner,
+            channel_id: ChannelId::new(config.channel_id as u64),
+            locale: config.locale(),
+        }))
+    }
+}
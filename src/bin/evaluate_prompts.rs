@@ -0,0 +1,146 @@
+//! Runs the fixture corpus through the analysis prompts and reports rating
+//! agreement, format adherence and, with `--baseline`, the regressions of
+//! the prompts against a baseline prompt directory.
+//!
+//! ```sh
+//! cargo run --bin evaluate-prompts -- --replay
+//! cargo run --bin evaluate-prompts -- --prompts my_prompts --baseline analysis_prompts
+//! ```
+//!
+//! Options:
+//! - `--fixtures DIR`: the corpus (default `analysis_fixtures`).
+//! - `--prompts DIR`: the prompts evaluated (default `analysis_prompts`).
+//! - `--baseline DIR`: prompts to compare against; needs an LLM server.
+//! - `--replay`: answer with the answers recorded in the fixtures instead of
+//!   an LLM server. The recorded answers do not depend on the prompts, so a
+//!   replay only checks the parsing and format pipeline, not the prompts,
+//!   and cannot be combined with `--baseline`.
+//!
+//! Without `--replay` the server is read from `LLM_BACKEND`, `LLM_BASE_URL`,
+//! `LLM_MODEL` and `LLM_API_KEY`, with the bot's defaults. Exits with status
+//! 1 on any regression.
+
+use sfg_bot::analysis::evaluation::{
+    EvaluationReport, Fixture, evaluate, load_fixtures, regressions, replay_backend,
+};
+use sfg_bot::analysis::llm::{LlmBackend, LlmBackendKind, create_backend};
+use sfg_bot::analysis::pipeline::AnalysisPipeline;
+
+use std::env;
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::Arc;
+use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
+
+struct Options {
+    fixtures: String,
+    prompts: String,
+    baseline: Option<String>,
+    replay: bool,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self {
+            fixtures: "analysis_fixtures".to_string(),
+            prompts: "analysis_prompts".to_string(),
+            baseline: None,
+            replay: false,
+        };
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{arg} needs a value"));
+            match arg.as_str() {
+                "--fixtures" => options.fixtures = value()?,
+                "--prompts" => options.prompts = value()?,
+                "--baseline" => options.baseline = Some(value()?),
+                "--replay" => options.replay = true,
+                _ => return Err(format!("unknown argument `{arg}`")),
+            }
+        }
+        if options.replay && options.baseline.is_some() {
+            return Err(
+                "--baseline needs an LLM server: replayed answers are the same for every prompt directory"
+                    .to_string(),
+            );
+        }
+        Ok(options)
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer())
+        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("sfg_bot=error")))
+        .init();
+
+    match run().await {
+        Ok(true) => ExitCode::FAILURE,
+        Ok(false) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("evaluate-prompts: {error}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Returns whether the prompts regressed against the baseline.
+async fn run() -> Result<bool, String> {
+    let options = Options::parse(env::args().skip(1))?;
+    let fixtures = load_fixtures(Path::new(&options.fixtures)).map_err(|e| e.to_string())?;
+
+    let candidate = evaluate_dir(&options, &options.prompts, &fixtures).await?;
+    println!("== {}\n{}", options.prompts, candidate.render());
+
+    let Some(baseline_dir) = &options.baseline else {
+        return Ok(false);
+    };
+    let baseline = evaluate_dir(&options, baseline_dir, &fixtures).await?;
+    println!("\n== {baseline_dir} (baseline)\n{}", baseline.render());
+
+    let regressed = regressions(&baseline, &candidate);
+    if regressed.is_empty() {
+        println!("\nNo regression against {baseline_dir}");
+        return Ok(false);
+    }
+    println!("\nRegressions against {baseline_dir}:");
+    for regression in &regressed {
+        let what: Vec<&str> = [
+            (regression.rating, "rating out of range"),
+            (regression.format, "format broken"),
+        ]
+        .into_iter()
+        .filter_map(|(regressed, what)| regressed.then_some(what))
+        .collect();
+        println!("- {}: {}", regression.name, what.join(", "));
+    }
+    Ok(true)
+}
+
+async fn evaluate_dir(
+    options: &Options,
+    prompts_dir: &str,
+    fixtures: &[Fixture],
+) -> Result<EvaluationReport, String> {
+    let llm: Arc<dyn LlmBackend> = if options.replay {
+        Arc::new(replay_backend(fixtures))
+    } else {
+        live_backend()?
+    };
+    let pipeline = AnalysisPipeline::new(llm, prompts_dir).map_err(|e| e.to_string())?;
+    Ok(evaluate(&pipeline, fixtures).await)
+}
+
+fn live_backend() -> Result<Arc<dyn LlmBackend>, String> {
+    let var = |name: &str, default: &str| env::var(name).unwrap_or_else(|_| default.to_string());
+    let backend = var("LLM_BACKEND", "openai");
+    let kind = LlmBackendKind::parse(&backend)
+        .ok_or_else(|| format!("unknown LLM_BACKEND `{backend}`"))?;
+    create_backend(
+        kind,
+        env::var("LLM_API_KEY").ok(),
+        var("LLM_BASE_URL", "http://jarvis:8080/v1"),
+        var("LLM_MODEL", "gemma-4-26b"),
+    )
+    .map_err(|e| e.to_string())
+}
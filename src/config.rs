@@ -9,11 +9,26 @@ pub struct Config {
     pub database_url: String,
     pub default_region: String,
     pub polling_interval_secs: u64,
+    /// Required by the `openai` and `anthropic` backends only.
     pub llm_api_key: Option<String>,
+    /// Wire format of the LLM server: `openai`, `ollama`, `anthropic` or
+    /// `llamacpp`.
+    pub llm_backend: String,
     pub llm_base_url: String,
     pub llm_model: String,
     pub analysis_prompts_dir: String,
+    /// Seconds between checks of the prompts directory for edits; 0 only
+    /// reads it at startup.
+    pub analysis_prompts_reload_secs: u64,
     pub health_check_port: Option<u16>,
+    /// Days delivered notifications are kept before being pruned; 0 keeps
+    /// them forever. Dead letters are not pruned.
+    pub notification_retention_days: u32,
+    /// Post-game analyses run at the same time; the LLM server handles one
+    /// request at a time by default.
+    pub analysis_concurrency: usize,
+    /// Sends the stats-based verdict to the coach as a sanity anchor.
+    pub analysis_stats_anchor: bool,
 }
 
 #[derive(Debug, Error)]
@@ -46,12 +61,17 @@ impl Config {
             .parse()
             .unwrap_or(180);
 
-        let llm_api_key = env::var("LLM_API_KEY").ok();
+        let llm_api_key = env::var("LLM_API_KEY").ok().filter(|key| !key.is_empty());
+        let llm_backend = env::var("LLM_BACKEND").unwrap_or_else(|_| "openai".to_string());
         let llm_base_url =
             env::var("LLM_BASE_URL").unwrap_or_else(|_| "http://jarvis:8080/v1".to_string());
         let llm_model = env::var("LLM_MODEL").unwrap_or_else(|_| "gemma-4-26b".to_string());
         let analysis_prompts_dir =
             env::var("ANALYSIS_PROMPTS_DIR").unwrap_or_else(|_| "analysis_prompts".to_string());
+        let analysis_prompts_reload_secs = env::var("ANALYSIS_PROMPTS_RELOAD_SECS")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()
+            .unwrap_or(10);
         let health_check_port = env::var("HEALTH_CHECK_PORT")
             .ok()
             .filter(|raw| !raw.trim().is_empty())
@@ -66,12 +86,37 @@ impl Config {
                     .ok()
             });
 
+        let notification_retention_days = env::var("NOTIFICATION_RETENTION_DAYS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .unwrap_or(30);
+
+        let analysis_concurrency = env::var("ANALYSIS_CONCURRENCY")
+            .unwrap_or_else(|_| "1".to_string())
+            .parse::<usize>()
+            .unwrap_or(1)
+            .max(1);
+
+        let analysis_stats_anchor = env::var("ANALYSIS_STATS_ANCHOR")
+            .map(|raw| {
+                matches!(
+                    raw.trim().to_ascii_lowercase().as_str(),
+                    "1" | "true" | "yes"
+                )
+            })
+            .unwrap_or(false);
+
         tracing::info!(
             has_llm_api_key = llm_api_key.is_some(),
+            llm_backend = llm_backend.as_str(),
             llm_base_url = llm_base_url.as_str(),
             llm_model = llm_model.as_str(),
             analysis_prompts_dir = analysis_prompts_dir.as_str(),
+            analysis_prompts_reload_secs,
             health_check_port = ?health_check_port,
+            notification_retention_days,
+            analysis_concurrency,
+            analysis_stats_anchor,
         );
 
         Self {
@@ -82,10 +127,15 @@ impl Config {
             default_region,
             polling_interval_secs,
             llm_api_key,
+            llm_backend,
             llm_base_url,
             llm_model,
             analysis_prompts_dir,
+            analysis_prompts_reload_secs,
             health_check_port,
+            notification_retention_days,
+            analysis_concurrency,
+            analysis_stats_anchor,
         }
     }
 }
@@ -1,4 +1,6 @@
-use chrono::{DateTime, Utc};
+use crate::i18n::Locale;
+use crate::riot::models::RankedEntry;
+use chrono::{DateTime, NaiveTime, Utc};
 use sqlx::FromRow;
 use uuid::Uuid;
 
@@ -11,6 +13,9 @@ pub struct Summoner {
     pub region: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// `TierBand` override set with `/benchmark-tier`; `None` follows the
+    /// current ranked tier.
+    pub benchmark_tier: Option<String>,
 }
 
 #[derive(Debug, Clone, FromRow)]
@@ -92,6 +97,8 @@ pub struct Champion {
     pub champion_id: i32,
     pub champion_name: String,
     pub created_at: DateTime<Utc>,
+    /// Data Dragon class tags, primary first.
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, FromRow)]
@@ -125,6 +132,10 @@ pub struct NotificationEvent {
     pub processed_at: Option<DateTime<Utc>>,
     pub retry_count: i32,
     pub error_message: Option<String>,
+    /// Set when the event exhausted its retries (dead letter).
+    pub failed_at: Option<DateTime<Utc>>,
+    /// Earliest time the event is (re)tried.
+    pub next_attempt_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone)]
@@ -154,6 +165,15 @@ pub struct NewNotificationEvent {
     pub role_gaps: Option<String>,
 }
 
+/// Result of queueing a notification event. `is_new` is `false` when the
+/// same summoner, event type and game was already queued; `event` is then
+/// the existing row.
+#[derive(Debug, Clone)]
+pub struct QueuedNotification {
+    pub event: NotificationEvent,
+    pub is_new: bool,
+}
+
 #[derive(Debug, Clone, FromRow)]
 pub struct BotConfig {
     pub id: Uuid,
@@ -161,6 +181,82 @@ pub struct BotConfig {
     pub channel_id: i64,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Locale code (`fr`, `en`); see [`BotConfig::locale`].
+    pub locale: String,
+}
+
+impl BotConfig {
+    /// The guild's language, falling back to French for unknown codes.
+    pub fn locale(&self) -> Locale {
+        Locale::from_code(&self.locale).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct QuietHours {
+    pub guild_id: i64,
+    /// Local start of the quiet window; past `end_time` means it wraps
+    /// around midnight.
+    pub start_time: NaiveTime,
+    pub end_time: NaiveTime,
+    /// IANA time zone name (`Europe/Paris`).
+    pub timezone: String,
+    /// `notification_queue.event_type` values still sent during quiet hours.
+    pub bypass_event_types: Vec<String>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct NotificationSinkConfig {
+    pub id: Uuid,
+    pub guild_id: i64,
+    /// `discord`, `webhook` or `stdout`; see
+    /// [`crate::notification::sinks::SinkKind`].
+    pub kind: String,
+    pub url: Option<String>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DigestSchedule {
+    pub id: Uuid,
+    pub guild_id: i64,
+    /// `daily` or `weekly`; see [`crate::digest::DigestPeriod`].
+    pub period: String,
+    /// 5-field cron expression, evaluated in `timezone`.
+    pub schedule: String,
+    /// IANA time zone name (`Europe/Paris`).
+    pub timezone: String,
+    pub enabled: bool,
+    pub last_sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct RankSnapshot {
+    pub id: Uuid,
+    pub summoner_id: Uuid,
+    pub queue_type: String,
+    pub tier: String,
+    pub division: Option<String>,
+    pub league_points: i32,
+    pub captured_at: DateTime<Utc>,
+}
+
+impl RankSnapshot {
+    pub fn entry(&self) -> RankedEntry {
+        RankedEntry {
+            queue_type: self.queue_type.clone(),
+            tier: self.tier.clone(),
+            division: self.division.clone(),
+            league_points: self.league_points,
+        }
+    }
 }
 
 #[derive(Debug, Clone, FromRow)]
@@ -174,6 +270,13 @@ pub struct AnalysisHistoryEntry {
     pub overall_rating: Option<String>,
     /// JSON-serialized `AnalysisData` snapshot (history stripped).
     pub analysis_data: String,
+    /// JSON-serialized `AnalysisResult` of the coach; `None` for snapshots
+    /// stored without a reusable answer (group reports, older rows).
+    pub analysis_result: Option<String>,
+    /// Hash of the prompt the analysis was generated with: keys the cached
+    /// answer and groups the results of one prompt version.
+    pub prompt_hash: Option<String>,
+    pub model: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -186,4 +289,54 @@ pub struct NewAnalysisHistory {
     pub win: bool,
     pub overall_rating: Option<String>,
     pub analysis_data: String,
+    pub analysis_result: Option<String>,
+    pub prompt_hash: Option<String>,
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct AnalysisJob {
+    pub id: Uuid,
+    pub summoner_id: Uuid,
+    pub match_id: String,
+    pub created_at: DateTime<Utc>,
+    /// Set once the job is `done` or `failed`.
+    pub completed_at: Option<DateTime<Utc>>,
+    /// `pending`, `running`, `done`, `failed` or `grouped` (merged into
+    /// another job of the same match).
+    pub status: String,
+    /// Attempts started so far, the running one included.
+    pub attempts: i32,
+    /// Earliest time a pending job is (re)tried.
+    pub next_attempt_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    /// Why the last attempt failed.
+    pub error_message: Option<String>,
+    /// Every player analyzed together by this job, itself included; empty
+    /// for a single-player analysis.
+    pub group_summoner_ids: Vec<Uuid>,
+    /// Members whose analysis was already posted when the group fell back
+    /// to one analysis per member; skipped when the job is retried.
+    pub group_done_summoner_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewAnalysisJob {
+    pub summoner_id: Uuid,
+    pub match_id: String,
+    /// Leaves time for the job to be grouped with its teammates' jobs.
+    pub not_before: DateTime<Utc>,
+}
+
+/// Everything written when a finished game's result is found. Committed as
+/// one transaction, so a crash cannot keep the match while losing its
+/// recap or analysis.
+#[derive(Debug, Clone)]
+pub struct GameEndRecord {
+    pub match_result: NewMatchResult,
+    /// `game_id` of the active_games row to delete.
+    pub game_id: i64,
+    pub event: NewNotificationEvent,
+    /// `None` when the game mode is not analyzable or analysis is disabled.
+    pub analysis_job: Option<NewAnalysisJob>,
 }
@@ -1,9 +1,14 @@
 use crate::db::models::{
-    ActiveGame, AnalysisHistoryEntry, BotConfig, Champion, MatchHistory, NewActiveGame,
-    NewAnalysisHistory, NewMatchResult, NewNotificationEvent, NotificationEvent, Summoner,
+    ActiveGame, AnalysisHistoryEntry, AnalysisJob, BotConfig, Champion, DigestSchedule,
+    GameEndRecord, MatchHistory, NewActiveGame, NewAnalysisHistory, NewMatchResult,
+    NewNotificationEvent, NotificationEvent, NotificationSinkConfig, QueuedNotification,
+    QuietHours, RankSnapshot, Summoner,
 };
+use crate::digest::DigestPeriod;
+use crate::riot::models::RankedEntry;
 use async_trait::async_trait;
-use sqlx::PgPool;
+use chrono::{DateTime, NaiveTime, Utc};
+use sqlx::{PgExecutor, PgPool};
 use uuid::Uuid;
 
 #[derive(Debug, thiserror::Error)]
@@ -26,6 +31,8 @@ pub trait Repository: Send + Sync {
     async fn get_summoner_by_puuid(&self, puuid: &str)
     -> Result<Option<Summoner>, RepositoryError>;
 
+    async fn get_summoner_by_id(&self, id: Uuid) -> Result<Option<Summoner>, RepositoryError>;
+
     async fn get_all_summoners(&self) -> Result<Vec<Summoner>, RepositoryError>;
 
     async fn insert_active_game(&self, game: &NewActiveGame)
@@ -70,10 +77,73 @@ pub trait Repository: Send + Sync {
         match_id: &str,
     ) -> Result<Option<MatchHistory>, RepositoryError>;
 
+    /// Stores the match, deletes the active game, queues the GAME_ENDED
+    /// event and the analysis job in a single transaction. Returns the
+    /// analysis job when one was newly queued.
+    async fn record_game_end(
+        &self,
+        record: &GameEndRecord,
+    ) -> Result<Option<AnalysisJob>, RepositoryError>;
+
+    /// Marks the oldest due pending job as running and returns it. Safe to
+    /// call from several workers: a job is only handed out once.
+    async fn claim_analysis_job(&self) -> Result<Option<AnalysisJob>, RepositoryError>;
+
+    /// Earliest `next_attempt_at` among pending jobs that are not due yet.
+    async fn get_next_analysis_attempt(&self) -> Result<Option<DateTime<Utc>>, RepositoryError>;
+
+    async fn complete_analysis_job(&self, id: Uuid) -> Result<(), RepositoryError>;
+
+    /// Records a failed attempt and puts the job back in the queue.
+    async fn retry_analysis_job(
+        &self,
+        id: Uuid,
+        error_message: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError>;
+
+    async fn fail_analysis_job(&self, id: Uuid, error_message: &str)
+    -> Result<(), RepositoryError>;
+
+    /// Puts a claimed job back in the queue until `next_attempt_at`
+    /// without counting the claim as an attempt.
+    async fn postpone_analysis_job(
+        &self,
+        id: Uuid,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError>;
+
+    /// Puts jobs left running by a previous process back in the queue.
+    /// Returns how many were requeued.
+    async fn requeue_running_analysis_jobs(&self) -> Result<u64, RepositoryError>;
+
+    /// Merges the pending, never attempted jobs of `summoner_ids` for
+    /// `match_id` into the oldest one, which then analyzes the players
+    /// together. Returns the merged job, `None` when fewer than two jobs
+    /// could be merged.
+    async fn group_analysis_jobs(
+        &self,
+        match_id: &str,
+        summoner_ids: &[Uuid],
+    ) -> Result<Option<Uuid>, RepositoryError>;
+
+    /// Records that the analysis of `summoner_id`, a member of the group
+    /// job `id`, was posted.
+    async fn mark_analysis_group_member_done(
+        &self,
+        id: Uuid,
+        summoner_id: Uuid,
+    ) -> Result<(), RepositoryError>;
+
+    /// Pending and running jobs, plus those that failed in the last day,
+    /// oldest first.
+    async fn get_analysis_job_queue(&self) -> Result<Vec<AnalysisJob>, RepositoryError>;
+
     async fn upsert_champion(
         &self,
         champion_id: i32,
         champion_name: &str,
+        tags: &[String],
     ) -> Result<Champion, RepositoryError>;
 
     async fn get_champion_by_id(
@@ -81,22 +151,32 @@ pub trait Repository: Send + Sync {
         champion_id: i32,
     ) -> Result<Option<Champion>, RepositoryError>;
 
+    /// Queues an event unless the same summoner, event type and game is
+    /// already queued, in which case the existing row is returned.
     async fn insert_notification_event(
         &self,
         event: &NewNotificationEvent,
-    ) -> Result<NotificationEvent, RepositoryError>;
+    ) -> Result<QueuedNotification, RepositoryError>;
 
+    /// Unprocessed events whose next attempt is due.
     async fn get_pending_notification_events(
         &self,
     ) -> Result<Vec<NotificationEvent>, RepositoryError>;
 
+    /// Earliest `next_attempt_at` among unprocessed events that are not due
+    /// yet, so the processor knows when to wake for a scheduled retry.
+    async fn get_next_notification_attempt(&self)
+    -> Result<Option<DateTime<Utc>>, RepositoryError>;
+
     async fn mark_notifications_processed(&self, event_ids: &[Uuid])
     -> Result<(), RepositoryError>;
 
+    /// Records a failed attempt and schedules the next one.
     async fn increment_notification_retry_count(
         &self,
         event_ids: &[Uuid],
         error_message: &str,
+        next_attempt_at: DateTime<Utc>,
     ) -> Result<(), RepositoryError>;
 
     async fn mark_notifications_failed(
@@ -105,6 +185,29 @@ pub trait Repository: Send + Sync {
         error_message: &str,
     ) -> Result<(), RepositoryError>;
 
+    /// Dead letters: events that exhausted their retries, most recent first.
+    async fn get_failed_notification_events(
+        &self,
+    ) -> Result<Vec<NotificationEvent>, RepositoryError>;
+
+    /// Puts dead letters back in the pending queue with a fresh retry
+    /// budget. Returns how many rows were requeued.
+    async fn requeue_failed_notifications(
+        &self,
+        event_ids: &[Uuid],
+    ) -> Result<u64, RepositoryError>;
+
+    /// Deletes dead letters. Returns how many rows were deleted.
+    async fn delete_failed_notifications(&self, event_ids: &[Uuid])
+    -> Result<u64, RepositoryError>;
+
+    /// Deletes delivered events processed before `before`. Dead letters
+    /// are kept for `/dead-letters`. Returns how many rows were deleted.
+    async fn prune_processed_notifications(
+        &self,
+        before: DateTime<Utc>,
+    ) -> Result<u64, RepositoryError>;
+
     async fn upsert_bot_config(
         &self,
         guild_id: i64,
@@ -113,6 +216,10 @@ pub trait Repository: Send + Sync {
 
     async fn get_bot_config(&self, guild_id: i64) -> Result<Option<BotConfig>, RepositoryError>;
 
+    /// Sets the guild's message language. Returns `false` when the guild has
+    /// no bot_config row yet (`/init-sfg-bot` not run).
+    async fn set_bot_locale(&self, guild_id: i64, locale: &str) -> Result<bool, RepositoryError>;
+
     async fn get_all_bot_configs(&self) -> Result<Vec<BotConfig>, RepositoryError>;
 
     async fn insert_analysis_history(
@@ -126,11 +233,104 @@ pub trait Repository: Send + Sync {
         limit: i64,
     ) -> Result<Vec<AnalysisHistoryEntry>, RepositoryError>;
 
+    /// The snapshot stored for `riot_puuid` in `match_id`, if any.
+    async fn get_analysis_history_entry(
+        &self,
+        riot_puuid: &str,
+        match_id: &str,
+    ) -> Result<Option<AnalysisHistoryEntry>, RepositoryError>;
+
+    /// Snapshots of `riot_puuid` stored in `[start, end)`, oldest first.
+    async fn get_analysis_history_between(
+        &self,
+        riot_puuid: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<AnalysisHistoryEntry>, RepositoryError>;
+
     async fn delete_summoner_by_name_and_tag(
         &self,
         game_name: &str,
         tag_line: &str,
     ) -> Result<bool, RepositoryError>;
+
+    /// Sets or clears (`None`) the benchmark tier band of a summoner.
+    /// Returns `false` when no summoner matches.
+    async fn set_summoner_benchmark_tier(
+        &self,
+        game_name: &str,
+        tag_line: &str,
+        benchmark_tier: Option<String>,
+    ) -> Result<bool, RepositoryError>;
+
+    /// Matches of every tracked summoner finished in `[start, end)`.
+    async fn get_match_history_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<MatchHistory>, RepositoryError>;
+
+    async fn get_analysis_history_for_matches(
+        &self,
+        match_ids: &[String],
+    ) -> Result<Vec<AnalysisHistoryEntry>, RepositoryError>;
+
+    async fn get_all_digest_schedules(&self) -> Result<Vec<DigestSchedule>, RepositoryError>;
+
+    async fn upsert_digest_schedule(
+        &self,
+        guild_id: i64,
+        period: &str,
+        schedule: &str,
+        timezone: &str,
+        enabled: bool,
+    ) -> Result<DigestSchedule, RepositoryError>;
+
+    /// Records the fire time of the digest just posted.
+    async fn mark_digest_sent(
+        &self,
+        id: Uuid,
+        fired_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError>;
+
+    async fn insert_rank_snapshot(
+        &self,
+        summoner_id: Uuid,
+        entry: &RankedEntry,
+    ) -> Result<(), RepositoryError>;
+
+    /// Latest snapshot of the queue captured at or before `before`.
+    async fn get_rank_snapshot_before(
+        &self,
+        summoner_id: Uuid,
+        queue_type: &str,
+        before: DateTime<Utc>,
+    ) -> Result<Option<RankSnapshot>, RepositoryError>;
+
+    async fn get_quiet_hours(&self, guild_id: i64) -> Result<Option<QuietHours>, RepositoryError>;
+
+    async fn upsert_quiet_hours(
+        &self,
+        guild_id: i64,
+        start_time: NaiveTime,
+        end_time: NaiveTime,
+        timezone: &str,
+        bypass_event_types: &[String],
+        enabled: bool,
+    ) -> Result<QuietHours, RepositoryError>;
+
+    async fn get_notification_sinks(
+        &self,
+        guild_id: i64,
+    ) -> Result<Vec<NotificationSinkConfig>, RepositoryError>;
+
+    async fn upsert_notification_sink(
+        &self,
+        guild_id: i64,
+        kind: &str,
+        url: Option<String>,
+        enabled: bool,
+    ) -> Result<NotificationSinkConfig, RepositoryError>;
 }
 
 pub struct PgRepository {
@@ -160,7 +360,7 @@ impl Repository for PgRepository {
                 game_name = EXCLUDED.game_name,
                 tag_line = EXCLUDED.tag_line,
                 updated_at = NOW()
-            RETURNING id, riot_puuid, game_name, tag_line, region, created_at, updated_at
+            RETURNING id, riot_puuid, game_name, tag_line, region, created_at, updated_at, benchmark_tier
             "#,
         )
         .bind(puuid)
@@ -184,6 +384,14 @@ impl Repository for PgRepository {
         Ok(summoner)
     }
 
+    async fn get_summoner_by_id(&self, id: Uuid) -> Result<Option<Summoner>, RepositoryError> {
+        let summoner = sqlx::query_as::<_, Summoner>("SELECT * FROM summoners WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(summoner)
+    }
+
     async fn get_all_summoners(&self) -> Result<Vec<Summoner>, RepositoryError> {
         let summoners = sqlx::query_as::<_, Summoner>("SELECT * FROM summoners")
             .fetch_all(&self.pool)
@@ -285,37 +493,9 @@ impl Repository for PgRepository {
         &self,
         result: &NewMatchResult,
     ) -> Result<MatchHistory, RepositoryError> {
-        let match_history = sqlx::query_as::<_, MatchHistory>(
-             r#"
-             INSERT INTO match_history (summoner_id, match_id, game_id, win, kills, deaths, assists, champion_id, game_duration_secs, game_mode, role, total_cs, total_gold, total_damage, enemy_champion_name, enemy_cs, enemy_gold, enemy_damage, queue_id, finished_at)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
-             ON CONFLICT (summoner_id, match_id) DO NOTHING
-             RETURNING id, summoner_id, match_id, game_id, win, kills, deaths, assists, champion_id, game_duration_secs, game_mode, role, queue_id, total_cs, total_gold, total_damage, enemy_champion_name, enemy_cs, enemy_gold, enemy_damage, finished_at, created_at
-             "#,
-         )
-         .bind(result.summoner_id)
-         .bind(&result.match_id)
-         .bind(result.game_id)
-         .bind(result.win)
-         .bind(result.kills)
-         .bind(result.deaths)
-         .bind(result.assists)
-         .bind(result.champion_id)
-         .bind(result.game_duration_secs)
-         .bind(&result.game_mode)
-         .bind(&result.role)
-         .bind(result.total_cs)
-         .bind(result.total_gold)
-         .bind(result.total_damage)
-         .bind(&result.enemy_champion_name)
-         .bind(result.enemy_cs)
-         .bind(result.enemy_gold)
-         .bind(result.enemy_damage)
-         .bind(result.queue_id)
-         .bind(result.finished_at)
-         .fetch_one(&self.pool)
-         .await?;
-        Ok(match_history)
+        insert_match_result(&self.pool, result)
+            .await?
+            .ok_or(RepositoryError::Database(sqlx::Error::RowNotFound))
     }
 
     async fn get_match_history_by_match_id(
@@ -333,22 +513,235 @@ impl Repository for PgRepository {
         Ok(match_history)
     }
 
+    async fn record_game_end(
+        &self,
+        record: &GameEndRecord,
+    ) -> Result<Option<AnalysisJob>, RepositoryError> {
+        let mut tx = self.pool.begin().await?;
+
+        // An already stored match (duplicate end detection) is not an error.
+        insert_match_result(&mut *tx, &record.match_result).await?;
+
+        sqlx::query("DELETE FROM active_games WHERE summoner_id = $1 AND game_id = $2")
+            .bind(record.match_result.summoner_id)
+            .bind(record.game_id)
+            .execute(&mut *tx)
+            .await?;
+
+        // A duplicate GAME_ENDED is skipped by the unique index.
+        insert_notification_event(&mut *tx, &record.event).await?;
+
+        let job = match &record.analysis_job {
+            Some(job) => {
+                sqlx::query_as::<_, AnalysisJob>(
+                    r#"
+                    INSERT INTO analysis_jobs (summoner_id, match_id, next_attempt_at)
+                    VALUES ($1, $2, $3)
+                    ON CONFLICT (summoner_id, match_id) DO NOTHING
+                    RETURNING *
+                    "#,
+                )
+                .bind(job.summoner_id)
+                .bind(&job.match_id)
+                .bind(job.not_before)
+                .fetch_optional(&mut *tx)
+                .await?
+            }
+            None => None,
+        };
+
+        tx.commit().await?;
+        Ok(job)
+    }
+
+    async fn claim_analysis_job(&self) -> Result<Option<AnalysisJob>, RepositoryError> {
+        let job = sqlx::query_as::<_, AnalysisJob>(
+            r#"
+            UPDATE analysis_jobs
+            SET status = 'running', attempts = attempts + 1, started_at = NOW()
+            WHERE id = (
+                SELECT id FROM analysis_jobs
+                WHERE status = 'pending' AND next_attempt_at <= NOW()
+                ORDER BY next_attempt_at ASC, created_at ASC
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
+            "#,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(job)
+    }
+
+    async fn get_next_analysis_attempt(&self) -> Result<Option<DateTime<Utc>>, RepositoryError> {
+        let next = sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+            "SELECT MIN(next_attempt_at) FROM analysis_jobs WHERE status = 'pending' AND next_attempt_at > NOW()",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(next)
+    }
+
+    async fn complete_analysis_job(&self, id: Uuid) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "UPDATE analysis_jobs SET status = 'done', completed_at = NOW(), error_message = NULL WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn retry_analysis_job(
+        &self,
+        id: Uuid,
+        error_message: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "UPDATE analysis_jobs SET status = 'pending', error_message = $2, next_attempt_at = $3 WHERE id = $1",
+        )
+        .bind(id)
+        .bind(error_message)
+        .bind(next_attempt_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn fail_analysis_job(
+        &self,
+        id: Uuid,
+        error_message: &str,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "UPDATE analysis_jobs SET status = 'failed', completed_at = NOW(), error_message = $2 WHERE id = $1",
+        )
+        .bind(id)
+        .bind(error_message)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn postpone_analysis_job(
+        &self,
+        id: Uuid,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "UPDATE analysis_jobs SET status = 'pending', attempts = attempts - 1, next_attempt_at = $2 WHERE id = $1",
+        )
+        .bind(id)
+        .bind(next_attempt_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn requeue_running_analysis_jobs(&self) -> Result<u64, RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE analysis_jobs SET status = 'pending', next_attempt_at = NOW() WHERE status = 'running'",
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn group_analysis_jobs(
+        &self,
+        match_id: &str,
+        summoner_ids: &[Uuid],
+    ) -> Result<Option<Uuid>, RepositoryError> {
+        let mut tx = self.pool.begin().await?;
+
+        let jobs = sqlx::query_as::<_, AnalysisJob>(
+            r#"
+            SELECT * FROM analysis_jobs
+            WHERE match_id = $1 AND summoner_id = ANY($2)
+              AND status = 'pending' AND attempts = 0
+            ORDER BY created_at ASC
+            FOR UPDATE SKIP LOCKED
+            "#,
+        )
+        .bind(match_id)
+        .bind(summoner_ids)
+        .fetch_all(&mut *tx)
+        .await?;
+        let [lead, others @ ..] = jobs.as_slice() else {
+            return Ok(None);
+        };
+        if others.is_empty() {
+            return Ok(None);
+        }
+
+        let members: Vec<Uuid> = jobs.iter().map(|job| job.summoner_id).collect();
+        sqlx::query("UPDATE analysis_jobs SET group_summoner_ids = $2 WHERE id = $1")
+            .bind(lead.id)
+            .bind(&members)
+            .execute(&mut *tx)
+            .await?;
+        let merged: Vec<Uuid> = others.iter().map(|job| job.id).collect();
+        sqlx::query(
+            "UPDATE analysis_jobs SET status = 'grouped', completed_at = NOW() WHERE id = ANY($1)",
+        )
+        .bind(&merged)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(lead.id))
+    }
+
+    async fn mark_analysis_group_member_done(
+        &self,
+        id: Uuid,
+        summoner_id: Uuid,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "UPDATE analysis_jobs SET group_done_summoner_ids = array_append(group_done_summoner_ids, $2) WHERE id = $1",
+        )
+        .bind(id)
+        .bind(summoner_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_analysis_job_queue(&self) -> Result<Vec<AnalysisJob>, RepositoryError> {
+        let jobs = sqlx::query_as::<_, AnalysisJob>(
+            r#"
+            SELECT * FROM analysis_jobs
+            WHERE status IN ('pending', 'running')
+               OR (status = 'failed' AND completed_at > NOW() - INTERVAL '1 day')
+            ORDER BY created_at ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(jobs)
+    }
+
     async fn upsert_champion(
         &self,
         champion_id: i32,
         champion_name: &str,
+        tags: &[String],
     ) -> Result<Champion, RepositoryError> {
         let champion = sqlx::query_as::<_, Champion>(
             r#"
-            INSERT INTO champions (champion_id, champion_name)
-            VALUES ($1, $2)
+            INSERT INTO champions (champion_id, champion_name, tags)
+            VALUES ($1, $2, $3)
             ON CONFLICT (champion_id) DO UPDATE SET
-                champion_name = EXCLUDED.champion_name
-            RETURNING id, champion_id, champion_name, created_at
+                champion_name = EXCLUDED.champion_name,
+                tags = EXCLUDED.tags
+            RETURNING id, champion_id, champion_name, created_at, tags
             "#,
         )
         .bind(champion_id)
         .bind(champion_name)
+        .bind(tags)
         .fetch_one(&self.pool)
         .await?;
         Ok(champion)
@@ -369,53 +762,59 @@ impl Repository for PgRepository {
     async fn insert_notification_event(
         &self,
         event: &NewNotificationEvent,
-    ) -> Result<NotificationEvent, RepositoryError> {
-        let notification = sqlx::query_as::<_, NotificationEvent>(
-              r#"
-              INSERT INTO notification_queue (summoner_id, event_type, game_id, match_id, champion_id, champion_name, role, win, kills, deaths, assists, game_duration_secs, game_mode, queue_id, is_featured_mode, total_cs, total_gold, total_damage, enemy_champion_name, enemy_cs, enemy_gold, enemy_damage, role_gaps)
-              VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23)
-              RETURNING id, summoner_id, event_type, game_id, match_id, champion_id, champion_name, role, win, kills, deaths, assists, game_duration_secs, game_mode, queue_id, is_featured_mode, total_cs, total_gold, total_damage, enemy_champion_name, enemy_cs, enemy_gold, enemy_damage, role_gaps, processed, created_at, processed_at, retry_count, error_message
-              "#,
-          )
-          .bind(event.summoner_id)
-          .bind(&event.event_type)
-          .bind(event.game_id)
-          .bind(&event.match_id)
-          .bind(event.champion_id)
-          .bind(&event.champion_name)
-          .bind(&event.role)
-          .bind(event.win)
-          .bind(event.kills)
-          .bind(event.deaths)
-          .bind(event.assists)
-          .bind(event.game_duration_secs)
-          .bind(&event.game_mode)
-          .bind(event.queue_id)
-          .bind(event.is_featured_mode)
-          .bind(event.total_cs)
-          .bind(event.total_gold)
-          .bind(event.total_damage)
-          .bind(&event.enemy_champion_name)
-          .bind(event.enemy_cs)
-          .bind(event.enemy_gold)
-          .bind(event.enemy_damage)
-          .bind(&event.role_gaps)
-         .fetch_one(&self.pool)
-         .await?;
-        Ok(notification)
+    ) -> Result<QueuedNotification, RepositoryError> {
+        if let Some(event) = insert_notification_event(&self.pool, event).await? {
+            return Ok(QueuedNotification {
+                event,
+                is_new: true,
+            });
+        }
+
+        // Lost the race (or a duplicate detection): the unique index made the
+        // insert a no-op, report the row that won.
+        let existing = sqlx::query_as::<_, NotificationEvent>(
+            r#"
+            SELECT * FROM notification_queue
+            WHERE summoner_id = $1 AND event_type = $2
+              AND (game_id = $3 OR match_id = $4)
+            ORDER BY created_at ASC
+            LIMIT 1
+            "#,
+        )
+        .bind(event.summoner_id)
+        .bind(&event.event_type)
+        .bind(event.game_id)
+        .bind(&event.match_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(QueuedNotification {
+            event: existing,
+            is_new: false,
+        })
     }
 
     async fn get_pending_notification_events(
         &self,
     ) -> Result<Vec<NotificationEvent>, RepositoryError> {
         let events = sqlx::query_as::<_, NotificationEvent>(
-            "SELECT * FROM notification_queue WHERE processed = false AND retry_count < 3 ORDER BY created_at ASC",
+            "SELECT * FROM notification_queue WHERE processed = false AND next_attempt_at <= NOW() ORDER BY created_at ASC",
         )
         .fetch_all(&self.pool)
         .await?;
         Ok(events)
     }
 
+    async fn get_next_notification_attempt(
+        &self,
+    ) -> Result<Option<DateTime<Utc>>, RepositoryError> {
+        let next = sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+            "SELECT MIN(next_attempt_at) FROM notification_queue WHERE processed = false AND next_attempt_at > NOW()",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(next)
+    }
+
     async fn mark_notifications_processed(
         &self,
         event_ids: &[Uuid],
@@ -433,12 +832,14 @@ impl Repository for PgRepository {
         &self,
         event_ids: &[Uuid],
         error_message: &str,
+        next_attempt_at: DateTime<Utc>,
     ) -> Result<(), RepositoryError> {
         sqlx::query(
-            "UPDATE notification_queue SET retry_count = retry_count + 1, error_message = $2 WHERE id = ANY($1)",
+            "UPDATE notification_queue SET retry_count = retry_count + 1, error_message = $2, next_attempt_at = $3 WHERE id = ANY($1)",
         )
         .bind(event_ids)
         .bind(error_message)
+        .bind(next_attempt_at)
         .execute(&self.pool)
         .await?;
         Ok(())
@@ -450,7 +851,7 @@ impl Repository for PgRepository {
         error_message: &str,
     ) -> Result<(), RepositoryError> {
         sqlx::query(
-            "UPDATE notification_queue SET processed = true, processed_at = NOW(), error_message = $2 WHERE id = ANY($1)",
+            "UPDATE notification_queue SET processed = true, processed_at = NOW(), failed_at = NOW(), error_message = $2 WHERE id = ANY($1)",
         )
         .bind(event_ids)
         .bind(error_message)
@@ -459,6 +860,61 @@ impl Repository for PgRepository {
         Ok(())
     }
 
+    async fn get_failed_notification_events(
+        &self,
+    ) -> Result<Vec<NotificationEvent>, RepositoryError> {
+        let events = sqlx::query_as::<_, NotificationEvent>(
+            "SELECT * FROM notification_queue WHERE failed_at IS NOT NULL ORDER BY failed_at DESC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(events)
+    }
+
+    async fn requeue_failed_notifications(
+        &self,
+        event_ids: &[Uuid],
+    ) -> Result<u64, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE notification_queue
+            SET processed = false, processed_at = NULL, failed_at = NULL,
+                retry_count = 0, error_message = NULL, next_attempt_at = NOW()
+            WHERE id = ANY($1) AND failed_at IS NOT NULL
+            "#,
+        )
+        .bind(event_ids)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_failed_notifications(
+        &self,
+        event_ids: &[Uuid],
+    ) -> Result<u64, RepositoryError> {
+        let result = sqlx::query(
+            "DELETE FROM notification_queue WHERE id = ANY($1) AND failed_at IS NOT NULL",
+        )
+        .bind(event_ids)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn prune_processed_notifications(
+        &self,
+        before: DateTime<Utc>,
+    ) -> Result<u64, RepositoryError> {
+        let result = sqlx::query(
+            "DELETE FROM notification_queue WHERE processed = true AND failed_at IS NULL AND processed_at < $1",
+        )
+        .bind(before)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     async fn upsert_bot_config(
         &self,
         guild_id: i64,
@@ -471,13 +927,27 @@ impl Repository for PgRepository {
             ON CONFLICT (guild_id) DO UPDATE SET
                 channel_id = EXCLUDED.channel_id,
                 updated_at = NOW()
-            RETURNING id, guild_id, channel_id, created_at, updated_at
+            RETURNING id, guild_id, channel_id, created_at, updated_at, locale
             "#,
         )
         .bind(guild_id)
         .bind(channel_id)
         .fetch_one(&self.pool)
         .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO digest_schedules (guild_id, period, schedule)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (guild_id, period) DO NOTHING
+            "#,
+        )
+        .bind(guild_id)
+        .bind(DigestPeriod::Weekly.as_str())
+        .bind(DigestPeriod::Weekly.default_schedule())
+        .execute(&self.pool)
+        .await?;
+
         Ok(config)
     }
 
@@ -489,6 +959,17 @@ impl Repository for PgRepository {
         Ok(config)
     }
 
+    async fn set_bot_locale(&self, guild_id: i64, locale: &str) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE bot_config SET locale = $2, updated_at = NOW() WHERE guild_id = $1",
+        )
+        .bind(guild_id)
+        .bind(locale)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     async fn get_all_bot_configs(&self) -> Result<Vec<BotConfig>, RepositoryError> {
         let configs = sqlx::query_as::<_, BotConfig>("SELECT * FROM bot_config")
             .fetch_all(&self.pool)
@@ -503,14 +984,18 @@ impl Repository for PgRepository {
         sqlx::query(
             r#"
             INSERT INTO analysis_history
-                (riot_puuid, match_id, role, champion_name, win, overall_rating, analysis_data)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+                (riot_puuid, match_id, role, champion_name, win, overall_rating, analysis_data,
+                 analysis_result, prompt_hash, model)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             ON CONFLICT (riot_puuid, match_id) DO UPDATE SET
                 role = EXCLUDED.role,
                 champion_name = EXCLUDED.champion_name,
                 win = EXCLUDED.win,
                 overall_rating = EXCLUDED.overall_rating,
                 analysis_data = EXCLUDED.analysis_data,
+                analysis_result = EXCLUDED.analysis_result,
+                prompt_hash = EXCLUDED.prompt_hash,
+                model = EXCLUDED.model,
                 created_at = NOW()
             "#,
         )
@@ -521,14 +1006,19 @@ impl Repository for PgRepository {
         .bind(entry.win)
         .bind(&entry.overall_rating)
         .bind(&entry.analysis_data)
+        .bind(&entry.analysis_result)
+        .bind(&entry.prompt_hash)
+        .bind(&entry.model)
         .execute(&self.pool)
         .await?;
 
-        // Bound growth: keep only the most recent snapshots per player.
+        // Bound growth: keep the most recent snapshots per player, and every
+        // snapshot young enough for a progression report (`MAX_DAYS`).
         sqlx::query(
             r#"
             DELETE FROM analysis_history
             WHERE riot_puuid = $1
+              AND created_at < NOW() - INTERVAL '90 days'
               AND id NOT IN (
                 SELECT id FROM analysis_history
                 WHERE riot_puuid = $1
@@ -564,6 +1054,42 @@ impl Repository for PgRepository {
         Ok(entries)
     }
 
+    async fn get_analysis_history_entry(
+        &self,
+        riot_puuid: &str,
+        match_id: &str,
+    ) -> Result<Option<AnalysisHistoryEntry>, RepositoryError> {
+        let entry = sqlx::query_as::<_, AnalysisHistoryEntry>(
+            "SELECT * FROM analysis_history WHERE riot_puuid = $1 AND match_id = $2",
+        )
+        .bind(riot_puuid)
+        .bind(match_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(entry)
+    }
+
+    async fn get_analysis_history_between(
+        &self,
+        riot_puuid: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<AnalysisHistoryEntry>, RepositoryError> {
+        let entries = sqlx::query_as::<_, AnalysisHistoryEntry>(
+            r#"
+            SELECT * FROM analysis_history
+            WHERE riot_puuid = $1 AND created_at >= $2 AND created_at < $3
+            ORDER BY created_at
+            "#,
+        )
+        .bind(riot_puuid)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(entries)
+    }
+
     async fn delete_summoner_by_name_and_tag(
         &self,
         game_name: &str,
@@ -578,4 +1104,314 @@ impl Repository for PgRepository {
         .await?;
         Ok(result.rows_affected() > 0)
     }
+
+    async fn set_summoner_benchmark_tier(
+        &self,
+        game_name: &str,
+        tag_line: &str,
+        benchmark_tier: Option<String>,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE summoners SET benchmark_tier = $3, updated_at = NOW()
+            WHERE LOWER(game_name) = LOWER($1) AND LOWER(tag_line) = LOWER($2)
+            "#,
+        )
+        .bind(game_name)
+        .bind(tag_line)
+        .bind(benchmark_tier)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_match_history_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<MatchHistory>, RepositoryError> {
+        let matches = sqlx::query_as::<_, MatchHistory>(
+            r#"
+            SELECT * FROM match_history
+            WHERE finished_at >= $1 AND finished_at < $2
+            ORDER BY finished_at
+            "#,
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(matches)
+    }
+
+    async fn get_analysis_history_for_matches(
+        &self,
+        match_ids: &[String],
+    ) -> Result<Vec<AnalysisHistoryEntry>, RepositoryError> {
+        let entries = sqlx::query_as::<_, AnalysisHistoryEntry>(
+            "SELECT * FROM analysis_history WHERE match_id = ANY($1)",
+        )
+        .bind(match_ids)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(entries)
+    }
+
+    async fn get_all_digest_schedules(&self) -> Result<Vec<DigestSchedule>, RepositoryError> {
+        let schedules = sqlx::query_as::<_, DigestSchedule>("SELECT * FROM digest_schedules")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(schedules)
+    }
+
+    async fn upsert_digest_schedule(
+        &self,
+        guild_id: i64,
+        period: &str,
+        schedule: &str,
+        timezone: &str,
+        enabled: bool,
+    ) -> Result<DigestSchedule, RepositoryError> {
+        let digest = sqlx::query_as::<_, DigestSchedule>(
+            r#"
+            INSERT INTO digest_schedules (guild_id, period, schedule, timezone, enabled)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (guild_id, period) DO UPDATE SET
+                schedule = EXCLUDED.schedule,
+                timezone = EXCLUDED.timezone,
+                enabled = EXCLUDED.enabled,
+                updated_at = NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(guild_id)
+        .bind(period)
+        .bind(schedule)
+        .bind(timezone)
+        .bind(enabled)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(digest)
+    }
+
+    async fn mark_digest_sent(
+        &self,
+        id: Uuid,
+        fired_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query("UPDATE digest_schedules SET last_sent_at = $2 WHERE id = $1")
+            .bind(id)
+            .bind(fired_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn insert_rank_snapshot(
+        &self,
+        summoner_id: Uuid,
+        entry: &RankedEntry,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO rank_snapshots (summoner_id, queue_type, tier, division, league_points)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(summoner_id)
+        .bind(&entry.queue_type)
+        .bind(&entry.tier)
+        .bind(&entry.division)
+        .bind(entry.league_points)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_rank_snapshot_before(
+        &self,
+        summoner_id: Uuid,
+        queue_type: &str,
+        before: DateTime<Utc>,
+    ) -> Result<Option<RankSnapshot>, RepositoryError> {
+        let snapshot = sqlx::query_as::<_, RankSnapshot>(
+            r#"
+            SELECT * FROM rank_snapshots
+            WHERE summoner_id = $1 AND queue_type = $2 AND captured_at <= $3
+            ORDER BY captured_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(summoner_id)
+        .bind(queue_type)
+        .bind(before)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(snapshot)
+    }
+
+    async fn get_quiet_hours(&self, guild_id: i64) -> Result<Option<QuietHours>, RepositoryError> {
+        let quiet_hours =
+            sqlx::query_as::<_, QuietHours>("SELECT * FROM quiet_hours WHERE guild_id = $1")
+                .bind(guild_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(quiet_hours)
+    }
+
+    async fn upsert_quiet_hours(
+        &self,
+        guild_id: i64,
+        start_time: NaiveTime,
+        end_time: NaiveTime,
+        timezone: &str,
+        bypass_event_types: &[String],
+        enabled: bool,
+    ) -> Result<QuietHours, RepositoryError> {
+        let quiet_hours = sqlx::query_as::<_, QuietHours>(
+            r#"
+            INSERT INTO quiet_hours (guild_id, start_time, end_time, timezone, bypass_event_types, enabled)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (guild_id) DO UPDATE SET
+                start_time = EXCLUDED.start_time,
+                end_time = EXCLUDED.end_time,
+                timezone = EXCLUDED.timezone,
+                bypass_event_types = EXCLUDED.bypass_event_types,
+                enabled = EXCLUDED.enabled,
+                updated_at = NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(guild_id)
+        .bind(start_time)
+        .bind(end_time)
+        .bind(timezone)
+        .bind(bypass_event_types)
+        .bind(enabled)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(quiet_hours)
+    }
+
+    async fn get_notification_sinks(
+        &self,
+        guild_id: i64,
+    ) -> Result<Vec<NotificationSinkConfig>, RepositoryError> {
+        let sinks = sqlx::query_as::<_, NotificationSinkConfig>(
+            "SELECT * FROM notification_sinks WHERE guild_id = $1",
+        )
+        .bind(guild_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(sinks)
+    }
+
+    async fn upsert_notification_sink(
+        &self,
+        guild_id: i64,
+        kind: &str,
+        url: Option<String>,
+        enabled: bool,
+    ) -> Result<NotificationSinkConfig, RepositoryError> {
+        // A missing URL keeps the stored one, so a webhook can be toggled
+        // without repeating it.
+        let sink = sqlx::query_as::<_, NotificationSinkConfig>(
+            r#"
+            INSERT INTO notification_sinks (guild_id, kind, url, enabled)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (guild_id, kind) DO UPDATE SET
+                url = COALESCE(EXCLUDED.url, notification_sinks.url),
+                enabled = EXCLUDED.enabled,
+                updated_at = NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(guild_id)
+        .bind(kind)
+        .bind(url)
+        .bind(enabled)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(sink)
+    }
+}
+
+/// Inserts a match_history row. `None` when the match is already stored.
+async fn insert_match_result<'e>(
+    executor: impl PgExecutor<'e>,
+    result: &NewMatchResult,
+) -> Result<Option<MatchHistory>, RepositoryError> {
+    let match_history = sqlx::query_as::<_, MatchHistory>(
+        r#"
+        INSERT INTO match_history (summoner_id, match_id, game_id, win, kills, deaths, assists, champion_id, game_duration_secs, game_mode, role, total_cs, total_gold, total_damage, enemy_champion_name, enemy_cs, enemy_gold, enemy_damage, queue_id, finished_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
+        ON CONFLICT (summoner_id, match_id) DO NOTHING
+        RETURNING id, summoner_id, match_id, game_id, win, kills, deaths, assists, champion_id, game_duration_secs, game_mode, role, queue_id, total_cs, total_gold, total_damage, enemy_champion_name, enemy_cs, enemy_gold, enemy_damage, finished_at, created_at
+        "#,
+    )
+    .bind(result.summoner_id)
+    .bind(&result.match_id)
+    .bind(result.game_id)
+    .bind(result.win)
+    .bind(result.kills)
+    .bind(result.deaths)
+    .bind(result.assists)
+    .bind(result.champion_id)
+    .bind(result.game_duration_secs)
+    .bind(&result.game_mode)
+    .bind(&result.role)
+    .bind(result.total_cs)
+    .bind(result.total_gold)
+    .bind(result.total_damage)
+    .bind(&result.enemy_champion_name)
+    .bind(result.enemy_cs)
+    .bind(result.enemy_gold)
+    .bind(result.enemy_damage)
+    .bind(result.queue_id)
+    .bind(result.finished_at)
+    .fetch_optional(executor)
+    .await?;
+    Ok(match_history)
+}
+
+/// Inserts a notification_queue row. `None` when the event is already
+/// queued.
+async fn insert_notification_event<'e>(
+    executor: impl PgExecutor<'e>,
+    event: &NewNotificationEvent,
+) -> Result<Option<NotificationEvent>, RepositoryError> {
+    let notification = sqlx::query_as::<_, NotificationEvent>(
+        r#"
+        INSERT INTO notification_queue (summoner_id, event_type, game_id, match_id, champion_id, champion_name, role, win, kills, deaths, assists, game_duration_secs, game_mode, queue_id, is_featured_mode, total_cs, total_gold, total_damage, enemy_champion_name, enemy_cs, enemy_gold, enemy_damage, role_gaps)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23)
+        ON CONFLICT DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(event.summoner_id)
+    .bind(&event.event_type)
+    .bind(event.game_id)
+    .bind(&event.match_id)
+    .bind(event.champion_id)
+    .bind(&event.champion_name)
+    .bind(&event.role)
+    .bind(event.win)
+    .bind(event.kills)
+    .bind(event.deaths)
+    .bind(event.assists)
+    .bind(event.game_duration_secs)
+    .bind(&event.game_mode)
+    .bind(event.queue_id)
+    .bind(event.is_featured_mode)
+    .bind(event.total_cs)
+    .bind(event.total_gold)
+    .bind(event.total_damage)
+    .bind(&event.enemy_champion_name)
+    .bind(event.enemy_cs)
+    .bind(event.enemy_gold)
+    .bind(event.enemy_damage)
+    .bind(&event.role_gaps)
+    .fetch_optional(executor)
+    .await?;
+    Ok(notification)
 }
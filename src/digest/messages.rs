@@ -0,0 +1,139 @@
+use crate::digest::DigestPeriod;
+use crate::digest::stats::{GameLine, PlayerDigest};
+use crate::i18n::{Catalog, Locale, render};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+use serenity::model::{Colour, Timestamp};
+
+/// Discord caps embeds at 25 fields.
+const MAX_PLAYER_FIELDS: usize = 25;
+
+/// Digest embed: one field per player who played during `[start, end)`,
+/// dates shown in the guild's time zone.
+pub fn format_digest(
+    period: DigestPeriod,
+    players: &[PlayerDigest],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    tz: Tz,
+    locale: Locale,
+) -> CreateEmbed {
+    let text = locale.catalog();
+    let total_games: u32 = players.iter().map(|p| p.games).sum();
+    let date = |at: DateTime<Utc>| {
+        at.with_timezone(&tz)
+            .format(text.digest_date_format)
+            .to_string()
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title(period.title(text))
+        .colour(Colour::from_rgb(155, 89, 182))
+        .footer(CreateEmbedFooter::new("League of Legends · SFG"))
+        .timestamp(Timestamp::from(end));
+
+    if players.is_empty() {
+        return embed.description(text.digest_empty);
+    }
+
+    // The period ends at the fire time; show the last day it covers.
+    let last_day = end - chrono::Duration::seconds(1);
+    embed = embed.description(render(
+        text.digest_period,
+        &[
+            ("start", &date(start)),
+            ("end", &date(last_day)),
+            ("games", &total_games.to_string()),
+        ],
+    ));
+
+    for player in players.iter().take(MAX_PLAYER_FIELDS) {
+        embed = embed.field(
+            render(
+                text.digest_player,
+                &[
+                    ("name", &player.summoner_name),
+                    ("wins", &player.wins.to_string()),
+                    ("losses", &player.losses().to_string()),
+                    ("winrate", &player.winrate_percent().to_string()),
+                ],
+            ),
+            format_player_lines(player, text),
+            false,
+        );
+    }
+
+    embed
+}
+
+fn format_player_lines(player: &PlayerDigest, text: &Catalog) -> String {
+    let games = player.games.max(1) as f64;
+    let mut first_line = render(
+        text.digest_kda,
+        &[
+            ("kda", &format!("{:.1}", player.kda())),
+            ("kills", &format!("{:.1}", player.kills as f64 / games)),
+            ("deaths", &format!("{:.1}", player.deaths as f64 / games)),
+            ("assists", &format!("{:.1}", player.assists as f64 / games)),
+        ],
+    );
+    if let Some(lp) = player.lp_change {
+        first_line.push_str(" · ");
+        first_line.push_str(&render(text.digest_lp, &[("lp", &format!("{lp:+}"))]));
+    }
+
+    let mut lines = vec![first_line];
+
+    if !player.top_champions.is_empty() {
+        let champions: Vec<String> = player
+            .top_champions
+            .iter()
+            .map(|(name, count)| format!("{name} ×{count}"))
+            .collect();
+        lines.push(render(
+            text.digest_champions,
+            &[("champions", &champions.join(", "))],
+        ));
+    }
+    if let Some(best) = &player.best_game {
+        lines.push(render(
+            text.digest_best,
+            &[("game", &format_game(best, text))],
+        ));
+    }
+    if let Some(worst) = &player.worst_game {
+        lines.push(render(
+            text.digest_worst,
+            &[("game", &format_game(worst, text))],
+        ));
+    }
+    if !player.ratings.is_empty() {
+        lines.push(render(
+            text.digest_coach,
+            &[
+                ("good", &player.ratings.good.to_string()),
+                ("average", &player.ratings.average.to_string()),
+                ("poor", &player.ratings.poor.to_string()),
+            ],
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn format_game(game: &GameLine, text: &Catalog) -> String {
+    let result = if game.win {
+        text.win_short
+    } else {
+        text.loss_short
+    };
+    let mut line = format!(
+        "{} {}/{}/{} ({})",
+        game.champion_name, game.kills, game.deaths, game.assists, result
+    );
+    if let Some(rating) = &game.rating {
+        line.push_str(&format!(" · {rating}"));
+    }
+    line
+}
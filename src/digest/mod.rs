@@ -0,0 +1,76 @@
+//! Scheduled per-guild digests ("semaine SFG", optional daily recap).
+//!
+//! Each guild has up to one schedule per [`DigestPeriod`] in
+//! `digest_schedules`, set with `/digest`. The [`scheduler`] wakes up every
+//! minute, posts the digests whose cron expression fired since their last
+//! post, and aggregates the period from `match_history` and
+//! `analysis_history`.
+//! The weekly digest is followed by the progression report of each player
+//! with enough analyzed games that week.
+
+pub mod messages;
+pub mod schedule;
+pub mod scheduler;
+pub mod stats;
+
+use crate::i18n::Catalog;
+use chrono::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestPeriod {
+    Daily,
+    Weekly,
+}
+
+impl DigestPeriod {
+    pub const ALL: &'static [DigestPeriod] = &[DigestPeriod::Daily, DigestPeriod::Weekly];
+
+    /// Value stored in `digest_schedules.period`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DigestPeriod::Daily => "daily",
+            DigestPeriod::Weekly => "weekly",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "daily" => Some(DigestPeriod::Daily),
+            "weekly" => Some(DigestPeriod::Weekly),
+            _ => None,
+        }
+    }
+
+    /// Monday 09:00 for the weekly post, every morning for the daily one.
+    pub fn default_schedule(self) -> &'static str {
+        match self {
+            DigestPeriod::Daily => "0 9 * * *",
+            DigestPeriod::Weekly => "0 9 * * 1",
+        }
+    }
+
+    /// Span covered by a digest, ending at its fire time.
+    pub fn length(self) -> Duration {
+        match self {
+            DigestPeriod::Daily => Duration::days(1),
+            DigestPeriod::Weekly => Duration::weeks(1),
+        }
+    }
+
+    pub fn title(self, text: &Catalog) -> &'static str {
+        match self {
+            DigestPeriod::Daily => text.digest_daily_title,
+            DigestPeriod::Weekly => text.digest_weekly_title,
+        }
+    }
+
+    pub fn label(self, text: &Catalog) -> &'static str {
+        match self {
+            DigestPeriod::Daily => text.period_daily,
+            DigestPeriod::Weekly => text.period_weekly,
+        }
+    }
+}
+
+/// Time zone used when a guild has not picked one.
+pub const DEFAULT_TIMEZONE: &str = "Europe/Paris";
@@ -0,0 +1,242 @@
+//! Minimal 5-field cron expressions (`minute hour day-of-month month
+//! day-of-week`) evaluated in a guild's time zone.
+//!
+//! Each field accepts `*`, numbers, lists (`1,3,5`), ranges (`1-5`) and steps
+//! (`*/15`, `0-30/10`). Day-of-week is 0–7 with both 0 and 7 meaning Sunday.
+//! As in classic cron, when both day fields are restricted a day matching
+//! either one fires.
+
+use chrono::{DateTime, Datelike, Duration, DurationRound, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ScheduleError {
+    #[error("expected 5 fields (minute hour day month weekday), got {0}")]
+    FieldCount(usize),
+    #[error("invalid {field} field: {value:?}")]
+    InvalidField { field: &'static str, value: String },
+}
+
+/// `(name, min, max)` of each cron field, in expression order.
+const FIELDS: [(&str, u32, u32); 5] = [
+    ("minute", 0, 59),
+    ("hour", 0, 23),
+    ("day-of-month", 1, 31),
+    ("month", 1, 12),
+    ("day-of-week", 0, 7),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    /// One bitmask per field; bit `n` set means value `n` matches.
+    masks: [u64; 5],
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl FromStr for CronSchedule {
+    type Err = ScheduleError;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = expression.split_whitespace().collect();
+        if parts.len() != FIELDS.len() {
+            return Err(ScheduleError::FieldCount(parts.len()));
+        }
+
+        let mut masks = [0u64; 5];
+        for (index, (part, (name, min, max))) in parts.iter().zip(FIELDS).enumerate() {
+            masks[index] = parse_field(part, min, max).ok_or(ScheduleError::InvalidField {
+                field: name,
+                value: part.to_string(),
+            })?;
+        }
+
+        // Sunday is both 0 and 7.
+        if masks[4] & (1 << 7) != 0 {
+            masks[4] |= 1;
+        }
+
+        Ok(Self {
+            masks,
+            day_of_month_restricted: parts[2] != "*",
+            day_of_week_restricted: parts[4] != "*",
+        })
+    }
+}
+
+impl CronSchedule {
+    /// Whether the schedule fires at this local minute.
+    pub fn matches<T: Datelike + Timelike>(&self, local: &T) -> bool {
+        let hit = |field: usize, value: u32| self.masks[field] & (1 << value) != 0;
+
+        if !hit(0, local.minute()) || !hit(1, local.hour()) || !hit(3, local.month()) {
+            return false;
+        }
+
+        let day_of_month = hit(2, local.day());
+        let day_of_week = hit(4, local.weekday().num_days_from_sunday());
+        match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        }
+    }
+
+    /// The most recent fire time in `(after, until]`, walking back minute by
+    /// minute in UTC so DST gaps and overlaps resolve naturally.
+    pub fn latest_fire_between(
+        &self,
+        tz: Tz,
+        after: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let mut minute = until.duration_trunc(Duration::minutes(1)).ok()?;
+        while minute > after {
+            if self.matches(&tz.from_utc_datetime(&minute.naive_utc())) {
+                return Some(minute);
+            }
+            minute -= Duration::minutes(1);
+        }
+        None
+    }
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> Option<u64> {
+    let mut mask = 0u64;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().ok().filter(|s| *s > 0)?),
+            None => (item, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (start.parse().ok()?, end.parse().ok()?),
+                None => {
+                    let value = range.parse().ok()?;
+                    // `5/15` means "from 5 to the end, every 15".
+                    if item.contains('/') {
+                        (value, max)
+                    } else {
+                        (value, value)
+                    }
+                }
+            },
+        };
+        if start < min || end > max || start > end {
+            return None;
+        }
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Some(mask)
+}
+
+/// Parses an IANA time zone name (`Europe/Paris`).
+pub fn parse_timezone(name: &str) -> Option<Tz> {
+    name.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.from_utc_datetime(
+            &NaiveDate::from_ymd_opt(y, m, d)
+                .unwrap()
+                .and_hms_opt(h, min, 0)
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn parses_lists_ranges_and_steps() {
+        let cron: CronSchedule = "*/15 9-11 * * 1,3,5".parse().unwrap();
+        let paris = chrono_tz::Europe::Paris;
+        // Monday 2026-10-19 09:45 Paris.
+        assert!(cron.matches(&paris.with_ymd_and_hms(2026, 10, 19, 9, 45, 0).unwrap()));
+        assert!(!cron.matches(&paris.with_ymd_and_hms(2026, 10, 19, 9, 50, 0).unwrap()));
+        // Tuesday.
+        assert!(!cron.matches(&paris.with_ymd_and_hms(2026, 10, 20, 9, 45, 0).unwrap()));
+    }
+
+    #[test]
+    fn sunday_is_zero_or_seven() {
+        let paris = chrono_tz::Europe::Paris;
+        let sunday = paris.with_ymd_and_hms(2026, 10, 18, 20, 0, 0).unwrap();
+        for expression in ["0 20 * * 0", "0 20 * * 7"] {
+            let cron: CronSchedule = expression.parse().unwrap();
+            assert!(cron.matches(&sunday), "{expression}");
+        }
+    }
+
+    #[test]
+    fn restricted_day_fields_are_ored() {
+        // 1st of the month OR any Monday.
+        let cron: CronSchedule = "0 9 1 * 1".parse().unwrap();
+        let paris = chrono_tz::Europe::Paris;
+        assert!(cron.matches(&paris.with_ymd_and_hms(2026, 10, 1, 9, 0, 0).unwrap()));
+        assert!(cron.matches(&paris.with_ymd_and_hms(2026, 10, 19, 9, 0, 0).unwrap()));
+        assert!(!cron.matches(&paris.with_ymd_and_hms(2026, 10, 20, 9, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        assert_eq!(
+            "0 9 * *".parse::<CronSchedule>(),
+            Err(ScheduleError::FieldCount(4))
+        );
+        for expression in [
+            "60 9 * * 1",
+            "0 9 * * 8",
+            "0 9-7 * * *",
+            "*/0 * * * *",
+            "a * * * *",
+        ] {
+            assert!(expression.parse::<CronSchedule>().is_err(), "{expression}");
+        }
+    }
+
+    #[test]
+    fn latest_fire_uses_the_guild_time_zone() {
+        let cron: CronSchedule = "0 9 * * 1".parse().unwrap();
+        // Monday 09:00 in Paris (CEST, UTC+2) is 07:00 UTC.
+        let fire = cron.latest_fire_between(
+            chrono_tz::Europe::Paris,
+            utc(2026, 10, 19, 6, 0),
+            utc(2026, 10, 19, 7, 5),
+        );
+        assert_eq!(fire, Some(utc(2026, 10, 19, 7, 0)));
+
+        // Same instant is not yet 09:00 in New York.
+        let fire = cron.latest_fire_between(
+            chrono_tz::America::New_York,
+            utc(2026, 10, 19, 6, 0),
+            utc(2026, 10, 19, 7, 5),
+        );
+        assert_eq!(fire, None);
+    }
+
+    #[test]
+    fn latest_fire_excludes_the_lower_bound() {
+        let cron: CronSchedule = "0 9 * * *".parse().unwrap();
+        let fired = utc(2026, 10, 19, 7, 0);
+        assert_eq!(
+            cron.latest_fire_between(chrono_tz::Europe::Paris, fired, utc(2026, 10, 19, 7, 10)),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_iana_time_zones() {
+        assert_eq!(
+            parse_timezone("Europe/Paris"),
+            Some(chrono_tz::Europe::Paris)
+        );
+        assert_eq!(parse_timezone("Mars/Olympus"), None);
+    }
+}
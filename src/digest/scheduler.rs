@@ -0,0 +1,345 @@
+use crate::analysis::discord::format_progression_embed;
+use crate::analysis::pipeline::AnalysisPipeline;
+use crate::analysis::progression::progression_report;
+use crate::db::models::{DigestSchedule, Summoner};
+use crate::db::repository::Repository;
+use crate::digest::DigestPeriod;
+use crate::digest::messages::format_digest;
+use crate::digest::schedule::{CronSchedule, parse_timezone};
+use crate::digest::stats::build_player_digests;
+use crate::i18n::Locale;
+use crate::riot::client::{RiotApiClient, RiotClient};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serenity::builder::CreateMessage;
+use serenity::model::id::ChannelId;
+use serenity::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+const TICK_INTERVAL_SECS: u64 = 60;
+/// How far back a missed fire time is still honoured (slow tick, short
+/// restart). Longer outages skip the digest rather than posting it late.
+const CATCH_UP_MINUTES: i64 = 15;
+/// A rank snapshot stored this long after a period started still counts as
+/// its baseline (snapshots are taken while building the previous digest).
+const SNAPSHOT_SLACK_MINUTES: i64 = 60;
+
+pub struct DigestScheduler {
+    repository: Arc<dyn Repository>,
+    riot_client: Arc<dyn RiotApiClient>,
+    /// Writes the coaching paragraph of the weekly progression reports.
+    analysis_pipeline: Option<Arc<AnalysisPipeline>>,
+    ctx: Context,
+}
+
+impl DigestScheduler {
+    pub fn new(
+        repository: Arc<dyn Repository>,
+        riot_client: Arc<dyn RiotApiClient>,
+        analysis_pipeline: Option<Arc<AnalysisPipeline>>,
+        ctx: Context,
+    ) -> Self {
+        Self {
+            repository,
+            riot_client,
+            analysis_pipeline,
+            ctx,
+        }
+    }
+
+    pub async fn start(self) {
+        let mut ticker = tokio::time::interval(Duration::from_secs(TICK_INTERVAL_SECS));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.run_due_digests(Utc::now()).await {
+                tracing::error!("Error running scheduled digests: {}", e);
+            }
+        }
+    }
+
+    async fn run_due_digests(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        for digest in self.repository.get_all_digest_schedules().await? {
+            if !digest.enabled {
+                continue;
+            }
+            let Some(fired_at) = due_fire_time(&digest, now) else {
+                continue;
+            };
+            if let Err(e) = self.send_digest(&digest, fired_at).await {
+                tracing::error!(
+                    guild_id = digest.guild_id,
+                    period = digest.period.as_str(),
+                    "Failed to send digest: {}",
+                    e
+                );
+            }
+            // Recorded even on failure: a broken channel must not make the
+            // digest retry every minute.
+            self.repository
+                .mark_digest_sent(digest.id, fired_at)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn send_digest(
+        &self,
+        digest: &DigestSchedule,
+        fired_at: DateTime<Utc>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let Some(period) = DigestPeriod::parse(&digest.period) else {
+            return Ok(());
+        };
+        let Some(config) = self.repository.get_bot_config(digest.guild_id).await? else {
+            return Ok(());
+        };
+        let tz = parse_timezone(&digest.timezone).unwrap_or(chrono_tz::Europe::Paris);
+        let start = fired_at - period.length();
+
+        let matches = self
+            .repository
+            .get_match_history_between(start, fired_at)
+            .await?;
+        let match_ids: Vec<String> = matches.iter().map(|m| m.match_id.clone()).collect();
+        let analyses = if match_ids.is_empty() {
+            Vec::new()
+        } else {
+            self.repository
+                .get_analysis_history_for_matches(&match_ids)
+                .await?
+        };
+
+        let active: Vec<Summoner> = self
+            .repository
+            .get_all_summoners()
+            .await?
+            .into_iter()
+            .filter(|s| matches.iter().any(|m| m.summoner_id == s.id))
+            .collect();
+
+        let mut champion_names = HashMap::new();
+        for champion_id in matches.iter().map(|m| m.champion_id) {
+            if champion_names.contains_key(&champion_id) {
+                continue;
+            }
+            if let Some(champion) = self.repository.get_champion_by_id(champion_id).await? {
+                champion_names.insert(champion_id, champion.champion_name);
+            }
+        }
+
+        let lp_changes = self.lp_changes(&active, start).await;
+        let players =
+            build_player_digests(&active, &matches, &analyses, &champion_names, &lp_changes);
+
+        let embed = format_digest(period, &players, start, fired_at, tz, config.locale());
+        let channel = ChannelId::new(config.channel_id as u64);
+        channel
+            .send_message(&self.ctx.http, CreateMessage::new().embed(embed))
+            .await?;
+        if period == DigestPeriod::Weekly {
+            self.send_progression_reports(channel, &active, start, fired_at, tz, config.locale())
+                .await;
+        }
+
+        tracing::info!(
+            guild_id = digest.guild_id,
+            period = period.as_str(),
+            players = players.len(),
+            "Sent scheduled digest"
+        );
+        Ok(())
+    }
+
+    /// Posts the progression report of each player of the week with enough
+    /// analyzed games. Best-effort: failures are logged and skip the player.
+    async fn send_progression_reports(
+        &self,
+        channel: ChannelId,
+        summoners: &[Summoner],
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        tz: Tz,
+        locale: Locale,
+    ) {
+        for summoner in summoners {
+            let summoner_name = format!("{}#{}", summoner.game_name, summoner.tag_line);
+            let report = match progression_report(
+                self.repository.as_ref(),
+                self.analysis_pipeline.as_deref(),
+                &summoner_name,
+                &summoner.riot_puuid,
+                start,
+                end,
+                locale,
+            )
+            .await
+            {
+                Ok(Some(report)) => report,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!(
+                        summoner = summoner_name.as_str(),
+                        error = %e,
+                        "Failed to build progression report"
+                    );
+                    continue;
+                }
+            };
+            let embed = format_progression_embed(&report, tz, locale);
+            if let Err(e) = channel
+                .send_message(&self.ctx.http, CreateMessage::new().embed(embed))
+                .await
+            {
+                tracing::warn!(
+                    summoner = summoner_name.as_str(),
+                    error = %e,
+                    "Failed to send progression report"
+                );
+            }
+        }
+    }
+
+    /// Snapshots each player's current Solo/Duo rank and returns the LP net
+    /// change against the latest snapshot taken around `since` — normally
+    /// the one the previous digest stored, a few seconds after it fired. An
+    /// older snapshot is still a valid baseline since LP only moves when
+    /// playing. Players without one (first digest, unranked) are left out.
+    async fn lp_changes(&self, summoners: &[Summoner], since: DateTime<Utc>) -> HashMap<Uuid, i32> {
+        let baseline_cutoff = since + chrono::Duration::minutes(SNAPSHOT_SLACK_MINUTES);
+        let mut changes = HashMap::new();
+        for summoner in summoners {
+            let platform = RiotClient::platform_for_region(&summoner.region);
+            let current = match self
+                .riot_client
+                .get_ranked_solo_entry(&summoner.riot_puuid, platform)
+                .await
+            {
+                Ok(Some(entry)) => entry,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!(
+                        summoner = summoner.game_name.as_str(),
+                        error = %e,
+                        "Failed to fetch ranked entry for digest"
+                    );
+                    continue;
+                }
+            };
+
+            if let Err(e) = self
+                .repository
+                .insert_rank_snapshot(summoner.id, &current)
+                .await
+            {
+                tracing::warn!(error = %e, "Failed to store rank snapshot");
+            }
+
+            let previous = match self
+                .repository
+                .get_rank_snapshot_before(summoner.id, &current.queue_type, baseline_cutoff)
+                .await
+            {
+                Ok(Some(snapshot)) => snapshot,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to fetch rank snapshot");
+                    continue;
+                }
+            };
+            if let (Some(now), Some(before)) =
+                (current.absolute_lp(), previous.entry().absolute_lp())
+            {
+                changes.insert(summoner.id, now - before);
+            }
+        }
+        changes
+    }
+}
+
+/// The fire time to post for, if the schedule fired since its last post
+/// (looking back at most [`CATCH_UP_MINUTES`]). Invalid schedules or time
+/// zones are logged and never fire.
+fn due_fire_time(digest: &DigestSchedule, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let cron: CronSchedule = match digest.schedule.parse() {
+        Ok(cron) => cron,
+        Err(e) => {
+            tracing::warn!(
+                guild_id = digest.guild_id,
+                schedule = digest.schedule.as_str(),
+                error = %e,
+                "Invalid digest schedule"
+            );
+            return None;
+        }
+    };
+    let Some(tz) = parse_timezone(&digest.timezone) else {
+        tracing::warn!(
+            guild_id = digest.guild_id,
+            timezone = digest.timezone.as_str(),
+            "Invalid digest time zone"
+        );
+        return None;
+    };
+
+    let catch_up_floor = now - chrono::Duration::minutes(CATCH_UP_MINUTES);
+    let after = digest
+        .last_sent_at
+        .map_or(catch_up_floor, |sent| sent.max(catch_up_floor));
+    cron.latest_fire_between(tz, after, now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn schedule(last_sent_at: Option<DateTime<Utc>>) -> DigestSchedule {
+        DigestSchedule {
+            id: Uuid::new_v4(),
+            guild_id: 1,
+            period: "weekly".to_string(),
+            schedule: "0 9 * * 1".to_string(),
+            timezone: "Europe/Paris".to_string(),
+            enabled: true,
+            last_sent_at,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    // Monday 2026-10-19 09:00 Europe/Paris.
+    fn monday_nine() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 19, 7, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn fires_once_per_occurrence() {
+        let now = monday_nine() + chrono::Duration::minutes(2);
+        assert_eq!(due_fire_time(&schedule(None), now), Some(monday_nine()));
+        assert_eq!(due_fire_time(&schedule(Some(monday_nine())), now), None);
+    }
+
+    #[test]
+    fn skips_occurrences_older_than_the_catch_up_window() {
+        let now = monday_nine() + chrono::Duration::hours(3);
+        assert_eq!(due_fire_time(&schedule(None), now), None);
+    }
+
+    #[test]
+    fn invalid_schedule_never_fires() {
+        let mut digest = schedule(None);
+        digest.schedule = "every monday".to_string();
+        assert_eq!(due_fire_time(&digest, monday_nine()), None);
+
+        let mut digest = schedule(None);
+        digest.timezone = "Nowhere/Land".to_string();
+        assert_eq!(due_fire_time(&digest, monday_nine()), None);
+    }
+}
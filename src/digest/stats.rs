@@ -0,0 +1,355 @@
+//! Per-player aggregation of a digest period.
+
+use crate::db::models::{AnalysisHistoryEntry, MatchHistory, Summoner};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// How many champions are listed per player.
+pub const TOP_CHAMPIONS: usize = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameLine {
+    pub champion_name: String,
+    pub win: bool,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    /// Coach rating (`Good`/`Average`/`Poor`) when the game was analyzed.
+    pub rating: Option<String>,
+}
+
+impl GameLine {
+    pub fn kda(&self) -> f64 {
+        kda_ratio(self.kills, self.deaths, self.assists)
+    }
+
+    /// Ranking used to pick the best and worst game: KDA, plus a bonus for
+    /// a win and for the coach's verdict when there is one.
+    fn score(&self) -> f64 {
+        let win_bonus = if self.win { 1.0 } else { 0.0 };
+        let rating_bonus = match self.rating.as_deref() {
+            Some(r) if r.eq_ignore_ascii_case("good") => 2.0,
+            Some(r) if r.eq_ignore_ascii_case("poor") => -2.0,
+            _ => 0.0,
+        };
+        self.kda() + win_bonus + rating_bonus
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RatingCounts {
+    pub good: u32,
+    pub average: u32,
+    pub poor: u32,
+}
+
+impl RatingCounts {
+    pub fn is_empty(&self) -> bool {
+        self.good + self.average + self.poor == 0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerDigest {
+    pub summoner_name: String,
+    pub games: u32,
+    pub wins: u32,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    /// Most played champions, most games first.
+    pub top_champions: Vec<(String, u32)>,
+    /// Solo/Duo LP net change over the period, when a previous rank
+    /// snapshot exists.
+    pub lp_change: Option<i32>,
+    pub best_game: Option<GameLine>,
+    /// Only set when the player has at least two games.
+    pub worst_game: Option<GameLine>,
+    pub ratings: RatingCounts,
+}
+
+impl PlayerDigest {
+    pub fn losses(&self) -> u32 {
+        self.games - self.wins
+    }
+
+    pub fn winrate_percent(&self) -> u32 {
+        if self.games == 0 {
+            0
+        } else {
+            (self.wins as f64 * 100.0 / self.games as f64).round() as u32
+        }
+    }
+
+    pub fn kda(&self) -> f64 {
+        kda_ratio(self.kills, self.deaths, self.assists)
+    }
+}
+
+fn kda_ratio(kills: i32, deaths: i32, assists: i32) -> f64 {
+    (kills + assists) as f64 / deaths.max(1) as f64
+}
+
+/// Builds one digest per summoner who played in the period, most active
+/// first. `champion_names` maps champion IDs to names (IDs are shown as-is
+/// when missing); `lp_changes` is keyed by summoner ID.
+pub fn build_player_digests(
+    summoners: &[Summoner],
+    matches: &[MatchHistory],
+    analyses: &[AnalysisHistoryEntry],
+    champion_names: &HashMap<i32, String>,
+    lp_changes: &HashMap<Uuid, i32>,
+) -> Vec<PlayerDigest> {
+    let ratings: HashMap<(&str, &str), &str> = analyses
+        .iter()
+        .filter_map(|a| {
+            let rating = a.overall_rating.as_deref()?;
+            Some(((a.riot_puuid.as_str(), a.match_id.as_str()), rating))
+        })
+        .collect();
+
+    let mut digests: Vec<PlayerDigest> = summoners
+        .iter()
+        .filter_map(|summoner| {
+            let games: Vec<&MatchHistory> = matches
+                .iter()
+                .filter(|m| m.summoner_id == summoner.id)
+                .collect();
+            if games.is_empty() {
+                return None;
+            }
+
+            let lines: Vec<GameLine> = games
+                .iter()
+                .map(|m| GameLine {
+                    champion_name: champion_names
+                        .get(&m.champion_id)
+                        .cloned()
+                        .unwrap_or_else(|| m.champion_id.to_string()),
+                    win: m.win,
+                    kills: m.kills,
+                    deaths: m.deaths,
+                    assists: m.assists,
+                    rating: ratings
+                        .get(&(summoner.riot_puuid.as_str(), m.match_id.as_str()))
+                        .map(|r| r.to_string()),
+                })
+                .collect();
+
+            let mut rating_counts = RatingCounts::default();
+            for rating in lines.iter().filter_map(|l| l.rating.as_deref()) {
+                match rating.to_ascii_lowercase().as_str() {
+                    "good" => rating_counts.good += 1,
+                    "average" => rating_counts.average += 1,
+                    "poor" => rating_counts.poor += 1,
+                    _ => {}
+                }
+            }
+
+            let by_score = |a: &&GameLine, b: &&GameLine| a.score().total_cmp(&b.score());
+            let best_game = lines.iter().max_by(by_score).cloned();
+            let worst_game = if lines.len() >= 2 {
+                lines.iter().min_by(by_score).cloned()
+            } else {
+                None
+            };
+
+            Some(PlayerDigest {
+                summoner_name: format!("{}#{}", summoner.game_name, summoner.tag_line),
+                games: lines.len() as u32,
+                wins: lines.iter().filter(|l| l.win).count() as u32,
+                kills: lines.iter().map(|l| l.kills).sum(),
+                deaths: lines.iter().map(|l| l.deaths).sum(),
+                assists: lines.iter().map(|l| l.assists).sum(),
+                top_champions: top_champions(&lines),
+                lp_change: lp_changes.get(&summoner.id).copied(),
+                best_game,
+                worst_game,
+                ratings: rating_counts,
+            })
+        })
+        .collect();
+
+    digests.sort_by(|a, b| {
+        b.games
+            .cmp(&a.games)
+            .then_with(|| a.summoner_name.cmp(&b.summoner_name))
+    });
+    digests
+}
+
+fn top_champions(lines: &[GameLine]) -> Vec<(String, u32)> {
+    let mut counts: HashMap<&str, u32> = HashMap::new();
+    for line in lines {
+        *counts.entry(line.champion_name.as_str()).or_default() += 1;
+    }
+    let mut sorted: Vec<(String, u32)> = counts
+        .into_iter()
+        .map(|(name, count)| (name.to_string(), count))
+        .collect();
+    sorted.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    sorted.truncate(TOP_CHAMPIONS);
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn summoner(name: &str) -> Summoner {
+        Summoner {
+            id: Uuid::new_v4(),
+            riot_puuid: format!("puuid-{name}"),
+            game_name: name.to_string(),
+            tag_line: "EUW".to_string(),
+            region: "euw1".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            benchmark_tier: None,
+        }
+    }
+
+    fn game(
+        summoner: &Summoner,
+        match_id: &str,
+        champion_id: i32,
+        win: bool,
+        kda: (i32, i32, i32),
+    ) -> MatchHistory {
+        MatchHistory {
+            id: Uuid::new_v4(),
+            summoner_id: summoner.id,
+            match_id: match_id.to_string(),
+            game_id: 1,
+            win,
+            kills: kda.0,
+            deaths: kda.1,
+            assists: kda.2,
+            champion_id,
+            game_duration_secs: 1800,
+            game_mode: "CLASSIC".to_string(),
+            role: Some("MIDDLE".to_string()),
+            queue_id: Some(420),
+            finished_at: Utc::now(),
+            created_at: Utc::now(),
+            total_cs: 200,
+            total_gold: 12000,
+            total_damage: 20000,
+            enemy_champion_name: None,
+            enemy_cs: None,
+            enemy_gold: None,
+            enemy_damage: None,
+        }
+    }
+
+    fn analysis(summoner: &Summoner, match_id: &str, rating: &str) -> AnalysisHistoryEntry {
+        AnalysisHistoryEntry {
+            id: Uuid::new_v4(),
+            riot_puuid: summoner.riot_puuid.clone(),
+            match_id: match_id.to_string(),
+            role: "MIDDLE".to_string(),
+            champion_name: "Ahri".to_string(),
+            win: true,
+            overall_rating: Some(rating.to_string()),
+            analysis_data: "{}".to_string(),
+            analysis_result: None,
+            prompt_hash: None,
+            model: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn champions() -> HashMap<i32, String> {
+        HashMap::from([(103, "Ahri".to_string()), (99, "Lux".to_string())])
+    }
+
+    #[test]
+    fn aggregates_record_kda_and_champions() {
+        let player = summoner("Alpha");
+        let matches = vec![
+            game(&player, "M1", 103, true, (10, 2, 8)),
+            game(&player, "M2", 103, false, (3, 6, 4)),
+            game(&player, "M3", 99, true, (5, 1, 12)),
+        ];
+        let lp = HashMap::from([(player.id, 42)]);
+
+        let digests = build_player_digests(&[player], &matches, &[], &champions(), &lp);
+
+        assert_eq!(digests.len(), 1);
+        let digest = &digests[0];
+        assert_eq!(digest.summoner_name, "Alpha#EUW");
+        assert_eq!((digest.games, digest.wins, digest.losses()), (3, 2, 1));
+        assert_eq!(digest.winrate_percent(), 67);
+        assert_eq!((digest.kills, digest.deaths, digest.assists), (18, 9, 24));
+        assert!((digest.kda() - 42.0 / 9.0).abs() < 1e-9);
+        assert_eq!(
+            digest.top_champions,
+            vec![("Ahri".to_string(), 2), ("Lux".to_string(), 1)]
+        );
+        assert_eq!(digest.lp_change, Some(42));
+    }
+
+    #[test]
+    fn best_and_worst_games_use_kda_and_coach_rating() {
+        let player = summoner("Alpha");
+        let matches = vec![
+            game(&player, "M1", 103, true, (6, 2, 6)),
+            game(&player, "M2", 99, true, (7, 2, 6)),
+            game(&player, "M3", 103, false, (1, 8, 2)),
+        ];
+        // M1 has a slightly lower KDA but the coach rated it Good.
+        let analyses = vec![
+            analysis(&player, "M1", "Good"),
+            analysis(&player, "M3", "Poor"),
+        ];
+
+        let digests = build_player_digests(
+            &[player],
+            &matches,
+            &analyses,
+            &champions(),
+            &HashMap::new(),
+        );
+        let digest = &digests[0];
+
+        let best = digest.best_game.as_ref().unwrap();
+        assert_eq!((best.kills, best.rating.as_deref()), (6, Some("Good")));
+        let worst = digest.worst_game.as_ref().unwrap();
+        assert_eq!((worst.kills, worst.deaths), (1, 8));
+        assert_eq!(
+            digest.ratings,
+            RatingCounts {
+                good: 1,
+                average: 0,
+                poor: 1
+            }
+        );
+    }
+
+    #[test]
+    fn skips_idle_players_and_sorts_by_activity() {
+        let idle = summoner("Idle");
+        let casual = summoner("Casual");
+        let grinder = summoner("Grinder");
+        let matches = vec![
+            game(&casual, "M1", 103, true, (1, 1, 1)),
+            game(&grinder, "M2", 103, true, (1, 1, 1)),
+            game(&grinder, "M3", 103, true, (1, 1, 1)),
+        ];
+
+        let digests = build_player_digests(
+            &[idle, casual, grinder],
+            &matches,
+            &[],
+            &champions(),
+            &HashMap::new(),
+        );
+
+        let names: Vec<_> = digests.iter().map(|d| d.summoner_name.as_str()).collect();
+        assert_eq!(names, vec!["Grinder#EUW", "Casual#EUW"]);
+        // A single game is the best one, never also the worst.
+        assert!(digests[1].best_game.is_some());
+        assert!(digests[1].worst_game.is_none());
+    }
+}
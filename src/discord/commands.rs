@@ -1,13 +1,27 @@
-use crate::analysis::discord::{format_analysis_embed, format_analysis_error_embed};
+use crate::analysis::discord::{
+    format_analysis_embed, format_analysis_error_embed, format_progression_embed,
+};
+use crate::analysis::jobs::format_analysis_jobs;
 use crate::analysis::pipeline::AnalysisPipeline;
+use crate::analysis::progression::{MAX_DAYS, MIN_GAMES, progression_report};
+use crate::analysis::roles::TierBand;
 use crate::db::repository::Repository;
+use crate::digest::schedule::{CronSchedule, parse_timezone};
+use crate::digest::{DEFAULT_TIMEZONE, DigestPeriod};
+use crate::i18n::{Catalog, Locale, render};
+use crate::notification::dead_letters::{SelectError, format_dead_letters, select_dead_letters};
 use crate::notification::messages::format_single_game_ended;
+use crate::notification::quiet_hours::{
+    DEFAULT_END, DEFAULT_START, HOLDABLE_EVENT_TYPES, parse_bypass_list, parse_time,
+};
+use crate::notification::sinks::SinkKind;
 use crate::riot::client::{RiotApiClient, RiotClient};
 use serenity::builder::{
     CreateCommand, CreateCommandOption, CreateInteractionResponse,
     CreateInteractionResponseFollowup, CreateInteractionResponseMessage,
 };
 use serenity::model::application::{CommandOptionType, ResolvedValue};
+use serenity::model::id::GuildId;
 use serenity::model::permissions::Permissions;
 use serenity::prelude::*;
 use std::sync::Arc;
@@ -19,55 +33,264 @@ pub fn register_all() -> Vec<CreateCommand> {
         register_list_summoners(),
         register_add_summoner(),
         register_remove_summoner(),
+        register_set_language(),
+        register_digest(),
+        register_quiet_hours(),
+        register_notification_sink(),
+        register_dead_letters(),
+        register_analysis_jobs(),
+        register_benchmark_tier(),
+        register_progression(),
     ]
 }
 
+/// Builds a command whose description is French by default and translated
+/// for every other locale's Discord clients.
+fn localized_command(name: &str, description: fn(&Catalog) -> &'static str) -> CreateCommand {
+    let mut command = CreateCommand::new(name).description(description(Locale::Fr.catalog()));
+    for locale in Locale::ALL.iter().filter(|l| **l != Locale::Fr) {
+        for discord_locale in locale.discord_locales() {
+            command = command.description_localized(*discord_locale, description(locale.catalog()));
+        }
+    }
+    command
+}
+
+fn localized_option(
+    kind: CommandOptionType,
+    name: &str,
+    description: fn(&Catalog) -> &'static str,
+) -> CreateCommandOption {
+    let mut option = CreateCommandOption::new(kind, name, description(Locale::Fr.catalog()));
+    for locale in Locale::ALL.iter().filter(|l| **l != Locale::Fr) {
+        for discord_locale in locale.discord_locales() {
+            option = option.description_localized(*discord_locale, description(locale.catalog()));
+        }
+    }
+    option
+}
+
+fn summoner_name_option() -> CreateCommandOption {
+    localized_option(CommandOptionType::String, "summoner_name", |t| {
+        t.opt_summoner_name
+    })
+    .required(true)
+}
+
 fn register_analyze_last_game() -> CreateCommand {
-    CreateCommand::new("analyze-last-game")
-        .description("Analyse la dernière partie d'un invocateur")
-        .add_option(
-            CreateCommandOption::new(
-                CommandOptionType::String,
-                "summoner_name",
-                "Nom d'invocateur au format : Nom#Tag",
-            )
-            .required(true),
-        )
+    localized_command("analyze-last-game", |t| t.cmd_analyze_last_game)
+        .add_option(summoner_name_option())
+        .add_option(localized_option(CommandOptionType::Boolean, "force", |t| {
+            t.opt_analysis_force
+        }))
 }
 
 fn register_init_sfg_bot() -> CreateCommand {
-    CreateCommand::new("init-sfg-bot")
-        .description("Configure ce salon comme salon de notifications du bot")
+    localized_command("init-sfg-bot", |t| t.cmd_init_sfg_bot)
 }
 
 fn register_list_summoners() -> CreateCommand {
-    CreateCommand::new("list-summoners").description("Affiche la liste des invocateurs suivis")
+    localized_command("list-summoners", |t| t.cmd_list_summoners)
 }
 
 fn register_add_summoner() -> CreateCommand {
-    CreateCommand::new("add-summoner")
-        .description("Ajoute un invocateur à la liste de suivi")
+    localized_command("add-summoner", |t| t.cmd_add_summoner).add_option(summoner_name_option())
+}
+
+fn register_remove_summoner() -> CreateCommand {
+    localized_command("remove-summoner", |t| t.cmd_remove_summoner)
+        .add_option(summoner_name_option())
+}
+
+fn register_set_language() -> CreateCommand {
+    let mut option =
+        localized_option(CommandOptionType::String, "language", |t| t.opt_language).required(true);
+    for locale in Locale::ALL {
+        option = option.add_string_choice(locale.catalog().language_name, locale.code());
+    }
+    localized_command("set-language", |t| t.cmd_set_language)
+        .default_member_permissions(Permissions::MANAGE_GUILD)
+        .add_option(option)
+}
+
+fn register_digest() -> CreateCommand {
+    let mut period = localized_option(CommandOptionType::String, "period", |t| t.opt_digest_period)
+        .required(true);
+    for digest_period in DigestPeriod::ALL {
+        let translations = Locale::ALL
+            .iter()
+            .filter(|l| **l != Locale::Fr)
+            .flat_map(|l| {
+                l.discord_locales()
+                    .iter()
+                    .map(move |code| (*code, digest_period.label(l.catalog())))
+            });
+        period = period.add_string_choice_localized(
+            digest_period.label(Locale::Fr.catalog()),
+            digest_period.as_str(),
+            translations,
+        );
+    }
+
+    localized_command("digest", |t| t.cmd_digest)
+        .default_member_permissions(Permissions::MANAGE_GUILD)
+        .add_option(period)
         .add_option(
-            CreateCommandOption::new(
-                CommandOptionType::String,
-                "summoner_name",
-                "Nom d'invocateur au format : Nom#Tag",
-            )
+            localized_option(CommandOptionType::Boolean, "enabled", |t| {
+                t.opt_digest_enabled
+            })
             .required(true),
         )
+        .add_option(localized_option(
+            CommandOptionType::String,
+            "schedule",
+            |t| t.opt_digest_schedule,
+        ))
+        .add_option(localized_option(
+            CommandOptionType::String,
+            "timezone",
+            |t| t.opt_timezone,
+        ))
 }
 
-fn register_remove_summoner() -> CreateCommand {
-    CreateCommand::new("remove-summoner")
-        .description("Retire un invocateur de la liste de suivi")
+fn register_quiet_hours() -> CreateCommand {
+    localized_command("quiet-hours", |t| t.cmd_quiet_hours)
+        .default_member_permissions(Permissions::MANAGE_GUILD)
         .add_option(
-            CreateCommandOption::new(
-                CommandOptionType::String,
-                "summoner_name",
-                "Nom d'invocateur au format : Nom#Tag",
-            )
+            localized_option(CommandOptionType::Boolean, "enabled", |t| {
+                t.opt_quiet_enabled
+            })
             .required(true),
         )
+        .add_option(localized_option(CommandOptionType::String, "start", |t| {
+            t.opt_quiet_start
+        }))
+        .add_option(localized_option(CommandOptionType::String, "end", |t| {
+            t.opt_quiet_end
+        }))
+        .add_option(localized_option(
+            CommandOptionType::String,
+            "timezone",
+            |t| t.opt_timezone,
+        ))
+        .add_option(localized_option(CommandOptionType::String, "bypass", |t| {
+            t.opt_quiet_bypass
+        }))
+}
+
+fn register_notification_sink() -> CreateCommand {
+    let mut kind =
+        localized_option(CommandOptionType::String, "kind", |t| t.opt_sink_kind).required(true);
+    for sink_kind in SinkKind::ALL {
+        kind = kind.add_string_choice(sink_kind.label(), sink_kind.as_str());
+    }
+
+    localized_command("notification-sink", |t| t.cmd_notification_sink)
+        .default_member_permissions(Permissions::MANAGE_GUILD)
+        .add_option(kind)
+        .add_option(
+            localized_option(CommandOptionType::Boolean, "enabled", |t| {
+                t.opt_sink_enabled
+            })
+            .required(true),
+        )
+        .add_option(localized_option(CommandOptionType::String, "url", |t| {
+            t.opt_sink_url
+        }))
+}
+
+fn register_dead_letters() -> CreateCommand {
+    let id_option = || localized_option(CommandOptionType::String, "id", |t| t.opt_dead_letter_id);
+
+    localized_command("dead-letters", |t| t.cmd_dead_letters)
+        .default_member_permissions(Permissions::MANAGE_GUILD)
+        .add_option(localized_option(
+            CommandOptionType::SubCommand,
+            "list",
+            |t| t.sub_dead_letters_list,
+        ))
+        .add_option(
+            localized_option(CommandOptionType::SubCommand, "retry", |t| {
+                t.sub_dead_letters_retry
+            })
+            .add_sub_option(id_option()),
+        )
+        .add_option(
+            localized_option(CommandOptionType::SubCommand, "discard", |t| {
+                t.sub_dead_letters_discard
+            })
+            .add_sub_option(id_option()),
+        )
+}
+
+fn register_analysis_jobs() -> CreateCommand {
+    localized_command("analysis-jobs", |t| t.cmd_analysis_jobs)
+}
+
+/// Value of the `/benchmark-tier` choice that clears the override.
+const BENCHMARK_TIER_AUTO: &str = "auto";
+
+fn register_benchmark_tier() -> CreateCommand {
+    let mut tier = localized_option(CommandOptionType::String, "tier", |t| t.opt_benchmark_tier)
+        .required(true);
+    let choices = std::iter::once((BENCHMARK_TIER_AUTO, None)).chain(
+        TierBand::ALL
+            .into_iter()
+            .map(|band| (band.as_str(), Some(band))),
+    );
+    for (value, band) in choices {
+        let label = move |text: &Catalog| match band {
+            Some(band) => band.label(text),
+            None => text.tier_band_auto,
+        };
+        let translations = Locale::ALL
+            .iter()
+            .filter(|l| **l != Locale::Fr)
+            .flat_map(|l| {
+                l.discord_locales()
+                    .iter()
+                    .map(move |code| (*code, label(l.catalog())))
+            });
+        tier = tier.add_string_choice_localized(label(Locale::Fr.catalog()), value, translations);
+    }
+
+    localized_command("benchmark-tier", |t| t.cmd_benchmark_tier)
+        .add_option(summoner_name_option())
+        .add_option(tier)
+}
+
+/// Days covered by `/progression` when the option is left out.
+const PROGRESSION_DEFAULT_DAYS: i64 = 7;
+
+fn register_progression() -> CreateCommand {
+    localized_command("progression", |t| t.cmd_progression)
+        .add_option(summoner_name_option())
+        .add_option(
+            localized_option(CommandOptionType::Integer, "days", |t| {
+                t.opt_progression_days
+            })
+            .min_int_value(1)
+            .max_int_value(MAX_DAYS as u64),
+        )
+}
+
+/// Language configured for the guild the interaction came from; French for
+/// DMs, unconfigured guilds and lookup failures.
+pub(crate) async fn guild_locale(
+    repository: &Arc<dyn Repository>,
+    guild_id: Option<GuildId>,
+) -> Locale {
+    let Some(guild_id) = guild_id else {
+        return Locale::default();
+    };
+    match repository.get_bot_config(guild_id.get() as i64).await {
+        Ok(Some(config)) => config.locale(),
+        Ok(None) => Locale::default(),
+        Err(e) => {
+            tracing::warn!(error = %e, guild_id = guild_id.get(), "Failed to load guild locale");
+            Locale::default()
+        }
+    }
 }
 
 pub async fn run_init_sfg_bot(
@@ -75,13 +298,14 @@ pub async fn run_init_sfg_bot(
     command: &serenity::model::application::CommandInteraction,
     repository: &Arc<dyn Repository>,
 ) {
+    let text = guild_locale(repository, command.guild_id).await.catalog();
     let Some(guild_id) = command.guild_id else {
         let _ = command
             .create_response(
                 &ctx.http,
                 CreateInteractionResponse::Message(
                     CreateInteractionResponseMessage::new()
-                        .content("❌ Cette commande ne peut être utilisée que dans un serveur.")
+                        .content(text.guild_only)
                         .ephemeral(true),
                 ),
             )
@@ -107,22 +331,22 @@ pub async fn run_init_sfg_bot(
             let missing = required_permissions - permissions;
             let mut missing_names = Vec::new();
             if missing.contains(Permissions::VIEW_CHANNEL) {
-                missing_names.push("Voir le salon");
+                missing_names.push(text.perm_view_channel);
             }
             if missing.contains(Permissions::SEND_MESSAGES) {
-                missing_names.push("Envoyer des messages");
+                missing_names.push(text.perm_send_messages);
             }
             if missing.contains(Permissions::EMBED_LINKS) {
-                missing_names.push("Intégrer des liens");
+                missing_names.push(text.perm_embed_links);
             }
             let _ = command
                 .create_response(
                     &ctx.http,
                     CreateInteractionResponse::Message(
                         CreateInteractionResponseMessage::new()
-                            .content(format!(
-                                "❌ Droits manquants : {}",
-                                missing_names.join(", ")
+                            .content(render(
+                                text.missing_permissions,
+                                &[("permissions", &missing_names.join(", "))],
                             ))
                             .ephemeral(true),
                     ),
@@ -154,9 +378,9 @@ pub async fn run_init_sfg_bot(
                 .create_response(
                     &ctx.http,
                     CreateInteractionResponse::Message(
-                        CreateInteractionResponseMessage::new().content(format!(
-                            "✅ Notifications configurées dans <#{}>. Les alertes de parties seront envoyées ici !",
-                            channel_id.get()
+                        CreateInteractionResponseMessage::new().content(render(
+                            text.init_success,
+                            &[("channel", &channel_id.get().to_string())],
                         )),
                     ),
                 )
@@ -169,7 +393,7 @@ pub async fn run_init_sfg_bot(
                     &ctx.http,
                     CreateInteractionResponse::Message(
                         CreateInteractionResponseMessage::new()
-                            .content("❌ Erreur lors de la sauvegarde de la configuration.")
+                            .content(text.init_failed)
                             .ephemeral(true),
                     ),
                 )
@@ -183,6 +407,7 @@ pub async fn run_list_summoners(
     command: &serenity::model::application::CommandInteraction,
     repository: &Arc<dyn Repository>,
 ) {
+    let text = guild_locale(repository, command.guild_id).await.catalog();
     let summoners = match repository.get_all_summoners().await {
         Ok(s) => s,
         Err(e) => {
@@ -192,9 +417,7 @@ pub async fn run_list_summoners(
                     &ctx.http,
                     CreateInteractionResponse::Message(
                         CreateInteractionResponseMessage::new()
-                            .content(
-                                "❌ Erreur lors de la récupération de la liste des invocateurs.",
-                            )
+                            .content(text.list_failed)
                             .ephemeral(true),
                     ),
                 )
@@ -204,10 +427,12 @@ pub async fn run_list_summoners(
     };
 
     let content = if summoners.is_empty() {
-        "📋 Aucun invocateur suivi pour le moment.\nUtilise `/add-summoner` pour en ajouter."
-            .to_string()
+        text.list_empty.to_string()
     } else {
-        let mut lines = vec![format!("📋 **Invocateurs suivis ({})** :", summoners.len())];
+        let mut lines = vec![render(
+            text.list_header,
+            &[("count", &summoners.len().to_string())],
+        )];
         for summoner in &summoners {
             lines.push(format!(
                 "• **{}#{}** ({})",
@@ -234,6 +459,7 @@ pub async fn run_add_summoner(
     riot_client: &Arc<dyn RiotApiClient>,
     default_region: &str,
 ) {
+    let text = guild_locale(repository, command.guild_id).await.catalog();
     let options = command.data.options();
     let summoner_input = options
         .iter()
@@ -256,7 +482,7 @@ pub async fn run_add_summoner(
                 &ctx.http,
                 CreateInteractionResponse::Message(
                     CreateInteractionResponseMessage::new()
-                        .content("❌ Merci de fournir un nom d'invocateur au format : `Nom#Tag`")
+                        .content(format!("❌ {}", text.summoner_name_missing))
                         .ephemeral(true),
                 ),
             )
@@ -270,7 +496,7 @@ pub async fn run_add_summoner(
                 &ctx.http,
                 CreateInteractionResponse::Message(
                     CreateInteractionResponseMessage::new()
-                        .content("❌ Format invalide. Utilise `Nom#Tag` (ex: `Doublelift#NA1`)")
+                        .content(format!("❌ {}", text.summoner_name_invalid))
                         .ephemeral(true),
                 ),
             )
@@ -287,7 +513,7 @@ pub async fn run_add_summoner(
                 &ctx.http,
                 CreateInteractionResponse::Message(
                     CreateInteractionResponseMessage::new()
-                        .content("❌ Format invalide. Utilise `Nom#Tag` (ex: `Doublelift#NA1`)")
+                        .content(format!("❌ {}", text.summoner_name_invalid))
                         .ephemeral(true),
                 ),
             )
@@ -318,7 +544,8 @@ pub async fn run_add_summoner(
                 .create_followup(
                     &ctx.http,
                     CreateInteractionResponseFollowup::new().content(format!(
-                        "❌ Compte `{summoner_input}` introuvable. Vérifie le nom et le tag."
+                        "❌ {}",
+                        render(text.account_not_found, &[("summoner", &summoner_input)])
                     )),
                 )
                 .await;
@@ -344,9 +571,12 @@ pub async fn run_add_summoner(
             let _ = command
                 .create_followup(
                     &ctx.http,
-                    CreateInteractionResponseFollowup::new().content(format!(
-                        "✅ **{}#{}** ajouté à la liste de suivi !",
-                        summoner_info.game_name, summoner_info.tag_line
+                    CreateInteractionResponseFollowup::new().content(render(
+                        text.add_success,
+                        &[(
+                            "summoner",
+                            &format!("{}#{}", summoner_info.game_name, summoner_info.tag_line),
+                        )],
                     )),
                 )
                 .await;
@@ -356,8 +586,7 @@ pub async fn run_add_summoner(
             let _ = command
                 .create_followup(
                     &ctx.http,
-                    CreateInteractionResponseFollowup::new()
-                        .content("❌ Erreur lors de l'ajout de l'invocateur."),
+                    CreateInteractionResponseFollowup::new().content(text.add_failed),
                 )
                 .await;
         }
@@ -369,6 +598,7 @@ pub async fn run_remove_summoner(
     command: &serenity::model::application::CommandInteraction,
     repository: &Arc<dyn Repository>,
 ) {
+    let text = guild_locale(repository, command.guild_id).await.catalog();
     let options = command.data.options();
     let summoner_input = options
         .iter()
@@ -391,7 +621,7 @@ pub async fn run_remove_summoner(
                 &ctx.http,
                 CreateInteractionResponse::Message(
                     CreateInteractionResponseMessage::new()
-                        .content("❌ Merci de fournir un nom d'invocateur au format : `Nom#Tag`")
+                        .content(format!("❌ {}", text.summoner_name_missing))
                         .ephemeral(true),
                 ),
             )
@@ -405,7 +635,7 @@ pub async fn run_remove_summoner(
                 &ctx.http,
                 CreateInteractionResponse::Message(
                     CreateInteractionResponseMessage::new()
-                        .content("❌ Format invalide. Utilise `Nom#Tag` (ex: `Doublelift#NA1`)")
+                        .content(format!("❌ {}", text.summoner_name_invalid))
                         .ephemeral(true),
                 ),
             )
@@ -422,7 +652,7 @@ pub async fn run_remove_summoner(
                 &ctx.http,
                 CreateInteractionResponse::Message(
                     CreateInteractionResponseMessage::new()
-                        .content("❌ Format invalide. Utilise `Nom#Tag` (ex: `Doublelift#NA1`)")
+                        .content(format!("❌ {}", text.summoner_name_invalid))
                         .ephemeral(true),
                 ),
             )
@@ -444,8 +674,9 @@ pub async fn run_remove_summoner(
                 .create_response(
                     &ctx.http,
                     CreateInteractionResponse::Message(
-                        CreateInteractionResponseMessage::new().content(format!(
-                            "✅ **{game_name}#{tag_line}** retiré de la liste de suivi."
+                        CreateInteractionResponseMessage::new().content(render(
+                            text.remove_success,
+                            &[("summoner", &format!("{game_name}#{tag_line}"))],
                         )),
                     ),
                 )
@@ -457,8 +688,9 @@ pub async fn run_remove_summoner(
                     &ctx.http,
                     CreateInteractionResponse::Message(
                         CreateInteractionResponseMessage::new()
-                            .content(format!(
-                                "❌ Invocateur `{game_name}#{tag_line}` introuvable dans la liste de suivi."
+                            .content(render(
+                                text.remove_not_found,
+                                &[("summoner", &format!("{game_name}#{tag_line}"))],
                             ))
                             .ephemeral(true),
                     ),
@@ -472,7 +704,7 @@ pub async fn run_remove_summoner(
                     &ctx.http,
                     CreateInteractionResponse::Message(
                         CreateInteractionResponseMessage::new()
-                            .content("❌ Erreur lors de la suppression de l'invocateur.")
+                            .content(text.remove_failed)
                             .ephemeral(true),
                     ),
                 )
@@ -481,6 +713,662 @@ pub async fn run_remove_summoner(
     }
 }
 
+pub async fn run_benchmark_tier(
+    ctx: &Context,
+    command: &serenity::model::application::CommandInteraction,
+    repository: &Arc<dyn Repository>,
+) {
+    let text = guild_locale(repository, command.guild_id).await.catalog();
+
+    let mut summoner_input = String::new();
+    let mut tier = None;
+    for option in command.data.options() {
+        match (option.name, option.value) {
+            ("summoner_name", ResolvedValue::String(value)) => {
+                summoner_input = value.trim().to_string()
+            }
+            ("tier", ResolvedValue::String(value)) => tier = Some(value),
+            _ => {}
+        }
+    }
+
+    if summoner_input.is_empty() {
+        send_ephemeral_response(ctx, command, &format!("❌ {}", text.summoner_name_missing)).await;
+        return;
+    }
+    let Some((game_name, tag_line)) = summoner_input
+        .rfind('#')
+        .map(|hash_pos| {
+            (
+                summoner_input[..hash_pos].trim(),
+                summoner_input[hash_pos + 1..].trim(),
+            )
+        })
+        .filter(|(game_name, tag_line)| !game_name.is_empty() && !tag_line.is_empty())
+    else {
+        send_ephemeral_response(ctx, command, &format!("❌ {}", text.summoner_name_invalid)).await;
+        return;
+    };
+
+    // `None` clears the override; Discord only offers the registered
+    // choices, so anything else is a stale client.
+    let band = match tier {
+        Some(BENCHMARK_TIER_AUTO) => None,
+        Some(value) => match TierBand::parse(value) {
+            Some(band) => Some(band),
+            None => {
+                send_ephemeral_response(ctx, command, text.benchmark_tier_failed).await;
+                return;
+            }
+        },
+        None => {
+            send_ephemeral_response(ctx, command, text.benchmark_tier_failed).await;
+            return;
+        }
+    };
+
+    let summoner = format!("{game_name}#{tag_line}");
+    match repository
+        .set_summoner_benchmark_tier(game_name, tag_line, band.map(|b| b.as_str().to_string()))
+        .await
+    {
+        Ok(true) => {
+            tracing::info!(
+                summoner = summoner.as_str(),
+                band = band.map(TierBand::as_str),
+                "Benchmark tier changed via /benchmark-tier"
+            );
+            let content = match band {
+                Some(band) => render(
+                    text.benchmark_tier_set,
+                    &[("summoner", &summoner), ("band", band.label(text))],
+                ),
+                None => render(text.benchmark_tier_auto, &[("summoner", &summoner)]),
+            };
+            let _ = command
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new().content(content),
+                    ),
+                )
+                .await;
+        }
+        Ok(false) => {
+            send_ephemeral_response(
+                ctx,
+                command,
+                &render(text.remove_not_found, &[("summoner", &summoner)]),
+            )
+            .await;
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to save benchmark tier");
+            send_ephemeral_response(ctx, command, text.benchmark_tier_failed).await;
+        }
+    }
+}
+
+pub async fn run_set_language(
+    ctx: &Context,
+    command: &serenity::model::application::CommandInteraction,
+    repository: &Arc<dyn Repository>,
+) {
+    let current = guild_locale(repository, command.guild_id).await.catalog();
+    let Some(guild_id) = command.guild_id else {
+        send_ephemeral_response(ctx, command, current.guild_only).await;
+        return;
+    };
+
+    let requested = command.data.options().iter().find_map(|opt| {
+        if opt.name == "language" {
+            if let ResolvedValue::String(s) = opt.value {
+                Locale::from_code(s)
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    });
+    let Some(locale) = requested else {
+        // Discord only offers the registered choices, so this is a stale client.
+        send_ephemeral_response(ctx, command, current.language_save_failed).await;
+        return;
+    };
+
+    match repository
+        .set_bot_locale(guild_id.get() as i64, locale.code())
+        .await
+    {
+        Ok(true) => {
+            tracing::info!(
+                guild_id = guild_id.get(),
+                locale = locale.code(),
+                "Guild locale changed via /set-language"
+            );
+            let text = locale.catalog();
+            let _ = command
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new().content(render(
+                            text.language_set,
+                            &[("language", text.language_name)],
+                        )),
+                    ),
+                )
+                .await;
+        }
+        Ok(false) => {
+            send_ephemeral_response(ctx, command, current.init_required).await;
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to save guild locale");
+            send_ephemeral_response(ctx, command, current.language_save_failed).await;
+        }
+    }
+}
+
+/// `/digest`: enables, disables or reschedules one of the guild's digests.
+/// Options left out keep their current value (or the period's default).
+pub async fn run_digest(
+    ctx: &Context,
+    command: &serenity::model::application::CommandInteraction,
+    repository: &Arc<dyn Repository>,
+) {
+    let text = guild_locale(repository, command.guild_id).await.catalog();
+    let Some(guild_id) = command.guild_id else {
+        send_ephemeral_response(ctx, command, text.guild_only).await;
+        return;
+    };
+    let guild_id = guild_id.get() as i64;
+
+    let mut period = None;
+    let mut enabled = true;
+    let mut schedule = None;
+    let mut timezone = None;
+    for option in command.data.options() {
+        match (option.name, option.value) {
+            ("period", ResolvedValue::String(value)) => period = DigestPeriod::parse(value),
+            ("enabled", ResolvedValue::Boolean(value)) => enabled = value,
+            ("schedule", ResolvedValue::String(value)) => schedule = Some(value.trim().to_string()),
+            ("timezone", ResolvedValue::String(value)) => timezone = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+    let Some(period) = period else {
+        send_ephemeral_response(ctx, command, text.digest_save_failed).await;
+        return;
+    };
+
+    match repository.get_bot_config(guild_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            send_ephemeral_response(ctx, command, text.init_required).await;
+            return;
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to fetch bot config for /digest");
+            send_ephemeral_response(ctx, command, text.digest_save_failed).await;
+            return;
+        }
+    }
+
+    let current = match repository.get_all_digest_schedules().await {
+        Ok(schedules) => schedules
+            .into_iter()
+            .find(|d| d.guild_id == guild_id && d.period == period.as_str()),
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to fetch digest schedules");
+            send_ephemeral_response(ctx, command, text.digest_save_failed).await;
+            return;
+        }
+    };
+
+    let schedule = schedule
+        .or_else(|| current.as_ref().map(|d| d.schedule.clone()))
+        .unwrap_or_else(|| period.default_schedule().to_string());
+    let timezone = timezone
+        .or_else(|| current.as_ref().map(|d| d.timezone.clone()))
+        .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string());
+
+    if let Err(e) = schedule.parse::<CronSchedule>() {
+        let message = render(text.digest_invalid_schedule, &[("error", &e.to_string())]);
+        send_ephemeral_response(ctx, command, &message).await;
+        return;
+    }
+    if parse_timezone(&timezone).is_none() {
+        let message = render(text.invalid_timezone, &[("timezone", &timezone)]);
+        send_ephemeral_response(ctx, command, &message).await;
+        return;
+    }
+
+    match repository
+        .upsert_digest_schedule(guild_id, period.as_str(), &schedule, &timezone, enabled)
+        .await
+    {
+        Ok(_) => {
+            tracing::info!(
+                guild_id,
+                period = period.as_str(),
+                schedule = schedule.as_str(),
+                timezone = timezone.as_str(),
+                enabled,
+                "Digest schedule saved via /digest"
+            );
+            let content = if enabled {
+                render(
+                    text.digest_saved,
+                    &[
+                        ("period", period.label(text)),
+                        ("schedule", &schedule),
+                        ("timezone", &timezone),
+                    ],
+                )
+            } else {
+                render(text.digest_disabled, &[("period", period.label(text))])
+            };
+            let _ = command
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new().content(content),
+                    ),
+                )
+                .await;
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to save digest schedule");
+            send_ephemeral_response(ctx, command, text.digest_save_failed).await;
+        }
+    }
+}
+
+/// `/quiet-hours`: turns the guild's quiet hours on or off. Options left
+/// out keep their current value (or the 23:00–08:00 Europe/Paris default).
+pub async fn run_quiet_hours(
+    ctx: &Context,
+    command: &serenity::model::application::CommandInteraction,
+    repository: &Arc<dyn Repository>,
+) {
+    let text = guild_locale(repository, command.guild_id).await.catalog();
+    let Some(guild_id) = command.guild_id else {
+        send_ephemeral_response(ctx, command, text.guild_only).await;
+        return;
+    };
+    let guild_id = guild_id.get() as i64;
+
+    let mut enabled = true;
+    let mut start = None;
+    let mut end = None;
+    let mut timezone = None;
+    let mut bypass = None;
+    for option in command.data.options() {
+        match (option.name, option.value) {
+            ("enabled", ResolvedValue::Boolean(value)) => enabled = value,
+            ("start", ResolvedValue::String(value)) => start = Some(value.trim().to_string()),
+            ("end", ResolvedValue::String(value)) => end = Some(value.trim().to_string()),
+            ("timezone", ResolvedValue::String(value)) => timezone = Some(value.trim().to_string()),
+            ("bypass", ResolvedValue::String(value)) => bypass = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    match repository.get_bot_config(guild_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            send_ephemeral_response(ctx, command, text.init_required).await;
+            return;
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to fetch bot config for /quiet-hours");
+            send_ephemeral_response(ctx, command, text.quiet_hours_save_failed).await;
+            return;
+        }
+    }
+
+    let current = match repository.get_quiet_hours(guild_id).await {
+        Ok(current) => current,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to fetch quiet hours");
+            send_ephemeral_response(ctx, command, text.quiet_hours_save_failed).await;
+            return;
+        }
+    };
+
+    let mut bounds = Vec::with_capacity(2);
+    for (value, stored, default) in [
+        (start, current.as_ref().map(|q| q.start_time), DEFAULT_START),
+        (end, current.as_ref().map(|q| q.end_time), DEFAULT_END),
+    ] {
+        let parsed = match value {
+            Some(value) => match parse_time(&value) {
+                Some(time) => time,
+                None => {
+                    let message = render(text.invalid_time, &[("value", &value)]);
+                    send_ephemeral_response(ctx, command, &message).await;
+                    return;
+                }
+            },
+            None => stored.or_else(|| parse_time(default)).unwrap_or_default(),
+        };
+        bounds.push(parsed);
+    }
+    let (start_time, end_time) = (bounds[0], bounds[1]);
+    if start_time == end_time {
+        send_ephemeral_response(ctx, command, text.quiet_hours_empty_window).await;
+        return;
+    }
+
+    let timezone = timezone
+        .or_else(|| current.as_ref().map(|q| q.timezone.clone()))
+        .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string());
+    if parse_timezone(&timezone).is_none() {
+        let message = render(text.invalid_timezone, &[("timezone", &timezone)]);
+        send_ephemeral_response(ctx, command, &message).await;
+        return;
+    }
+
+    let bypass_event_types = match bypass {
+        Some(value) => match parse_bypass_list(&value) {
+            Ok(event_types) => event_types,
+            Err(unknown) => {
+                let allowed = HOLDABLE_EVENT_TYPES
+                    .iter()
+                    .map(|t| t.to_ascii_lowercase())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let message = render(
+                    text.quiet_hours_invalid_bypass,
+                    &[("value", &unknown), ("allowed", &allowed)],
+                );
+                send_ephemeral_response(ctx, command, &message).await;
+                return;
+            }
+        },
+        None => current
+            .as_ref()
+            .map(|q| q.bypass_event_types.clone())
+            .unwrap_or_default(),
+    };
+
+    match repository
+        .upsert_quiet_hours(
+            guild_id,
+            start_time,
+            end_time,
+            &timezone,
+            &bypass_event_types,
+            enabled,
+        )
+        .await
+    {
+        Ok(_) => {
+            tracing::info!(
+                guild_id,
+                start = %start_time,
+                end = %end_time,
+                timezone = timezone.as_str(),
+                enabled,
+                "Quiet hours saved via /quiet-hours"
+            );
+            let content = if enabled {
+                let bypass = if bypass_event_types.is_empty() {
+                    text.quiet_hours_no_bypass.to_string()
+                } else {
+                    bypass_event_types
+                        .iter()
+                        .map(|t| t.to_ascii_lowercase())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                render(
+                    text.quiet_hours_saved,
+                    &[
+                        ("start", &start_time.format("%H:%M").to_string()),
+                        ("end", &end_time.format("%H:%M").to_string()),
+                        ("timezone", &timezone),
+                        ("bypass", &bypass),
+                    ],
+                )
+            } else {
+                text.quiet_hours_disabled.to_string()
+            };
+            let _ = command
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new().content(content),
+                    ),
+                )
+                .await;
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to save quiet hours");
+            send_ephemeral_response(ctx, command, text.quiet_hours_save_failed).await;
+        }
+    }
+}
+
+/// `/notification-sink`: enables or disables one destination of the
+/// guild's notifications. The webhook keeps its stored URL when `url` is
+/// left out.
+pub async fn run_notification_sink(
+    ctx: &Context,
+    command: &serenity::model::application::CommandInteraction,
+    repository: &Arc<dyn Repository>,
+) {
+    let text = guild_locale(repository, command.guild_id).await.catalog();
+    let Some(guild_id) = command.guild_id else {
+        send_ephemeral_response(ctx, command, text.guild_only).await;
+        return;
+    };
+    let guild_id = guild_id.get() as i64;
+
+    let mut kind = None;
+    let mut enabled = true;
+    let mut url = None;
+    for option in command.data.options() {
+        match (option.name, option.value) {
+            ("kind", ResolvedValue::String(value)) => kind = SinkKind::parse(value),
+            ("enabled", ResolvedValue::Boolean(value)) => enabled = value,
+            ("url", ResolvedValue::String(value)) => url = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+    let Some(kind) = kind else {
+        send_ephemeral_response(ctx, command, text.sink_save_failed).await;
+        return;
+    };
+
+    if let Some(url) = &url {
+        let valid = reqwest::Url::parse(url)
+            .map(|parsed| matches!(parsed.scheme(), "http" | "https"))
+            .unwrap_or(false);
+        if !valid {
+            let message = render(text.sink_invalid_url, &[("url", url)]);
+            send_ephemeral_response(ctx, command, &message).await;
+            return;
+        }
+    }
+
+    match repository.get_bot_config(guild_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            send_ephemeral_response(ctx, command, text.init_required).await;
+            return;
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to fetch bot config for /notification-sink");
+            send_ephemeral_response(ctx, command, text.sink_save_failed).await;
+            return;
+        }
+    }
+
+    if kind == SinkKind::Webhook && url.is_none() {
+        let stored_url = match repository.get_notification_sinks(guild_id).await {
+            Ok(sinks) => sinks
+                .into_iter()
+                .find(|s| s.kind == kind.as_str())
+                .and_then(|s| s.url),
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to fetch notification sinks");
+                send_ephemeral_response(ctx, command, text.sink_save_failed).await;
+                return;
+            }
+        };
+        if stored_url.is_none() {
+            send_ephemeral_response(ctx, command, text.sink_url_required).await;
+            return;
+        }
+    }
+
+    // Only the webhook has a target; a URL given for another sink is ignored.
+    let url = url.filter(|_| kind == SinkKind::Webhook);
+    match repository
+        .upsert_notification_sink(guild_id, kind.as_str(), url, enabled)
+        .await
+    {
+        Ok(_) => {
+            tracing::info!(
+                guild_id,
+                sink = kind.as_str(),
+                enabled,
+                "Notification sink saved via /notification-sink"
+            );
+            let template = if enabled {
+                text.sink_saved
+            } else {
+                text.sink_disabled
+            };
+            // The webhook URL may hold a secret token: keep the answer
+            // private.
+            send_ephemeral_response(ctx, command, &render(template, &[("sink", kind.label())]))
+                .await;
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to save notification sink");
+            send_ephemeral_response(ctx, command, text.sink_save_failed).await;
+        }
+    }
+}
+
+/// `/dead-letters list|retry|discard`: inspects the notifications that
+/// exhausted their retries, requeues them with a fresh retry budget or
+/// deletes them.
+pub async fn run_dead_letters(
+    ctx: &Context,
+    command: &serenity::model::application::CommandInteraction,
+    repository: &Arc<dyn Repository>,
+) {
+    let locale = guild_locale(repository, command.guild_id).await;
+    let text = locale.catalog();
+
+    let Some((action, id)) =
+        command
+            .data
+            .options()
+            .into_iter()
+            .find_map(|option| match option.value {
+                ResolvedValue::SubCommand(sub_options) => {
+                    let id = sub_options.iter().find_map(|o| match o.value {
+                        ResolvedValue::String(value) if o.name == "id" => Some(value.to_string()),
+                        _ => None,
+                    });
+                    Some((option.name, id))
+                }
+                _ => None,
+            })
+    else {
+        send_ephemeral_response(ctx, command, text.dead_letters_failed).await;
+        return;
+    };
+
+    let events = match repository.get_failed_notification_events().await {
+        Ok(events) => events,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to fetch dead letters");
+            send_ephemeral_response(ctx, command, text.dead_letters_failed).await;
+            return;
+        }
+    };
+
+    if action == "list" {
+        let summoners = repository.get_all_summoners().await.unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to fetch summoners for /dead-letters");
+            Vec::new()
+        });
+        let listing = format_dead_letters(&events, &summoners, locale);
+        send_ephemeral_response(ctx, command, &listing).await;
+        return;
+    }
+
+    let event_ids = match select_dead_letters(&events, id.as_deref()) {
+        Ok(event_ids) => event_ids,
+        Err(error) => {
+            let template = match error {
+                SelectError::NotFound => text.dead_letter_not_found,
+                SelectError::Ambiguous => text.dead_letter_ambiguous,
+            };
+            let message = render(template, &[("id", id.as_deref().unwrap_or_default())]);
+            send_ephemeral_response(ctx, command, &message).await;
+            return;
+        }
+    };
+    if event_ids.is_empty() {
+        send_ephemeral_response(ctx, command, text.dead_letters_empty).await;
+        return;
+    }
+
+    let (result, template) = if action == "retry" {
+        (
+            repository.requeue_failed_notifications(&event_ids).await,
+            text.dead_letters_requeued,
+        )
+    } else {
+        (
+            repository.delete_failed_notifications(&event_ids).await,
+            text.dead_letters_discarded,
+        )
+    };
+    match result {
+        Ok(count) => {
+            tracing::info!(action, count, "Dead letters handled via /dead-letters");
+            let message = render(template, &[("count", &count.to_string())]);
+            send_ephemeral_response(ctx, command, &message).await;
+        }
+        Err(e) => {
+            tracing::error!(error = %e, action, "Failed to update dead letters");
+            send_ephemeral_response(ctx, command, text.dead_letters_failed).await;
+        }
+    }
+}
+
+pub async fn run_analysis_jobs(
+    ctx: &Context,
+    command: &serenity::model::application::CommandInteraction,
+    repository: &Arc<dyn Repository>,
+) {
+    let locale = guild_locale(repository, command.guild_id).await;
+
+    let jobs = match repository.get_analysis_job_queue().await {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to fetch analysis jobs");
+            send_ephemeral_response(ctx, command, locale.catalog().analysis_jobs_failed).await;
+            return;
+        }
+    };
+
+    let summoners = repository.get_all_summoners().await.unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "Failed to fetch summoners for /analysis-jobs");
+        Vec::new()
+    });
+    let listing = format_analysis_jobs(&jobs, &summoners, chrono::Utc::now(), locale);
+    send_ephemeral_response(ctx, command, &listing).await;
+}
+
 pub async fn run(
     ctx: &Context,
     command: &serenity::model::application::CommandInteraction,
@@ -495,6 +1383,8 @@ pub async fn run(
         return;
     }
 
+    let locale = guild_locale(repository, command.guild_id).await;
+    let text = locale.catalog();
     let options = command.data.options();
     let summoner_input = options
         .iter()
@@ -510,24 +1400,17 @@ pub async fn run(
             }
         })
         .unwrap_or_default();
+    let force = options
+        .iter()
+        .any(|opt| opt.name == "force" && matches!(opt.value, ResolvedValue::Boolean(true)));
 
     if summoner_input.is_empty() {
-        send_error_followup(
-            ctx,
-            command,
-            "Merci de fournir un nom d'invocateur au format : `Nom#Tag`",
-        )
-        .await;
+        send_error_followup(ctx, command, text.summoner_name_missing).await;
         return;
     }
 
     let Some(hash_pos) = summoner_input.rfind('#') else {
-        send_error_followup(
-            ctx,
-            command,
-            "Format invalide. Utilise `Nom#Tag` (ex: `Doublelift#NA1`)",
-        )
-        .await;
+        send_error_followup(ctx, command, text.summoner_name_invalid).await;
         return;
     };
 
@@ -535,12 +1418,7 @@ pub async fn run(
     let tag_line = summoner_input[hash_pos + 1..].trim();
 
     if game_name.is_empty() || tag_line.is_empty() {
-        send_error_followup(
-            ctx,
-            command,
-            "Format invalide. Utilise `Nom#Tag` (ex: `Doublelift#NA1`)",
-        )
-        .await;
+        send_error_followup(ctx, command, text.summoner_name_invalid).await;
         return;
     }
 
@@ -560,7 +1438,7 @@ pub async fn run(
             send_error_followup(
                 ctx,
                 command,
-                &format!("Compte `{summoner_input}` introuvable. Vérifie le nom et le tag."),
+                &render(text.account_not_found, &[("summoner", &summoner_input)]),
             )
             .await;
             return;
@@ -576,7 +1454,7 @@ pub async fn run(
             send_error_followup(
                 ctx,
                 command,
-                &format!("Aucune partie récente trouvée pour `{summoner_input}`."),
+                &render(text.no_recent_match, &[("summoner", &summoner_input)]),
             )
             .await;
             return;
@@ -587,12 +1465,7 @@ pub async fn run(
                 error = %e,
                 "Failed to fetch recent match ID"
             );
-            send_error_followup(
-                ctx,
-                command,
-                "Impossible de récupérer les données de la dernière partie depuis l'API Riot.",
-            )
-            .await;
+            send_error_followup(ctx, command, text.recent_match_failed).await;
             return;
         }
     };
@@ -603,12 +1476,7 @@ pub async fn run(
     {
         Ok(Some(result)) => result,
         Ok(None) => {
-            send_error_followup(
-                ctx,
-                command,
-                "Impossible de récupérer les détails de la partie.",
-            )
-            .await;
+            send_error_followup(ctx, command, text.match_details_not_found).await;
             return;
         }
         Err(e) => {
@@ -618,18 +1486,13 @@ pub async fn run(
                 error = %e,
                 "Failed to fetch match result"
             );
-            send_error_followup(
-                ctx,
-                command,
-                "Impossible de récupérer les détails de la partie depuis l'API Riot.",
-            )
-            .await;
+            send_error_followup(ctx, command, text.match_details_failed).await;
             return;
         }
     };
 
     let summoner_display = format!("{}#{}", summoner_info.game_name, summoner_info.tag_line);
-    let recap_embed = format_single_game_ended(&summoner_display, &match_result);
+    let recap_embed = format_single_game_ended(&summoner_display, &match_result, locale);
 
     if let Err(e) = command
         .create_followup(
@@ -641,17 +1504,14 @@ pub async fn run(
         tracing::error!("Failed to send recap followup: {}", e);
     }
 
-    let Some(pipeline) = analysis_pipeline else {
-        return;
-    };
-
-    let analysis_data = match riot_client
+    let mut analysis_data = match riot_client
         .get_match_analysis_data(&match_id, &summoner_info.puuid, &summoner_display, region)
         .await
     {
         Ok(Some(data)) => data,
         Ok(None) => {
-            let embed = format_analysis_error_embed(&summoner_display, "match data not found");
+            let embed =
+                format_analysis_error_embed(&summoner_display, "match data not found", locale);
             let _ = command
                 .create_followup(
                     &ctx.http,
@@ -666,8 +1526,11 @@ pub async fn run(
                 error = %e,
                 "Failed to fetch analysis data"
             );
-            let embed =
-                format_analysis_error_embed(&summoner_display, "could not retrieve match data");
+            let embed = format_analysis_error_embed(
+                &summoner_display,
+                "could not retrieve match data",
+                locale,
+            );
             let _ = command
                 .create_followup(
                     &ctx.http,
@@ -682,21 +1545,29 @@ pub async fn run(
         let _ = command
             .create_followup(
                 &ctx.http,
-                CreateInteractionResponseFollowup::new().content(
-                    "🎪 Pas d'analyse pour ce mode de jeu (Arena) : le coach ne gère que \
-                     les modes classiques avec lanes et rôles.",
-                ),
+                CreateInteractionResponseFollowup::new().content(text.analysis_mode_unsupported),
             )
             .await;
         return;
     }
 
-    let result = crate::analysis::history::analyze_with_memory(
+    crate::analysis::history::add_benchmark_context(
         repository.as_ref(),
-        pipeline,
+        riot_client.as_ref(),
+        &mut analysis_data,
+        &summoner_info.puuid,
+        default_region,
+    )
+    .await;
+
+    let result = crate::analysis::history::analyze_or_score(
+        repository.as_ref(),
+        analysis_pipeline.as_deref(),
         analysis_data,
         &summoner_info.puuid,
         &match_id,
+        locale,
+        force,
     )
     .await;
 
@@ -704,9 +1575,10 @@ pub async fn run(
         format_analysis_error_embed(
             &summoner_display,
             result.error.as_deref().unwrap_or("unknown error"),
+            locale,
         )
     } else {
-        format_analysis_embed(&result)
+        format_analysis_embed(&result, locale)
     };
 
     if let Err(e) = command
@@ -720,6 +1592,126 @@ pub async fn run(
     }
 }
 
+/// `/progression`: the player's trend over the last days, from their
+/// analyzed games.
+pub async fn run_progression(
+    ctx: &Context,
+    command: &serenity::model::application::CommandInteraction,
+    riot_client: &Arc<dyn RiotApiClient>,
+    repository: &Arc<dyn Repository>,
+    analysis_pipeline: &Option<Arc<AnalysisPipeline>>,
+    default_region: &str,
+) {
+    // The coach's paragraph can take longer than the 3 s answer deadline.
+    let defer = CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new());
+    if let Err(e) = command.create_response(&ctx.http, defer).await {
+        tracing::error!("Failed to defer interaction: {}", e);
+        return;
+    }
+
+    let locale = guild_locale(repository, command.guild_id).await;
+    let text = locale.catalog();
+    let mut summoner_input = String::new();
+    let mut days = PROGRESSION_DEFAULT_DAYS;
+    for option in command.data.options() {
+        match (option.name, option.value) {
+            ("summoner_name", ResolvedValue::String(value)) => {
+                summoner_input = value.trim().to_string()
+            }
+            ("days", ResolvedValue::Integer(value)) => days = value.clamp(1, MAX_DAYS),
+            _ => {}
+        }
+    }
+
+    if summoner_input.is_empty() {
+        send_error_followup(ctx, command, text.summoner_name_missing).await;
+        return;
+    }
+    let Some((game_name, tag_line)) = summoner_input
+        .rfind('#')
+        .map(|hash_pos| {
+            (
+                summoner_input[..hash_pos].trim(),
+                summoner_input[hash_pos + 1..].trim(),
+            )
+        })
+        .filter(|(game_name, tag_line)| !game_name.is_empty() && !tag_line.is_empty())
+    else {
+        send_error_followup(ctx, command, text.summoner_name_invalid).await;
+        return;
+    };
+
+    let account = match riot_client
+        .get_account_by_riot_id(
+            game_name,
+            tag_line,
+            RiotClient::regional_for_region(default_region),
+        )
+        .await
+    {
+        Ok(account) => account,
+        Err(e) => {
+            tracing::warn!(
+                summoner = summoner_input.as_str(),
+                error = %e,
+                "Failed to resolve summoner account"
+            );
+            send_error_followup(
+                ctx,
+                command,
+                &render(text.account_not_found, &[("summoner", &summoner_input)]),
+            )
+            .await;
+            return;
+        }
+    };
+
+    let summoner_display = format!("{}#{}", account.game_name, account.tag_line);
+    let end = chrono::Utc::now();
+    let start = end - chrono::Duration::days(days);
+    let report = match progression_report(
+        repository.as_ref(),
+        analysis_pipeline.as_deref(),
+        &summoner_display,
+        &account.puuid,
+        start,
+        end,
+        locale,
+    )
+    .await
+    {
+        Ok(Some(report)) => report,
+        Ok(None) => {
+            let message = render(
+                text.progression_not_enough,
+                &[
+                    ("summoner", &summoner_display),
+                    ("min", &MIN_GAMES.to_string()),
+                ],
+            );
+            send_error_followup(ctx, command, &message).await;
+            return;
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to build progression report");
+            send_error_followup(ctx, command, text.progression_failed).await;
+            return;
+        }
+    };
+
+    let tz = parse_timezone(DEFAULT_TIMEZONE).unwrap_or(chrono_tz::Europe::Paris);
+    if let Err(e) = command
+        .create_followup(
+            &ctx.http,
+            CreateInteractionResponseFollowup::new()
+                .embed(format_progression_embed(&report, tz, locale)),
+        )
+        .await
+    {
+        tracing::error!("Failed to send progression followup: {}", e);
+    }
+}
+
 async fn send_error_followup(
     ctx: &Context,
     command: &serenity::model::application::CommandInteraction,
@@ -732,3 +1724,20 @@ async fn send_error_followup(
         )
         .await;
 }
+
+async fn send_ephemeral_response(
+    ctx: &Context,
+    command: &serenity::model::application::CommandInteraction,
+    message: &str,
+) {
+    let _ = command
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(message)
+                    .ephemeral(true),
+            ),
+        )
+        .await;
+}
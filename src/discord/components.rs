@@ -0,0 +1,462 @@
+//! Message components attached to game recaps ("Analyser", "Détails",
+//! "Timeline") and their interaction handlers.
+//!
+//! Custom IDs carry everything a handler needs (action, match ID, summoner
+//! ID), so buttons on recaps posted before a restart keep working — no
+//! in-memory state is involved.
+
+use crate::analysis::discord::{format_analysis_embed, format_analysis_error_embed};
+use crate::analysis::pipeline::AnalysisPipeline;
+use crate::db::models::{NotificationEvent, Summoner};
+use crate::db::repository::Repository;
+use crate::discord::commands::guild_locale;
+use crate::i18n::{Locale, render};
+use crate::notification::messages::{format_match_details, format_timeline_diffs};
+use crate::riot::client::{RiotApiClient, RiotClient, lane_diffs};
+use serenity::builder::{
+    CreateActionRow, CreateButton, CreateInteractionResponse, CreateInteractionResponseFollowup,
+    CreateInteractionResponseMessage,
+};
+use serenity::model::application::{ButtonStyle, ComponentInteraction};
+use serenity::prelude::*;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const CUSTOM_ID_PREFIX: &str = "recap";
+/// Discord allows at most 5 buttons per action row.
+const BUTTONS_PER_ROW: usize = 5;
+
+/// Action encoded in a recap button's custom ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecapAction {
+    Analyze { summoner_id: Uuid, match_id: String },
+    Details { match_id: String },
+    Timeline { match_id: String },
+}
+
+impl RecapAction {
+    pub fn to_custom_id(&self) -> String {
+        match self {
+            RecapAction::Analyze {
+                summoner_id,
+                match_id,
+            } => format!("{CUSTOM_ID_PREFIX}:analyze:{summoner_id}:{match_id}"),
+            RecapAction::Details { match_id } => {
+                format!("{CUSTOM_ID_PREFIX}:details:{match_id}")
+            }
+            RecapAction::Timeline { match_id } => {
+                format!("{CUSTOM_ID_PREFIX}:timeline:{match_id}")
+            }
+        }
+    }
+
+    /// Parses a custom ID produced by [`RecapAction::to_custom_id`]. Returns
+    /// `None` for foreign or malformed IDs.
+    pub fn parse(custom_id: &str) -> Option<Self> {
+        let mut parts = custom_id.splitn(3, ':');
+        if parts.next()? != CUSTOM_ID_PREFIX {
+            return None;
+        }
+        let action = parts.next()?;
+        let rest = parts.next().filter(|r| !r.is_empty())?;
+
+        match action {
+            "analyze" => {
+                let (summoner_id, match_id) = rest.split_once(':')?;
+                if match_id.is_empty() {
+                    return None;
+                }
+                Some(RecapAction::Analyze {
+                    summoner_id: Uuid::parse_str(summoner_id).ok()?,
+                    match_id: match_id.to_string(),
+                })
+            }
+            "details" => Some(RecapAction::Details {
+                match_id: rest.to_string(),
+            }),
+            "timeline" => Some(RecapAction::Timeline {
+                match_id: rest.to_string(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Buttons for a grouped game-ended recap: one "Analyser" per tracked player
+/// (analyzable modes only), then "Détails" and "Timeline" for the match.
+pub fn recap_components(
+    summoners: &[Summoner],
+    events: &[NotificationEvent],
+    locale: Locale,
+) -> Vec<CreateActionRow> {
+    let text = locale.catalog();
+    let Some(match_id) = events.iter().find_map(|e| e.match_id.clone()) else {
+        return Vec::new();
+    };
+
+    let analyzable = events
+        .first()
+        .map(|e| crate::analysis::is_analyzable_mode(&e.game_mode))
+        .unwrap_or(false);
+
+    let mut rows = Vec::new();
+
+    if analyzable {
+        let analyze_buttons: Vec<CreateButton> = summoners
+            .iter()
+            .filter(|s| events.iter().any(|e| e.summoner_id == s.id))
+            .map(|s| {
+                let action = RecapAction::Analyze {
+                    summoner_id: s.id,
+                    match_id: match_id.clone(),
+                };
+                CreateButton::new(action.to_custom_id())
+                    .label(render(text.button_analyze, &[("name", &s.game_name)]))
+                    .emoji('📊')
+                    .style(ButtonStyle::Primary)
+            })
+            .collect();
+
+        for chunk in analyze_buttons.chunks(BUTTONS_PER_ROW) {
+            rows.push(CreateActionRow::Buttons(chunk.to_vec()));
+        }
+    }
+
+    rows.push(CreateActionRow::Buttons(vec![
+        CreateButton::new(
+            RecapAction::Details {
+                match_id: match_id.clone(),
+            }
+            .to_custom_id(),
+        )
+        .label(text.button_details)
+        .emoji('📋')
+        .style(ButtonStyle::Secondary),
+        CreateButton::new(RecapAction::Timeline { match_id }.to_custom_id())
+            .label(text.button_timeline)
+            .emoji('📈')
+            .style(ButtonStyle::Secondary),
+    ]));
+
+    rows
+}
+
+pub async fn run(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    riot_client: &Arc<dyn RiotApiClient>,
+    repository: &Arc<dyn Repository>,
+    analysis_pipeline: &Option<Arc<AnalysisPipeline>>,
+    default_region: &str,
+) {
+    let Some(action) = RecapAction::parse(&component.data.custom_id) else {
+        tracing::warn!(
+            custom_id = component.data.custom_id.as_str(),
+            "Unknown component interaction"
+        );
+        return;
+    };
+    let locale = guild_locale(repository, component.guild_id).await;
+
+    match action {
+        RecapAction::Analyze {
+            summoner_id,
+            match_id,
+        } => {
+            run_analyze(
+                ctx,
+                component,
+                riot_client,
+                repository,
+                analysis_pipeline,
+                default_region,
+                summoner_id,
+                &match_id,
+                locale,
+            )
+            .await
+        }
+        RecapAction::Details { match_id } => {
+            run_details(
+                ctx,
+                component,
+                riot_client,
+                default_region,
+                &match_id,
+                locale,
+            )
+            .await
+        }
+        RecapAction::Timeline { match_id } => {
+            run_timeline(
+                ctx,
+                component,
+                riot_client,
+                repository,
+                default_region,
+                &match_id,
+                locale,
+            )
+            .await
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_analyze(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    riot_client: &Arc<dyn RiotApiClient>,
+    repository: &Arc<dyn Repository>,
+    analysis_pipeline: &Option<Arc<AnalysisPipeline>>,
+    default_region: &str,
+    summoner_id: Uuid,
+    match_id: &str,
+    locale: Locale,
+) {
+    let text = locale.catalog();
+    let summoner = match repository.get_summoner_by_id(summoner_id).await {
+        Ok(summoner) => summoner,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to fetch summoner for analyze button");
+            send_ephemeral(ctx, component, text.summoner_fetch_failed).await;
+            return;
+        }
+    };
+
+    let Some(summoner) = summoner else {
+        send_ephemeral(ctx, component, text.summoner_untracked).await;
+        return;
+    };
+
+    let defer = CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new());
+    if let Err(e) = component.create_response(&ctx.http, defer).await {
+        tracing::error!("Failed to defer component interaction: {}", e);
+        return;
+    }
+
+    let summoner_name = format!("{}#{}", summoner.game_name, summoner.tag_line);
+    let region = RiotClient::regional_for_region(default_region);
+
+    let embed = match riot_client
+        .get_match_analysis_data(match_id, &summoner.riot_puuid, &summoner_name, region)
+        .await
+    {
+        Ok(Some(mut data)) => {
+            crate::analysis::history::add_benchmark_context(
+                repository.as_ref(),
+                riot_client.as_ref(),
+                &mut data,
+                &summoner.riot_puuid,
+                default_region,
+            )
+            .await;
+            let result = crate::analysis::history::analyze_or_score(
+                repository.as_ref(),
+                analysis_pipeline.as_deref(),
+                data,
+                &summoner.riot_puuid,
+                match_id,
+                locale,
+                false,
+            )
+            .await;
+            if result.error.is_some() {
+                format_analysis_error_embed(
+                    &summoner_name,
+                    result.error.as_deref().unwrap_or("unknown error"),
+                    locale,
+                )
+            } else {
+                format_analysis_embed(&result, locale)
+            }
+        }
+        Ok(None) => format_analysis_error_embed(&summoner_name, "match data not found", locale),
+        Err(e) => {
+            tracing::warn!(
+                summoner = summoner_name.as_str(),
+                match_id,
+                error = %e,
+                "Failed to fetch analysis data for analyze button"
+            );
+            format_analysis_error_embed(&summoner_name, "could not retrieve match data", locale)
+        }
+    };
+
+    if let Err(e) = component
+        .create_followup(
+            &ctx.http,
+            CreateInteractionResponseFollowup::new().embed(embed),
+        )
+        .await
+    {
+        tracing::error!("Failed to send analysis followup: {}", e);
+    }
+}
+
+async fn run_details(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    riot_client: &Arc<dyn RiotApiClient>,
+    default_region: &str,
+    match_id: &str,
+    locale: Locale,
+) {
+    let text = locale.catalog();
+    if !defer_ephemeral(ctx, component).await {
+        return;
+    }
+
+    let region = RiotClient::regional_for_region(default_region);
+    let followup = match riot_client.get_match_scoreboard(match_id, region).await {
+        Ok(Some(scoreboard)) => CreateInteractionResponseFollowup::new()
+            .embed(format_match_details(&scoreboard, locale)),
+        Ok(None) => CreateInteractionResponseFollowup::new().content(text.match_not_found),
+        Err(e) => {
+            tracing::warn!(match_id, error = %e, "Failed to fetch match scoreboard");
+            CreateInteractionResponseFollowup::new().content(text.details_failed)
+        }
+    };
+
+    if let Err(e) = component
+        .create_followup(&ctx.http, followup.ephemeral(true))
+        .await
+    {
+        tracing::error!("Failed to send details followup: {}", e);
+    }
+}
+
+async fn run_timeline(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    riot_client: &Arc<dyn RiotApiClient>,
+    repository: &Arc<dyn Repository>,
+    default_region: &str,
+    match_id: &str,
+    locale: Locale,
+) {
+    let text = locale.catalog();
+    if !defer_ephemeral(ctx, component).await {
+        return;
+    }
+
+    let region = RiotClient::regional_for_region(default_region);
+    let fetched = tokio::try_join!(
+        riot_client.get_match_scoreboard(match_id, region),
+        riot_client.get_match_timeline(match_id, region)
+    );
+
+    let followup = match fetched {
+        Ok((Some(scoreboard), Some(timeline))) => {
+            let tracked: Vec<String> = match repository.get_all_summoners().await {
+                Ok(summoners) => summoners.into_iter().map(|s| s.riot_puuid).collect(),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to fetch summoners for timeline button");
+                    Vec::new()
+                }
+            };
+
+            let rows: Vec<_> = scoreboard
+                .players
+                .iter()
+                .filter(|p| tracked.contains(&p.puuid))
+                .map(|player| {
+                    let opponent = scoreboard.lane_opponent(player);
+                    let diffs = opponent
+                        .map(|o| lane_diffs(&timeline, player.participant_id, o.participant_id))
+                        .unwrap_or_default();
+                    (
+                        player.display_name.clone(),
+                        player.champion_name.clone(),
+                        opponent.map(|o| o.champion_name.clone()),
+                        diffs,
+                    )
+                })
+                .collect();
+
+            CreateInteractionResponseFollowup::new().embed(format_timeline_diffs(&rows, locale))
+        }
+        Ok(_) => CreateInteractionResponseFollowup::new().content(text.timeline_not_found),
+        Err(e) => {
+            tracing::warn!(match_id, error = %e, "Failed to fetch match timeline");
+            CreateInteractionResponseFollowup::new().content(text.timeline_failed)
+        }
+    };
+
+    if let Err(e) = component
+        .create_followup(&ctx.http, followup.ephemeral(true))
+        .await
+    {
+        tracing::error!("Failed to send timeline followup: {}", e);
+    }
+}
+
+async fn defer_ephemeral(ctx: &Context, component: &ComponentInteraction) -> bool {
+    let defer =
+        CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new().ephemeral(true));
+    match component.create_response(&ctx.http, defer).await {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::error!("Failed to defer component interaction: {}", e);
+            false
+        }
+    }
+}
+
+async fn send_ephemeral(ctx: &Context, component: &ComponentInteraction, message: &str) {
+    let _ = component
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(message)
+                    .ephemeral(true),
+            ),
+        )
+        .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn analyze_custom_id_round_trips() {
+        let action = RecapAction::Analyze {
+            summoner_id: Uuid::new_v4(),
+            match_id: "EUW1_7123456789".to_string(),
+        };
+        let custom_id = action.to_custom_id();
+        assert!(
+            custom_id.len() <= 100,
+            "Discord caps custom IDs at 100 chars"
+        );
+        assert_eq!(RecapAction::parse(&custom_id), Some(action));
+    }
+
+    #[test]
+    fn details_and_timeline_custom_ids_round_trip() {
+        for action in [
+            RecapAction::Details {
+                match_id: "EUW1_1".to_string(),
+            },
+            RecapAction::Timeline {
+                match_id: "EUW1_1".to_string(),
+            },
+        ] {
+            assert_eq!(RecapAction::parse(&action.to_custom_id()), Some(action));
+        }
+    }
+
+    #[test]
+    fn parse_rejects_foreign_and_malformed_ids() {
+        assert_eq!(RecapAction::parse("other:details:EUW1_1"), None);
+        assert_eq!(RecapAction::parse("recap:unknown:EUW1_1"), None);
+        assert_eq!(RecapAction::parse("recap:details:"), None);
+        assert_eq!(RecapAction::parse("recap:analyze:not-a-uuid:EUW1_1"), None);
+        assert_eq!(
+            RecapAction::parse(&format!("recap:analyze:{}:", Uuid::new_v4())),
+            None
+        );
+    }
+}
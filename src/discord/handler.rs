@@ -1,16 +1,17 @@
-use crate::analysis::discord::{format_analysis_embed, format_analysis_error_embed};
-use crate::analysis::llm::LlmClient;
-use crate::analysis::models::AnalysisResult;
+use crate::analysis::llm::{LlmBackendKind, LlmError, create_backend};
 use crate::analysis::pipeline::AnalysisPipeline;
+use crate::analysis::worker::AnalysisWorker;
 use crate::config::Config;
 use crate::db::models::{NewNotificationEvent, Summoner};
 use crate::db::repository::Repository;
+use crate::digest::scheduler::DigestScheduler;
 use crate::discord::commands;
+use crate::discord::components;
 use crate::discord::messages::format_mention_response;
 use crate::notification::NotificationProcessor;
+use crate::notification::listener::spawn_queue_listener;
 use crate::notification::messages::format_report_unavailable;
 use crate::riot::client::RiotApiClient;
-use crate::riot::client::RiotClient;
 use crate::riot::models::{GameStateChange, MatchLookup};
 use crate::riot::tracker::{GameTracker, MAX_END_RETRY_CYCLES};
 use serenity::async_trait;
@@ -23,6 +24,7 @@ use serenity::prelude::*;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
+use tokio::sync::Notify;
 
 pub struct Bot {
     pub repository: Arc<dyn Repository>,
@@ -41,36 +43,41 @@ impl Bot {
         riot_client: Arc<dyn RiotApiClient>,
         config: Config,
     ) -> Self {
-        let analysis_pipeline = match config.llm_api_key.as_ref() {
-            Some(api_key) => {
-                let llm_client = match LlmClient::new(
-                    api_key.clone(),
+        let backend = LlmBackendKind::parse(&config.llm_backend)
+            .ok_or_else(|| format!("unknown LLM_BACKEND `{}`", config.llm_backend))
+            .and_then(|kind| {
+                match create_backend(
+                    kind,
+                    config.llm_api_key.clone(),
                     config.llm_base_url.clone(),
                     config.llm_model.clone(),
                 ) {
-                    Ok(client) => client,
-                    Err(error) => {
-                        tracing::warn!(error = %error, "Failed to initialize LLM client");
-                        return Self {
-                            repository,
-                            riot_client,
-                            config,
-                            analysis_pipeline: None,
-                            background_tasks_started: AtomicBool::new(false),
-                        };
-                    }
-                };
-
-                match AnalysisPipeline::new(llm_client, &config.analysis_prompts_dir) {
-                    Ok(pipeline) => Some(Arc::new(pipeline)),
-                    Err(error) => {
-                        tracing::warn!(error = %error, "Failed to load analysis prompt");
-                        None
-                    }
+                    Ok(llm) => Ok(Some(llm)),
+                    Err(LlmError::MissingApiKey(_)) => Ok(None),
+                    Err(error) => Err(error.to_string()),
                 }
+            });
+        let analysis_pipeline = match backend {
+            Ok(Some(llm)) => match AnalysisPipeline::new(llm, &config.analysis_prompts_dir) {
+                Ok(pipeline) => Some(Arc::new(
+                    pipeline
+                        .with_stats_anchor(config.analysis_stats_anchor)
+                        .with_concurrency(config.analysis_concurrency),
+                )),
+                Err(error) => {
+                    tracing::warn!(error = %error, "Failed to load analysis prompt");
+                    None
+                }
+            },
+            Ok(None) => {
+                tracing::info!(
+                    backend = config.llm_backend.as_str(),
+                    "LLM API key not configured, analysis disabled"
+                );
+                None
             }
-            None => {
-                tracing::info!("LLM API key not configured, analysis disabled");
+            Err(error) => {
+                tracing::warn!(error = %error, "Failed to initialize LLM client");
                 None
             }
         };
@@ -125,35 +132,61 @@ impl EventHandler for Bot {
 
         tracing::info!("Cache ready, starting background tasks");
 
+        if let Some(pipeline) = &self.analysis_pipeline
+            && self.config.analysis_prompts_reload_secs > 0
+        {
+            let every = Duration::from_secs(self.config.analysis_prompts_reload_secs);
+            tokio::spawn(pipeline.clone().watch_prompts(every));
+        }
+
+        // Without a pipeline the worker posts the stats-based verdict.
+        let analysis_wake = Arc::new(Notify::new());
+        let worker = AnalysisWorker::new(
+            self.repository.clone(),
+            self.riot_client.clone(),
+            self.analysis_pipeline.clone(),
+            ctx.clone(),
+            self.config.default_region.clone(),
+            self.config.analysis_concurrency,
+            analysis_wake.clone(),
+        );
+        tokio::spawn(worker.start());
+
         let repository = self.repository.clone();
         let riot_client = self.riot_client.clone();
         let config = self.config.clone();
-        let analysis_pipeline = self.analysis_pipeline.clone();
         let ctx_clone = ctx.clone();
 
         tokio::spawn(async move {
-            start_polling_task(
-                ctx_clone,
-                repository,
-                riot_client,
-                config,
-                analysis_pipeline,
-            )
-            .await;
+            start_polling_task(ctx_clone, repository, riot_client, config, analysis_wake).await;
         });
 
         let repository = self.repository.clone();
         let ctx_clone = ctx.clone();
+        let queue_wake = Arc::new(Notify::new());
+        spawn_queue_listener(self.config.database_url.clone(), queue_wake.clone());
 
         tokio::spawn(async move {
-            let processor = NotificationProcessor::new(repository, ctx_clone, 5);
+            let processor =
+                NotificationProcessor::new(repository, ctx_clone.http.clone(), queue_wake);
             processor.start().await;
         });
+
+        let repository = self.repository.clone();
+        let riot_client = self.riot_client.clone();
+        let analysis_pipeline = self.analysis_pipeline.clone();
+        let ctx_clone = ctx.clone();
+
+        tokio::spawn(async move {
+            DigestScheduler::new(repository, riot_client, analysis_pipeline, ctx_clone)
+                .start()
+                .await;
+        });
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        if let Interaction::Command(command) = interaction {
-            match command.data.name.as_str() {
+        match interaction {
+            Interaction::Command(command) => match command.data.name.as_str() {
                 "analyze-last-game" => {
                     commands::run(
                         &ctx,
@@ -184,10 +217,54 @@ impl EventHandler for Bot {
                 "remove-summoner" => {
                     commands::run_remove_summoner(&ctx, &command, &self.repository).await;
                 }
+                "set-language" => {
+                    commands::run_set_language(&ctx, &command, &self.repository).await;
+                }
+                "digest" => {
+                    commands::run_digest(&ctx, &command, &self.repository).await;
+                }
+                "quiet-hours" => {
+                    commands::run_quiet_hours(&ctx, &command, &self.repository).await;
+                }
+                "notification-sink" => {
+                    commands::run_notification_sink(&ctx, &command, &self.repository).await;
+                }
+                "dead-letters" => {
+                    commands::run_dead_letters(&ctx, &command, &self.repository).await;
+                }
+                "analysis-jobs" => {
+                    commands::run_analysis_jobs(&ctx, &command, &self.repository).await;
+                }
+                "benchmark-tier" => {
+                    commands::run_benchmark_tier(&ctx, &command, &self.repository).await;
+                }
+                "progression" => {
+                    commands::run_progression(
+                        &ctx,
+                        &command,
+                        &self.riot_client,
+                        &self.repository,
+                        &self.analysis_pipeline,
+                        &self.config.default_region,
+                    )
+                    .await;
+                }
                 _ => {
                     tracing::warn!("Unknown slash command: {}", command.data.name);
                 }
+            },
+            Interaction::Component(component) => {
+                components::run(
+                    &ctx,
+                    &component,
+                    &self.riot_client,
+                    &self.repository,
+                    &self.analysis_pipeline,
+                    &self.config.default_region,
+                )
+                .await;
             }
+            _ => {}
         }
     }
 
@@ -215,7 +292,7 @@ async fn start_polling_task(
     repository: Arc<dyn Repository>,
     riot_client: Arc<dyn RiotApiClient>,
     config: Config,
-    analysis_pipeline: Option<Arc<AnalysisPipeline>>,
+    analysis_wake: Arc<Notify>,
 ) {
     let interval_secs = config.polling_interval_secs;
 
@@ -235,7 +312,7 @@ async fn start_polling_task(
         let ctx = ctx.clone();
         let riot_client = riot_client.clone();
         let repository = repository.clone();
-        let analysis_pipeline = analysis_pipeline.clone();
+        let analysis_wake = analysis_wake.clone();
         let region = config.default_region.clone();
 
         let handle = tokio::spawn(async move {
@@ -248,9 +325,7 @@ async fn start_polling_task(
             );
 
             loop {
-                if let Err(e) =
-                    check_and_notify(&ctx, &tracker, &summoner, analysis_pipeline.clone()).await
-                {
+                if let Err(e) = check_and_notify(&ctx, &tracker, &summoner, &analysis_wake).await {
                     tracing::error!(
                         "Error checking summoner {}#{}: {}",
                         summoner.game_name,
@@ -279,7 +354,7 @@ async fn check_and_notify<R: RiotApiClient + ?Sized + 'static, D: Repository + ?
     ctx: &Context,
     tracker: &GameTracker<R, D>,
     summoner: &Summoner,
-    analysis_pipeline: Option<Arc<AnalysisPipeline>>,
+    analysis_wake: &Notify,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let state_change = tracker.check_summoner_game_state(summoner).await?;
 
@@ -327,7 +402,15 @@ async fn check_and_notify<R: RiotApiClient + ?Sized + 'static, D: Repository + ?
                 role_gaps: None,
             };
 
-            tracker.repository.insert_notification_event(&event).await?;
+            let queued = tracker.repository.insert_notification_event(&event).await?;
+            if !queued.is_new {
+                tracing::info!(
+                    "Game start for {}#{} game {} already queued, skipping duplicate",
+                    summoner.game_name,
+                    summoner.tag_line,
+                    game_info.game_id
+                );
+            }
         }
         GameStateChange::GameEnded { game_id } => {
             tracing::info!(
@@ -342,58 +425,19 @@ async fn check_and_notify<R: RiotApiClient + ?Sized + 'static, D: Repository + ?
 
             match tracker_result {
                 Ok(MatchLookup::Found(match_result)) => {
-                    let match_result = *match_result;
                     let analyzable = crate::analysis::is_analyzable_mode(&match_result.game_mode);
-                    let champion_name = tracker
-                        .repository
-                        .get_champion_by_id(match_result.champion_id)
-                        .await?
-                        .map(|c| c.champion_name)
-                        .unwrap_or_else(|| format!("Champion #{}", match_result.champion_id));
-
-                    let match_id = match_result.match_id.clone();
-                    let event = NewNotificationEvent {
-                        summoner_id: summoner_clone.id,
-                        event_type: "GAME_ENDED".to_string(),
-                        game_id: match_result.game_id,
-                        match_id: Some(match_id.clone()),
-                        champion_id: match_result.champion_id,
-                        champion_name,
-                        role: Some(match_result.role),
-                        win: Some(match_result.win),
-                        kills: Some(match_result.kills),
-                        deaths: Some(match_result.deaths),
-                        assists: Some(match_result.assists),
-                        game_duration_secs: Some(match_result.game_duration_secs),
-                        game_mode: match_result.game_mode,
-                        queue_id: match_result.queue_id,
-                        is_featured_mode: false,
-                        total_cs: Some(match_result.total_cs),
-                        total_gold: Some(match_result.total_gold),
-                        total_damage: Some(match_result.total_damage),
-                        enemy_champion_name: match_result.enemy_champion_name.clone(),
-                        enemy_cs: match_result.enemy_cs,
-                        enemy_gold: match_result.enemy_gold,
-                        enemy_damage: match_result.enemy_damage,
-                        role_gaps: match_result.role_gaps.clone(),
-                    };
-
-                    tracker.repository.insert_notification_event(&event).await?;
-
-                    if analyzable {
-                        spawn_analysis_task(
-                            ctx,
-                            tracker,
-                            &summoner_clone,
-                            &match_id,
-                            analysis_pipeline.clone(),
-                        );
-                    } else {
+                    let job = tracker
+                        .record_game_end(&summoner_clone, game_id, &match_result, false, analyzable)
+                        .await?;
+
+                    if job.is_some() {
+                        analysis_wake.notify_one();
+                    } else if !analyzable {
                         tracing::info!(
                             "Skipping analysis for {}#{} match {}: game mode not analyzable",
                             summoner_clone.game_name,
                             summoner_clone.tag_line,
-                            match_id
+                            match_result.match_id
                         );
                     }
                 }
@@ -440,58 +484,19 @@ async fn check_and_notify<R: RiotApiClient + ?Sized + 'static, D: Repository + ?
 
             match tracker_result {
                 Ok(MatchLookup::Found(match_result)) => {
-                    let match_result = *match_result;
                     let analyzable = crate::analysis::is_analyzable_mode(&match_result.game_mode);
-                    let champion_name = tracker
-                        .repository
-                        .get_champion_by_id(match_result.champion_id)
-                        .await?
-                        .map(|c| c.champion_name)
-                        .unwrap_or_else(|| format!("Champion #{}", match_result.champion_id));
-
-                    let match_id = match_result.match_id.clone();
-                    let event = NewNotificationEvent {
-                        summoner_id: summoner_clone.id,
-                        event_type: "GAME_ENDED".to_string(),
-                        game_id: match_result.game_id,
-                        match_id: Some(match_id.clone()),
-                        champion_id: match_result.champion_id,
-                        champion_name,
-                        role: Some(match_result.role),
-                        win: Some(match_result.win),
-                        kills: Some(match_result.kills),
-                        deaths: Some(match_result.deaths),
-                        assists: Some(match_result.assists),
-                        game_duration_secs: Some(match_result.game_duration_secs),
-                        game_mode: match_result.game_mode,
-                        queue_id: match_result.queue_id,
-                        is_featured_mode: true,
-                        total_cs: Some(match_result.total_cs),
-                        total_gold: Some(match_result.total_gold),
-                        total_damage: Some(match_result.total_damage),
-                        enemy_champion_name: match_result.enemy_champion_name.clone(),
-                        enemy_cs: match_result.enemy_cs,
-                        enemy_gold: match_result.enemy_gold,
-                        enemy_damage: match_result.enemy_damage,
-                        role_gaps: match_result.role_gaps.clone(),
-                    };
-
-                    tracker.repository.insert_notification_event(&event).await?;
-
-                    if analyzable {
-                        spawn_analysis_task(
-                            ctx,
-                            tracker,
-                            &summoner_clone,
-                            &match_id,
-                            analysis_pipeline.clone(),
-                        );
-                    } else {
+                    let job = tracker
+                        .record_game_end(&summoner_clone, game_id, &match_result, true, analyzable)
+                        .await?;
+
+                    if job.is_some() {
+                        analysis_wake.notify_one();
+                    } else if !analyzable {
                         tracing::info!(
                             "Skipping analysis for {}#{} match {}: game mode not analyzable",
                             summoner_clone.game_name,
                             summoner_clone.tag_line,
-                            match_id
+                            match_result.match_id
                         );
                     }
                 }
@@ -537,9 +542,9 @@ async fn send_report_unavailable<R: RiotApiClient + ?Sized, D: Repository + ?Siz
     summoner: &Summoner,
     game_id: i64,
 ) {
-    let channel_id = match tracker.repository.get_all_bot_configs().await {
+    let (channel_id, locale) = match tracker.repository.get_all_bot_configs().await {
         Ok(configs) => match configs.first() {
-            Some(c) => ChannelId::new(c.channel_id as u64),
+            Some(c) => (ChannelId::new(c.channel_id as u64), c.locale()),
             None => return,
         },
         Err(e) => {
@@ -552,7 +557,7 @@ async fn send_report_unavailable<R: RiotApiClient + ?Sized, D: Repository + ?Siz
     };
 
     let summoner_name = format!("{}#{}", summoner.game_name, summoner.tag_line);
-    let embed = format_report_unavailable(&summoner_name, game_id);
+    let embed = format_report_unavailable(&summoner_name, game_id, locale);
     if let Err(error) = channel_id
         .send_message(&ctx.http, CreateMessage::new().embed(embed))
         .await
@@ -565,107 +570,6 @@ async fn send_report_unavailable<R: RiotApiClient + ?Sized, D: Repository + ?Siz
     }
 }
 
-fn spawn_analysis_task<R: RiotApiClient + ?Sized + 'static, D: Repository + ?Sized + 'static>(
-    ctx: &Context,
-    tracker: &GameTracker<R, D>,
-    summoner: &Summoner,
-    match_id: &str,
-    analysis_pipeline: Option<Arc<AnalysisPipeline>>,
-) {
-    let Some(analysis_pipeline) = analysis_pipeline else {
-        return;
-    };
-
-    let ctx = ctx.clone();
-    let riot_client = tracker.riot_client();
-    let repository = tracker.repository.clone();
-    let summoner_clone = summoner.clone();
-    let match_id = match_id.to_string();
-    let region = RiotClient::regional_for_region(tracker.default_region());
-    let analysis_pipeline = analysis_pipeline.clone();
-
-    tokio::spawn(async move {
-        let summoner_name = format!("{}#{}", summoner_clone.game_name, summoner_clone.tag_line);
-
-        let channel_id = match repository.get_all_bot_configs().await {
-            Ok(configs) => match configs.first() {
-                Some(c) => ChannelId::new(c.channel_id as u64),
-                None => {
-                    tracing::warn!(
-                        "No notification channel configured, skipping analysis for {}",
-                        summoner_name
-                    );
-                    return;
-                }
-            },
-            Err(e) => {
-                tracing::error!("Failed to fetch bot config for analysis: {}", e);
-                return;
-            }
-        };
-
-        let analysis_data = riot_client
-            .get_match_analysis_data(
-                &match_id,
-                &summoner_clone.riot_puuid,
-                &summoner_name,
-                region,
-            )
-            .await;
-
-        let result = match analysis_data {
-            Ok(Some(data)) => {
-                crate::analysis::history::analyze_with_memory(
-                    repository.as_ref(),
-                    &analysis_pipeline,
-                    data,
-                    &summoner_clone.riot_puuid,
-                    &match_id,
-                )
-                .await
-            }
-            Ok(None) => AnalysisResult {
-                summoner_name: summoner_name.clone(),
-                champion_name: "Unknown".to_string(),
-                overall_rating: None,
-                summary: "Analysis unavailable: match data not found".to_string(),
-                error: Some("match data not found".to_string()),
-            },
-            Err(error) => {
-                tracing::warn!(
-                    summoner = summoner_name.as_str(),
-                    error = %error,
-                    "Failed to fetch match analysis data"
-                );
-                AnalysisResult {
-                    summoner_name: summoner_name.clone(),
-                    champion_name: "Unknown".to_string(),
-                    overall_rating: None,
-                    summary: "Analysis unavailable: could not retrieve match data".to_string(),
-                    error: Some("could not retrieve match data".to_string()),
-                }
-            }
-        };
-
-        let embed = if result.error.is_some() {
-            format_analysis_error_embed(&summoner_name, result.error.as_deref().unwrap_or(""))
-        } else {
-            format_analysis_embed(&result)
-        };
-
-        if let Err(error) = channel_id
-            .send_message(&ctx.http, CreateMessage::new().embed(embed))
-            .await
-        {
-            tracing::error!(
-                summoner = summoner_name.as_str(),
-                error = %error,
-                "Failed to send analysis message"
-            );
-        }
-    });
-}
-
 #[cfg(all(test, feature = "test-mocks"))]
 mod tests {
     use super::*;
@@ -681,10 +585,15 @@ mod tests {
             default_region: "euw1".to_string(),
             polling_interval_secs: 180,
             llm_api_key: None,
+            llm_backend: "openai".to_string(),
             llm_base_url: "http://localhost:8080/v1".to_string(),
             llm_model: "gemma-4-26b".to_string(),
             analysis_prompts_dir: "analysis_prompts".to_string(),
+            analysis_prompts_reload_secs: 0,
             health_check_port: None,
+            notification_retention_days: 30,
+            analysis_concurrency: 1,
+            analysis_stats_anchor: false,
         }
     }
 
@@ -1,3 +1,4 @@
 pub mod commands;
+pub mod components;
 pub mod handler;
 pub mod messages;
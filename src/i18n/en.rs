@@ -0,0 +1,200 @@
+use super::Catalog;
+
+pub const EN: Catalog = Catalog {
+    language_name: "English",
+    prompt_language: "anglais",
+
+    queue_ranked_solo: "Ranked Solo/Duo",
+    queue_ranked_flex: "Ranked Flex",
+    queue_draft: "Draft Pick",
+    queue_blind: "Blind Pick",
+    queue_aram: "ARAM",
+    queue_quickplay: "Quickplay",
+    queue_arena: "Arena",
+    queue_other: "Queue {id}",
+    unknown: "Unknown",
+    list_and: " and ",
+    list_more: "… and {count} more",
+
+    game_started_title: "🎮 Game started!",
+    game_started_description: "{players} started a {mode} game ({queue})",
+    game_won_title: "Game won!",
+    game_lost_title: "Game lost!",
+    game_ended_description: "The {mode} game is over! Check your stats.",
+    featured_game_ended_description: "The {mode} featured mode game is over! Match history may take a moment to update.",
+    single_game_ended_description: "{queue} game finished for {name}.",
+    stats_field: "📊 Stats",
+    versus_field: "⚔️ vs",
+    role_gaps_field: "🎯 Lane gaps",
+    enemy_unknown: "⚔️ vs Unknown (no role data)",
+    report_unavailable_title: "⚠️ Recap unavailable",
+    report_unavailable_description: "Could not retrieve data for game `{game_id}` of **{name}** from the Riot API (data unavailable or API overloaded). No recap or analysis for this game.",
+    details_title: "📋 Game details",
+    blue_team: "🔵 Blue team",
+    red_team: "🔴 Red team",
+    victory: "Victory",
+    defeat: "Defeat",
+    timeline_title: "📈 Timeline",
+    timeline_empty: "No timeline data for this game.",
+
+    button_analyze: "Analyze {name}",
+    button_details: "Details",
+    button_timeline: "Timeline",
+    summoner_fetch_failed: "❌ Failed to look up the summoner.",
+    summoner_untracked: "❌ This summoner is no longer tracked by the bot.",
+    match_not_found: "❌ Game not found on the Riot API.",
+    details_failed: "❌ Could not retrieve the game details from the Riot API.",
+    timeline_not_found: "❌ Timeline not found on the Riot API.",
+    timeline_failed: "❌ Could not retrieve the timeline from the Riot API.",
+
+    analysis_title: "📊 Game analysis — {name}",
+    analysis_unavailable: "⚠️ Analysis unavailable: {error}",
+    analysis_footer: "Powered by {engine}",
+    analysis_in_progress: "⏳ Analysis in progress…",
+    analysis_mode_unsupported: "🎪 No analysis for this game mode (Arena): the coach only handles classic modes with lanes and roles.",
+    analysis_strengths: "💪 Strengths",
+    analysis_weaknesses: "🩹 Weaknesses",
+    analysis_skills: "📋 Skills",
+    analysis_key_tip: "🎯 Coach's tip",
+    analysis_stats_summary: "📏 Verdict computed from the game's stats: the AI coach is unavailable.",
+    analysis_stats_footer: "{band} benchmarks",
+    analysis_cached: "Cached analysis (use the force option to regenerate it)",
+    skill_cs_per_minute: "CS/min",
+    skill_damage_per_minute: "Damage/min",
+    skill_kills_assists: "Kill participation",
+    skill_deaths: "Deaths",
+    skill_death_timing: "Costly deaths",
+    skill_objectives: "Objective presence",
+    skill_vision_score: "Vision/min",
+    metric_cs_diff_at_10: "CS diff at 10 min",
+    tier_band_iron_bronze: "Iron / Bronze",
+    tier_band_silver_gold: "Silver / Gold",
+    tier_band_platinum_emerald: "Platinum / Emerald",
+    tier_band_diamond_plus: "Diamond and above",
+    tier_band_auto: "Auto (Solo/Duo rank)",
+
+    digest_weekly_title: "📅 SFG week",
+    digest_daily_title: "☀️ SFG day",
+    digest_date_format: "%m/%d",
+    digest_period: "From {start} to {end} · {games} games",
+    digest_empty: "No games played in this period.",
+    digest_player: "{name} — {wins}W {losses}L ({winrate}%)",
+    digest_kda: "KDA {kda} ({kills}/{deaths}/{assists})",
+    digest_lp: "LP {lp}",
+    digest_champions: "Champions: {champions}",
+    digest_best: "⭐ Best: {game}",
+    digest_worst: "💀 Worst: {game}",
+    digest_coach: "Coach: {good} Good · {average} Average · {poor} Poor",
+    win_short: "W",
+    loss_short: "L",
+    period_daily: "daily",
+    period_weekly: "weekly",
+
+    progression_title: "📈 Progression — {name}",
+    progression_period: "{start} to {end} · {games} analyzed games, {wins} wins",
+    progression_metrics: "📊 Moving averages ({window} games)",
+    progression_metric: "{metric}: {first} → {last} (average {average})",
+    progression_improved: "📈 Most improved",
+    progression_regressed: "📉 Most regressed",
+    progression_skill: "{skill}: {before} → {after} grade points out of 2",
+    progression_coaching: "🎯 Coach's take",
+
+    quiet_summary_title: "🌙 During quiet hours",
+    quiet_summary_description: "{games} game(s) finished during quiet hours:",
+
+    cmd_analyze_last_game: "Analyze a summoner's last game",
+    cmd_init_sfg_bot: "Use this channel for the bot's notifications",
+    cmd_list_summoners: "Show the tracked summoners",
+    cmd_add_summoner: "Add a summoner to the tracking list",
+    cmd_remove_summoner: "Remove a summoner from the tracking list",
+    cmd_set_language: "Change the bot's language on this server",
+    cmd_digest: "Configure the server's daily or weekly digest",
+    cmd_quiet_hours: "Configure the server's quiet hours (notifications are batched)",
+    cmd_notification_sink: "Turn a notification destination on or off (Discord, webhook, console)",
+    cmd_dead_letters: "Manage failed notifications",
+    cmd_analysis_jobs: "Show the queue of post-game analyses",
+    cmd_benchmark_tier: "Choose the rank whose benchmarks a summoner's games are graded against",
+    cmd_progression: "Progression report of a summoner over their last analyzed games",
+    opt_summoner_name: "Summoner name as: Name#Tag",
+    opt_language: "Language of the bot's messages",
+    opt_digest_period: "Digest to configure",
+    opt_digest_enabled: "Enable or disable this digest",
+    opt_digest_schedule: "Cron schedule: minute hour day month weekday (e.g. 0 9 * * 1)",
+    opt_timezone: "IANA time zone (e.g. Europe/Paris)",
+    opt_quiet_enabled: "Turn quiet hours on or off",
+    opt_quiet_start: "Start time as HH:MM (e.g. 23:00)",
+    opt_quiet_end: "End time as HH:MM (e.g. 08:00)",
+    opt_quiet_bypass: "Notifications still sent: game_started, game_ended, analysis or none",
+    opt_sink_kind: "Destination to configure",
+    opt_sink_enabled: "Turn this destination on or off",
+    opt_sink_url: "Webhook URL (http or https)",
+    sub_dead_letters_list: "List failed notifications with their error",
+    sub_dead_letters_retry: "Send a failed notification again (all of them without an ID)",
+    sub_dead_letters_discard: "Delete a failed notification (all of them without an ID)",
+    opt_dead_letter_id: "ID (or its beginning) shown by /dead-letters list",
+    opt_benchmark_tier: "Rank band (auto follows the current Solo/Duo rank)",
+    opt_analysis_force: "Ask the coach again even if the game was already analyzed",
+    opt_progression_days: "Days covered (7 by default, 90 at most)",
+
+    guild_only: "❌ This command can only be used in a server.",
+    missing_permissions: "❌ Missing permissions: {permissions}",
+    perm_view_channel: "View Channel",
+    perm_send_messages: "Send Messages",
+    perm_embed_links: "Embed Links",
+    init_success: "✅ Notifications set up in <#{channel}>. Game alerts will be posted here!",
+    init_failed: "❌ Failed to save the configuration.",
+    list_failed: "❌ Failed to fetch the summoner list.",
+    list_empty: "📋 No summoner tracked yet.\nUse `/add-summoner` to add one.",
+    list_header: "📋 **Tracked summoners ({count})**:",
+    summoner_name_missing: "Please provide a summoner name as: `Name#Tag`",
+    summoner_name_invalid: "Invalid format. Use `Name#Tag` (e.g. `Doublelift#NA1`)",
+    account_not_found: "Account `{summoner}` not found. Check the name and tag.",
+    add_success: "✅ **{summoner}** added to the tracking list!",
+    add_failed: "❌ Failed to add the summoner.",
+    remove_success: "✅ **{summoner}** removed from the tracking list.",
+    remove_not_found: "❌ Summoner `{summoner}` is not in the tracking list.",
+    remove_failed: "❌ Failed to remove the summoner.",
+    no_recent_match: "No recent game found for `{summoner}`.",
+    recent_match_failed: "Could not retrieve the last game from the Riot API.",
+    match_details_not_found: "Could not retrieve the game details.",
+    match_details_failed: "Could not retrieve the game details from the Riot API.",
+    language_set: "✅ Bot language for this server: {language}.",
+    init_required: "❌ Set up the notification channel first with `/init-sfg-bot`.",
+    language_save_failed: "❌ Failed to save the language.",
+    digest_saved: "✅ {period} digest enabled: `{schedule}` ({timezone}).",
+    digest_disabled: "✅ {period} digest disabled.",
+    digest_invalid_schedule: "❌ Invalid schedule: {error}",
+    invalid_timezone: "❌ Unknown time zone: `{timezone}` (e.g. `Europe/Paris`).",
+    digest_save_failed: "❌ Failed to save the digest.",
+    quiet_hours_saved: "✅ Quiet hours: {start}–{end} ({timezone}). Still sent: {bypass}.",
+    quiet_hours_disabled: "✅ Quiet hours disabled.",
+    quiet_hours_no_bypass: "none",
+    invalid_time: "❌ Invalid time: `{value}` (use HH:MM).",
+    quiet_hours_empty_window: "❌ Quiet hours must start and end at different times.",
+    quiet_hours_invalid_bypass: "❌ Unknown notification type: `{value}` (allowed: {allowed}).",
+    quiet_hours_save_failed: "❌ Failed to save quiet hours.",
+    sink_saved: "✅ {sink} destination enabled.",
+    sink_disabled: "✅ {sink} destination disabled.",
+    sink_url_required: "❌ Give the webhook URL with the `url` option.",
+    sink_invalid_url: "❌ Invalid URL: `{url}` (expected http or https).",
+    sink_save_failed: "❌ Failed to save the destination.",
+    dead_letters_header: "📭 **Failed notifications ({count})**:",
+    dead_letters_empty: "✅ No failed notifications.",
+    dead_letters_requeued: "🔁 {count} notification(s) queued again.",
+    dead_letters_discarded: "🗑️ {count} notification(s) deleted.",
+    dead_letter_not_found: "❌ No failed notification matches `{id}`.",
+    dead_letter_ambiguous: "❌ `{id}` matches several notifications: give more of the ID.",
+    dead_letters_failed: "❌ Failed to access the failed notifications.",
+    analysis_jobs_header: "🧠 **Analysis queue ({count})**:",
+    analysis_jobs_empty: "✅ No analysis waiting.",
+    analysis_jobs_failed: "❌ Failed to fetch the analysis queue.",
+    job_pending: "⏳ waiting",
+    job_running: "🔄 running",
+    job_failed: "❌ failed",
+    job_attempts: "attempt {attempts}/{max}",
+    benchmark_tier_set: "✅ **{summoner}**'s games will be graded against {band} benchmarks.",
+    benchmark_tier_auto: "✅ **{summoner}**'s games will be graded against their Solo/Duo rank.",
+    benchmark_tier_failed: "❌ Failed to save the benchmark tier.",
+    progression_not_enough: "Not enough analyzed games for `{summoner}` in this period (at least {min} needed).",
+    progression_failed: "❌ Failed to build the progression report.",
+};
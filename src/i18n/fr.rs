@@ -0,0 +1,200 @@
+use super::Catalog;
+
+pub const FR: Catalog = Catalog {
+    language_name: "Français",
+    prompt_language: "français",
+
+    queue_ranked_solo: "Classée Solo/Duo",
+    queue_ranked_flex: "Classée Flex",
+    queue_draft: "Draft Pick",
+    queue_blind: "Blind Pick",
+    queue_aram: "ARAM",
+    queue_quickplay: "Quickplay",
+    queue_arena: "Arène",
+    queue_other: "File {id}",
+    unknown: "Inconnu",
+    list_and: " et ",
+    list_more: "… et {count} de plus",
+
+    game_started_title: "🎮 Partie lancée !",
+    game_started_description: "{players} a lancé une partie {mode} ({queue})",
+    game_won_title: "Partie gagnée !",
+    game_lost_title: "Partie perdue !",
+    game_ended_description: "La partie {mode} est terminée ! Vérifie tes stats.",
+    featured_game_ended_description: "Le mode featured {mode} est terminé ! L'historique des matchs peut prendre un moment à se mettre à jour.",
+    single_game_ended_description: "Partie {queue} terminée pour {name}.",
+    stats_field: "📊 Stats",
+    versus_field: "⚔️ vs",
+    role_gaps_field: "🎯 Écarts par rôle",
+    enemy_unknown: "⚔️ vs Inconnu (pas de données de rôle)",
+    report_unavailable_title: "⚠️ Récap indisponible",
+    report_unavailable_description: "Impossible de récupérer les données de la partie `{game_id}` de **{name}** auprès de l'API Riot (données indisponibles ou API saturée). Pas de récap ni d'analyse pour cette partie.",
+    details_title: "📋 Détails de la partie",
+    blue_team: "🔵 Équipe bleue",
+    red_team: "🔴 Équipe rouge",
+    victory: "Victoire",
+    defeat: "Défaite",
+    timeline_title: "📈 Timeline",
+    timeline_empty: "Pas de données de timeline pour cette partie.",
+
+    button_analyze: "Analyser {name}",
+    button_details: "Détails",
+    button_timeline: "Timeline",
+    summoner_fetch_failed: "❌ Erreur lors de la récupération de l'invocateur.",
+    summoner_untracked: "❌ Cet invocateur n'est plus suivi par le bot.",
+    match_not_found: "❌ Partie introuvable auprès de l'API Riot.",
+    details_failed: "❌ Impossible de récupérer les détails de la partie depuis l'API Riot.",
+    timeline_not_found: "❌ Timeline introuvable auprès de l'API Riot.",
+    timeline_failed: "❌ Impossible de récupérer la timeline depuis l'API Riot.",
+
+    analysis_title: "📊 Analyse de partie — {name}",
+    analysis_unavailable: "⚠️ Analyse indisponible : {error}",
+    analysis_footer: "Propulsé par {engine}",
+    analysis_in_progress: "⏳ Analyse en cours…",
+    analysis_mode_unsupported: "🎪 Pas d'analyse pour ce mode de jeu (Arena) : le coach ne gère que les modes classiques avec lanes et rôles.",
+    analysis_strengths: "💪 Points forts",
+    analysis_weaknesses: "🩹 Points faibles",
+    analysis_skills: "📋 Compétences",
+    analysis_key_tip: "🎯 Conseil de coach",
+    analysis_stats_summary: "📏 Verdict calculé à partir des stats de la partie : le coach IA n'est pas disponible.",
+    analysis_stats_footer: "Repères {band}",
+    analysis_cached: "Analyse en cache (option force pour la relancer)",
+    skill_cs_per_minute: "CS/min",
+    skill_damage_per_minute: "Dégâts/min",
+    skill_kills_assists: "Participation aux kills",
+    skill_deaths: "Morts",
+    skill_death_timing: "Morts évitables",
+    skill_objectives: "Présence aux objectifs",
+    skill_vision_score: "Vision/min",
+    metric_cs_diff_at_10: "Écart de CS à 10 min",
+    tier_band_iron_bronze: "Fer / Bronze",
+    tier_band_silver_gold: "Argent / Or",
+    tier_band_platinum_emerald: "Platine / Émeraude",
+    tier_band_diamond_plus: "Diamant et plus",
+    tier_band_auto: "Auto (rang Solo/Duo)",
+
+    digest_weekly_title: "📅 Semaine SFG",
+    digest_daily_title: "☀️ Journée SFG",
+    digest_date_format: "%d/%m",
+    digest_period: "Du {start} au {end} · {games} parties",
+    digest_empty: "Aucune partie jouée sur la période.",
+    digest_player: "{name} — {wins}V {losses}D ({winrate} %)",
+    digest_kda: "KDA {kda} ({kills}/{deaths}/{assists})",
+    digest_lp: "LP {lp}",
+    digest_champions: "Champions : {champions}",
+    digest_best: "⭐ Meilleure : {game}",
+    digest_worst: "💀 Pire : {game}",
+    digest_coach: "Coach : {good} Good · {average} Average · {poor} Poor",
+    win_short: "V",
+    loss_short: "D",
+    period_daily: "quotidien",
+    period_weekly: "hebdomadaire",
+
+    progression_title: "📈 Progression — {name}",
+    progression_period: "Du {start} au {end} · {games} parties analysées, {wins} victoires",
+    progression_metrics: "📊 Moyennes glissantes ({window} parties)",
+    progression_metric: "{metric} : {first} → {last} (moyenne {average})",
+    progression_improved: "📈 Plus forte progression",
+    progression_regressed: "📉 Plus forte régression",
+    progression_skill: "{skill} : {before} → {after} points de note sur 2",
+    progression_coaching: "🎯 Le mot du coach",
+
+    quiet_summary_title: "🌙 Pendant les heures calmes",
+    quiet_summary_description: "{games} partie(s) terminée(s) pendant les heures calmes :",
+
+    cmd_analyze_last_game: "Analyse la dernière partie d'un invocateur",
+    cmd_init_sfg_bot: "Configure ce salon comme salon de notifications du bot",
+    cmd_list_summoners: "Affiche la liste des invocateurs suivis",
+    cmd_add_summoner: "Ajoute un invocateur à la liste de suivi",
+    cmd_remove_summoner: "Retire un invocateur de la liste de suivi",
+    cmd_set_language: "Change la langue du bot sur ce serveur",
+    cmd_digest: "Configure le récap quotidien ou hebdomadaire du serveur",
+    cmd_quiet_hours: "Configure les heures calmes du serveur (notifications regroupées)",
+    cmd_notification_sink: "Active ou désactive une destination des notifications (Discord, webhook, console)",
+    cmd_dead_letters: "Gère les notifications en échec",
+    cmd_analysis_jobs: "Affiche la file des analyses d'après-partie",
+    cmd_benchmark_tier: "Choisit le rang dont les repères servent à noter les parties d'un invocateur",
+    cmd_progression: "Rapport de progression d'un invocateur sur ses dernières parties analysées",
+    opt_summoner_name: "Nom d'invocateur au format : Nom#Tag",
+    opt_language: "Langue des messages du bot",
+    opt_digest_period: "Récap à configurer",
+    opt_digest_enabled: "Activer ou désactiver ce récap",
+    opt_digest_schedule: "Horaire au format cron : minute heure jour mois jour-de-semaine (ex : 0 9 * * 1)",
+    opt_timezone: "Fuseau horaire IANA (ex : Europe/Paris)",
+    opt_quiet_enabled: "Activer ou désactiver les heures calmes",
+    opt_quiet_start: "Début au format HH:MM (ex : 23:00)",
+    opt_quiet_end: "Fin au format HH:MM (ex : 08:00)",
+    opt_quiet_bypass: "Notifications envoyées quand même : game_started, game_ended, analysis ou none",
+    opt_sink_kind: "Destination à configurer",
+    opt_sink_enabled: "Activer ou désactiver cette destination",
+    opt_sink_url: "URL du webhook (http ou https)",
+    sub_dead_letters_list: "Liste les notifications en échec avec leur erreur",
+    sub_dead_letters_retry: "Renvoie une notification en échec (toutes si aucun identifiant)",
+    sub_dead_letters_discard: "Supprime une notification en échec (toutes si aucun identifiant)",
+    opt_dead_letter_id: "Identifiant (ou son début) affiché par /dead-letters list",
+    opt_benchmark_tier: "Palier de rang (auto suit le rang Solo/Duo actuel)",
+    opt_analysis_force: "Relancer le coach même si la partie a déjà été analysée",
+    opt_progression_days: "Nombre de jours couverts (7 par défaut, 90 au plus)",
+
+    guild_only: "❌ Cette commande ne peut être utilisée que dans un serveur.",
+    missing_permissions: "❌ Droits manquants : {permissions}",
+    perm_view_channel: "Voir le salon",
+    perm_send_messages: "Envoyer des messages",
+    perm_embed_links: "Intégrer des liens",
+    init_success: "✅ Notifications configurées dans <#{channel}>. Les alertes de parties seront envoyées ici !",
+    init_failed: "❌ Erreur lors de la sauvegarde de la configuration.",
+    list_failed: "❌ Erreur lors de la récupération de la liste des invocateurs.",
+    list_empty: "📋 Aucun invocateur suivi pour le moment.\nUtilise `/add-summoner` pour en ajouter.",
+    list_header: "📋 **Invocateurs suivis ({count})** :",
+    summoner_name_missing: "Merci de fournir un nom d'invocateur au format : `Nom#Tag`",
+    summoner_name_invalid: "Format invalide. Utilise `Nom#Tag` (ex: `Doublelift#NA1`)",
+    account_not_found: "Compte `{summoner}` introuvable. Vérifie le nom et le tag.",
+    add_success: "✅ **{summoner}** ajouté à la liste de suivi !",
+    add_failed: "❌ Erreur lors de l'ajout de l'invocateur.",
+    remove_success: "✅ **{summoner}** retiré de la liste de suivi.",
+    remove_not_found: "❌ Invocateur `{summoner}` introuvable dans la liste de suivi.",
+    remove_failed: "❌ Erreur lors de la suppression de l'invocateur.",
+    no_recent_match: "Aucune partie récente trouvée pour `{summoner}`.",
+    recent_match_failed: "Impossible de récupérer les données de la dernière partie depuis l'API Riot.",
+    match_details_not_found: "Impossible de récupérer les détails de la partie.",
+    match_details_failed: "Impossible de récupérer les détails de la partie depuis l'API Riot.",
+    language_set: "✅ Langue du bot sur ce serveur : {language}.",
+    init_required: "❌ Configure d'abord le salon de notifications avec `/init-sfg-bot`.",
+    language_save_failed: "❌ Erreur lors de la sauvegarde de la langue.",
+    digest_saved: "✅ Récap {period} activé : `{schedule}` ({timezone}).",
+    digest_disabled: "✅ Récap {period} désactivé.",
+    digest_invalid_schedule: "❌ Horaire invalide : {error}",
+    invalid_timezone: "❌ Fuseau horaire inconnu : `{timezone}` (ex : `Europe/Paris`).",
+    digest_save_failed: "❌ Erreur lors de la sauvegarde du récap.",
+    quiet_hours_saved: "✅ Heures calmes : {start}–{end} ({timezone}). Envoyées quand même : {bypass}.",
+    quiet_hours_disabled: "✅ Heures calmes désactivées.",
+    quiet_hours_no_bypass: "aucune",
+    invalid_time: "❌ Heure invalide : `{value}` (format HH:MM).",
+    quiet_hours_empty_window: "❌ Le début et la fin des heures calmes doivent être différents.",
+    quiet_hours_invalid_bypass: "❌ Type de notification inconnu : `{value}` (valeurs possibles : {allowed}).",
+    quiet_hours_save_failed: "❌ Erreur lors de la sauvegarde des heures calmes.",
+    sink_saved: "✅ Destination {sink} activée.",
+    sink_disabled: "✅ Destination {sink} désactivée.",
+    sink_url_required: "❌ Indique l'URL du webhook avec l'option `url`.",
+    sink_invalid_url: "❌ URL invalide : `{url}` (http ou https attendu).",
+    sink_save_failed: "❌ Erreur lors de la sauvegarde de la destination.",
+    dead_letters_header: "📭 **Notifications en échec ({count})** :",
+    dead_letters_empty: "✅ Aucune notification en échec.",
+    dead_letters_requeued: "🔁 {count} notification(s) remise(s) en file d'envoi.",
+    dead_letters_discarded: "🗑️ {count} notification(s) supprimée(s).",
+    dead_letter_not_found: "❌ Aucune notification en échec ne correspond à `{id}`.",
+    dead_letter_ambiguous: "❌ `{id}` correspond à plusieurs notifications : précise l'identifiant.",
+    dead_letters_failed: "❌ Erreur lors de l'accès aux notifications en échec.",
+    analysis_jobs_header: "🧠 **File des analyses ({count})** :",
+    analysis_jobs_empty: "✅ Aucune analyse en attente.",
+    analysis_jobs_failed: "❌ Erreur lors de la récupération de la file des analyses.",
+    job_pending: "⏳ en attente",
+    job_running: "🔄 en cours",
+    job_failed: "❌ échec",
+    job_attempts: "tentative {attempts}/{max}",
+    benchmark_tier_set: "✅ Les parties de **{summoner}** seront notées selon les repères {band}.",
+    benchmark_tier_auto: "✅ Les parties de **{summoner}** seront notées selon son rang Solo/Duo.",
+    benchmark_tier_failed: "❌ Erreur lors de la sauvegarde du palier de repères.",
+    progression_not_enough: "Pas assez de parties analysées pour `{summoner}` sur la période (il en faut au moins {min}).",
+    progression_failed: "❌ Erreur lors de la création du rapport de progression.",
+};
@@ -0,0 +1,448 @@
+//! User-facing text catalogs.
+//!
+//! Every string the bot posts to Discord lives in a [`Catalog`], with one
+//! bundle per supported [`Locale`] (`fr.rs`, `en.rs`). The locale is a
+//! per-guild setting stored in `bot_config.locale` and changed with
+//! `/set-language`. Templates use `{name}` placeholders filled by
+//! [`render`], the same convention as the analysis prompt files.
+
+mod en;
+mod fr;
+
+pub use en::EN;
+pub use fr::FR;
+
+/// Language the bot speaks in a guild. French is the default: the bot was
+/// built for a French-speaking server and existing guilds keep it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    Fr,
+    En,
+}
+
+impl Locale {
+    pub const ALL: &'static [Locale] = &[Locale::Fr, Locale::En];
+
+    /// Parses a stored locale code or a Discord locale (`fr`, `en-US`, ...).
+    pub fn from_code(code: &str) -> Option<Self> {
+        let lang = code.split(['-', '_']).next().unwrap_or(code);
+        match lang.to_ascii_lowercase().as_str() {
+            "fr" => Some(Locale::Fr),
+            "en" => Some(Locale::En),
+            _ => None,
+        }
+    }
+
+    /// Short code stored in `bot_config.locale`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::Fr => "fr",
+            Locale::En => "en",
+        }
+    }
+
+    /// Discord locale identifiers this bundle is registered under for
+    /// `description_localized`.
+    pub fn discord_locales(self) -> &'static [&'static str] {
+        match self {
+            Locale::Fr => &["fr"],
+            Locale::En => &["en-US", "en-GB"],
+        }
+    }
+
+    pub fn catalog(self) -> &'static Catalog {
+        match self {
+            Locale::Fr => &FR,
+            Locale::En => &EN,
+        }
+    }
+}
+
+/// Substitutes `{key}` placeholders in a catalog template.
+pub fn render(template: &str, args: &[(&str, &str)]) -> String {
+    let mut out = template.to_string();
+    for (key, value) in args {
+        out = out.replace(&format!("{{{key}}}"), value);
+    }
+    out
+}
+
+/// All user-facing text for one language.
+pub struct Catalog {
+    /// Native name shown in `/set-language` choices.
+    pub language_name: &'static str,
+    /// Language name as written in the (French) analysis prompts, substituted
+    /// into `{response_language}`.
+    pub prompt_language: &'static str,
+
+    // Queues and generic words
+    pub queue_ranked_solo: &'static str,
+    pub queue_ranked_flex: &'static str,
+    pub queue_draft: &'static str,
+    pub queue_blind: &'static str,
+    pub queue_aram: &'static str,
+    pub queue_quickplay: &'static str,
+    pub queue_arena: &'static str,
+    /// `{id}`
+    pub queue_other: &'static str,
+    pub unknown: &'static str,
+    /// Separator before the last item of a list ("A, B et C").
+    pub list_and: &'static str,
+    /// `{count}` items left out of a truncated list.
+    pub list_more: &'static str,
+
+    // Game recaps
+    pub game_started_title: &'static str,
+    /// `{players}`, `{mode}`, `{queue}`
+    pub game_started_description: &'static str,
+    pub game_won_title: &'static str,
+    pub game_lost_title: &'static str,
+    /// `{mode}`
+    pub game_ended_description: &'static str,
+    /// `{mode}`
+    pub featured_game_ended_description: &'static str,
+    /// `{queue}`, `{name}`
+    pub single_game_ended_description: &'static str,
+    pub stats_field: &'static str,
+    pub versus_field: &'static str,
+    pub role_gaps_field: &'static str,
+    pub enemy_unknown: &'static str,
+    pub report_unavailable_title: &'static str,
+    /// `{game_id}`, `{name}`
+    pub report_unavailable_description: &'static str,
+    pub details_title: &'static str,
+    pub blue_team: &'static str,
+    pub red_team: &'static str,
+    pub victory: &'static str,
+    pub defeat: &'static str,
+    pub timeline_title: &'static str,
+    pub timeline_empty: &'static str,
+
+    // Recap buttons
+    /// `{name}`
+    pub button_analyze: &'static str,
+    pub button_details: &'static str,
+    pub button_timeline: &'static str,
+    pub summoner_fetch_failed: &'static str,
+    pub summoner_untracked: &'static str,
+    pub match_not_found: &'static str,
+    pub details_failed: &'static str,
+    pub timeline_not_found: &'static str,
+    pub timeline_failed: &'static str,
+
+    // Analysis
+    /// `{name}`
+    pub analysis_title: &'static str,
+    /// `{error}`
+    pub analysis_unavailable: &'static str,
+    pub analysis_footer: &'static str,
+    pub analysis_in_progress: &'static str,
+    pub analysis_mode_unsupported: &'static str,
+    pub analysis_strengths: &'static str,
+    pub analysis_weaknesses: &'static str,
+    pub analysis_skills: &'static str,
+    pub analysis_key_tip: &'static str,
+    pub analysis_stats_summary: &'static str,
+    pub analysis_stats_footer: &'static str,
+    pub analysis_cached: &'static str,
+    pub skill_cs_per_minute: &'static str,
+    pub skill_damage_per_minute: &'static str,
+    pub skill_kills_assists: &'static str,
+    pub skill_deaths: &'static str,
+    pub skill_death_timing: &'static str,
+    pub skill_objectives: &'static str,
+    pub skill_vision_score: &'static str,
+    pub metric_cs_diff_at_10: &'static str,
+    pub tier_band_iron_bronze: &'static str,
+    pub tier_band_silver_gold: &'static str,
+    pub tier_band_platinum_emerald: &'static str,
+    pub tier_band_diamond_plus: &'static str,
+    /// Choice of `/benchmark-tier` that clears the override.
+    pub tier_band_auto: &'static str,
+
+    // Digests
+    pub digest_weekly_title: &'static str,
+    pub digest_daily_title: &'static str,
+    /// `chrono` format of the period bounds.
+    pub digest_date_format: &'static str,
+    /// `{start}`, `{end}`, `{games}`
+    pub digest_period: &'static str,
+    pub digest_empty: &'static str,
+    /// `{name}`, `{wins}`, `{losses}`, `{winrate}`
+    pub digest_player: &'static str,
+    /// `{kda}`, `{kills}`, `{deaths}`, `{assists}`
+    pub digest_kda: &'static str,
+    /// `{lp}`
+    pub digest_lp: &'static str,
+    /// `{champions}`
+    pub digest_champions: &'static str,
+    /// `{game}`
+    pub digest_best: &'static str,
+    /// `{game}`
+    pub digest_worst: &'static str,
+    /// `{good}`, `{average}`, `{poor}`
+    pub digest_coach: &'static str,
+    pub win_short: &'static str,
+    pub loss_short: &'static str,
+    pub period_daily: &'static str,
+    pub period_weekly: &'static str,
+
+    // Progression reports
+    /// `{name}`
+    pub progression_title: &'static str,
+    /// `{start}`, `{end}`, `{games}`, `{wins}`
+    pub progression_period: &'static str,
+    /// `{window}`
+    pub progression_metrics: &'static str,
+    /// `{metric}`, `{first}`, `{last}`, `{average}`
+    pub progression_metric: &'static str,
+    pub progression_improved: &'static str,
+    pub progression_regressed: &'static str,
+    /// `{skill}`, `{before}`, `{after}`
+    pub progression_skill: &'static str,
+    pub progression_coaching: &'static str,
+
+    // Quiet hours
+    pub quiet_summary_title: &'static str,
+    /// `{games}`
+    pub quiet_summary_description: &'static str,
+
+    // Slash command descriptions (registered via `description_localized`)
+    pub cmd_analyze_last_game: &'static str,
+    pub cmd_init_sfg_bot: &'static str,
+    pub cmd_list_summoners: &'static str,
+    pub cmd_add_summoner: &'static str,
+    pub cmd_remove_summoner: &'static str,
+    pub cmd_set_language: &'static str,
+    pub cmd_digest: &'static str,
+    pub cmd_quiet_hours: &'static str,
+    pub cmd_notification_sink: &'static str,
+    pub cmd_dead_letters: &'static str,
+    pub cmd_analysis_jobs: &'static str,
+    pub cmd_benchmark_tier: &'static str,
+    pub cmd_progression: &'static str,
+    pub opt_summoner_name: &'static str,
+    pub opt_language: &'static str,
+    pub opt_digest_period: &'static str,
+    pub opt_digest_enabled: &'static str,
+    pub opt_digest_schedule: &'static str,
+    pub opt_timezone: &'static str,
+    pub opt_quiet_enabled: &'static str,
+    pub opt_quiet_start: &'static str,
+    pub opt_quiet_end: &'static str,
+    pub opt_quiet_bypass: &'static str,
+    pub opt_sink_kind: &'static str,
+    pub opt_sink_enabled: &'static str,
+    pub opt_sink_url: &'static str,
+    pub sub_dead_letters_list: &'static str,
+    pub sub_dead_letters_retry: &'static str,
+    pub sub_dead_letters_discard: &'static str,
+    pub opt_dead_letter_id: &'static str,
+    pub opt_benchmark_tier: &'static str,
+    pub opt_analysis_force: &'static str,
+    pub opt_progression_days: &'static str,
+
+    // Slash command responses
+    pub guild_only: &'static str,
+    /// `{permissions}`
+    pub missing_permissions: &'static str,
+    pub perm_view_channel: &'static str,
+    pub perm_send_messages: &'static str,
+    pub perm_embed_links: &'static str,
+    /// `{channel}`
+    pub init_success: &'static str,
+    pub init_failed: &'static str,
+    pub list_failed: &'static str,
+    pub list_empty: &'static str,
+    /// `{count}`
+    pub list_header: &'static str,
+    pub summoner_name_missing: &'static str,
+    pub summoner_name_invalid: &'static str,
+    /// `{summoner}`
+    pub account_not_found: &'static str,
+    /// `{summoner}`
+    pub add_success: &'static str,
+    pub add_failed: &'static str,
+    /// `{summoner}`
+    pub remove_success: &'static str,
+    /// `{summoner}`
+    pub remove_not_found: &'static str,
+    pub remove_failed: &'static str,
+    /// `{summoner}`
+    pub no_recent_match: &'static str,
+    pub recent_match_failed: &'static str,
+    pub match_details_not_found: &'static str,
+    pub match_details_failed: &'static str,
+    /// `{language}`
+    pub language_set: &'static str,
+    pub init_required: &'static str,
+    pub language_save_failed: &'static str,
+    /// `{period}`, `{schedule}`, `{timezone}`
+    pub digest_saved: &'static str,
+    /// `{period}`
+    pub digest_disabled: &'static str,
+    /// `{error}`
+    pub digest_invalid_schedule: &'static str,
+    /// `{timezone}`
+    pub invalid_timezone: &'static str,
+    pub digest_save_failed: &'static str,
+    /// `{start}`, `{end}`, `{timezone}`, `{bypass}`
+    pub quiet_hours_saved: &'static str,
+    pub quiet_hours_disabled: &'static str,
+    pub quiet_hours_no_bypass: &'static str,
+    /// `{value}`
+    pub invalid_time: &'static str,
+    pub quiet_hours_empty_window: &'static str,
+    /// `{value}`, `{allowed}`
+    pub quiet_hours_invalid_bypass: &'static str,
+    pub quiet_hours_save_failed: &'static str,
+    /// `{sink}`
+    pub sink_saved: &'static str,
+    /// `{sink}`
+    pub sink_disabled: &'static str,
+    pub sink_url_required: &'static str,
+    /// `{url}`
+    pub sink_invalid_url: &'static str,
+    pub sink_save_failed: &'static str,
+    /// `{count}`
+    pub dead_letters_header: &'static str,
+    pub dead_letters_empty: &'static str,
+    /// `{count}`
+    pub dead_letters_requeued: &'static str,
+    /// `{count}`
+    pub dead_letters_discarded: &'static str,
+    /// `{id}`
+    pub dead_letter_not_found: &'static str,
+    /// `{id}`
+    pub dead_letter_ambiguous: &'static str,
+    pub dead_letters_failed: &'static str,
+    /// `{count}`
+    pub analysis_jobs_header: &'static str,
+    pub analysis_jobs_empty: &'static str,
+    pub analysis_jobs_failed: &'static str,
+    pub job_pending: &'static str,
+    pub job_running: &'static str,
+    pub job_failed: &'static str,
+    /// `{attempts}`, `{max}`
+    pub job_attempts: &'static str,
+    /// `{summoner}`, `{band}`
+    pub benchmark_tier_set: &'static str,
+    /// `{summoner}`
+    pub benchmark_tier_auto: &'static str,
+    pub benchmark_tier_failed: &'static str,
+    /// `{summoner}`, `{min}`
+    pub progression_not_enough: &'static str,
+    pub progression_failed: &'static str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_code_accepts_stored_and_discord_codes() {
+        assert_eq!(Locale::from_code("fr"), Some(Locale::Fr));
+        assert_eq!(Locale::from_code("en"), Some(Locale::En));
+        assert_eq!(Locale::from_code("en-US"), Some(Locale::En));
+        assert_eq!(Locale::from_code("EN-gb"), Some(Locale::En));
+        assert_eq!(Locale::from_code("de"), None);
+    }
+
+    #[test]
+    fn codes_round_trip() {
+        for locale in Locale::ALL {
+            assert_eq!(Locale::from_code(locale.code()), Some(*locale));
+        }
+    }
+
+    #[test]
+    fn render_substitutes_every_placeholder() {
+        let out = render(
+            "{players} a lancé une partie {mode} ({mode})",
+            &[("players", "A et B"), ("mode", "CLASSIC")],
+        );
+        assert_eq!(out, "A et B a lancé une partie CLASSIC (CLASSIC)");
+    }
+
+    #[test]
+    fn bundles_declare_the_same_placeholders() {
+        // A placeholder missing from one bundle would silently print the raw
+        // `{key}` (or drop the value) in that language.
+        fn placeholders(template: &str) -> Vec<&str> {
+            let mut out: Vec<&str> = template
+                .match_indices('{')
+                .filter_map(|(start, _)| {
+                    let end = template[start..].find('}')?;
+                    Some(&template[start..start + end + 1])
+                })
+                .collect();
+            out.sort_unstable();
+            out
+        }
+
+        let pairs = [
+            (FR.game_started_description, EN.game_started_description),
+            (
+                FR.report_unavailable_description,
+                EN.report_unavailable_description,
+            ),
+            (
+                FR.single_game_ended_description,
+                EN.single_game_ended_description,
+            ),
+            (FR.button_analyze, EN.button_analyze),
+            (FR.analysis_title, EN.analysis_title),
+            (FR.analysis_unavailable, EN.analysis_unavailable),
+            (FR.analysis_footer, EN.analysis_footer),
+            (FR.analysis_stats_footer, EN.analysis_stats_footer),
+            (FR.init_success, EN.init_success),
+            (FR.list_header, EN.list_header),
+            (FR.account_not_found, EN.account_not_found),
+            (FR.add_success, EN.add_success),
+            (FR.remove_success, EN.remove_success),
+            (FR.remove_not_found, EN.remove_not_found),
+            (FR.no_recent_match, EN.no_recent_match),
+            (FR.language_set, EN.language_set),
+            (FR.missing_permissions, EN.missing_permissions),
+            (FR.digest_period, EN.digest_period),
+            (FR.digest_player, EN.digest_player),
+            (FR.digest_kda, EN.digest_kda),
+            (FR.digest_lp, EN.digest_lp),
+            (FR.digest_champions, EN.digest_champions),
+            (FR.digest_best, EN.digest_best),
+            (FR.digest_worst, EN.digest_worst),
+            (FR.digest_coach, EN.digest_coach),
+            (FR.digest_saved, EN.digest_saved),
+            (FR.digest_disabled, EN.digest_disabled),
+            (FR.digest_invalid_schedule, EN.digest_invalid_schedule),
+            (FR.invalid_timezone, EN.invalid_timezone),
+            (FR.quiet_summary_description, EN.quiet_summary_description),
+            (FR.list_more, EN.list_more),
+            (FR.quiet_hours_saved, EN.quiet_hours_saved),
+            (FR.invalid_time, EN.invalid_time),
+            (FR.quiet_hours_invalid_bypass, EN.quiet_hours_invalid_bypass),
+            (FR.sink_saved, EN.sink_saved),
+            (FR.sink_disabled, EN.sink_disabled),
+            (FR.sink_invalid_url, EN.sink_invalid_url),
+            (FR.dead_letters_header, EN.dead_letters_header),
+            (FR.dead_letters_requeued, EN.dead_letters_requeued),
+            (FR.dead_letters_discarded, EN.dead_letters_discarded),
+            (FR.dead_letter_not_found, EN.dead_letter_not_found),
+            (FR.dead_letter_ambiguous, EN.dead_letter_ambiguous),
+            (FR.analysis_jobs_header, EN.analysis_jobs_header),
+            (FR.job_attempts, EN.job_attempts),
+            (FR.benchmark_tier_set, EN.benchmark_tier_set),
+            (FR.benchmark_tier_auto, EN.benchmark_tier_auto),
+            (FR.progression_title, EN.progression_title),
+            (FR.progression_period, EN.progression_period),
+            (FR.progression_metrics, EN.progression_metrics),
+            (FR.progression_metric, EN.progression_metric),
+            (FR.progression_skill, EN.progression_skill),
+            (FR.progression_not_enough, EN.progression_not_enough),
+        ];
+        for (fr, en) in pairs {
+            assert_eq!(placeholders(fr), placeholders(en), "{fr:?} vs {en:?}");
+        }
+    }
+}
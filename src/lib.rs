@@ -1,8 +1,10 @@
 pub mod analysis;
 pub mod config;
 pub mod db;
+pub mod digest;
 pub mod discord;
 pub mod health;
+pub mod i18n;
 pub mod notification;
 pub mod riot;
 
@@ -14,6 +14,7 @@ use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitEx
 
 const CHAMPION_REFRESH_INTERVAL_SECS: u64 = 24 * 60 * 60;
 const INITIAL_CHAMPION_FETCH_TIMEOUT_SECS: u64 = 30;
+const NOTIFICATION_RETENTION_INTERVAL_SECS: u64 = 60 * 60;
 
 #[tokio::main]
 async fn main() {
@@ -64,6 +65,7 @@ async fn main() {
     }
 
     spawn_champion_refresh_task(repository.clone(), riot_client.clone());
+    spawn_notification_retention_task(repository.clone(), config.notification_retention_days);
 
     if let Some(port) = config.health_check_port {
         health::spawn(port);
@@ -102,12 +104,12 @@ async fn refresh_champion_cache(repository: &dyn Repository, riot_client: &dyn R
                 champions.len()
             );
             let mut success_count = 0;
-            for (champion_id, champion_name) in champions {
+            for (champion_id, champion) in champions {
                 if let Err(e) = repository
-                    .upsert_champion(champion_id, &champion_name)
+                    .upsert_champion(champion_id, &champion.name, &champion.tags)
                     .await
                 {
-                    tracing::error!("Failed to upsert champion {}: {}", champion_name, e);
+                    tracing::error!("Failed to upsert champion {}: {}", champion.name, e);
                 } else {
                     success_count += 1;
                 }
@@ -142,3 +144,32 @@ fn spawn_champion_refresh_task(
         }
     });
 }
+
+/// Spawns a background task that deletes delivered notifications older
+/// than the retention window once an hour, so `notification_queue` stops
+/// growing forever. Dead letters are kept until retried or discarded with
+/// `/dead-letters`. A retention of 0 keeps everything.
+fn spawn_notification_retention_task(repository: Arc<dyn Repository>, retention_days: u32) {
+    if retention_days == 0 {
+        tracing::info!("Notification retention disabled; processed notifications are kept");
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut ticker = interval(Duration::from_secs(NOTIFICATION_RETENTION_INTERVAL_SECS));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(retention_days));
+            match repository.prune_processed_notifications(cutoff).await {
+                Ok(0) => {}
+                Ok(deleted) => tracing::info!(
+                    deleted,
+                    retention_days,
+                    "Pruned processed notifications past retention"
+                ),
+                Err(e) => tracing::error!("Failed to prune notification queue: {}", e),
+            }
+        }
+    });
+}
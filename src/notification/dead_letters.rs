@@ -0,0 +1,171 @@
+//! Admin view of notifications that exhausted their retries
+//! (`notification_queue.failed_at` set), used by `/dead-letters`.
+
+use crate::db::models::{NotificationEvent, Summoner};
+use crate::i18n::{Locale, render};
+use uuid::Uuid;
+
+/// Rows listed before the rest is collapsed into a count, keeping the
+/// reply under Discord's 2000-character message limit.
+const MAX_LISTED: usize = 12;
+const MAX_ERROR_CHARS: usize = 80;
+/// Characters of the ID shown in the list; any unique prefix is accepted.
+const SHORT_ID_LEN: usize = 8;
+
+#[derive(Debug, PartialEq)]
+pub enum SelectError {
+    NotFound,
+    Ambiguous,
+}
+
+pub fn format_dead_letters(
+    events: &[NotificationEvent],
+    summoners: &[Summoner],
+    locale: Locale,
+) -> String {
+    let text = locale.catalog();
+    if events.is_empty() {
+        return text.dead_letters_empty.to_string();
+    }
+
+    let mut lines = vec![render(
+        text.dead_letters_header,
+        &[("count", &events.len().to_string())],
+    )];
+    for event in events.iter().take(MAX_LISTED) {
+        let name = summoners
+            .iter()
+            .find(|s| s.id == event.summoner_id)
+            .map_or(text.unknown, |s| s.game_name.as_str());
+        let failed_at = event.failed_at.unwrap_or(event.created_at).timestamp();
+        let error = event.error_message.as_deref().unwrap_or(text.unknown);
+        lines.push(format!(
+            "`{}` · {} · {} · {} · <t:{}:f> — {}",
+            &event.id.to_string()[..SHORT_ID_LEN],
+            event.event_type,
+            name,
+            event.champion_name,
+            failed_at,
+            truncate(error, MAX_ERROR_CHARS)
+        ));
+    }
+    if events.len() > MAX_LISTED {
+        lines.push(render(
+            text.list_more,
+            &[("count", &(events.len() - MAX_LISTED).to_string())],
+        ));
+    }
+    lines.join("\n")
+}
+
+/// IDs targeted by a retry or discard: every dead letter without `id`,
+/// otherwise the single one whose ID starts with it.
+pub fn select_dead_letters(
+    events: &[NotificationEvent],
+    id: Option<&str>,
+) -> Result<Vec<Uuid>, SelectError> {
+    let Some(id) = id.map(|i| i.trim().to_ascii_lowercase()) else {
+        return Ok(events.iter().map(|e| e.id).collect());
+    };
+
+    let matching: Vec<Uuid> = events
+        .iter()
+        .map(|e| e.id)
+        .filter(|event_id| !id.is_empty() && event_id.to_string().starts_with(&id))
+        .collect();
+    match matching.len() {
+        0 => Err(SelectError::NotFound),
+        1 => Ok(matching),
+        _ => Err(SelectError::Ambiguous),
+    }
+}
+
+pub(crate) fn truncate(value: &str, max_chars: usize) -> String {
+    let single_line = value.replace('\n', " ");
+    if single_line.chars().count() <= max_chars {
+        return single_line;
+    }
+    let mut truncated: String = single_line.chars().take(max_chars - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn dead_letter(id: &str, error: &str) -> NotificationEvent {
+        NotificationEvent {
+            id: Uuid::parse_str(id).unwrap(),
+            summoner_id: Uuid::nil(),
+            event_type: "GAME_ENDED".to_string(),
+            game_id: 1,
+            match_id: Some("EUW1_1".to_string()),
+            champion_id: 103,
+            champion_name: "Ahri".to_string(),
+            role: None,
+            win: Some(true),
+            kills: None,
+            deaths: None,
+            assists: None,
+            game_duration_secs: None,
+            game_mode: "CLASSIC".to_string(),
+            queue_id: Some(420),
+            is_featured_mode: false,
+            total_cs: None,
+            total_gold: None,
+            total_damage: None,
+            enemy_champion_name: None,
+            enemy_cs: None,
+            enemy_gold: None,
+            enemy_damage: None,
+            role_gaps: None,
+            processed: true,
+            created_at: Utc::now(),
+            processed_at: Some(Utc::now()),
+            retry_count: 2,
+            error_message: Some(error.to_string()),
+            failed_at: Some(Utc::now()),
+            next_attempt_at: Utc::now(),
+        }
+    }
+
+    const FIRST: &str = "1a2b3c4d-0000-4000-8000-000000000001";
+    const SECOND: &str = "1a2b9999-0000-4000-8000-000000000002";
+
+    #[test]
+    fn lists_short_ids_and_truncated_errors() {
+        let events = [dead_letter(FIRST, &"Missing Access ".repeat(20))];
+        let listing = format_dead_letters(&events, &[], Locale::En);
+
+        let mut lines = listing.lines();
+        assert_eq!(lines.next(), Some("📭 **Failed notifications (1)**:"));
+        let row = lines.next().unwrap();
+        assert!(row.starts_with("`1a2b3c4d` · GAME_ENDED · Unknown · Ahri · <t:"));
+        assert!(row.ends_with('…'));
+        assert_eq!(
+            format_dead_letters(&[], &[], Locale::Fr),
+            "✅ Aucune notification en échec."
+        );
+    }
+
+    #[test]
+    fn selects_by_unique_prefix_or_all() {
+        let events = [dead_letter(FIRST, "a"), dead_letter(SECOND, "b")];
+
+        assert_eq!(select_dead_letters(&events, None).unwrap().len(), 2);
+        assert_eq!(
+            select_dead_letters(&events, Some("1A2B3C")),
+            Ok(vec![Uuid::parse_str(FIRST).unwrap()])
+        );
+        assert_eq!(
+            select_dead_letters(&events, Some("1a2b")),
+            Err(SelectError::Ambiguous)
+        );
+        assert_eq!(
+            select_dead_letters(&events, Some("ffff")),
+            Err(SelectError::NotFound)
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! Wakes the notification processor when `notification_queue` gets a
+//! pending row, through the Postgres channel fed by the queue's trigger.
+
+use sqlx::postgres::PgListener;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Channel signalled by the `notification_queue_notify` trigger.
+pub const CHANNEL: &str = "notification_queue";
+
+const RECONNECT_BASE_SECS: u64 = 1;
+const RECONNECT_MAX_SECS: u64 = 60;
+
+/// Listens on [`CHANNEL`] in the background and calls `wake.notify_one()`
+/// for every signal. The processor is also woken after each (re)connect, so
+/// events queued while the connection was down are not left waiting for
+/// the safety sweep.
+pub fn spawn_queue_listener(database_url: String, wake: Arc<Notify>) {
+    tokio::spawn(async move {
+        let mut failures = 0;
+        loop {
+            match listen(&database_url).await {
+                Ok(mut listener) => {
+                    failures = 0;
+                    tracing::info!("Listening for notification queue changes");
+                    wake.notify_one();
+                    forward(&mut listener, &wake).await;
+                }
+                Err(e) => tracing::error!("Failed to listen on {}: {}", CHANNEL, e),
+            }
+            failures += 1;
+            let delay = reconnect_delay(failures);
+            tracing::warn!(
+                "Notification queue listener down, reconnecting in {}s",
+                delay.as_secs()
+            );
+            tokio::time::sleep(delay).await;
+        }
+    });
+}
+
+async fn listen(database_url: &str) -> Result<PgListener, sqlx::Error> {
+    let mut listener = PgListener::connect(database_url).await?;
+    listener.listen(CHANNEL).await?;
+    Ok(listener)
+}
+
+/// Relays signals until the listener cannot reconnect on its own.
+async fn forward(listener: &mut PgListener, wake: &Notify) {
+    loop {
+        match listener.try_recv().await {
+            Ok(Some(_)) => wake.notify_one(),
+            // The connection dropped; the next call reconnects and listens
+            // again. Signals sent in between are lost, so sweep now.
+            Ok(None) => {
+                tracing::warn!("Notification queue listener lost its connection");
+                wake.notify_one();
+            }
+            Err(e) => {
+                tracing::error!("Notification queue listener failed: {}", e);
+                return;
+            }
+        }
+    }
+}
+
+/// Delay before reconnect attempt number `failures` (1-based): doubles from
+/// one second up to a minute.
+fn reconnect_delay(failures: u32) -> Duration {
+    let exponent = failures.saturating_sub(1).min(6);
+    Duration::from_secs((RECONNECT_BASE_SECS << exponent).min(RECONNECT_MAX_SECS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reconnect_delay_doubles_up_to_a_minute() {
+        assert_eq!(reconnect_delay(1), Duration::from_secs(1));
+        assert_eq!(reconnect_delay(2), Duration::from_secs(2));
+        assert_eq!(reconnect_delay(6), Duration::from_secs(32));
+        assert_eq!(reconnect_delay(7), Duration::from_secs(60));
+        assert_eq!(reconnect_delay(40), Duration::from_secs(60));
+    }
+}
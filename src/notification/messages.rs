@@ -1,22 +1,24 @@
 use crate::db::models::{NotificationEvent, Summoner};
-use crate::riot::models::MatchResult;
+use crate::i18n::{Locale, render};
+use crate::riot::models::{LaneDiffs, MatchResult, MatchScoreboard};
 use serenity::builder::{CreateEmbed, CreateEmbedFooter};
 use serenity::model::{Colour, Timestamp};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 /// Convert a Riot API queue_id to a friendly name
-fn get_queue_type_name(queue_id: Option<i32>) -> String {
+pub(crate) fn get_queue_type_name(queue_id: Option<i32>, locale: Locale) -> String {
+    let text = locale.catalog();
     match queue_id {
-        Some(420) => "Classée Solo/Duo".to_string(),
-        Some(440) => "Classée Flex".to_string(),
-        Some(400) => "Draft Pick".to_string(),
-        Some(430) => "Blind Pick".to_string(),
-        Some(450) => "ARAM".to_string(),
-        Some(490) => "Quickplay".to_string(),
-        Some(1700) => "Arène".to_string(),
-        Some(id) => format!("File {}", id),
-        None => "Inconnu".to_string(),
+        Some(420) => text.queue_ranked_solo.to_string(),
+        Some(440) => text.queue_ranked_flex.to_string(),
+        Some(400) => text.queue_draft.to_string(),
+        Some(430) => text.queue_blind.to_string(),
+        Some(450) => text.queue_aram.to_string(),
+        Some(490) => text.queue_quickplay.to_string(),
+        Some(1700) => text.queue_arena.to_string(),
+        Some(id) => render(text.queue_other, &[("id", &id.to_string())]),
+        None => text.unknown.to_string(),
     }
 }
 
@@ -25,15 +27,19 @@ pub fn format_grouped_game_started(
     champions: &[(Uuid, String, String)],
     game_mode: &str,
     queue_id: Option<i32>,
+    locale: Locale,
 ) -> CreateEmbed {
+    let text = locale.catalog();
     let summoner_names: Vec<String> = summoners.iter().map(|s| s.game_name.clone()).collect();
 
-    let queue_type = get_queue_type_name(queue_id);
-    let description = format!(
-        "{} a lancé une partie {} ({})",
-        format_list(&summoner_names),
-        game_mode,
-        queue_type
+    let queue_type = get_queue_type_name(queue_id, locale);
+    let description = render(
+        text.game_started_description,
+        &[
+            ("players", &format_list(&summoner_names, locale)),
+            ("mode", game_mode),
+            ("queue", &queue_type),
+        ],
     );
 
     let champion_map: HashMap<Uuid, String> = champions
@@ -44,7 +50,7 @@ pub fn format_grouped_game_started(
     let footer_text = format!("League of Legends · {} · {}", game_mode, queue_type);
 
     let mut embed = CreateEmbed::new()
-        .title("🎮 Partie lancée !")
+        .title(text.game_started_title)
         .description(description)
         .colour(Colour::from_rgb(52, 152, 219))
         .footer(CreateEmbedFooter::new(footer_text))
@@ -55,7 +61,7 @@ pub fn format_grouped_game_started(
         let champion = champion_map
             .get(&summoner.id)
             .cloned()
-            .unwrap_or_else(|| "Inconnu".to_string());
+            .unwrap_or_else(|| text.unknown.to_string());
 
         embed = embed.field(name, champion, true);
     }
@@ -67,7 +73,9 @@ pub fn format_grouped_game_ended(
     summoners: &[Summoner],
     events: &[NotificationEvent],
     game_mode: &str,
+    locale: Locale,
 ) -> CreateEmbed {
+    let text = locale.catalog();
     let event_map: HashMap<Uuid, &NotificationEvent> =
         events.iter().map(|e| (e.summoner_id, e)).collect();
 
@@ -98,15 +106,12 @@ pub fn format_grouped_game_ended(
     let queue_type = events
         .first()
         .and_then(|event| event.queue_id)
-        .map(|qid| get_queue_type_name(Some(qid)));
+        .map(|qid| get_queue_type_name(Some(qid), locale));
 
     let description = if is_featured_mode {
-        format!(
-            "Le mode featured {} est terminé ! L'historique des matchs peut prendre un moment à se mettre à jour.",
-            game_mode
-        )
+        render(text.featured_game_ended_description, &[("mode", game_mode)])
     } else {
-        format!("La partie {} est terminée ! Vérifie tes stats.", game_mode)
+        render(text.game_ended_description, &[("mode", game_mode)])
     };
 
     let footer_text = if let Some(queue) = queue_type {
@@ -123,9 +128,9 @@ pub fn format_grouped_game_ended(
 
     let mut embed = CreateEmbed::new()
         .title(if wins > losses {
-            "Partie gagnée !"
+            text.game_won_title
         } else {
-            "Partie perdue !"
+            text.game_lost_title
         })
         .description(description)
         .colour(color)
@@ -147,7 +152,7 @@ pub fn format_grouped_game_ended(
             );
 
             let result_char = if is_win { "W" } else { "L" };
-            let role = event.role.as_deref().unwrap_or("Inconnu");
+            let role = event.role.as_deref().unwrap_or(text.unknown);
             let champion_name = &event.champion_name;
 
             let value_field = format!("💎 {} · {} · {} {}", champion_name, role, result_char, kda);
@@ -165,22 +170,81 @@ pub fn format_grouped_game_ended(
                 event.enemy_gold,
                 event.enemy_damage,
                 event.game_duration_secs.unwrap_or(0),
+                locale,
             );
 
             embed = embed
                 .field(name_field, value_field, true)
-                .field("📊 Stats", stats_line, true)
-                .field("⚔️ vs", enemy_line, true);
+                .field(text.stats_field, stats_line, true)
+                .field(text.versus_field, enemy_line, true);
         }
     }
 
     if let Some(gaps) = pick_role_gaps(events) {
-        embed = embed.field("🎯 Écarts par rôle", gaps, false);
+        embed = embed.field(text.role_gaps_field, gaps, false);
     }
 
     embed
 }
 
+/// Lines listed in a quiet hours summary before it collapses the rest into
+/// a count, keeping the description well under Discord's 4096 characters.
+const MAX_SUMMARY_LINES: usize = 30;
+
+/// Condensed recap of the games that finished during quiet hours: one line
+/// per player and game, oldest first.
+pub fn format_quiet_hours_summary(
+    summoners: &[Summoner],
+    events: &[NotificationEvent],
+    locale: Locale,
+) -> CreateEmbed {
+    let text = locale.catalog();
+    let names: HashMap<Uuid, &str> = summoners
+        .iter()
+        .map(|s| (s.id, s.game_name.as_str()))
+        .collect();
+
+    let mut sorted: Vec<&NotificationEvent> = events.iter().collect();
+    sorted.sort_by_key(|e| e.created_at);
+
+    let games: HashSet<i64> = sorted.iter().map(|e| e.game_id).collect();
+    let mut lines = vec![render(
+        text.quiet_summary_description,
+        &[("games", &games.len().to_string())],
+    )];
+
+    for event in sorted.iter().take(MAX_SUMMARY_LINES) {
+        let is_win = event.win.unwrap_or(false);
+        lines.push(format!(
+            "{} **{}** · {} · {} {}/{}/{} · {}",
+            if is_win { "🏆" } else { "💔" },
+            names
+                .get(&event.summoner_id)
+                .copied()
+                .unwrap_or(text.unknown),
+            event.champion_name,
+            if is_win { "W" } else { "L" },
+            event.kills.unwrap_or(0),
+            event.deaths.unwrap_or(0),
+            event.assists.unwrap_or(0),
+            get_queue_type_name(event.queue_id, locale),
+        ));
+    }
+    if sorted.len() > MAX_SUMMARY_LINES {
+        lines.push(render(
+            text.list_more,
+            &[("count", &(sorted.len() - MAX_SUMMARY_LINES).to_string())],
+        ));
+    }
+
+    CreateEmbed::new()
+        .title(text.quiet_summary_title)
+        .description(lines.join("\n"))
+        .colour(Colour::from_rgb(52, 73, 94))
+        .footer(CreateEmbedFooter::new("League of Legends"))
+        .timestamp(Timestamp::now())
+}
+
 /// Picks the first non-empty `role_gaps` summary across grouped events
 /// (they all come from the same match, so any one is canonical) — surfaces
 /// lane-gap context in the recap regardless of which side won.
@@ -195,26 +259,31 @@ fn pick_role_gaps(events: &[NotificationEvent]) -> Option<String> {
 /// Posted when a finished game exhausted its match-lookup retry budget:
 /// the recap and analysis will never arrive, so say it instead of staying
 /// silent.
-pub fn format_report_unavailable(summoner_name: &str, game_id: i64) -> CreateEmbed {
+pub fn format_report_unavailable(summoner_name: &str, game_id: i64, locale: Locale) -> CreateEmbed {
+    let text = locale.catalog();
     CreateEmbed::new()
-        .title("⚠️ Récap indisponible")
-        .description(format!(
-            "Impossible de récupérer les données de la partie `{game_id}` de **{summoner_name}** \
-             auprès de l'API Riot (données indisponibles ou API saturée). \
-             Pas de récap ni d'analyse pour cette partie."
+        .title(text.report_unavailable_title)
+        .description(render(
+            text.report_unavailable_description,
+            &[("game_id", &game_id.to_string()), ("name", summoner_name)],
         ))
         .colour(Colour::from_rgb(230, 126, 34))
         .timestamp(Timestamp::now())
 }
 
-pub fn format_single_game_ended(summoner_name: &str, match_result: &MatchResult) -> CreateEmbed {
+pub fn format_single_game_ended(
+    summoner_name: &str,
+    match_result: &MatchResult,
+    locale: Locale,
+) -> CreateEmbed {
+    let text = locale.catalog();
     let color = if match_result.win {
         Colour::from_rgb(46, 204, 113)
     } else {
         Colour::from_rgb(231, 76, 60)
     };
 
-    let queue_type = get_queue_type_name(match_result.queue_id);
+    let queue_type = get_queue_type_name(match_result.queue_id, locale);
     let result_char = if match_result.win { "W" } else { "L" };
     let name_prefix = if match_result.win { "🏆" } else { "💔" };
 
@@ -241,12 +310,13 @@ pub fn format_single_game_ended(summoner_name: &str, match_result: &MatchResult)
         match_result.enemy_gold,
         match_result.enemy_damage,
         match_result.game_duration_secs,
+        locale,
     );
 
     let title = if match_result.win {
-        "Partie gagnée !"
+        text.game_won_title
     } else {
-        "Partie perdue !"
+        text.game_lost_title
     };
 
     let footer_text = format!(
@@ -256,9 +326,9 @@ pub fn format_single_game_ended(summoner_name: &str, match_result: &MatchResult)
 
     let mut embed = CreateEmbed::new()
         .title(title)
-        .description(format!(
-            "Partie {} terminée pour {}.",
-            queue_type, summoner_name
+        .description(render(
+            text.single_game_ended_description,
+            &[("queue", &queue_type), ("name", summoner_name)],
         ))
         .colour(color)
         .field(
@@ -266,11 +336,11 @@ pub fn format_single_game_ended(summoner_name: &str, match_result: &MatchResult)
             value_field,
             true,
         )
-        .field("📊 Stats", stats_line, true)
-        .field("⚔️ vs", enemy_line, true);
+        .field(text.stats_field, stats_line, true)
+        .field(text.versus_field, enemy_line, true);
 
     if let Some(gaps) = match_result.role_gaps.as_deref().filter(|s| !s.is_empty()) {
-        embed = embed.field("🎯 Écarts par rôle", gaps.to_string(), false);
+        embed = embed.field(text.role_gaps_field, gaps.to_string(), false);
     }
 
     let timestamp = match_result
@@ -291,15 +361,119 @@ pub fn format_single_game_ended(summoner_name: &str, match_result: &MatchResult)
     embed
 }
 
-fn format_list(items: &[String]) -> String {
+/// Full scoreboard shown by the recap "Détails" button: one field per team,
+/// one line per player.
+pub fn format_match_details(scoreboard: &MatchScoreboard, locale: Locale) -> CreateEmbed {
+    let text = locale.catalog();
+    let queue_type = get_queue_type_name(scoreboard.queue_id, locale);
+    let minutes = scoreboard.game_duration_secs / 60;
+    let seconds = scoreboard.game_duration_secs % 60;
+
+    let mut embed = CreateEmbed::new()
+        .title(text.details_title)
+        .description(format!(
+            "{} · {} · {}m{:02}",
+            scoreboard.game_mode, queue_type, minutes, seconds
+        ))
+        .colour(Colour::from_rgb(52, 152, 219))
+        .footer(CreateEmbedFooter::new(scoreboard.match_id.clone()));
+
+    for (team_id, team_name) in [(100u16, text.blue_team), (200u16, text.red_team)] {
+        let players: Vec<_> = scoreboard
+            .players
+            .iter()
+            .filter(|p| p.team_id == team_id)
+            .collect();
+        if players.is_empty() {
+            continue;
+        }
+
+        let result = if players.iter().any(|p| p.win) {
+            text.victory
+        } else {
+            text.defeat
+        };
+        let lines: Vec<String> = players
+            .iter()
+            .map(|p| {
+                format!(
+                    "**{}** · {} · {}/{}/{} · {} CS · {:.1}k gold · {:.1}k dmg · {} vision",
+                    p.champion_name,
+                    p.display_name,
+                    p.kills,
+                    p.deaths,
+                    p.assists,
+                    p.total_cs,
+                    p.gold_earned as f64 / 1000.0,
+                    p.total_damage as f64 / 1000.0,
+                    p.vision_score
+                )
+            })
+            .collect();
+
+        embed = embed.field(
+            format!("{} — {}", team_name, result),
+            lines.join("\n"),
+            false,
+        );
+    }
+
+    embed
+}
+
+/// Per-player gold/CS differences against the lane opponent at 10/15/20
+/// minutes, shown by the recap "Timeline" button. Each row is
+/// `(player name, champion, lane opponent champion, diffs)`.
+pub fn format_timeline_diffs(
+    rows: &[(String, String, Option<String>, LaneDiffs)],
+    locale: Locale,
+) -> CreateEmbed {
+    let text = locale.catalog();
+    let mut embed = CreateEmbed::new()
+        .title(text.timeline_title)
+        .colour(Colour::from_rgb(52, 152, 219));
+
+    if rows.is_empty() {
+        return embed.description(text.timeline_empty);
+    }
+
+    for (name, champion, enemy_champion, diffs) in rows {
+        let title = match enemy_champion {
+            Some(enemy) => format!("{} — {} vs {}", name, champion, enemy),
+            None => format!("{} — {}", name, champion),
+        };
+        let value = format!(
+            "Gold : {} · {} · {}\nCS : {} · {} · {}",
+            format_diff_at(10, diffs.gold[0]),
+            format_diff_at(15, diffs.gold[1]),
+            format_diff_at(20, diffs.gold[2]),
+            format_diff_at(10, diffs.cs[0]),
+            format_diff_at(15, diffs.cs[1]),
+            format_diff_at(20, diffs.cs[2]),
+        );
+        embed = embed.field(title, value, false);
+    }
+
+    embed
+}
+
+fn format_diff_at(minute: u32, diff: Option<i32>) -> String {
+    match diff {
+        Some(value) => format!("{}' {:+}", minute, value),
+        None => format!("{}' —", minute),
+    }
+}
+
+fn format_list(items: &[String], locale: Locale) -> String {
+    let and = locale.catalog().list_and;
     match items.len() {
         0 => String::new(),
         1 => items[0].clone(),
-        2 => format!("{} et {}", items[0], items[1]),
+        2 => format!("{}{}{}", items[0], and, items[1]),
         _ => {
             let last = items.last().unwrap();
             let rest = &items[..items.len() - 1];
-            format!("{} et {}", rest.join(", "), last)
+            format!("{}{}{}", rest.join(", "), and, last)
         }
     }
 }
@@ -335,12 +509,13 @@ fn format_enemy_comparison(
     enemy_gold: Option<i32>,
     enemy_damage: Option<i32>,
     game_duration_secs: i32,
+    locale: Locale,
 ) -> String {
     match (enemy_champion, enemy_cs, enemy_gold, enemy_damage) {
         (Some(champ), Some(cs), Some(gold), Some(dmg)) => {
             let stats = format_stats_line(cs, gold, dmg, game_duration_secs);
             format!("{} ({})", champ, stats)
         }
-        _ => "⚔️ vs Inconnu (pas de données de rôle)".to_string(),
+        _ => locale.catalog().enemy_unknown.to_string(),
     }
 }
@@ -1,4 +1,9 @@
+pub mod dead_letters;
+pub mod listener;
 pub mod messages;
 pub mod processor;
+pub mod quiet_hours;
+pub mod retry;
+pub mod sinks;
 
 pub use processor::NotificationProcessor;
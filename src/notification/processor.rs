@@ -1,65 +1,151 @@
 use crate::db::models::{NotificationEvent, Summoner};
 use crate::db::repository::Repository;
-use crate::notification::messages::{format_grouped_game_ended, format_grouped_game_started};
-use serenity::builder::CreateMessage;
+use crate::i18n::Locale;
+use crate::notification::quiet_hours::{Delivery, QuietWindow};
+use crate::notification::retry::{
+    FailureKind, MAX_RETRIES, backoff_delay, classify, random_jitter,
+};
+use crate::notification::sinks::{
+    DiscordSink, Notification, NotificationKind, NotificationSink, SinkKind, StdoutSink,
+    WebhookSink, enabled_sinks,
+};
+use chrono::{DateTime, Utc};
+use serenity::http::Http;
 use serenity::model::id::ChannelId;
-use serenity::prelude::*;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
-
-const MAX_RETRIES: i32 = 3;
+use tokio::sync::Notify;
+
+/// How long an event waits for the other players' events of the same game,
+/// so they are posted together.
+const GROUPING_WINDOW: Duration = Duration::from_secs(30);
+
+/// How long a new analysis job waits before it runs: long enough for the
+/// game-ended group of its match to form and merge the jobs of teammates.
+pub const ANALYSIS_GROUPING_DELAY: Duration = Duration::from_secs(45);
+
+/// Longest sleep between two passes, in case a queue signal was missed or
+/// quiet hours ended.
+const SAFETY_SWEEP: Duration = Duration::from_secs(60);
+
+/// Where and how the guild's notifications are posted.
+struct NotificationTarget {
+    /// Enabled sinks, the one whose failures are retried first.
+    sinks: Vec<Box<dyn NotificationSink>>,
+    locale: Locale,
+    quiet_window: Option<QuietWindow>,
+}
 
 pub struct NotificationProcessor {
     repository: Arc<dyn Repository>,
-    ctx: Context,
-    interval_secs: u64,
+    http: Arc<Http>,
+    http_client: reqwest::Client,
+    /// Signalled by the queue listener when events become pending.
+    wake: Arc<Notify>,
 }
 
 impl NotificationProcessor {
-    pub fn new(repository: Arc<dyn Repository>, ctx: Context, interval_secs: u64) -> Self {
+    pub fn new(repository: Arc<dyn Repository>, http: Arc<Http>, wake: Arc<Notify>) -> Self {
         Self {
             repository,
-            ctx,
-            interval_secs,
+            http,
+            http_client: reqwest::Client::new(),
+            wake,
         }
     }
 
+    /// Processes the queue, then sleeps until the queue is signalled, a
+    /// grouping window closes, held events are released or a retry is due,
+    /// whichever comes first.
     pub async fn start(self) {
         loop {
-            if let Err(e) = self.process_pending_events().await {
-                tracing::error!("Error processing notification events: {}", e);
-            }
+            let grouping_deadline = match self.process_pending_events().await {
+                Ok(deadline) => deadline,
+                Err(e) => {
+                    tracing::error!("Error processing notification events: {}", e);
+                    None
+                }
+            };
 
-            tokio::time::sleep(Duration::from_secs(self.interval_secs)).await;
+            let next_attempt = match self.repository.get_next_notification_attempt().await {
+                Ok(next) => next,
+                Err(e) => {
+                    tracing::error!("Failed to get next notification attempt: {}", e);
+                    None
+                }
+            };
+
+            let delay = wake_delay(Utc::now(), [grouping_deadline, next_attempt]);
+            tokio::select! {
+                _ = self.wake.notified() => {}
+                _ = tokio::time::sleep(delay) => {}
+            }
         }
     }
 
-    async fn process_pending_events(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// Returns when the earliest event still inside its grouping window, or
+    /// held by the quiet hours, becomes ready.
+    async fn process_pending_events(
+        &self,
+    ) -> Result<Option<DateTime<Utc>>, Box<dyn std::error::Error + Send + Sync>> {
         let events = self.repository.get_pending_notification_events().await?;
 
         if events.is_empty() {
-            return Ok(());
+            return Ok(None);
         }
 
-        let channel_id = match self.get_notification_channel().await {
-            Some(id) => id,
+        let NotificationTarget {
+            sinks,
+            locale,
+            quiet_window,
+        } = match self.get_notification_target().await {
+            Some(target) => target,
             None => {
-                tracing::debug!("No notification channel configured, skipping event processing");
-                return Ok(());
+                tracing::debug!(
+                    "No notification channel or sink configured, skipping event processing"
+                );
+                return Ok(None);
             }
         };
 
-        let now = chrono::Utc::now();
-        let wait_threshold = Duration::from_secs(30);
+        let now = Utc::now();
+        let mut grouping_deadline: Option<DateTime<Utc>> = None;
 
         let mut game_started_groups: HashMap<i64, Vec<NotificationEvent>> = HashMap::new();
         let mut game_ended_groups: HashMap<String, Vec<NotificationEvent>> = HashMap::new();
+        let mut quiet_hours_events = Vec::new();
 
         for event in events {
+            match quiet_window
+                .as_ref()
+                .map_or(Delivery::Send, |window| window.delivery(&event, now))
+            {
+                Delivery::Send => {}
+                Delivery::Hold => {
+                    // Wake up as the window closes, so the summary merges
+                    // the held games' analysis jobs before the worker runs
+                    // them.
+                    if let Some(window) = &quiet_window {
+                        let ready_at = window.next_end(now);
+                        grouping_deadline = Some(
+                            grouping_deadline.map_or(ready_at, |deadline| deadline.min(ready_at)),
+                        );
+                    }
+                    continue;
+                }
+                Delivery::Summarize => {
+                    quiet_hours_events.push(event);
+                    continue;
+                }
+            }
+
             let event_age = now.signed_duration_since(event.created_at);
 
-            if event_age.to_std().unwrap_or(Duration::ZERO) < wait_threshold {
+            if event_age.to_std().unwrap_or(Duration::ZERO) < GROUPING_WINDOW {
+                let ready_at = event.created_at + GROUPING_WINDOW;
+                grouping_deadline =
+                    Some(grouping_deadline.map_or(ready_at, |deadline| deadline.min(ready_at)));
                 continue;
             }
 
@@ -83,8 +169,15 @@ impl NotificationProcessor {
         }
 
         for (game_id, group_events) in game_started_groups {
+            let label = format!("game started notification for game {}", game_id);
             if let Err(e) = self
-                .send_grouped_game_started(game_id, group_events, channel_id)
+                .deliver(
+                    NotificationKind::GameStarted,
+                    group_events,
+                    &sinks,
+                    locale,
+                    &label,
+                )
                 .await
             {
                 tracing::error!("Failed to send grouped game started notification: {}", e);
@@ -92,141 +185,175 @@ impl NotificationProcessor {
         }
 
         for (match_id, group_events) in game_ended_groups {
+            self.group_analyses(&match_id, &group_events).await;
+            let label = format!("game ended notification for match {}", match_id);
             if let Err(e) = self
-                .send_grouped_game_ended(&match_id, group_events, channel_id)
+                .deliver(
+                    NotificationKind::GameEnded,
+                    group_events,
+                    &sinks,
+                    locale,
+                    &label,
+                )
                 .await
             {
                 tracing::error!("Failed to send grouped game ended notification: {}", e);
             }
         }
 
-        Ok(())
+        if !quiet_hours_events.is_empty()
+            && let Err(e) = self
+                .send_quiet_hours_summary(quiet_hours_events, &sinks, locale)
+                .await
+        {
+            tracing::error!("Failed to send quiet hours summary: {}", e);
+        }
+
+        Ok(grouping_deadline)
+    }
+
+    /// Merges the analysis jobs of tracked teammates in the match, so they
+    /// get one group report instead of an analysis each. Best-effort: the
+    /// players are analyzed separately when it fails.
+    async fn group_analyses(&self, match_id: &str, events: &[NotificationEvent]) {
+        let players: Vec<_> = events.iter().map(|e| (e.summoner_id, e.win)).collect();
+        for team in teammate_groups(&players) {
+            match self.repository.group_analysis_jobs(match_id, &team).await {
+                Ok(Some(job_id)) => tracing::info!(
+                    match_id,
+                    players = team.len(),
+                    "Grouped analysis jobs into {}",
+                    job_id
+                ),
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!("Failed to group analysis jobs of match {}: {}", match_id, e)
+                }
+            }
+        }
     }
 
-    async fn send_grouped_game_started(
+    /// Posts everything held during the quiet hours that just ended as one
+    /// message, after merging the analysis jobs of teammates in each held
+    /// game. Held game-start pings are only marked processed: the games
+    /// they announce are over (or reported by their end event) by now.
+    async fn send_quiet_hours_summary(
         &self,
-        game_id: i64,
         events: Vec<NotificationEvent>,
-        channel_id: ChannelId,
+        sinks: &[Box<dyn NotificationSink>],
+        locale: Locale,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let summoner_ids: Vec<_> = events.iter().map(|e| e.summoner_id).collect();
-        let event_ids: Vec<_> = events.iter().map(|e| e.id).collect();
-
-        let summoners = self.fetch_summoners(&summoner_ids).await?;
-
-        let champions: Vec<_> = events
-            .iter()
-            .map(|e| (e.summoner_id, e.champion_name.clone(), String::new()))
-            .collect();
-
-        let game_mode = events
-            .first()
-            .map(|e| e.game_mode.as_str())
-            .unwrap_or("UNKNOWN");
-
-        let queue_id = events.first().and_then(|e| e.queue_id);
-
-        let embed = format_grouped_game_started(&summoners, &champions, game_mode, queue_id);
-        let builder = CreateMessage::new().embed(embed);
-
-        match channel_id.send_message(&self.ctx.http, builder).await {
-            Ok(_) => {
-                self.repository
-                    .mark_notifications_processed(&event_ids)
-                    .await?;
-
-                tracing::info!(
-                    "Sent grouped game started notification for game {} with {} players",
-                    game_id,
-                    events.len()
-                );
-            }
-            Err(e) => {
-                let max_retry = events.iter().map(|ev| ev.retry_count).max().unwrap_or(0);
-                let error_msg = e.to_string();
+        let (ended, started): (Vec<_>, Vec<_>) = events
+            .into_iter()
+            .partition(|e| e.event_type == "GAME_ENDED");
 
-                if max_retry + 1 >= MAX_RETRIES {
-                    tracing::warn!(
-                        channel_id = %channel_id,
-                        retry_count = max_retry + 1,
-                        "Permanently failing game started notification for game {} after {} retries: {}",
-                        game_id, max_retry + 1, error_msg
-                    );
-                    self.repository
-                        .mark_notifications_failed(&event_ids, &error_msg)
-                        .await?;
-                } else {
-                    tracing::warn!(
-                        channel_id = %channel_id,
-                        retry_count = max_retry + 1,
-                        "Retryable failure for game started notification for game {}: {}",
-                        game_id, error_msg
-                    );
-                    self.repository
-                        .increment_notification_retry_count(&event_ids, &error_msg)
-                        .await?;
-                }
+        if !started.is_empty() {
+            let started_ids: Vec<_> = started.iter().map(|e| e.id).collect();
+            self.repository
+                .mark_notifications_processed(&started_ids)
+                .await?;
+        }
+        if ended.is_empty() {
+            return Ok(());
+        }
 
-                return Err(e.into());
+        let mut matches: HashMap<&str, Vec<NotificationEvent>> = HashMap::new();
+        for event in &ended {
+            if let Some(match_id) = &event.match_id {
+                matches.entry(match_id).or_default().push(event.clone());
             }
         }
+        for (match_id, events) in matches {
+            self.group_analyses(match_id, &events).await;
+        }
 
-        Ok(())
+        self.deliver(
+            NotificationKind::QuietHoursSummary,
+            ended,
+            sinks,
+            locale,
+            "quiet hours summary",
+        )
+        .await
     }
 
-    async fn send_grouped_game_ended(
+    /// Sends one group to every sink. Only the first sink's outcome decides
+    /// whether the events are processed or retried; the others are mirrors
+    /// tried once it succeeded, so a flaky mirror never makes the channel
+    /// post twice.
+    async fn deliver(
         &self,
-        match_id: &str,
+        kind: NotificationKind,
         events: Vec<NotificationEvent>,
-        channel_id: ChannelId,
+        sinks: &[Box<dyn NotificationSink>],
+        locale: Locale,
+        label: &str,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let Some((primary, mirrors)) = sinks.split_first() else {
+            return Ok(());
+        };
+
         let summoner_ids: Vec<_> = events.iter().map(|e| e.summoner_id).collect();
         let event_ids: Vec<_> = events.iter().map(|e| e.id).collect();
+        let notification = Notification {
+            kind,
+            summoners: self.fetch_summoners(&summoner_ids).await?,
+            events,
+            locale,
+        };
 
-        let summoners = self.fetch_summoners(&summoner_ids).await?;
-
-        let game_mode = events
-            .first()
-            .map(|e| e.game_mode.as_str())
-            .unwrap_or("UNKNOWN");
-        let embed = format_grouped_game_ended(&summoners, &events, game_mode);
-        let builder = CreateMessage::new().embed(embed);
-
-        match channel_id.send_message(&self.ctx.http, builder).await {
-            Ok(_) => {
+        match primary.send(&notification).await {
+            Ok(()) => {
                 self.repository
                     .mark_notifications_processed(&event_ids)
                     .await?;
 
                 tracing::info!(
-                    "Sent grouped game ended notification for match {} with {} players",
-                    match_id,
-                    events.len()
+                    sink = primary.name(),
+                    "Sent {} with {} players",
+                    label,
+                    notification.events.len()
                 );
             }
             Err(e) => {
-                let max_retry = events.iter().map(|ev| ev.retry_count).max().unwrap_or(0);
+                let max_retry = notification
+                    .events
+                    .iter()
+                    .map(|ev| ev.retry_count)
+                    .max()
+                    .unwrap_or(0);
                 let error_msg = e.to_string();
+                let failure = classify(&e);
 
-                if max_retry + 1 >= MAX_RETRIES {
+                if failure == FailureKind::Permanent || max_retry + 1 >= MAX_RETRIES {
                     tracing::warn!(
-                        channel_id = %channel_id,
+                        sink = primary.name(),
                         retry_count = max_retry + 1,
-                        "Permanently failing game ended notification for match {} after {} retries: {}",
-                        match_id, max_retry + 1, error_msg
+                        ?failure,
+                        "Permanently failing {} after {} attempts: {}",
+                        label,
+                        max_retry + 1,
+                        error_msg
                     );
                     self.repository
                         .mark_notifications_failed(&event_ids, &error_msg)
                         .await?;
                 } else {
+                    let delay = backoff_delay(max_retry, random_jitter());
                     tracing::warn!(
-                        channel_id = %channel_id,
+                        sink = primary.name(),
                         retry_count = max_retry + 1,
-                        "Retryable failure for game ended notification for match {}: {}",
-                        match_id, error_msg
+                        retry_in_secs = delay.num_seconds(),
+                        "Retryable failure for {}: {}",
+                        label,
+                        error_msg
                     );
                     self.repository
-                        .increment_notification_retry_count(&event_ids, &error_msg)
+                        .increment_notification_retry_count(
+                            &event_ids,
+                            &error_msg,
+                            chrono::Utc::now() + delay,
+                        )
                         .await?;
                 }
 
@@ -234,6 +361,12 @@ impl NotificationProcessor {
             }
         }
 
+        for mirror in mirrors {
+            if let Err(e) = mirror.send(&notification).await {
+                tracing::warn!(sink = mirror.name(), "Failed to mirror {}: {}", label, e);
+            }
+        }
+
         Ok(())
     }
 
@@ -249,13 +382,215 @@ impl NotificationProcessor {
         Ok(summoners)
     }
 
-    async fn get_notification_channel(&self) -> Option<ChannelId> {
-        match self.repository.get_all_bot_configs().await {
-            Ok(configs) => configs.first().map(|c| ChannelId::new(c.channel_id as u64)),
+    async fn get_notification_target(&self) -> Option<NotificationTarget> {
+        let config = match self.repository.get_all_bot_configs().await {
+            Ok(configs) => configs.into_iter().next()?,
             Err(e) => {
                 tracing::error!("Failed to fetch bot config: {}", e);
+                return None;
+            }
+        };
+
+        // Without its settings the hold is skipped rather than the whole
+        // batch: a late ping beats a lost one.
+        let quiet_window = match self.repository.get_quiet_hours(config.guild_id).await {
+            Ok(settings) => settings.as_ref().and_then(QuietWindow::from_settings),
+            Err(e) => {
+                tracing::error!("Failed to fetch quiet hours: {}", e);
                 None
             }
+        };
+
+        // Same for the sink settings: fall back to the Discord channel.
+        let sink_configs = self
+            .repository
+            .get_notification_sinks(config.guild_id)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!("Failed to fetch notification sinks: {}", e);
+                Vec::new()
+            });
+        let sinks: Vec<Box<dyn NotificationSink>> = enabled_sinks(&sink_configs)
+            .into_iter()
+            .map(|(kind, url)| -> Box<dyn NotificationSink> {
+                match kind {
+                    SinkKind::Discord => Box::new(DiscordSink::new(
+                        self.http.clone(),
+                        ChannelId::new(config.channel_id as u64),
+                    )),
+                    SinkKind::Webhook => Box::new(WebhookSink::new(
+                        self.http_client.clone(),
+                        url.unwrap_or_default(),
+                    )),
+                    SinkKind::Stdout => Box::new(StdoutSink),
+                }
+            })
+            .collect();
+        if sinks.is_empty() {
+            return None;
         }
+
+        Some(NotificationTarget {
+            sinks,
+            locale: config.locale(),
+            quiet_window,
+        })
+    }
+}
+
+/// Time until the earliest of `deadlines`, never longer than the safety
+/// sweep.
+fn wake_delay(
+    now: DateTime<Utc>,
+    deadlines: impl IntoIterator<Item = Option<DateTime<Utc>>>,
+) -> Duration {
+    deadlines
+        .into_iter()
+        .flatten()
+        .map(|at| (at - now).to_std().unwrap_or(Duration::ZERO))
+        .fold(SAFETY_SWEEP, Duration::min)
+}
+
+/// Summoners of each team with two tracked players or more, from
+/// `(summoner, won)` pairs: teammates share the outcome of the game.
+fn teammate_groups(players: &[(uuid::Uuid, Option<bool>)]) -> Vec<Vec<uuid::Uuid>> {
+    [true, false]
+        .into_iter()
+        .map(|won| {
+            players
+                .iter()
+                .filter(|(_, win)| *win == Some(won))
+                .map(|(summoner_id, _)| *summoner_id)
+                .collect::<Vec<_>>()
+        })
+        .filter(|team| team.len() >= 2)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sleeps_until_the_earliest_deadline() {
+        let now = Utc::now();
+        let in_ten = now + chrono::Duration::seconds(10);
+        let in_twenty = now + chrono::Duration::seconds(20);
+
+        assert_eq!(
+            wake_delay(now, [Some(in_twenty), Some(in_ten)]),
+            Duration::from_secs(10)
+        );
+        assert_eq!(
+            wake_delay(now, [Some(now - chrono::Duration::seconds(5))]),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_safety_sweep() {
+        let now = Utc::now();
+        assert_eq!(wake_delay(now, [None, None]), SAFETY_SWEEP);
+        assert_eq!(
+            wake_delay(now, [Some(now + chrono::Duration::hours(1))]),
+            SAFETY_SWEEP
+        );
+    }
+
+    #[test]
+    fn groups_tracked_teammates_by_outcome() {
+        let ids: Vec<uuid::Uuid> = (0..4).map(|_| uuid::Uuid::new_v4()).collect();
+        let players = [
+            (ids[0], Some(true)),
+            (ids[1], Some(false)),
+            (ids[2], Some(true)),
+            (ids[3], None),
+        ];
+        assert_eq!(teammate_groups(&players), vec![vec![ids[0], ids[2]]]);
+        assert!(teammate_groups(&players[..2]).is_empty());
+    }
+
+    #[cfg(feature = "test-mocks")]
+    fn ended(summoner_id: uuid::Uuid, win: bool) -> NotificationEvent {
+        NotificationEvent {
+            id: uuid::Uuid::new_v4(),
+            summoner_id,
+            event_type: "GAME_ENDED".to_string(),
+            game_id: 1,
+            match_id: Some("EUW1_1".to_string()),
+            champion_id: 103,
+            champion_name: "Ahri".to_string(),
+            role: None,
+            win: Some(win),
+            kills: Some(1),
+            deaths: Some(1),
+            assists: Some(1),
+            game_duration_secs: Some(1800),
+            game_mode: "CLASSIC".to_string(),
+            queue_id: Some(420),
+            is_featured_mode: false,
+            total_cs: None,
+            total_gold: None,
+            total_damage: None,
+            enemy_champion_name: None,
+            enemy_cs: None,
+            enemy_gold: None,
+            enemy_damage: None,
+            role_gaps: None,
+            processed: false,
+            created_at: Utc::now(),
+            processed_at: None,
+            retry_count: 0,
+            error_message: None,
+            failed_at: None,
+            next_attempt_at: Utc::now(),
+        }
+    }
+
+    #[cfg(feature = "test-mocks")]
+    #[tokio::test]
+    async fn quiet_hours_summary_groups_held_teammates_before_marking_them_processed() {
+        use crate::db::repository::MockRepository;
+        use crate::notification::sinks::StdoutSink;
+
+        let ids = [
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+        ];
+        let mut seq = mockall::Sequence::new();
+        let mut repository = MockRepository::new();
+        repository
+            .expect_group_analysis_jobs()
+            .withf(move |match_id, team| match_id == "EUW1_1" && team == [ids[0], ids[1]])
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Ok(Some(uuid::Uuid::new_v4())));
+        repository
+            .expect_get_all_summoners()
+            .returning(|| Ok(Vec::new()));
+        repository
+            .expect_mark_notifications_processed()
+            .withf(|event_ids| event_ids.len() == 3)
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok(()));
+
+        let processor = NotificationProcessor::new(
+            Arc::new(repository),
+            Arc::new(Http::new("")),
+            Arc::new(Notify::new()),
+        );
+        let events = vec![
+            ended(ids[0], true),
+            ended(ids[1], true),
+            ended(ids[2], false),
+        ];
+        let sinks: Vec<Box<dyn NotificationSink>> = vec![Box::new(StdoutSink)];
+
+        processor
+            .send_quiet_hours_summary(events, &sinks, Locale::Fr)
+            .await
+            .unwrap();
     }
 }
@@ -0,0 +1,269 @@
+//! Per-guild quiet hours: which queued notifications go out now, which wait
+//! in `notification_queue`, and which are folded into the summary posted
+//! once the window closes. Post-game analyses are held too: the analysis
+//! worker postpones their jobs until the window closes.
+
+use crate::db::models::{NotificationEvent, QuietHours};
+use crate::digest::schedule::parse_timezone;
+use chrono::{DateTime, Days, NaiveTime, Utc};
+use chrono_tz::Tz;
+
+/// Event types that can be held (and therefore listed as bypassing the
+/// hold). `ANALYSIS` stands for the analysis worker's posts.
+pub const HOLDABLE_EVENT_TYPES: &[&str] = &["GAME_STARTED", "GAME_ENDED", ANALYSIS_EVENT_TYPE];
+
+/// Bypass entry of the post-game analyses.
+pub const ANALYSIS_EVENT_TYPE: &str = "ANALYSIS";
+
+/// Window used by `/quiet-hours` when no bound was ever given.
+pub const DEFAULT_START: &str = "23:00";
+pub const DEFAULT_END: &str = "08:00";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    /// Post it as usual.
+    Send,
+    /// Quiet hours are on: leave it queued.
+    Hold,
+    /// Queued during quiet hours that are now over: post it in the summary.
+    Summarize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuietWindow {
+    start: NaiveTime,
+    end: NaiveTime,
+    tz: Tz,
+    bypass_event_types: Vec<String>,
+}
+
+impl QuietWindow {
+    pub fn new(start: NaiveTime, end: NaiveTime, tz: Tz, bypass_event_types: Vec<String>) -> Self {
+        Self {
+            start,
+            end,
+            tz,
+            bypass_event_types,
+        }
+    }
+
+    /// `None` when the guild disabled quiet hours or stored an unknown time
+    /// zone (logged, notifications then flow as usual).
+    pub fn from_settings(settings: &QuietHours) -> Option<Self> {
+        if !settings.enabled {
+            return None;
+        }
+        let Some(tz) = parse_timezone(&settings.timezone) else {
+            tracing::warn!(
+                guild_id = settings.guild_id,
+                timezone = settings.timezone.as_str(),
+                "Invalid quiet hours time zone"
+            );
+            return None;
+        };
+        Some(Self::new(
+            settings.start_time,
+            settings.end_time,
+            tz,
+            settings.bypass_event_types.clone(),
+        ))
+    }
+
+    /// Whether `at` falls in the window, which wraps past midnight when it
+    /// starts after it ends (22:00–08:00).
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        let local = at.with_timezone(&self.tz).time();
+        if self.start <= self.end {
+            self.start <= local && local < self.end
+        } else {
+            local >= self.start || local < self.end
+        }
+    }
+
+    /// First end of the window after `at`.
+    pub fn next_end(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let local = at.with_timezone(&self.tz);
+        [local.date_naive(), local.date_naive() + Days::new(1)]
+            .into_iter()
+            .filter_map(|day| {
+                day.and_time(self.end)
+                    .and_local_timezone(self.tz)
+                    .earliest()
+            })
+            .map(|end| end.with_timezone(&Utc))
+            .find(|end| *end > at)
+            // The end time does not exist on both days (DST gap): retry
+            // an hour later.
+            .unwrap_or_else(|| at + chrono::Duration::hours(1))
+    }
+
+    pub fn bypasses(&self, event_type: &str) -> bool {
+        self.bypass_event_types.iter().any(|t| t == event_type)
+    }
+
+    pub fn delivery(&self, event: &NotificationEvent, now: DateTime<Utc>) -> Delivery {
+        if self.bypasses(&event.event_type) {
+            Delivery::Send
+        } else if self.contains(now) {
+            Delivery::Hold
+        } else if self.contains(event.created_at) {
+            Delivery::Summarize
+        } else {
+            Delivery::Send
+        }
+    }
+}
+
+/// Parses a `HH:MM` local time.
+pub fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+/// Parses a comma-separated list of event types (`game_ended`,
+/// `GAME-STARTED`, ...). `none` or an empty string clears the list; the
+/// first unknown entry is returned as the error.
+pub fn parse_bypass_list(value: &str) -> Result<Vec<String>, String> {
+    let value = value.trim();
+    if value.is_empty() || value.eq_ignore_ascii_case("none") {
+        return Ok(Vec::new());
+    }
+
+    let mut event_types = Vec::new();
+    for item in value.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+        let normalized = item.to_ascii_uppercase().replace('-', "_");
+        if !HOLDABLE_EVENT_TYPES.contains(&normalized.as_str()) {
+            return Err(item.to_string());
+        }
+        if !event_types.contains(&normalized) {
+            event_types.push(normalized);
+        }
+    }
+    Ok(event_types)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn paris(h: u32, m: u32) -> DateTime<Utc> {
+        chrono_tz::Europe::Paris
+            .with_ymd_and_hms(2026, 10, 19, h, m, 0)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn night(bypass: &[&str]) -> QuietWindow {
+        QuietWindow::new(
+            time(23, 0),
+            time(8, 0),
+            chrono_tz::Europe::Paris,
+            bypass.iter().map(|s| s.to_string()).collect(),
+        )
+    }
+
+    fn event(event_type: &str, created_at: DateTime<Utc>) -> NotificationEvent {
+        NotificationEvent {
+            id: Uuid::new_v4(),
+            summoner_id: Uuid::new_v4(),
+            event_type: event_type.to_string(),
+            game_id: 1,
+            match_id: Some("EUW1_1".to_string()),
+            champion_id: 103,
+            champion_name: "Ahri".to_string(),
+            role: None,
+            win: Some(true),
+            kills: Some(1),
+            deaths: Some(1),
+            assists: Some(1),
+            game_duration_secs: Some(1800),
+            game_mode: "CLASSIC".to_string(),
+            queue_id: Some(420),
+            is_featured_mode: false,
+            total_cs: None,
+            total_gold: None,
+            total_damage: None,
+            enemy_champion_name: None,
+            enemy_cs: None,
+            enemy_gold: None,
+            enemy_damage: None,
+            role_gaps: None,
+            processed: false,
+            created_at,
+            processed_at: None,
+            retry_count: 0,
+            error_message: None,
+            failed_at: None,
+            next_attempt_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn window_wraps_past_midnight() {
+        let window = night(&[]);
+        assert!(window.contains(paris(23, 30)));
+        assert!(window.contains(paris(3, 0)));
+        assert!(!window.contains(paris(8, 0)));
+        assert!(!window.contains(paris(22, 59)));
+
+        let lunch = QuietWindow::new(time(12, 0), time(14, 0), chrono_tz::Europe::Paris, vec![]);
+        assert!(lunch.contains(paris(13, 0)));
+        assert!(!lunch.contains(paris(3, 0)));
+    }
+
+    #[test]
+    fn events_are_held_then_summarized() {
+        let window = night(&[]);
+        let late_game = event("GAME_ENDED", paris(3, 0));
+
+        assert_eq!(window.delivery(&late_game, paris(3, 1)), Delivery::Hold);
+        assert_eq!(
+            window.delivery(&late_game, paris(8, 0)),
+            Delivery::Summarize
+        );
+
+        let day_game = event("GAME_ENDED", paris(15, 0));
+        assert_eq!(window.delivery(&day_game, paris(15, 1)), Delivery::Send);
+    }
+
+    #[test]
+    fn bypassed_event_types_are_always_sent() {
+        let window = night(&["GAME_ENDED"]);
+        assert_eq!(
+            window.delivery(&event("GAME_ENDED", paris(3, 0)), paris(3, 1)),
+            Delivery::Send
+        );
+        assert_eq!(
+            window.delivery(&event("GAME_STARTED", paris(3, 0)), paris(3, 1)),
+            Delivery::Hold
+        );
+    }
+
+    #[test]
+    fn next_end_is_the_coming_close_of_the_window() {
+        let window = night(&[]);
+        assert_eq!(window.next_end(paris(23, 30)), paris(8, 0) + Days::new(1));
+        assert_eq!(window.next_end(paris(3, 0)), paris(8, 0));
+    }
+
+    #[test]
+    fn parses_times_and_bypass_lists() {
+        assert_eq!(parse_time("23:30"), Some(time(23, 30)));
+        assert_eq!(parse_time("25:00"), None);
+        assert_eq!(
+            parse_bypass_list("game_ended, GAME-ENDED"),
+            Ok(vec!["GAME_ENDED".to_string()])
+        );
+        assert_eq!(parse_bypass_list("none"), Ok(vec![]));
+        assert_eq!(
+            parse_bypass_list("analysis"),
+            Ok(vec![ANALYSIS_EVENT_TYPE.to_string()])
+        );
+        assert_eq!(parse_bypass_list("rank_up"), Err("rank_up".to_string()));
+    }
+}
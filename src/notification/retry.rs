@@ -0,0 +1,116 @@
+//! Retry policy for notifications that failed to send: permanent failures
+//! are dead-lettered at once, transient ones are rescheduled with an
+//! exponential backoff so a short outage does not burn every attempt.
+
+use crate::notification::sinks::SinkError;
+use chrono::Duration;
+use serenity::http::HttpError;
+
+/// Attempts before a transiently failing notification is dead-lettered.
+/// With the backoff below the last one happens ~15 minutes after the first.
+pub const MAX_RETRIES: i32 = 6;
+
+const BASE_DELAY_SECS: f64 = 30.0;
+const MAX_DELAY_SECS: f64 = 30.0 * 60.0;
+/// Spread of the random factor applied to each delay (±20%), so events that
+/// failed together do not all retry in the same tick.
+const JITTER_RATIO: f64 = 0.2;
+
+/// Discord JSON error codes that no retry can fix.
+/// <https://discord.com/developers/docs/topics/opcodes-and-status-codes#json>
+const PERMANENT_DISCORD_CODES: &[isize] = &[
+    10003, // Unknown Channel
+    50001, // Missing Access
+    50013, // Missing Permissions
+    50035, // Invalid Form Body
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    /// Will fail the same way next time: dead-letter now.
+    Permanent,
+    /// Network trouble, rate limit or server error: back off and retry.
+    Transient,
+}
+
+pub fn classify(error: &SinkError) -> FailureKind {
+    match error {
+        SinkError::Discord(serenity::Error::Http(HttpError::UnsuccessfulRequest(response))) => {
+            classify_status(response.status_code.as_u16(), Some(response.error.code))
+        }
+        SinkError::Discord(serenity::Error::Http(HttpError::Request(_))) => FailureKind::Transient,
+        // Malformed URL, header or payload on our side.
+        SinkError::Discord(serenity::Error::Http(_) | serenity::Error::Model(_)) => {
+            FailureKind::Permanent
+        }
+        SinkError::Status(status) => classify_status(status.as_u16(), None),
+        SinkError::Discord(_) | SinkError::Http(_) | SinkError::Io(_) => FailureKind::Transient,
+    }
+}
+
+/// HTTP status (plus Discord JSON error code, when there is one) of a
+/// rejected request: 408, 429 and 5xx are worth retrying, other 4xx are
+/// not.
+pub fn classify_status(status: u16, discord_code: Option<isize>) -> FailureKind {
+    if discord_code.is_some_and(|code| PERMANENT_DISCORD_CODES.contains(&code)) {
+        return FailureKind::Permanent;
+    }
+    match status {
+        408 | 429 => FailureKind::Transient,
+        400..=499 => FailureKind::Permanent,
+        _ => FailureKind::Transient,
+    }
+}
+
+/// Delay before the next attempt of a notification that already failed
+/// `retry_count` times: 30s doubled each time, capped at 30 minutes, scaled
+/// by a jitter factor derived from `jitter` in `[0, 1)`.
+pub fn backoff_delay(retry_count: i32, jitter: f64) -> Duration {
+    let exponent = retry_count.clamp(0, 16);
+    let delay = (BASE_DELAY_SECS * 2f64.powi(exponent)).min(MAX_DELAY_SECS);
+    let factor = 1.0 - JITTER_RATIO + 2.0 * JITTER_RATIO * jitter.clamp(0.0, 1.0);
+    Duration::milliseconds((delay * factor * 1000.0) as i64)
+}
+
+/// A uniformly random jitter input for [`backoff_delay`].
+pub fn random_jitter() -> f64 {
+    (uuid::Uuid::new_v4().as_u128() >> 75) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_doubles_and_caps() {
+        assert_eq!(backoff_delay(0, 0.5), Duration::seconds(30));
+        assert_eq!(backoff_delay(1, 0.5), Duration::seconds(60));
+        assert_eq!(backoff_delay(3, 0.5), Duration::seconds(240));
+        assert_eq!(backoff_delay(10, 0.5), Duration::minutes(30));
+    }
+
+    #[test]
+    fn jitter_stays_within_twenty_percent() {
+        assert_eq!(backoff_delay(0, 0.0), Duration::seconds(24));
+        assert_eq!(backoff_delay(0, 1.0), Duration::seconds(36));
+        for _ in 0..100 {
+            let jitter = random_jitter();
+            assert!((0.0..1.0).contains(&jitter), "{jitter}");
+        }
+    }
+
+    #[test]
+    fn access_errors_fail_fast() {
+        assert_eq!(classify_status(403, Some(50001)), FailureKind::Permanent);
+        assert_eq!(classify_status(404, Some(10003)), FailureKind::Permanent);
+        assert_eq!(classify_status(404, None), FailureKind::Permanent);
+    }
+
+    #[test]
+    fn outages_and_rate_limits_back_off() {
+        assert_eq!(classify_status(429, Some(0)), FailureKind::Transient);
+        assert_eq!(classify_status(502, None), FailureKind::Transient);
+        assert_eq!(classify_status(503, Some(0)), FailureKind::Transient);
+        assert_eq!(classify_status(408, None), FailureKind::Transient);
+    }
+}
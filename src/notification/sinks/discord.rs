@@ -0,0 +1,72 @@
+use crate::discord::components::recap_components;
+use crate::notification::messages::{
+    format_grouped_game_ended, format_grouped_game_started, format_quiet_hours_summary,
+};
+use crate::notification::sinks::{Notification, NotificationKind, NotificationSink, SinkError};
+use async_trait::async_trait;
+use serenity::builder::CreateMessage;
+use serenity::http::Http;
+use serenity::model::id::ChannelId;
+use std::sync::Arc;
+
+/// Posts embeds (with the recap buttons) to the guild's notification
+/// channel.
+pub struct DiscordSink {
+    http: Arc<Http>,
+    channel_id: ChannelId,
+}
+
+impl DiscordSink {
+    pub fn new(http: Arc<Http>, channel_id: ChannelId) -> Self {
+        Self { http, channel_id }
+    }
+
+    fn render(notification: &Notification) -> CreateMessage {
+        let locale = notification.locale;
+        match notification.kind {
+            NotificationKind::GameStarted => {
+                let champions: Vec<_> = notification
+                    .events
+                    .iter()
+                    .map(|e| (e.summoner_id, e.champion_name.clone(), String::new()))
+                    .collect();
+                CreateMessage::new().embed(format_grouped_game_started(
+                    &notification.summoners,
+                    &champions,
+                    notification.game_mode(),
+                    notification.queue_id(),
+                    locale,
+                ))
+            }
+            NotificationKind::GameEnded => CreateMessage::new()
+                .embed(format_grouped_game_ended(
+                    &notification.summoners,
+                    &notification.events,
+                    notification.game_mode(),
+                    locale,
+                ))
+                .components(recap_components(
+                    &notification.summoners,
+                    &notification.events,
+                    locale,
+                )),
+            NotificationKind::QuietHoursSummary => CreateMessage::new().embed(
+                format_quiet_hours_summary(&notification.summoners, &notification.events, locale),
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationSink for DiscordSink {
+    fn name(&self) -> &'static str {
+        "discord"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), SinkError> {
+        self.channel_id
+            .send_message(&self.http, Self::render(notification))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+//! Destinations a grouped notification is delivered to. Each sink renders
+//! the same [`Notification`] its own way: an embed for Discord, a JSON body
+//! for the website webhook, a log line for headless runs.
+
+pub mod discord;
+pub mod stdout;
+pub mod webhook;
+
+use crate::db::models::{NotificationEvent, NotificationSinkConfig, Summoner};
+use crate::i18n::Locale;
+use async_trait::async_trait;
+use thiserror::Error;
+
+pub use discord::DiscordSink;
+pub use stdout::StdoutSink;
+pub use webhook::WebhookSink;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    GameStarted,
+    GameEnded,
+    /// Games that finished during quiet hours, posted once they end.
+    QuietHoursSummary,
+}
+
+impl NotificationKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NotificationKind::GameStarted => "game_started",
+            NotificationKind::GameEnded => "game_ended",
+            NotificationKind::QuietHoursSummary => "quiet_hours_summary",
+        }
+    }
+}
+
+/// One group of queued events, posted as a single message.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    /// Tracked summoners appearing in `events`.
+    pub summoners: Vec<Summoner>,
+    pub events: Vec<NotificationEvent>,
+    pub locale: Locale,
+}
+
+impl Notification {
+    pub fn game_mode(&self) -> &str {
+        self.events
+            .first()
+            .map(|e| e.game_mode.as_str())
+            .unwrap_or("UNKNOWN")
+    }
+
+    pub fn queue_id(&self) -> Option<i32> {
+        self.events.first().and_then(|e| e.queue_id)
+    }
+
+    /// `Name#Tag` of the summoner behind an event, when still tracked.
+    pub fn summoner_name(&self, event: &NotificationEvent) -> Option<String> {
+        self.summoners
+            .iter()
+            .find(|s| s.id == event.summoner_id)
+            .map(|s| format!("{}#{}", s.game_name, s.tag_line))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SinkError {
+    #[error("Discord error: {0}")]
+    Discord(#[from] serenity::Error),
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Webhook answered {0}")]
+    Status(reqwest::StatusCode),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[async_trait]
+pub trait NotificationSink: Send + Sync {
+    /// Short name used in logs.
+    fn name(&self) -> &'static str;
+
+    async fn send(&self, notification: &Notification) -> Result<(), SinkError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SinkKind {
+    Discord,
+    Webhook,
+    Stdout,
+}
+
+impl SinkKind {
+    /// Delivery order: the first enabled sink is the one whose failures
+    /// are retried.
+    pub const ALL: [SinkKind; 3] = [SinkKind::Discord, SinkKind::Webhook, SinkKind::Stdout];
+
+    /// Value stored in `notification_sinks.kind`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SinkKind::Discord => "discord",
+            SinkKind::Webhook => "webhook",
+            SinkKind::Stdout => "stdout",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SinkKind::Discord => "Discord",
+            SinkKind::Webhook => "Webhook JSON",
+            SinkKind::Stdout => "Console (stdout)",
+        }
+    }
+}
+
+/// Sink kinds a guild delivers to, in [`SinkKind::ALL`] order. Discord is
+/// on unless a row disables it; the others need an enabled row (and the
+/// webhook a URL).
+pub fn enabled_sinks(configs: &[NotificationSinkConfig]) -> Vec<(SinkKind, Option<String>)> {
+    SinkKind::ALL
+        .into_iter()
+        .filter_map(|kind| {
+            let config = configs.iter().find(|c| c.kind == kind.as_str());
+            match (kind, config) {
+                (SinkKind::Discord, None) => Some((kind, None)),
+                (_, Some(config)) if config.enabled => match kind {
+                    SinkKind::Webhook => Some((kind, Some(config.url.clone()?))),
+                    _ => Some((kind, None)),
+                },
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn config(kind: &str, url: Option<&str>, enabled: bool) -> NotificationSinkConfig {
+        NotificationSinkConfig {
+            id: Uuid::new_v4(),
+            guild_id: 1,
+            kind: kind.to_string(),
+            url: url.map(str::to_string),
+            enabled,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn discord_is_the_default_sink() {
+        assert_eq!(enabled_sinks(&[]), vec![(SinkKind::Discord, None)]);
+    }
+
+    #[test]
+    fn mirrors_follow_discord_and_can_replace_it() {
+        let url = "https://example.test/hook";
+        let configs = [
+            config("stdout", None, true),
+            config("webhook", Some(url), true),
+        ];
+        assert_eq!(
+            enabled_sinks(&configs),
+            vec![
+                (SinkKind::Discord, None),
+                (SinkKind::Webhook, Some(url.to_string())),
+                (SinkKind::Stdout, None),
+            ]
+        );
+
+        let headless = [config("discord", None, false), config("stdout", None, true)];
+        assert_eq!(enabled_sinks(&headless), vec![(SinkKind::Stdout, None)]);
+    }
+
+    #[test]
+    fn kinds_round_trip() {
+        for kind in SinkKind::ALL {
+            assert_eq!(SinkKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(SinkKind::parse("email"), None);
+    }
+}
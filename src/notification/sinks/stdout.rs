@@ -0,0 +1,55 @@
+use crate::notification::messages::get_queue_type_name;
+use crate::notification::sinks::{Notification, NotificationSink, SinkError};
+use async_trait::async_trait;
+use std::io::Write;
+
+/// Prints one line per notification, for runs without a Discord channel.
+pub struct StdoutSink;
+
+impl StdoutSink {
+    pub fn render(notification: &Notification) -> String {
+        let unknown = notification.locale.catalog().unknown;
+        let players: Vec<String> = notification
+            .events
+            .iter()
+            .map(|event| {
+                let name = notification
+                    .summoner_name(event)
+                    .unwrap_or_else(|| unknown.to_string());
+                match event.win {
+                    Some(win) => format!(
+                        "{} {} {} {}/{}/{}",
+                        name,
+                        event.champion_name,
+                        if win { "W" } else { "L" },
+                        event.kills.unwrap_or(0),
+                        event.deaths.unwrap_or(0),
+                        event.assists.unwrap_or(0)
+                    ),
+                    None => format!("{} {}", name, event.champion_name),
+                }
+            })
+            .collect();
+
+        format!(
+            "[{}] {} · {} · {}",
+            notification.kind.as_str(),
+            notification.game_mode(),
+            get_queue_type_name(notification.queue_id(), notification.locale),
+            players.join(" | ")
+        )
+    }
+}
+
+#[async_trait]
+impl NotificationSink for StdoutSink {
+    fn name(&self) -> &'static str {
+        "stdout"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), SinkError> {
+        let line = Self::render(notification);
+        writeln!(std::io::stdout().lock(), "{line}")?;
+        Ok(())
+    }
+}
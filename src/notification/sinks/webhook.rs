@@ -0,0 +1,96 @@
+use crate::notification::messages::get_queue_type_name;
+use crate::notification::sinks::{Notification, NotificationSink, SinkError};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::time::Duration;
+
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+/// POSTs each notification as JSON to a fixed URL (the website's recap
+/// feed). Any non-2xx answer is a failure.
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookPayload<'a> {
+    pub kind: &'static str,
+    pub locale: &'static str,
+    pub players: Vec<WebhookPlayer<'a>>,
+}
+
+/// One tracked summoner's line of a notification.
+#[derive(Debug, Serialize)]
+pub struct WebhookPlayer<'a> {
+    /// `Name#Tag`, `None` if the summoner was untracked meanwhile.
+    pub summoner: Option<String>,
+    pub game_id: i64,
+    pub match_id: Option<&'a str>,
+    pub champion: &'a str,
+    pub role: Option<&'a str>,
+    pub game_mode: &'a str,
+    pub queue_id: Option<i32>,
+    pub queue: String,
+    pub win: Option<bool>,
+    pub kills: Option<i32>,
+    pub deaths: Option<i32>,
+    pub assists: Option<i32>,
+    pub game_duration_secs: Option<i32>,
+    pub at: DateTime<Utc>,
+}
+
+impl WebhookSink {
+    pub fn new(client: reqwest::Client, url: String) -> Self {
+        Self { client, url }
+    }
+
+    pub fn render(notification: &Notification) -> WebhookPayload<'_> {
+        WebhookPayload {
+            kind: notification.kind.as_str(),
+            locale: notification.locale.code(),
+            players: notification
+                .events
+                .iter()
+                .map(|event| WebhookPlayer {
+                    summoner: notification.summoner_name(event),
+                    game_id: event.game_id,
+                    match_id: event.match_id.as_deref(),
+                    champion: &event.champion_name,
+                    role: event.role.as_deref(),
+                    game_mode: &event.game_mode,
+                    queue_id: event.queue_id,
+                    queue: get_queue_type_name(event.queue_id, notification.locale),
+                    win: event.win,
+                    kills: event.kills,
+                    deaths: event.deaths,
+                    assists: event.assists,
+                    game_duration_secs: event.game_duration_secs,
+                    at: event.created_at,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationSink for WebhookSink {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), SinkError> {
+        let response = self
+            .client
+            .post(&self.url)
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .json(&Self::render(notification))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(SinkError::Status(response.status()));
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,16 @@
-use crate::analysis::models::AnalysisData;
-use crate::riot::models::{ActiveGameInfo, MatchResult, SummonerInfo};
+use crate::analysis::models::{AnalysisData, JungleStats};
+use crate::analysis::roles::TierBand;
+use crate::riot::deaths::{self, Combatant, fight_events, player_deaths};
+use crate::riot::jungle::{self, jungle_frames};
+use crate::riot::models::{
+    ActiveGameInfo, ChampionInfo, LaneDiffs, MatchResult, MatchScoreboard, RankedEntry,
+    ScoreboardPlayer, SummonerInfo,
+};
+use crate::riot::objectives::{self, objective_kills, player_objectives, position_frames};
 use async_trait::async_trait;
 use chrono::{TimeZone, Utc};
 use riven::RiotApi;
-use riven::consts::{PlatformRoute, RegionalRoute};
+use riven::consts::{PlatformRoute, QueueType, RegionalRoute};
 use serde::Deserialize;
 use thiserror::Error;
 
@@ -61,6 +68,13 @@ pub trait RiotApiClient: Send + Sync {
         region: RegionalRoute,
     ) -> Result<Option<AnalysisData>, RiotClientError>;
 
+    /// Full 10-player scoreboard of a finished match
+    async fn get_match_scoreboard(
+        &self,
+        match_id: &str,
+        region: RegionalRoute,
+    ) -> Result<Option<MatchScoreboard>, RiotClientError>;
+
     /// Get the most recent match ID for a summoner
     async fn get_recent_match_id(
         &self,
@@ -70,7 +84,14 @@ pub trait RiotApiClient: Send + Sync {
 
     async fn get_all_champions(
         &self,
-    ) -> Result<std::collections::HashMap<i32, String>, RiotClientError>;
+    ) -> Result<std::collections::HashMap<i32, ChampionInfo>, RiotClientError>;
+
+    /// Current Solo/Duo standing; `None` when the summoner is unranked.
+    async fn get_ranked_solo_entry(
+        &self,
+        puuid: &str,
+        platform: PlatformRoute,
+    ) -> Result<Option<RankedEntry>, RiotClientError>;
 }
 
 pub struct RiotClient {
@@ -323,11 +344,67 @@ impl RiotApiClient for RiotClient {
                 f.minions_killed + f.jungle_minions_killed
             });
 
+        let fights = timeline.as_ref().map(fight_events).unwrap_or_default();
+        let combatants: std::collections::HashMap<i32, Combatant> = match_data
+            .info
+            .participants
+            .iter()
+            .map(|p| {
+                (
+                    p.participant_id,
+                    Combatant {
+                        team: p.team_id.into(),
+                        champion: p.champion_name.clone(),
+                    },
+                )
+            })
+            .collect();
+        let death_events = player_deaths(
+            &fights,
+            participant.participant_id,
+            &combatants,
+            match_data.info.game_mode.to_string() == "CLASSIC",
+        );
+        let death_summary = timeline.as_ref().map(|_| deaths::summarize(&death_events));
+
+        let objective_events = timeline
+            .as_ref()
+            .map(|timeline| {
+                player_objectives(
+                    &objective_kills(timeline),
+                    &position_frames(timeline),
+                    participant.participant_id,
+                    participant.team_id.into(),
+                )
+            })
+            .unwrap_or_default();
+        let objective_summary = timeline
+            .as_ref()
+            .map(|_| objectives::summarize(&objective_events, &death_events));
+
         let challenges = participant.challenges.as_ref();
 
+        let jungle = timeline
+            .as_ref()
+            .filter(|_| participant.team_position == "JUNGLE")
+            .map(|timeline| JungleStats {
+                counter_jungle_monsters: challenges
+                    .and_then(|c| c.enemy_jungle_monster_kills)
+                    .map(|monsters| monsters.round() as i32),
+                scuttle_crabs: challenges.and_then(|c| c.scuttle_crab_kills),
+                first_scuttles: challenges.and_then(|c| c.initial_crab_count),
+                ..jungle::analyze(
+                    &jungle_frames(timeline, participant.participant_id),
+                    &fights,
+                    participant.participant_id,
+                )
+            });
+
         Ok(Some(AnalysisData {
             summoner_name: summoner_name.to_string(),
             champion_name: participant.champion_name.clone(),
+            champion_id: participant.champion().map(|c| c.0 as i32).unwrap_or(0),
+            champion_class: None,
             win: participant.win,
             kills: participant.kills,
             deaths: participant.deaths,
@@ -361,10 +438,62 @@ impl RiotApiClient for RiotClient {
             cs_diff_at_10,
             cs_diff_at_15,
             cs_diff_at_20,
+            death_events,
+            death_summary,
+            objective_events,
+            objective_summary,
+            jungle,
             game_duration_secs: match_data.info.game_duration as i32,
             role: participant.team_position.clone(),
             game_mode: match_data.info.game_mode.to_string(),
             recent_games: Vec::new(),
+            tier_band: TierBand::default(),
+        }))
+    }
+
+    async fn get_match_scoreboard(
+        &self,
+        match_id: &str,
+        region: RegionalRoute,
+    ) -> Result<Option<MatchScoreboard>, RiotClientError> {
+        let match_data = self.api.match_v5().get_match(region, match_id).await?;
+
+        Ok(match_data.map(|m| {
+            let players = m
+                .info
+                .participants
+                .iter()
+                .map(|p| {
+                    let display_name = match (&p.riot_id_game_name, &p.riot_id_tagline) {
+                        (Some(name), Some(tag)) if !name.is_empty() => format!("{name}#{tag}"),
+                        _ => p.summoner_name.clone(),
+                    };
+                    ScoreboardPlayer {
+                        puuid: p.puuid.clone(),
+                        display_name,
+                        participant_id: p.participant_id,
+                        team_id: <riven::consts::Team as Into<u16>>::into(p.team_id),
+                        position: p.team_position.clone(),
+                        champion_name: p.champion_name.clone(),
+                        win: p.win,
+                        kills: p.kills,
+                        deaths: p.deaths,
+                        assists: p.assists,
+                        total_cs: p.total_minions_killed + p.neutral_minions_killed,
+                        gold_earned: p.gold_earned,
+                        total_damage: p.total_damage_dealt_to_champions,
+                        vision_score: p.vision_score,
+                    }
+                })
+                .collect();
+
+            MatchScoreboard {
+                match_id: m.metadata.match_id,
+                game_mode: m.info.game_mode.to_string(),
+                queue_id: Some(m.info.queue_id.0 as i32),
+                game_duration_secs: m.info.game_duration as i32,
+                players,
+            }
         }))
     }
 
@@ -384,11 +513,13 @@ impl RiotApiClient for RiotClient {
 
     async fn get_all_champions(
         &self,
-    ) -> Result<std::collections::HashMap<i32, String>, RiotClientError> {
+    ) -> Result<std::collections::HashMap<i32, ChampionInfo>, RiotClientError> {
         #[derive(Deserialize)]
         struct ChampionData {
             key: String, // This is the champion_id as a string
             name: String,
+            #[serde(default)]
+            tags: Vec<String>,
         }
 
         #[derive(Deserialize)]
@@ -405,14 +536,45 @@ impl RiotApiClient for RiotClient {
         let champions: ChampionsResponse = response.json().await?;
 
         let mut result = std::collections::HashMap::new();
-        for champion_data in champions.data.values() {
+        for champion_data in champions.data.into_values() {
             if let Ok(champion_id) = champion_data.key.parse::<i32>() {
-                result.insert(champion_id, champion_data.name.clone());
+                result.insert(
+                    champion_id,
+                    ChampionInfo {
+                        name: champion_data.name,
+                        tags: champion_data.tags,
+                    },
+                );
             }
         }
 
         Ok(result)
     }
+
+    async fn get_ranked_solo_entry(
+        &self,
+        puuid: &str,
+        platform: PlatformRoute,
+    ) -> Result<Option<RankedEntry>, RiotClientError> {
+        let entries = self
+            .api
+            .league_v4()
+            .get_league_entries_by_puuid(platform, puuid)
+            .await?;
+
+        Ok(entries
+            .into_iter()
+            .find(|e| e.queue_type == QueueType::RANKED_SOLO_5x5)
+            .and_then(|e| {
+                let tier = e.tier.filter(|t| t.is_ranked())?;
+                Some(RankedEntry {
+                    queue_type: e.queue_type.to_string(),
+                    tier: tier.to_string(),
+                    division: e.rank.filter(|_| tier.is_standard()).map(|d| d.to_string()),
+                    league_points: e.league_points,
+                })
+            }))
+    }
 }
 
 /// Per-lane gold gap from the tracked summoner's perspective.
@@ -594,12 +756,38 @@ fn extract_timeline_diff(
     let participant_id = participant.participant_id;
     let enemy_id = enemy.participant_id;
 
+    diffs_at_checkpoints(&timeline.info.frames, participant_id, enemy_id, &metric)
+}
+
+/// Gold and CS differences between two participants (by timeline
+/// `participant_id`) at 10/15/20 minutes.
+pub fn lane_diffs(
+    timeline: &riven::models::match_v5::Timeline,
+    participant_id: i32,
+    enemy_id: i32,
+) -> LaneDiffs {
     let frames = &timeline.info.frames;
+    let (gold_10, gold_15, gold_20) =
+        diffs_at_checkpoints(frames, participant_id, enemy_id, &|f| f.total_gold);
+    let (cs_10, cs_15, cs_20) = diffs_at_checkpoints(frames, participant_id, enemy_id, &|f| {
+        f.minions_killed + f.jungle_minions_killed
+    });
+    LaneDiffs {
+        gold: [gold_10, gold_15, gold_20],
+        cs: [cs_10, cs_15, cs_20],
+    }
+}
 
+fn diffs_at_checkpoints(
+    frames: &[riven::models::match_v5::FramesTimeLine],
+    participant_id: i32,
+    enemy_id: i32,
+    metric: &impl Fn(&riven::models::match_v5::ParticipantFrame) -> i32,
+) -> (Option<i32>, Option<i32>, Option<i32>) {
     (
-        diff_at_frame(frames, 10, participant_id, enemy_id, &metric),
-        diff_at_frame(frames, 15, participant_id, enemy_id, &metric),
-        diff_at_frame(frames, 20, participant_id, enemy_id, &metric),
+        diff_at_frame(frames, 10, participant_id, enemy_id, metric),
+        diff_at_frame(frames, 15, participant_id, enemy_id, metric),
+        diff_at_frame(frames, 20, participant_id, enemy_id, metric),
     )
 }
 
@@ -0,0 +1,399 @@
+//! Deaths of a player read from the match timeline's `CHAMPION_KILL`
+//! events, with the context that tells a costly death from a useful one:
+//! alone or in a fight, under an enemy turret, right before an enemy
+//! dragon or Baron.
+
+use crate::analysis::models::{DeathEvent, DeathSummary};
+use riven::models::match_v5::Timeline;
+use std::collections::HashMap;
+
+const EARLY_GAME_SECS: i32 = 10 * 60;
+/// Another kill involving an ally this close in time and space means the
+/// death happened in a fight.
+const FIGHT_WINDOW_MS: i64 = 15_000;
+const FIGHT_RADIUS: f32 = 2_500.0;
+/// Turret range (750) plus some margin for the dive that led to the death.
+const TURRET_RADIUS: f32 = 1_000.0;
+/// A `BUILDING_KILL` this close to a known turret position destroyed it.
+const TURRET_MATCH_RADIUS: f32 = 300.0;
+/// A death this long before an enemy dragon or Baron gave it away.
+const OBJECTIVE_WINDOW_MS: i64 = 60_000;
+const OBJECTIVE_MONSTERS: [&str; 2] = ["DRAGON", "BARON_NASHOR"];
+
+/// Summoner's Rift turret positions as `(owning team, x, y)`.
+const RIFT_TURRETS: &[(u16, i32, i32)] = &[
+    (100, 981, 10441),
+    (100, 1512, 6699),
+    (100, 1169, 4287),
+    (100, 5846, 6396),
+    (100, 5048, 4812),
+    (100, 3651, 3696),
+    (100, 10504, 1029),
+    (100, 6919, 1483),
+    (100, 4281, 1253),
+    (100, 1748, 2270),
+    (100, 2177, 1807),
+    (200, 4318, 13875),
+    (200, 7943, 13411),
+    (200, 10481, 13650),
+    (200, 8955, 8510),
+    (200, 9767, 10113),
+    (200, 11134, 11207),
+    (200, 13866, 4505),
+    (200, 13327, 8226),
+    (200, 13624, 10572),
+    (200, 12611, 13084),
+    (200, 13052, 12612),
+];
+
+/// Timeline event the death analysis reads, decoupled from riven so the
+/// rules can be tested on hand-written sequences.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FightEvent {
+    ChampionKill {
+        timestamp_ms: i64,
+        victim: i32,
+        /// `0` when no champion got the kill.
+        killer: i32,
+        assisters: Vec<i32>,
+        x: i32,
+        y: i32,
+    },
+    TurretKill {
+        timestamp_ms: i64,
+        x: i32,
+        y: i32,
+    },
+    EliteMonsterKill {
+        timestamp_ms: i64,
+        monster: String,
+        killer_team: u16,
+    },
+}
+
+/// Team and champion of a match participant, keyed by timeline
+/// `participant_id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Combatant {
+    pub team: u16,
+    pub champion: String,
+}
+
+/// Fight-related events of `timeline`, in order.
+pub fn fight_events(timeline: &Timeline) -> Vec<FightEvent> {
+    timeline
+        .info
+        .frames
+        .iter()
+        .flat_map(|frame| &frame.events)
+        .filter_map(|event| match event.r#type.as_str() {
+            "CHAMPION_KILL" => {
+                let position = event.position.as_ref()?;
+                Some(FightEvent::ChampionKill {
+                    timestamp_ms: event.timestamp,
+                    victim: event.victim_id?,
+                    killer: event.killer_id.unwrap_or(0),
+                    assisters: event.assisting_participant_ids.clone().unwrap_or_default(),
+                    x: position.x,
+                    y: position.y,
+                })
+            }
+            "BUILDING_KILL" if event.building_type.as_deref() == Some("TOWER_BUILDING") => {
+                let position = event.position.as_ref()?;
+                Some(FightEvent::TurretKill {
+                    timestamp_ms: event.timestamp,
+                    x: position.x,
+                    y: position.y,
+                })
+            }
+            "ELITE_MONSTER_KILL" => Some(FightEvent::EliteMonsterKill {
+                timestamp_ms: event.timestamp,
+                monster: event.monster_type.clone()?,
+                killer_team: event.killer_team_id?.into(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Deaths of `participant_id`, oldest first. Turret proximity is only
+/// checked on Summoner's Rift (`on_rift`), whose turret layout is known.
+pub fn player_deaths(
+    events: &[FightEvent],
+    participant_id: i32,
+    combatants: &HashMap<i32, Combatant>,
+    on_rift: bool,
+) -> Vec<DeathEvent> {
+    let Some(team) = combatants.get(&participant_id).map(|c| c.team) else {
+        return Vec::new();
+    };
+    let champion = |id: &i32| combatants.get(id).map(|c| c.champion.clone());
+    let is_ally = |id: &i32| combatants.get(id).is_some_and(|c| c.team == team);
+
+    events
+        .iter()
+        .filter_map(|event| match event {
+            FightEvent::ChampionKill {
+                timestamp_ms,
+                victim,
+                killer,
+                assisters,
+                x,
+                y,
+            } if *victim == participant_id => Some(DeathEvent {
+                timestamp_secs: (*timestamp_ms / 1000) as i32,
+                x: *x,
+                y: *y,
+                killer: champion(killer),
+                assisters: assisters.iter().filter_map(champion).collect(),
+                alone: !allies_fought_nearby(events, event, &is_ally),
+                near_enemy_tower: on_rift
+                    && enemy_turret_in_range(events, *timestamp_ms, *x, *y, team),
+                before_objective: objective_given_away(events, *timestamp_ms, team),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn summarize(deaths: &[DeathEvent]) -> DeathSummary {
+    let count = |predicate: fn(&DeathEvent) -> bool| {
+        deaths.iter().filter(|death| predicate(death)).count() as i32
+    };
+    DeathSummary {
+        before_10_min: count(|d| d.timestamp_secs < EARLY_GAME_SECS),
+        alone: count(|d| d.alone),
+        in_fights: count(|d| !d.alone),
+        near_enemy_tower: count(|d| d.near_enemy_tower),
+        before_objective: count(|d| d.before_objective.is_some()),
+        costly: count(|d| d.alone || d.near_enemy_tower || d.before_objective.is_some()),
+    }
+}
+
+/// Whether another kill around `death` involved one of the victim's allies
+/// (killed, killing or assisting).
+fn allies_fought_nearby(
+    events: &[FightEvent],
+    death: &FightEvent,
+    is_ally: &impl Fn(&i32) -> bool,
+) -> bool {
+    let FightEvent::ChampionKill {
+        timestamp_ms,
+        victim,
+        x,
+        y,
+        ..
+    } = death
+    else {
+        return false;
+    };
+    events.iter().any(|other| match other {
+        FightEvent::ChampionKill {
+            timestamp_ms: other_ms,
+            victim: other_victim,
+            killer: other_killer,
+            assisters: other_assisters,
+            x: other_x,
+            y: other_y,
+        } => {
+            !std::ptr::eq(other, death)
+                && (other_ms - timestamp_ms).abs() <= FIGHT_WINDOW_MS
+                && distance(*x, *y, *other_x, *other_y) <= FIGHT_RADIUS
+                && std::iter::once(other_victim)
+                    .chain(std::iter::once(other_killer))
+                    .chain(other_assisters)
+                    .any(|id| id != victim && is_ally(id))
+        }
+        _ => false,
+    })
+}
+
+fn enemy_turret_in_range(
+    events: &[FightEvent],
+    timestamp_ms: i64,
+    x: i32,
+    y: i32,
+    team: u16,
+) -> bool {
+    RIFT_TURRETS
+        .iter()
+        .filter(|(owner, _, _)| *owner != team)
+        .filter(|(_, tx, ty)| distance(x, y, *tx, *ty) <= TURRET_RADIUS)
+        .any(|(_, tx, ty)| {
+            !events.iter().any(|event| match event {
+                FightEvent::TurretKill {
+                    timestamp_ms: destroyed_ms,
+                    x: bx,
+                    y: by,
+                } => {
+                    *destroyed_ms <= timestamp_ms
+                        && distance(*tx, *ty, *bx, *by) <= TURRET_MATCH_RADIUS
+                }
+                _ => false,
+            })
+        })
+}
+
+fn objective_given_away(events: &[FightEvent], timestamp_ms: i64, team: u16) -> Option<String> {
+    events.iter().find_map(|event| match event {
+        FightEvent::EliteMonsterKill {
+            timestamp_ms: taken_ms,
+            monster,
+            killer_team,
+        } if *killer_team != team
+            && OBJECTIVE_MONSTERS.contains(&monster.as_str())
+            && (timestamp_ms..timestamp_ms + OBJECTIVE_WINDOW_MS).contains(taken_ms) =>
+        {
+            Some(monster.clone())
+        }
+        _ => None,
+    })
+}
+
+pub(crate) fn distance(x1: i32, y1: i32, x2: i32, y2: i32) -> f32 {
+    ((x1 - x2) as f32).hypot((y1 - y2) as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYER: i32 = 1;
+
+    /// Player 1 and ally 2 on blue, 6 and 7 on red.
+    fn combatants() -> HashMap<i32, Combatant> {
+        [
+            (1, 100, "Ahri"),
+            (2, 100, "LeeSin"),
+            (6, 200, "Zed"),
+            (7, 200, "Vi"),
+        ]
+        .into_iter()
+        .map(|(id, team, champion)| {
+            (
+                id,
+                Combatant {
+                    team,
+                    champion: champion.to_string(),
+                },
+            )
+        })
+        .collect()
+    }
+
+    fn kill(secs: i64, victim: i32, killer: i32, assisters: &[i32], x: i32, y: i32) -> FightEvent {
+        FightEvent::ChampionKill {
+            timestamp_ms: secs * 1000,
+            victim,
+            killer,
+            assisters: assisters.to_vec(),
+            x,
+            y,
+        }
+    }
+
+    fn deaths(events: &[FightEvent]) -> Vec<DeathEvent> {
+        player_deaths(events, PLAYER, &combatants(), true)
+    }
+
+    #[test]
+    fn solo_death_in_the_river_is_alone() {
+        let events = [kill(300, PLAYER, 6, &[7], 7000, 7500)];
+        let deaths = deaths(&events);
+        assert_eq!(deaths.len(), 1);
+        let death = &deaths[0];
+        assert_eq!(death.timestamp_secs, 300);
+        assert_eq!(death.killer.as_deref(), Some("Zed"));
+        assert_eq!(death.assisters, ["Vi"]);
+        assert!(death.alone);
+        assert!(!death.near_enemy_tower);
+        assert_eq!(death.before_objective, None);
+    }
+
+    #[test]
+    fn death_traded_next_to_an_ally_is_a_fight() {
+        let events = [
+            kill(900, PLAYER, 6, &[], 7000, 7500),
+            kill(905, 6, 2, &[], 7400, 7800),
+            // An unrelated kill across the map does not make a fight.
+            kill(1500, PLAYER, 7, &[], 2000, 12000),
+            kill(1502, 2, 6, &[], 12000, 2000),
+        ];
+        let deaths = deaths(&events);
+        assert!(!deaths[0].alone);
+        assert!(deaths[1].alone);
+    }
+
+    #[test]
+    fn diving_a_standing_turret_is_flagged_until_it_falls() {
+        // Red mid outer turret sits at (8955, 8510).
+        let events = [
+            kill(600, PLAYER, 6, &[], 9100, 8600),
+            FightEvent::TurretKill {
+                timestamp_ms: 700_000,
+                x: 8955,
+                y: 8510,
+            },
+            kill(800, PLAYER, 6, &[], 9100, 8600),
+        ];
+        let deaths = deaths(&events);
+        assert!(deaths[0].near_enemy_tower);
+        assert!(!deaths[1].near_enemy_tower);
+
+        // Own turrets and other maps never count.
+        let own_turret = [kill(600, PLAYER, 6, &[], 5846, 6396)];
+        assert!(!self::deaths(&own_turret)[0].near_enemy_tower);
+        assert!(!player_deaths(&events, PLAYER, &combatants(), false)[0].near_enemy_tower);
+    }
+
+    #[test]
+    fn death_before_an_enemy_dragon_gives_it_away() {
+        let dragon = |secs: i64, killer_team: u16| FightEvent::EliteMonsterKill {
+            timestamp_ms: secs * 1000,
+            monster: "DRAGON".to_string(),
+            killer_team,
+        };
+        let events = [
+            kill(1000, PLAYER, 6, &[], 9800, 4400),
+            dragon(1040, 200),
+            kill(1300, PLAYER, 6, &[], 9800, 4400),
+            dragon(1320, 100),
+            kill(1600, PLAYER, 6, &[], 9800, 4400),
+            dragon(1700, 200),
+        ];
+        let deaths = deaths(&events);
+        assert_eq!(deaths[0].before_objective.as_deref(), Some("DRAGON"));
+        assert_eq!(deaths[1].before_objective, None);
+        assert_eq!(deaths[2].before_objective, None);
+    }
+
+    #[test]
+    fn summary_counts_each_kind_of_death() {
+        let events = [
+            kill(300, PLAYER, 6, &[], 7000, 7500),
+            kill(900, PLAYER, 6, &[], 7000, 7500),
+            kill(902, 6, 2, &[], 7000, 7500),
+            kill(1200, PLAYER, 6, &[], 9100, 8600),
+            kill(1210, 7, 2, &[], 9200, 8700),
+        ];
+        let summary = summarize(&deaths(&events));
+        assert_eq!(
+            summary,
+            DeathSummary {
+                before_10_min: 1,
+                alone: 1,
+                in_fights: 2,
+                near_enemy_tower: 1,
+                before_objective: 0,
+                costly: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn other_players_deaths_are_ignored() {
+        let events = [kill(300, 2, 6, &[], 7000, 7500)];
+        assert!(deaths(&events).is_empty());
+        assert!(player_deaths(&events, 42, &combatants(), true).is_empty());
+    }
+}
@@ -0,0 +1,236 @@
+//! Jungle pathing and gank metrics read from the match timeline: the first
+//! clear, the camps taken in each game phase and the takedowns in each lane
+//! before the laning phase ends.
+//!
+//! The timeline only samples jungle CS once a minute, so the first clear
+//! time is interpolated between frames, and camps are counted from the CS
+//! they give. A gank that ends without a takedown leaves no event and is
+//! not counted.
+
+use crate::analysis::models::{JungleStats, LaneCounts, PhaseCounts};
+use crate::riot::deaths::FightEvent;
+use riven::models::match_v5::Timeline;
+
+/// Jungle CS given by one camp.
+const CS_PER_CAMP: i32 = 4;
+/// Jungle CS of a full six-camp clear.
+const FULL_CLEAR_CS: i32 = 6 * CS_PER_CAMP;
+/// Later than this, the jungler did not full clear before their first play.
+const FIRST_CLEAR_DEADLINE_MS: i64 = 6 * 60_000;
+/// End of the laning phase, when the first turret plates fall.
+const EARLY_GAME_END_MS: i64 = 14 * 60_000;
+const MID_GAME_END_MS: i64 = 25 * 60_000;
+
+/// Jungle CS of one participant in a timeline frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JungleFrame {
+    pub timestamp_ms: i64,
+    pub jungle_cs: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lane {
+    Top,
+    Mid,
+    Bot,
+}
+
+/// Jungle CS of `participant_id` in every frame of `timeline`, oldest first.
+pub fn jungle_frames(timeline: &Timeline, participant_id: i32) -> Vec<JungleFrame> {
+    timeline
+        .info
+        .frames
+        .iter()
+        .filter_map(|frame| {
+            let participant = frame.participant_frames.as_ref()?.get(&participant_id)?;
+            Some(JungleFrame {
+                timestamp_ms: frame.timestamp.into(),
+                jungle_cs: participant.jungle_minions_killed,
+            })
+        })
+        .collect()
+}
+
+/// Timeline part of the jungler's stats; the challenge-based counts are
+/// left for the caller to fill.
+pub fn analyze(frames: &[JungleFrame], events: &[FightEvent], participant_id: i32) -> JungleStats {
+    JungleStats {
+        first_clear_secs: first_clear_secs(frames),
+        jungle_camps_by_phase: camps_by_phase(frames),
+        ganks: ganks(events, participant_id),
+        ..JungleStats::default()
+    }
+}
+
+fn first_clear_secs(frames: &[JungleFrame]) -> Option<i32> {
+    frames.windows(2).find_map(|pair| {
+        let (before, after) = (pair[0], pair[1]);
+        if after.timestamp_ms > FIRST_CLEAR_DEADLINE_MS {
+            return None;
+        }
+        if before.jungle_cs >= FULL_CLEAR_CS || after.jungle_cs < FULL_CLEAR_CS {
+            return None;
+        }
+        let share =
+            (FULL_CLEAR_CS - before.jungle_cs) as f32 / (after.jungle_cs - before.jungle_cs) as f32;
+        let elapsed = (after.timestamp_ms - before.timestamp_ms) as f32 * share;
+        Some(((before.timestamp_ms as f32 + elapsed) / 1000.0).round() as i32)
+    })
+}
+
+fn camps_by_phase(frames: &[JungleFrame]) -> PhaseCounts {
+    let cs = frames
+        .windows(2)
+        .fold(PhaseCounts::default(), |mut phases, pair| {
+            let gained = (pair[1].jungle_cs - pair[0].jungle_cs).max(0);
+            match pair[1].timestamp_ms {
+                t if t <= EARLY_GAME_END_MS => phases.early += gained,
+                t if t <= MID_GAME_END_MS => phases.mid += gained,
+                _ => phases.late += gained,
+            }
+            phases
+        });
+    let camps = |cs: i32| (cs + CS_PER_CAMP / 2) / CS_PER_CAMP;
+    PhaseCounts {
+        early: camps(cs.early),
+        mid: camps(cs.mid),
+        late: camps(cs.late),
+    }
+}
+
+fn ganks(events: &[FightEvent], participant_id: i32) -> LaneCounts {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            FightEvent::ChampionKill {
+                timestamp_ms,
+                killer,
+                assisters,
+                x,
+                y,
+                ..
+            } if *timestamp_ms < EARLY_GAME_END_MS
+                && (*killer == participant_id || assisters.contains(&participant_id)) =>
+            {
+                lane_at(*x, *y)
+            }
+            _ => None,
+        })
+        .fold(LaneCounts::default(), |mut lanes, lane| {
+            match lane {
+                Lane::Top => lanes.top += 1,
+                Lane::Mid => lanes.mid += 1,
+                Lane::Bot => lanes.bot += 1,
+            }
+            lanes
+        })
+}
+
+/// Lane of a Summoner's Rift position; `None` in the jungle, the river
+/// and the bases.
+fn lane_at(x: i32, y: i32) -> Option<Lane> {
+    let in_base = (x < 4_500 && y < 4_500) || (x > 10_500 && y > 10_500);
+    if in_base {
+        None
+    } else if (x < 2_500 && y > 4_500) || (y > 12_300 && x < 10_300) {
+        Some(Lane::Top)
+    } else if (y < 2_500 && x > 4_500) || (x > 12_300 && y < 10_300) {
+        Some(Lane::Bot)
+    } else if (x - y).abs() < 1_500 {
+        Some(Lane::Mid)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JUNGLER: i32 = 2;
+
+    fn frames(cs_per_minute: &[i32]) -> Vec<JungleFrame> {
+        cs_per_minute
+            .iter()
+            .enumerate()
+            .map(|(minute, cs)| JungleFrame {
+                timestamp_ms: minute as i64 * 60_000,
+                jungle_cs: *cs,
+            })
+            .collect()
+    }
+
+    fn takedown(secs: i64, killer: i32, assisters: &[i32], x: i32, y: i32) -> FightEvent {
+        FightEvent::ChampionKill {
+            timestamp_ms: secs * 1000,
+            victim: 7,
+            killer,
+            assisters: assisters.to_vec(),
+            x,
+            y,
+        }
+    }
+
+    #[test]
+    fn first_clear_is_interpolated_between_frames() {
+        // 16 CS at 3:00, 28 at 4:00: the 24th CS fell at 3:40.
+        assert_eq!(first_clear_secs(&frames(&[0, 0, 4, 16, 28])), Some(220));
+    }
+
+    #[test]
+    fn no_full_clear_before_the_deadline_means_no_first_clear() {
+        assert_eq!(
+            first_clear_secs(&frames(&[0, 0, 4, 8, 8, 12, 16, 30])),
+            None
+        );
+        assert_eq!(first_clear_secs(&[]), None);
+    }
+
+    #[test]
+    fn camps_are_split_by_game_phase() {
+        // 70 CS until 14:00, 55 until 25:00 and 25 after.
+        let mut cs = vec![0];
+        for minute in 1..=30 {
+            cs.push(minute * 5);
+        }
+        let phases = camps_by_phase(&frames(&cs));
+        assert_eq!(
+            phases,
+            PhaseCounts {
+                early: 18,
+                mid: 14,
+                late: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn early_takedowns_in_a_lane_are_ganks() {
+        let events = [
+            // Top lane, bot lane and mid lane takedowns.
+            takedown(240, JUNGLER, &[], 1500, 11000),
+            takedown(400, 1, &[JUNGLER], 12000, 1200),
+            takedown(500, 3, &[JUNGLER], 7400, 7200),
+            // A river skirmish, a takedown without the jungler and one
+            // after the laning phase.
+            takedown(600, JUNGLER, &[], 4400, 9600),
+            takedown(650, 3, &[], 1500, 11000),
+            takedown(900, JUNGLER, &[], 1500, 11000),
+        ];
+        assert_eq!(
+            ganks(&events, JUNGLER),
+            LaneCounts {
+                top: 1,
+                mid: 1,
+                bot: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn bases_are_not_lanes() {
+        assert_eq!(lane_at(1000, 1000), None);
+        assert_eq!(lane_at(14000, 14000), None);
+        assert_eq!(lane_at(13500, 5000), Some(Lane::Bot));
+    }
+}
@@ -1,3 +1,6 @@
 pub mod client;
+pub mod deaths;
+pub mod jungle;
 pub mod models;
+pub mod objectives;
 pub mod tracker;
@@ -52,6 +52,56 @@ pub struct MatchResult {
     pub game_start_timestamp: Option<i64>,
 }
 
+/// One participant's line on the full post-game scoreboard
+#[derive(Debug, Clone)]
+pub struct ScoreboardPlayer {
+    pub puuid: String,
+    /// Riot ID (`Name#Tag`) when available, otherwise the legacy summoner name.
+    pub display_name: String,
+    pub participant_id: i32,
+    /// Riot team id: 100 (blue) or 200 (red).
+    pub team_id: u16,
+    pub position: String,
+    pub champion_name: String,
+    pub win: bool,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub total_cs: i32,
+    pub gold_earned: i32,
+    pub total_damage: i32,
+    pub vision_score: i32,
+}
+
+/// Every participant of a finished match, used by the recap "Détails" and
+/// "Timeline" buttons
+#[derive(Debug, Clone)]
+pub struct MatchScoreboard {
+    pub match_id: String,
+    pub game_mode: String,
+    pub queue_id: Option<i32>,
+    pub game_duration_secs: i32,
+    pub players: Vec<ScoreboardPlayer>,
+}
+
+impl MatchScoreboard {
+    /// Same-position player on the other team, if any.
+    pub fn lane_opponent(&self, player: &ScoreboardPlayer) -> Option<&ScoreboardPlayer> {
+        self.players.iter().find(|p| {
+            !player.position.is_empty()
+                && p.position == player.position
+                && p.team_id != player.team_id
+        })
+    }
+}
+
+/// Gold and CS differences against the lane opponent at 10/15/20 minutes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LaneDiffs {
+    pub gold: [Option<i32>; 3],
+    pub cs: [Option<i32>; 3],
+}
+
 /// Outcome of the match lookup performed when a game ends
 #[derive(Debug, Clone)]
 pub enum MatchLookup {
@@ -77,3 +127,81 @@ pub enum GameStateChange {
     /// No change in game state
     NoChange,
 }
+
+/// Data Dragon entry of a champion.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChampionInfo {
+    pub name: String,
+    /// Class tags (`Tank`, `Mage`, ...), primary first.
+    pub tags: Vec<String>,
+}
+
+/// Ranked standing of a summoner in one queue (league-v4)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankedEntry {
+    /// Riot queue type, e.g. `RANKED_SOLO_5x5`.
+    pub queue_type: String,
+    /// `IRON` … `CHALLENGER`.
+    pub tier: String,
+    /// `I` … `IV`; `None` for apex tiers, which have no divisions.
+    pub division: Option<String>,
+    pub league_points: i32,
+}
+
+/// Tiers below Master, lowest first; each spans 4 divisions of 100 LP.
+const DIVIDED_TIERS: &[&str] = &[
+    "IRON", "BRONZE", "SILVER", "GOLD", "PLATINUM", "EMERALD", "DIAMOND",
+];
+const DIVISIONS: &[&str] = &["IV", "III", "II", "I"];
+
+impl RankedEntry {
+    /// Position on a single LP ladder (Iron IV 0 LP = 0), so two entries can
+    /// be subtracted across promotions and demotions. Master and above sit
+    /// on top of Diamond I 100 LP and only count their league points.
+    /// `None` for an unrecognised tier.
+    pub fn absolute_lp(&self) -> Option<i32> {
+        let apex_floor = DIVIDED_TIERS.len() as i32 * 400;
+        match self.tier.as_str() {
+            "MASTER" | "GRANDMASTER" | "CHALLENGER" => Some(apex_floor + self.league_points),
+            tier => {
+                let tier_index = DIVIDED_TIERS.iter().position(|t| *t == tier)? as i32;
+                let division_index = self
+                    .division
+                    .as_deref()
+                    .and_then(|d| DIVISIONS.iter().position(|x| *x == d))
+                    .unwrap_or(0) as i32;
+                Some(tier_index * 400 + division_index * 100 + self.league_points)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(tier: &str, division: Option<&str>, league_points: i32) -> RankedEntry {
+        RankedEntry {
+            queue_type: "RANKED_SOLO_5x5".to_string(),
+            tier: tier.to_string(),
+            division: division.map(str::to_string),
+            league_points,
+        }
+    }
+
+    #[test]
+    fn absolute_lp_orders_tiers_and_divisions() {
+        assert_eq!(entry("IRON", Some("IV"), 0).absolute_lp(), Some(0));
+        assert_eq!(entry("GOLD", Some("II"), 50).absolute_lp(), Some(1450));
+        assert_eq!(entry("DIAMOND", Some("I"), 99).absolute_lp(), Some(2799));
+        assert_eq!(entry("MASTER", None, 120).absolute_lp(), Some(2920));
+        assert_eq!(entry("UNRANKED", None, 0).absolute_lp(), None);
+    }
+
+    #[test]
+    fn absolute_lp_difference_spans_promotion() {
+        let before = entry("SILVER", Some("I"), 80).absolute_lp().unwrap();
+        let after = entry("GOLD", Some("IV"), 15).absolute_lp().unwrap();
+        assert_eq!(after - before, 35);
+    }
+}
@@ -0,0 +1,319 @@
+//! Objective control read from the match timeline's `ELITE_MONSTER_KILL`
+//! and `BUILDING_KILL` events: what each team took, whether the player was
+//! there when it fell, and which of their deaths handed an objective over.
+//!
+//! Presence is credit for the kill or, failing that, a position near the
+//! objective in the participant frames around it. Frames are only sampled
+//! once a minute, so a player rotating in late can be missed.
+
+use crate::analysis::models::{DeathEvent, ObjectiveEvent, ObjectiveSummary, ObjectiveTally};
+use crate::riot::deaths::{self, distance};
+use riven::models::match_v5::Timeline;
+use std::collections::HashMap;
+
+const EPIC_MONSTERS: [&str; 4] = ["DRAGON", "HORDE", "RIFTHERALD", "BARON_NASHOR"];
+const BUILDINGS: [&str; 2] = ["TOWER_BUILDING", "INHIBITOR_BUILDING"];
+/// A player this close to the objective in a surrounding frame was there.
+const PRESENCE_RADIUS: f32 = 2_500.0;
+/// Void grubs die one by one; kills this close together are one camp.
+const GRUB_CAMP_WINDOW_SECS: i32 = 60;
+
+/// Objective taken, decoupled from riven so the rules can be tested on
+/// hand-written sequences.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectiveKill {
+    pub timestamp_ms: i64,
+    /// Monster type or building type.
+    pub objective: String,
+    pub detail: Option<String>,
+    /// Team that took it.
+    pub team: u16,
+    /// Killer and assisters; the killer is `0` for minions and monsters.
+    pub participants: Vec<i32>,
+    pub x: i32,
+    pub y: i32,
+}
+
+/// Participant positions of one timeline frame, keyed by `participant_id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionFrame {
+    pub timestamp_ms: i64,
+    pub positions: HashMap<i32, (i32, i32)>,
+}
+
+/// Epic monsters and buildings taken in `timeline`, in order.
+pub fn objective_kills(timeline: &Timeline) -> Vec<ObjectiveKill> {
+    timeline
+        .info
+        .frames
+        .iter()
+        .flat_map(|frame| &frame.events)
+        .filter_map(|event| {
+            let (objective, detail, team) = match event.r#type.as_str() {
+                "ELITE_MONSTER_KILL" => {
+                    let monster = event.monster_type.clone()?;
+                    EPIC_MONSTERS.contains(&monster.as_str()).then_some(())?;
+                    (
+                        monster,
+                        event.monster_sub_type.clone(),
+                        event.killer_team_id?.into(),
+                    )
+                }
+                "BUILDING_KILL" => {
+                    let building = event.building_type.clone()?;
+                    BUILDINGS.contains(&building.as_str()).then_some(())?;
+                    let owner: u16 = event.team_id?.into();
+                    (building, event.lane_type.clone(), enemy_team(owner))
+                }
+                _ => return None,
+            };
+            let position = event.position.as_ref()?;
+            Some(ObjectiveKill {
+                timestamp_ms: event.timestamp,
+                objective,
+                detail,
+                team,
+                participants: event
+                    .killer_id
+                    .into_iter()
+                    .chain(event.assisting_participant_ids.iter().flatten().copied())
+                    .collect(),
+                x: position.x,
+                y: position.y,
+            })
+        })
+        .collect()
+}
+
+/// Participant positions of every frame of `timeline`, oldest first.
+pub fn position_frames(timeline: &Timeline) -> Vec<PositionFrame> {
+    timeline
+        .info
+        .frames
+        .iter()
+        .map(|frame| PositionFrame {
+            timestamp_ms: frame.timestamp.into(),
+            positions: frame
+                .participant_frames
+                .iter()
+                .flatten()
+                .map(|(id, participant)| (*id, (participant.position.x, participant.position.y)))
+                .collect(),
+        })
+        .collect()
+}
+
+/// Objectives of the game as seen by `participant_id` of `team`.
+pub fn player_objectives(
+    kills: &[ObjectiveKill],
+    frames: &[PositionFrame],
+    participant_id: i32,
+    team: u16,
+) -> Vec<ObjectiveEvent> {
+    kills
+        .iter()
+        .map(|kill| ObjectiveEvent {
+            timestamp_secs: (kill.timestamp_ms / 1000) as i32,
+            objective: kill.objective.clone(),
+            detail: kill.detail.clone(),
+            ally: kill.team == team,
+            player_present: kill.participants.contains(&participant_id)
+                || surrounding_frames(frames, kill.timestamp_ms)
+                    .iter()
+                    .filter_map(|frame| frame.positions.get(&participant_id))
+                    .any(|&(x, y)| distance(x, y, kill.x, kill.y) <= PRESENCE_RADIUS),
+        })
+        .collect()
+}
+
+pub fn summarize(objectives: &[ObjectiveEvent], deaths: &[DeathEvent]) -> ObjectiveSummary {
+    let tally = |objective: &str| {
+        objectives
+            .iter()
+            .filter(|event| event.objective == objective)
+            .fold(ObjectiveTally::default(), |mut tally, event| {
+                if event.ally {
+                    tally.taken += 1;
+                } else {
+                    tally.lost += 1;
+                }
+                tally
+            })
+    };
+
+    // Each epic monster is one take, except a void grub camp: its grubs
+    // fall one by one but the player contests the camp as a whole.
+    let mut takes: Vec<(i32, &str, bool)> = Vec::new();
+    for event in objectives.iter().filter(|event| is_epic(event)) {
+        match takes.last_mut() {
+            Some((last_secs, "HORDE", present))
+                if event.objective == "HORDE"
+                    && event.timestamp_secs - *last_secs <= GRUB_CAMP_WINDOW_SECS =>
+            {
+                *last_secs = event.timestamp_secs;
+                *present |= event.player_present;
+            }
+            _ => takes.push((
+                event.timestamp_secs,
+                event.objective.as_str(),
+                event.player_present,
+            )),
+        }
+    }
+    let epic_monsters_total = takes.len() as i32;
+    let epic_monsters_present = takes.iter().filter(|(_, _, present)| *present).count() as i32;
+
+    ObjectiveSummary {
+        dragons: tally("DRAGON"),
+        void_grubs: tally("HORDE"),
+        heralds: tally("RIFTHERALD"),
+        barons: tally("BARON_NASHOR"),
+        towers: tally("TOWER_BUILDING"),
+        epic_monsters_present,
+        epic_monsters_total,
+        presence: (epic_monsters_total > 0)
+            .then(|| epic_monsters_present as f32 / epic_monsters_total as f32),
+        setup_deaths: deaths::summarize(deaths).before_objective,
+    }
+}
+
+fn enemy_team(team: u16) -> u16 {
+    if team == 100 { 200 } else { 100 }
+}
+
+fn is_epic(event: &ObjectiveEvent) -> bool {
+    EPIC_MONSTERS.contains(&event.objective.as_str())
+}
+
+/// The last frame at or before `timestamp_ms` and the first one after it.
+fn surrounding_frames(frames: &[PositionFrame], timestamp_ms: i64) -> &[PositionFrame] {
+    let after = frames.partition_point(|frame| frame.timestamp_ms <= timestamp_ms);
+    &frames[after.saturating_sub(1)..(after + 1).min(frames.len())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYER: i32 = 2;
+    const BLUE: u16 = 100;
+    const RED: u16 = 200;
+    /// Dragon pit and Baron pit centers.
+    const DRAGON_PIT: (i32, i32) = (9866, 4414);
+    const BARON_PIT: (i32, i32) = (5007, 10471);
+
+    fn monster(secs: i64, objective: &str, team: u16, participants: &[i32]) -> ObjectiveKill {
+        let (x, y) = if objective == "BARON_NASHOR" || objective == "HORDE" {
+            BARON_PIT
+        } else {
+            DRAGON_PIT
+        };
+        ObjectiveKill {
+            timestamp_ms: secs * 1000,
+            objective: objective.to_string(),
+            detail: None,
+            team,
+            participants: participants.to_vec(),
+            x,
+            y,
+        }
+    }
+
+    fn frame(minute: i64, player_at: (i32, i32)) -> PositionFrame {
+        PositionFrame {
+            timestamp_ms: minute * 60_000,
+            positions: HashMap::from([(PLAYER, player_at)]),
+        }
+    }
+
+    fn death(secs: i32, before_objective: Option<&str>) -> DeathEvent {
+        DeathEvent {
+            timestamp_secs: secs,
+            x: 9000,
+            y: 5000,
+            killer: None,
+            assisters: Vec::new(),
+            alone: true,
+            near_enemy_tower: false,
+            before_objective: before_objective.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn credit_for_the_kill_is_presence() {
+        let kills = [monster(330, "DRAGON", BLUE, &[1, PLAYER])];
+        let objectives = player_objectives(&kills, &[], PLAYER, BLUE);
+        assert!(objectives[0].ally);
+        assert!(objectives[0].player_present);
+    }
+
+    #[test]
+    fn standing_near_the_pit_in_a_surrounding_frame_is_presence() {
+        let kills = [
+            monster(330, "DRAGON", RED, &[7]),
+            monster(1230, "BARON_NASHOR", RED, &[7]),
+        ];
+        let frames = [
+            frame(5, (1000, 1000)),
+            frame(6, (9500, 4800)),
+            frame(20, (9500, 4800)),
+            frame(21, (9500, 4800)),
+        ];
+        let objectives = player_objectives(&kills, &frames, PLAYER, BLUE);
+        assert!(!objectives[0].ally);
+        assert!(objectives[0].player_present);
+        // Still bottom side while the enemy took Baron.
+        assert!(!objectives[1].player_present);
+    }
+
+    #[test]
+    fn summary_tallies_each_side_and_counts_a_grub_camp_once() {
+        let kills = [
+            monster(360, "HORDE", BLUE, &[1]),
+            monster(375, "HORDE", BLUE, &[1, PLAYER]),
+            monster(390, "HORDE", BLUE, &[1]),
+            monster(600, "DRAGON", RED, &[7]),
+            monster(1500, "BARON_NASHOR", BLUE, &[1, PLAYER]),
+            ObjectiveKill {
+                timestamp_ms: 800_000,
+                objective: "TOWER_BUILDING".to_string(),
+                detail: Some("MID_LANE".to_string()),
+                team: RED,
+                participants: vec![0],
+                x: 5846,
+                y: 6396,
+            },
+        ];
+        let objectives = player_objectives(&kills, &[], PLAYER, BLUE);
+        let summary = summarize(&objectives, &[]);
+        assert_eq!(summary.void_grubs, ObjectiveTally { taken: 3, lost: 0 });
+        assert_eq!(summary.dragons, ObjectiveTally { taken: 0, lost: 1 });
+        assert_eq!(summary.barons, ObjectiveTally { taken: 1, lost: 0 });
+        assert_eq!(summary.towers, ObjectiveTally { taken: 0, lost: 1 });
+        assert_eq!(summary.epic_monsters_total, 3);
+        assert_eq!(summary.epic_monsters_present, 2);
+        assert!((summary.presence.unwrap() - 2.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn deaths_that_gave_an_objective_away_are_setup_deaths() {
+        let kills = [
+            monster(600, "DRAGON", RED, &[7]),
+            monster(1200, "DRAGON", BLUE, &[1]),
+        ];
+        let objectives = player_objectives(&kills, &[], PLAYER, BLUE);
+        let deaths = [
+            death(570, Some("DRAGON")),
+            death(1170, None),
+            death(400, None),
+        ];
+        assert_eq!(summarize(&objectives, &deaths).setup_deaths, 1);
+    }
+
+    #[test]
+    fn no_epic_monster_means_no_presence() {
+        let summary = summarize(&[], &[]);
+        assert_eq!(summary.presence, None);
+        assert_eq!(summary.epic_monsters_total, 0);
+    }
+}
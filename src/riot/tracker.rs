@@ -1,5 +1,9 @@
-use crate::db::models::{NewActiveGame, NewMatchResult, Summoner};
+use crate::db::models::{
+    AnalysisJob, GameEndRecord, NewActiveGame, NewAnalysisJob, NewMatchResult,
+    NewNotificationEvent, Summoner,
+};
 use crate::db::repository::{Repository, RepositoryError};
+use crate::notification::processor::ANALYSIS_GROUPING_DELAY;
 use crate::riot::client::{RiotApiClient, RiotClient, RiotClientError};
 use crate::riot::models::{ActiveGameInfo, GameStateChange, MatchLookup, MatchResult};
 use std::sync::Arc;
@@ -113,10 +117,10 @@ impl<R: RiotApiClient + ?Sized, D: Repository + ?Sized> GameTracker<R, D> {
         Ok(())
     }
 
-    /// Handle game ended: fetch the match result, then resolve the active
-    /// game. The active_games row is only deleted once the lookup succeeds
-    /// (or the retry budget is exhausted), so a failed lookup is retried on
-    /// the next poll cycle instead of losing the game.
+    /// Handle game ended: fetch the match result. A found match is left to
+    /// [`Self::record_game_end`]; a failed lookup keeps the active_games row
+    /// so it is retried on the next poll cycle, until the retry budget is
+    /// exhausted.
     pub async fn handle_game_ended(
         &self,
         summoner: &Summoner,
@@ -143,8 +147,29 @@ impl<R: RiotApiClient + ?Sized, D: Repository + ?Sized> GameTracker<R, D> {
             });
         };
 
-        {
-            let new_match = NewMatchResult {
+        Ok(MatchLookup::Found(Box::new(match_result)))
+    }
+
+    /// Persists a found match together with its GAME_ENDED event and, when
+    /// `queue_analysis` is set, its analysis job, in one transaction.
+    /// Returns the analysis job when one was newly queued.
+    pub async fn record_game_end(
+        &self,
+        summoner: &Summoner,
+        game_id: i64,
+        match_result: &MatchResult,
+        is_featured_mode: bool,
+        queue_analysis: bool,
+    ) -> Result<Option<AnalysisJob>, TrackerError> {
+        let champion_name = self
+            .repository
+            .get_champion_by_id(match_result.champion_id)
+            .await?
+            .map(|c| c.champion_name)
+            .unwrap_or_else(|| format!("Champion #{}", match_result.champion_id));
+
+        let record = GameEndRecord {
+            match_result: NewMatchResult {
                 summoner_id: summoner.id,
                 match_id: match_result.match_id.clone(),
                 game_id: match_result.game_id,
@@ -165,17 +190,42 @@ impl<R: RiotApiClient + ?Sized, D: Repository + ?Sized> GameTracker<R, D> {
                 enemy_cs: match_result.enemy_cs,
                 enemy_gold: match_result.enemy_gold,
                 enemy_damage: match_result.enemy_damage,
-            };
-
-            // Ignore errors on insert (might be duplicate)
-            let _ = self.repository.insert_match_result(&new_match).await;
-        }
-
-        self.repository
-            .delete_active_game_by_summoner_and_game(summoner.id, game_id)
-            .await?;
+            },
+            game_id,
+            event: NewNotificationEvent {
+                summoner_id: summoner.id,
+                event_type: "GAME_ENDED".to_string(),
+                game_id: match_result.game_id,
+                match_id: Some(match_result.match_id.clone()),
+                champion_id: match_result.champion_id,
+                champion_name,
+                role: Some(match_result.role.clone()),
+                win: Some(match_result.win),
+                kills: Some(match_result.kills),
+                deaths: Some(match_result.deaths),
+                assists: Some(match_result.assists),
+                game_duration_secs: Some(match_result.game_duration_secs),
+                game_mode: match_result.game_mode.clone(),
+                queue_id: match_result.queue_id,
+                is_featured_mode,
+                total_cs: Some(match_result.total_cs),
+                total_gold: Some(match_result.total_gold),
+                total_damage: Some(match_result.total_damage),
+                enemy_champion_name: match_result.enemy_champion_name.clone(),
+                enemy_cs: match_result.enemy_cs,
+                enemy_gold: match_result.enemy_gold,
+                enemy_damage: match_result.enemy_damage,
+                role_gaps: match_result.role_gaps.clone(),
+            },
+            analysis_job: queue_analysis.then(|| NewAnalysisJob {
+                summoner_id: summoner.id,
+                match_id: match_result.match_id.clone(),
+                not_before: chrono::Utc::now()
+                    + chrono::Duration::from_std(ANALYSIS_GROUPING_DELAY).unwrap_or_default(),
+            }),
+        };
 
-        Ok(MatchLookup::Found(Box::new(match_result)))
+        Ok(self.repository.record_game_end(&record).await?)
     }
 
     /// Fetch match result with retries (match data appears delayed)
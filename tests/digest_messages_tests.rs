@@ -0,0 +1,76 @@
+use chrono::{TimeZone, Utc};
+use sfg_bot::digest::DigestPeriod;
+use sfg_bot::digest::messages::format_digest;
+use sfg_bot::digest::stats::{GameLine, PlayerDigest, RatingCounts};
+use sfg_bot::i18n::Locale;
+
+fn player() -> PlayerDigest {
+    PlayerDigest {
+        summoner_name: "Alpha#EUW".to_string(),
+        games: 4,
+        wins: 3,
+        kills: 24,
+        deaths: 8,
+        assists: 20,
+        top_champions: vec![("Ahri".to_string(), 3), ("Lux".to_string(), 1)],
+        lp_change: Some(37),
+        best_game: Some(GameLine {
+            champion_name: "Ahri".to_string(),
+            win: true,
+            kills: 12,
+            deaths: 1,
+            assists: 7,
+            rating: Some("Good".to_string()),
+        }),
+        worst_game: None,
+        ratings: RatingCounts {
+            good: 1,
+            average: 0,
+            poor: 0,
+        },
+    }
+}
+
+fn render(players: &[PlayerDigest], locale: Locale) -> serde_json::Value {
+    // Monday 2026-10-19 09:00 Europe/Paris.
+    let end = Utc.with_ymd_and_hms(2026, 10, 19, 7, 0, 0).unwrap();
+    let start = end - DigestPeriod::Weekly.length();
+    let embed = format_digest(
+        DigestPeriod::Weekly,
+        players,
+        start,
+        end,
+        chrono_tz::Europe::Paris,
+        locale,
+    );
+    serde_json::to_value(embed).expect("serialize embed")
+}
+
+#[test]
+fn weekly_digest_lists_each_player() {
+    let value = render(&[player()], Locale::Fr);
+
+    assert_eq!(value["title"], "📅 Semaine SFG");
+    assert_eq!(value["description"], "Du 12/10 au 19/10 · 4 parties");
+
+    let field = &value["fields"][0];
+    assert_eq!(field["name"], "Alpha#EUW — 3V 1D (75 %)");
+    let lines = field["value"].as_str().unwrap();
+    assert!(lines.contains("KDA 5.5 (6.0/2.0/5.0)"), "{lines}");
+    assert!(lines.contains("LP +37"), "{lines}");
+    assert!(lines.contains("Ahri ×3, Lux ×1"), "{lines}");
+    assert!(lines.contains("Ahri 12/1/7"), "{lines}");
+    assert!(!lines.contains("Pire"), "{lines}");
+}
+
+#[test]
+fn empty_digest_says_so() {
+    let value = render(&[], Locale::En);
+
+    assert!(
+        value
+            .get("fields")
+            .is_none_or(|f| f.as_array().unwrap().is_empty())
+    );
+    assert_eq!(value["description"], "No games played in this period.");
+}
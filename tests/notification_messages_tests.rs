@@ -1,6 +1,11 @@
 use chrono::Utc;
 use sfg_bot::db::models::{NotificationEvent, Summoner};
-use sfg_bot::notification::messages::format_grouped_game_ended;
+use sfg_bot::i18n::Locale;
+use sfg_bot::notification::messages::{
+    format_grouped_game_ended, format_match_details, format_quiet_hours_summary,
+    format_timeline_diffs,
+};
+use sfg_bot::riot::models::{LaneDiffs, MatchScoreboard, ScoreboardPlayer};
 use uuid::Uuid;
 
 fn create_dummy_summoner() -> Summoner {
@@ -12,6 +17,7 @@ fn create_dummy_summoner() -> Summoner {
         region: "euw1".to_string(),
         created_at: Utc::now(),
         updated_at: Utc::now(),
+        benchmark_tier: None,
     }
 }
 
@@ -46,6 +52,8 @@ fn create_dummy_event(win: bool) -> NotificationEvent {
         processed_at: None,
         retry_count: 0,
         error_message: None,
+        failed_at: None,
+        next_attempt_at: Utc::now(),
     }
 }
 
@@ -55,7 +63,7 @@ fn test_game_won_title() {
     let mut event = create_dummy_event(true); // Win
     event.summoner_id = summoner.id;
 
-    let embed = format_grouped_game_ended(&[summoner], &[event], "Ranked Solo/Duo");
+    let embed = format_grouped_game_ended(&[summoner], &[event], "Ranked Solo/Duo", Locale::Fr);
 
     // Debug print the embed to inspect the title, as fields might be private
     let debug_str = format!("{:?}", embed);
@@ -68,12 +76,58 @@ fn test_game_lost_title() {
     let mut event = create_dummy_event(false); // Loss
     event.summoner_id = summoner.id;
 
-    let embed = format_grouped_game_ended(&[summoner], &[event], "Ranked Solo/Duo");
+    let embed = format_grouped_game_ended(&[summoner], &[event], "Ranked Solo/Duo", Locale::Fr);
 
     let debug_str = format!("{:?}", embed);
     assert!(debug_str.contains("Partie perdue !"));
 }
 
+#[test]
+fn game_ended_recap_follows_locale() {
+    let summoner = create_dummy_summoner();
+    let mut event = create_dummy_event(true);
+    event.summoner_id = summoner.id;
+    event.enemy_champion_name = None;
+
+    let embed = format_grouped_game_ended(&[summoner], &[event], "CLASSIC", Locale::En);
+
+    let debug_str = format!("{:?}", embed);
+    assert!(debug_str.contains("Game won!"));
+    assert!(debug_str.contains("The CLASSIC game is over!"));
+    assert!(debug_str.contains("Ranked Solo/Duo"));
+    assert!(debug_str.contains("vs Unknown (no role data)"));
+    assert!(!debug_str.contains("Partie"));
+}
+
+#[test]
+fn quiet_hours_summary_lists_each_result() {
+    let summoner = create_dummy_summoner();
+    let mut win = create_dummy_event(true);
+    win.summoner_id = summoner.id;
+    let mut loss = create_dummy_event(false);
+    loss.summoner_id = summoner.id;
+    loss.game_id = 67890;
+    loss.champion_name = "Lux".to_string();
+
+    let embed = format_quiet_hours_summary(&[summoner], &[win, loss], Locale::Fr);
+
+    let value = serde_json::to_value(embed).expect("serialize embed");
+    assert_eq!(value["title"], "🌙 Pendant les heures calmes");
+    let description = value["description"].as_str().unwrap();
+    assert!(
+        description.starts_with("2 partie(s) terminée(s)"),
+        "{description}"
+    );
+    assert!(
+        description.contains("🏆 **TestUser** · Annie · W 10/2/5 · Classée Solo/Duo"),
+        "{description}"
+    );
+    assert!(
+        description.contains("💔 **TestUser** · Lux · L"),
+        "{description}"
+    );
+}
+
 #[test]
 fn role_gaps_field_appears_when_event_has_gaps() {
     let summoner = create_dummy_summoner();
@@ -81,7 +135,7 @@ fn role_gaps_field_appears_when_event_has_gaps() {
     event.summoner_id = summoner.id;
     event.role_gaps = Some("Bot gap (-5.5k), Top diff (+4.2k)".to_string());
 
-    let embed = format_grouped_game_ended(&[summoner], &[event], "Ranked Solo/Duo");
+    let embed = format_grouped_game_ended(&[summoner], &[event], "Ranked Solo/Duo", Locale::Fr);
 
     let debug_str = format!("{:?}", embed);
     assert!(debug_str.contains("Écarts par rôle"));
@@ -96,8 +150,92 @@ fn role_gaps_field_absent_when_no_gaps() {
     event.summoner_id = summoner.id;
     event.role_gaps = None;
 
-    let embed = format_grouped_game_ended(&[summoner], &[event], "Ranked Solo/Duo");
+    let embed = format_grouped_game_ended(&[summoner], &[event], "Ranked Solo/Duo", Locale::Fr);
 
     let debug_str = format!("{:?}", embed);
     assert!(!debug_str.contains("Écarts par rôle"));
 }
+
+fn create_scoreboard_player(team_id: u16, position: &str, champion: &str) -> ScoreboardPlayer {
+    ScoreboardPlayer {
+        puuid: format!("puuid-{champion}"),
+        display_name: format!("{champion}Main#EUW"),
+        participant_id: 1,
+        team_id,
+        position: position.to_string(),
+        champion_name: champion.to_string(),
+        win: team_id == 100,
+        kills: 7,
+        deaths: 3,
+        assists: 9,
+        total_cs: 210,
+        gold_earned: 12_400,
+        total_damage: 23_100,
+        vision_score: 31,
+    }
+}
+
+#[test]
+fn match_details_lists_both_teams() {
+    let scoreboard = MatchScoreboard {
+        match_id: "EUW1_12345".to_string(),
+        game_mode: "CLASSIC".to_string(),
+        queue_id: Some(420),
+        game_duration_secs: 1834,
+        players: vec![
+            create_scoreboard_player(100, "MIDDLE", "Ahri"),
+            create_scoreboard_player(200, "MIDDLE", "Orianna"),
+        ],
+    };
+
+    let debug_str = format!("{:?}", format_match_details(&scoreboard, Locale::Fr));
+    assert!(debug_str.contains("Équipe bleue — Victoire"));
+    assert!(debug_str.contains("Équipe rouge — Défaite"));
+    assert!(debug_str.contains("AhriMain#EUW"));
+    assert!(debug_str.contains("7/3/9 · 210 CS · 12.4k gold"));
+    assert!(debug_str.contains("30m34"));
+}
+
+#[test]
+fn lane_opponent_matches_position_on_other_team() {
+    let scoreboard = MatchScoreboard {
+        match_id: "EUW1_12345".to_string(),
+        game_mode: "CLASSIC".to_string(),
+        queue_id: Some(420),
+        game_duration_secs: 1800,
+        players: vec![
+            create_scoreboard_player(100, "MIDDLE", "Ahri"),
+            create_scoreboard_player(100, "TOP", "Garen"),
+            create_scoreboard_player(200, "MIDDLE", "Orianna"),
+        ],
+    };
+
+    let opponent = scoreboard.lane_opponent(&scoreboard.players[0]).unwrap();
+    assert_eq!(opponent.champion_name, "Orianna");
+    assert!(scoreboard.lane_opponent(&scoreboard.players[1]).is_none());
+}
+
+#[test]
+fn timeline_diffs_show_signed_values_and_missing_checkpoints() {
+    let rows = vec![(
+        "TestUser#EUW".to_string(),
+        "Ahri".to_string(),
+        Some("Orianna".to_string()),
+        LaneDiffs {
+            gold: [Some(350), Some(-120), None],
+            cs: [Some(12), Some(0), None],
+        },
+    )];
+
+    let debug_str = format!("{:?}", format_timeline_diffs(&rows, Locale::Fr));
+    assert!(debug_str.contains("TestUser#EUW — Ahri vs Orianna"));
+    assert!(debug_str.contains("10' +350"));
+    assert!(debug_str.contains("15' -120"));
+    assert!(debug_str.contains("20' —"));
+}
+
+#[test]
+fn timeline_diffs_without_rows_says_so() {
+    let debug_str = format!("{:?}", format_timeline_diffs(&[], Locale::Fr));
+    assert!(debug_str.contains("Pas de données de timeline"));
+}
@@ -0,0 +1,206 @@
+//! Deduplication and retention of `notification_queue` against a real
+//! Postgres.
+//!
+//! Ignored by default: they need a database the migrations can run on.
+//! Run explicitly with:
+//!
+//! ```sh
+//! DATABASE_URL=postgres://localhost/sfg_bot_test \
+//!     cargo test --test notification_queue_tests -- --ignored
+//! ```
+
+use sfg_bot::db::models::{NewNotificationEvent, Summoner};
+use sfg_bot::{PgRepository, Repository};
+use uuid::Uuid;
+
+async fn live_repository() -> PgRepository {
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = sfg_bot::db::create_pool(&database_url)
+        .await
+        .expect("connect to database");
+    sqlx::migrate!().run(&pool).await.expect("run migrations");
+    PgRepository::new(pool)
+}
+
+/// A summoner of its own: name, tag and puuid come from a fresh UUID, so
+/// tests can run side by side and again on the same database.
+async fn test_summoner(repository: &PgRepository) -> Summoner {
+    let id = Uuid::new_v4().simple().to_string();
+    let puuid = format!("dedup-test-{id}");
+    repository
+        .upsert_summoner(&puuid, &format!("Dedup{}", &id[..16]), &id[16..21], "euw1")
+        .await
+        .expect("insert summoner")
+}
+
+/// A game id no other test uses.
+fn unique_game_id() -> i64 {
+    (Uuid::new_v4().as_u64_pair().0 >> 1) as i64
+}
+
+fn game_ended_event(summoner_id: Uuid, game_id: i64) -> NewNotificationEvent {
+    NewNotificationEvent {
+        summoner_id,
+        event_type: "GAME_ENDED".to_string(),
+        game_id,
+        match_id: Some(format!("EUW1_{}", game_id)),
+        champion_id: 1,
+        champion_name: "Annie".to_string(),
+        role: Some("MIDDLE".to_string()),
+        win: Some(true),
+        kills: Some(5),
+        deaths: Some(2),
+        assists: Some(7),
+        game_duration_secs: Some(1800),
+        game_mode: "CLASSIC".to_string(),
+        queue_id: Some(420),
+        is_featured_mode: false,
+        total_cs: Some(200),
+        total_gold: Some(12000),
+        total_damage: Some(25000),
+        enemy_champion_name: None,
+        enemy_cs: None,
+        enemy_gold: None,
+        enemy_damage: None,
+        role_gaps: None,
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs DATABASE_URL"]
+async fn second_insert_reports_the_existing_row() {
+    let repository = live_repository().await;
+    let summoner = test_summoner(&repository).await;
+    let event = game_ended_event(summoner.id, unique_game_id());
+
+    let first = repository.insert_notification_event(&event).await.unwrap();
+    let second = repository.insert_notification_event(&event).await.unwrap();
+
+    assert!(first.is_new);
+    assert!(!second.is_new);
+    assert_eq!(first.event.id, second.event.id);
+}
+
+#[tokio::test]
+#[ignore = "needs DATABASE_URL"]
+async fn featured_mode_detection_of_the_same_game_is_a_duplicate() {
+    let repository = live_repository().await;
+    let summoner = test_summoner(&repository).await;
+    let spectator = game_ended_event(summoner.id, unique_game_id());
+    let featured = NewNotificationEvent {
+        is_featured_mode: true,
+        ..spectator.clone()
+    };
+
+    assert!(
+        repository
+            .insert_notification_event(&spectator)
+            .await
+            .unwrap()
+            .is_new
+    );
+    assert!(
+        !repository
+            .insert_notification_event(&featured)
+            .await
+            .unwrap()
+            .is_new
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore = "needs DATABASE_URL"]
+async fn concurrent_inserts_queue_a_single_event() {
+    let repository = std::sync::Arc::new(live_repository().await);
+    let summoner = test_summoner(&repository).await;
+    let event = game_ended_event(summoner.id, unique_game_id());
+
+    // Several polling loops detecting the same game end at the same time.
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let repository = repository.clone();
+            let event = event.clone();
+            tokio::spawn(async move { repository.insert_notification_event(&event).await })
+        })
+        .collect();
+
+    let mut queued = Vec::new();
+    for handle in handles {
+        queued.push(handle.await.unwrap().unwrap());
+    }
+
+    assert_eq!(queued.iter().filter(|q| q.is_new).count(), 1);
+    assert!(queued.iter().all(|q| q.event.id == queued[0].event.id));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[ignore = "needs DATABASE_URL"]
+async fn simultaneous_inserts_race_to_a_single_row() {
+    let repository = live_repository().await;
+    let summoner = test_summoner(&repository).await;
+    let event = game_ended_event(summoner.id, unique_game_id());
+
+    let (first, second) = tokio::join!(
+        repository.insert_notification_event(&event),
+        repository.insert_notification_event(&event)
+    );
+    let (first, second) = (first.unwrap(), second.unwrap());
+
+    assert_eq!(u8::from(first.is_new) + u8::from(second.is_new), 1);
+    assert_eq!(first.event.id, second.event.id);
+    let rows: Vec<_> = repository
+        .get_pending_notification_events()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|queued| queued.summoner_id == summoner.id)
+        .collect();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].id, first.event.id);
+}
+
+#[tokio::test]
+#[ignore = "needs DATABASE_URL"]
+async fn retention_prunes_delivered_events_but_keeps_dead_letters() {
+    let repository = live_repository().await;
+    let summoner = test_summoner(&repository).await;
+    let delivered = game_ended_event(summoner.id, unique_game_id());
+    let dead = game_ended_event(summoner.id, unique_game_id());
+    let delivered_id = repository
+        .insert_notification_event(&delivered)
+        .await
+        .unwrap()
+        .event
+        .id;
+    let dead_id = repository
+        .insert_notification_event(&dead)
+        .await
+        .unwrap()
+        .event
+        .id;
+    repository
+        .mark_notifications_processed(&[delivered_id])
+        .await
+        .unwrap();
+    repository
+        .mark_notifications_failed(&[dead_id], "channel not found")
+        .await
+        .unwrap();
+
+    let cutoff = chrono::Utc::now() + chrono::Duration::minutes(1);
+    repository
+        .prune_processed_notifications(cutoff)
+        .await
+        .unwrap();
+
+    let dead_letters = repository.get_failed_notification_events().await.unwrap();
+    assert!(dead_letters.iter().any(|event| event.id == dead_id));
+    // The delivered row is gone, so the same game queues again.
+    assert!(
+        repository
+            .insert_notification_event(&delivered)
+            .await
+            .unwrap()
+            .is_new
+    );
+}
@@ -0,0 +1,154 @@
+use chrono::Utc;
+use sfg_bot::db::models::{NotificationEvent, Summoner};
+use sfg_bot::i18n::Locale;
+use sfg_bot::notification::sinks::{
+    Notification, NotificationKind, NotificationSink, SinkError, StdoutSink, WebhookSink,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+fn summoner() -> Summoner {
+    Summoner {
+        id: Uuid::new_v4(),
+        riot_puuid: "test_puuid".to_string(),
+        game_name: "TestUser".to_string(),
+        tag_line: "EUW".to_string(),
+        region: "euw1".to_string(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        benchmark_tier: None,
+    }
+}
+
+fn game_ended(summoner: &Summoner) -> Notification {
+    let event = NotificationEvent {
+        id: Uuid::new_v4(),
+        summoner_id: summoner.id,
+        event_type: "GAME_ENDED".to_string(),
+        game_id: 12345,
+        match_id: Some("EUW1_12345".to_string()),
+        champion_id: 1,
+        champion_name: "Annie".to_string(),
+        role: Some("MIDDLE".to_string()),
+        win: Some(true),
+        kills: Some(10),
+        deaths: Some(2),
+        assists: Some(5),
+        game_duration_secs: Some(1800),
+        game_mode: "CLASSIC".to_string(),
+        queue_id: Some(420),
+        is_featured_mode: false,
+        total_cs: Some(200),
+        total_gold: Some(12000),
+        total_damage: Some(25000),
+        enemy_champion_name: None,
+        enemy_cs: None,
+        enemy_gold: None,
+        enemy_damage: None,
+        role_gaps: None,
+        processed: false,
+        created_at: Utc::now(),
+        processed_at: None,
+        retry_count: 0,
+        error_message: None,
+        failed_at: None,
+        next_attempt_at: Utc::now(),
+    };
+    Notification {
+        kind: NotificationKind::GameEnded,
+        summoners: vec![summoner.clone()],
+        events: vec![event],
+        locale: Locale::En,
+    }
+}
+
+/// Accepts one HTTP request, answers it with `status` and hands back the
+/// request body.
+async fn stand_in(status: &'static str) -> (String, oneshot::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/recaps", listener.local_addr().unwrap());
+    let (body_tx, body_rx) = oneshot::channel();
+
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let read = socket.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..read]);
+            let text = String::from_utf8_lossy(&request);
+            if let Some(header_end) = text.find("\r\n\r\n") {
+                let content_length = text[..header_end]
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().ok())?
+                    })
+                    .unwrap_or(0);
+                if request.len() >= header_end + 4 + content_length {
+                    let body = String::from_utf8_lossy(&request[header_end + 4..]).to_string();
+                    let _ = body_tx.send(body);
+                    break;
+                }
+            }
+            if read == 0 {
+                break;
+            }
+        }
+        let response =
+            format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n");
+        socket.write_all(response.as_bytes()).await.unwrap();
+    });
+
+    (url, body_rx)
+}
+
+#[tokio::test]
+async fn webhook_sink_posts_the_notification_as_json() {
+    let (url, body) = stand_in("200 OK").await;
+    let player = summoner();
+    let sink = WebhookSink::new(reqwest::Client::new(), url);
+
+    sink.send(&game_ended(&player))
+        .await
+        .expect("webhook accepted");
+
+    let payload: serde_json::Value = serde_json::from_str(&body.await.unwrap()).unwrap();
+    assert_eq!(payload["kind"], "game_ended");
+    assert_eq!(payload["locale"], "en");
+    let entry = &payload["players"][0];
+    assert_eq!(entry["summoner"], "TestUser#EUW");
+    assert_eq!(entry["match_id"], "EUW1_12345");
+    assert_eq!(entry["champion"], "Annie");
+    assert_eq!(entry["queue"], "Ranked Solo/Duo");
+    assert_eq!(entry["win"], true);
+    assert_eq!(
+        (&entry["kills"], &entry["deaths"], &entry["assists"]),
+        (&10.into(), &2.into(), &5.into())
+    );
+}
+
+#[tokio::test]
+async fn webhook_sink_fails_on_error_status() {
+    let (url, _body) = stand_in("503 Service Unavailable").await;
+    let sink = WebhookSink::new(reqwest::Client::new(), url);
+
+    let result = sink.send(&game_ended(&summoner())).await;
+
+    match result {
+        Err(SinkError::Status(status)) => assert_eq!(status.as_u16(), 503),
+        other => panic!("expected a status error, got {other:?}"),
+    }
+}
+
+#[test]
+fn stdout_sink_renders_one_line() {
+    let line = StdoutSink::render(&game_ended(&summoner()));
+    assert_eq!(
+        line,
+        "[game_ended] CLASSIC · Ranked Solo/Duo · TestUser#EUW Annie W 10/2/5"
+    );
+}
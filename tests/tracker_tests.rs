@@ -6,7 +6,7 @@
 use chrono::Utc;
 use mockall::predicate::*;
 use riven::consts::{PlatformRoute, RegionalRoute};
-use sfg_bot::db::models::{ActiveGame, MatchHistory, NewActiveGame, Summoner};
+use sfg_bot::db::models::{ActiveGame, AnalysisJob, NewActiveGame, Summoner};
 use sfg_bot::riot::client::RiotClientError;
 use sfg_bot::riot::models::{ActiveGameInfo, GameStateChange, MatchLookup, MatchResult};
 use sfg_bot::riot::tracker::{GameTracker, MAX_END_RETRY_CYCLES};
@@ -23,6 +23,7 @@ fn create_test_summoner() -> Summoner {
         region: "na1".to_string(),
         created_at: Utc::now(),
         updated_at: Utc::now(),
+        benchmark_tier: None,
     }
 }
 
@@ -77,32 +78,6 @@ fn create_test_match_result(game_id: i64) -> MatchResult {
     }
 }
 
-fn create_test_match_history(summoner_id: Uuid, game_id: i64) -> MatchHistory {
-    MatchHistory {
-        id: Uuid::new_v4(),
-        summoner_id,
-        match_id: format!("NA1_{}", game_id),
-        game_id,
-        win: true,
-        kills: 10,
-        deaths: 2,
-        assists: 5,
-        champion_id: 1,
-        game_duration_secs: 1800,
-        game_mode: "CLASSIC".to_string(),
-        role: Some("JUNGLE".to_string()),
-        finished_at: Utc::now(),
-        created_at: Utc::now(),
-        total_cs: 180,
-        total_gold: 13500,
-        total_damage: 20000,
-        enemy_champion_name: Some("Lee Sin".to_string()),
-        enemy_cs: Some(170),
-        enemy_gold: Some(12000),
-        enemy_damage: Some(18000),
-        queue_id: None,
-    }
-}
 mod check_summoner_game_state {
     use super::*;
 
@@ -420,7 +395,7 @@ mod handle_game_ended {
     use super::*;
 
     #[tokio::test]
-    async fn deletes_active_game_and_returns_match_result() {
+    async fn returns_match_result_without_writing() {
         let summoner = create_test_summoner();
         let game_id = 12345i64;
 
@@ -431,19 +406,8 @@ mod handle_game_ended {
             .times(1)
             .returning(move |_, _, _| Ok(Some(create_test_match_result(12345))));
 
-        let summoner_id = summoner.id;
-        let mut mock_repo = MockRepository::new();
-        mock_repo
-            .expect_delete_active_game_by_summoner_and_game()
-            .with(eq(summoner.id), eq(game_id))
-            .times(1)
-            .returning(|_, _| Ok(()));
-        mock_repo
-            .expect_insert_match_result()
-            .times(1)
-            .returning(move |new_match| {
-                Ok(create_test_match_history(summoner_id, new_match.game_id))
-            });
+        // Persisting is left to record_game_end: no repository call expected.
+        let mock_repo = MockRepository::new();
 
         let tracker = GameTracker::new(Arc::new(mock_riot), Arc::new(mock_repo), "na1".to_string());
 
@@ -569,83 +533,137 @@ mod handle_game_ended {
             .times(1)
             .returning(move |_, _, _| Ok(Some(create_test_match_result(12345))));
 
+        let mock_repo = MockRepository::new();
+
+        let tracker = GameTracker::new(Arc::new(mock_riot), Arc::new(mock_repo), "na1".to_string());
+
+        let result = tracker.handle_game_ended(&summoner, game_id).await.unwrap();
+
+        assert!(matches!(result, MatchLookup::Found(_)));
+    }
+}
+
+mod record_game_end {
+    use super::*;
+
+    fn create_test_analysis_job(summoner_id: Uuid, match_id: &str) -> AnalysisJob {
+        AnalysisJob {
+            id: Uuid::new_v4(),
+            summoner_id,
+            match_id: match_id.to_string(),
+            created_at: Utc::now(),
+            completed_at: None,
+            status: "pending".to_string(),
+            attempts: 0,
+            next_attempt_at: Utc::now(),
+            started_at: None,
+            error_message: None,
+            group_summoner_ids: Vec::new(),
+            group_done_summoner_ids: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn writes_match_event_and_analysis_job_together() {
+        let summoner = create_test_summoner();
         let summoner_id = summoner.id;
+        let match_result = create_test_match_result(12345);
+
         let mut mock_repo = MockRepository::new();
         mock_repo
-            .expect_delete_active_game_by_summoner_and_game()
-            .times(1)
-            .returning(|_, _| Ok(()));
+            .expect_get_champion_by_id()
+            .with(eq(1))
+            .returning(|_| Ok(None));
         mock_repo
-            .expect_insert_match_result()
+            .expect_record_game_end()
+            .withf(move |record| {
+                record.game_id == 12345
+                    && record.match_result.summoner_id == summoner_id
+                    && record.match_result.match_id == "NA1_12345"
+                    && record.event.event_type == "GAME_ENDED"
+                    && record.event.match_id.as_deref() == Some("NA1_12345")
+                    && record.event.champion_name == "Champion #1"
+                    && !record.event.is_featured_mode
+                    && record
+                        .analysis_job
+                        .as_ref()
+                        .is_some_and(|job| job.match_id == "NA1_12345")
+            })
             .times(1)
-            .returning(move |new_match| {
-                Ok(create_test_match_history(summoner_id, new_match.game_id))
+            .returning(move |record| {
+                Ok(Some(create_test_analysis_job(
+                    summoner_id,
+                    &record.match_result.match_id,
+                )))
             });
 
-        let tracker = GameTracker::new(Arc::new(mock_riot), Arc::new(mock_repo), "na1".to_string());
+        let tracker = GameTracker::new(
+            Arc::new(MockRiotApiClient::new()),
+            Arc::new(mock_repo),
+            "na1".to_string(),
+        );
 
-        let result = tracker.handle_game_ended(&summoner, game_id).await.unwrap();
+        let job = tracker
+            .record_game_end(&summoner, 12345, &match_result, false, true)
+            .await
+            .unwrap();
 
-        assert!(matches!(result, MatchLookup::Found(_)));
+        assert_eq!(job.map(|job| job.match_id), Some("NA1_12345".to_string()));
     }
 
     #[tokio::test]
-    async fn propagates_delete_error() {
+    async fn skips_analysis_job_when_not_queued() {
         let summoner = create_test_summoner();
-        let game_id = 12345i64;
+        let match_result = create_test_match_result(12345);
 
-        let mut mock_riot = MockRiotApiClient::new();
-        mock_riot
-            .expect_get_match_result()
-            .times(1)
-            .returning(move |_, _, _| Ok(Some(create_test_match_result(12345))));
-
-        let summoner_id = summoner.id;
         let mut mock_repo = MockRepository::new();
         mock_repo
-            .expect_insert_match_result()
-            .times(1)
-            .returning(move |new_match| {
-                Ok(create_test_match_history(summoner_id, new_match.game_id))
-            });
+            .expect_get_champion_by_id()
+            .returning(|_| Ok(None));
         mock_repo
-            .expect_delete_active_game_by_summoner_and_game()
+            .expect_record_game_end()
+            .withf(|record| record.analysis_job.is_none() && record.event.is_featured_mode)
             .times(1)
-            .returning(|_, _| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
+            .returning(|_| Ok(None));
 
-        let tracker = GameTracker::new(Arc::new(mock_riot), Arc::new(mock_repo), "na1".to_string());
+        let tracker = GameTracker::new(
+            Arc::new(MockRiotApiClient::new()),
+            Arc::new(mock_repo),
+            "na1".to_string(),
+        );
 
-        let result = tracker.handle_game_ended(&summoner, game_id).await;
+        let job = tracker
+            .record_game_end(&summoner, 12345, &match_result, true, false)
+            .await
+            .unwrap();
 
-        assert!(result.is_err());
+        assert!(job.is_none());
     }
 
     #[tokio::test]
-    async fn continues_when_insert_match_result_fails() {
+    async fn propagates_transaction_error() {
         let summoner = create_test_summoner();
-        let game_id = 12345i64;
-
-        let mut mock_riot = MockRiotApiClient::new();
-        mock_riot
-            .expect_get_match_result()
-            .withf(|match_id, _, _| match_id == "NA1_12345")
-            .times(1)
-            .returning(move |_, _, _| Ok(Some(create_test_match_result(12345))));
+        let match_result = create_test_match_result(12345);
 
         let mut mock_repo = MockRepository::new();
         mock_repo
-            .expect_delete_active_game_by_summoner_and_game()
-            .times(1)
-            .returning(|_, _| Ok(()));
+            .expect_get_champion_by_id()
+            .returning(|_| Ok(None));
         mock_repo
-            .expect_insert_match_result()
+            .expect_record_game_end()
             .times(1)
-            .returning(|_| Err(RepositoryError::Database(sqlx::Error::RowNotFound)));
+            .returning(|_| Err(RepositoryError::Database(sqlx::Error::PoolTimedOut)));
 
-        let tracker = GameTracker::new(Arc::new(mock_riot), Arc::new(mock_repo), "na1".to_string());
+        let tracker = GameTracker::new(
+            Arc::new(MockRiotApiClient::new()),
+            Arc::new(mock_repo),
+            "na1".to_string(),
+        );
 
-        let result = tracker.handle_game_ended(&summoner, game_id).await.unwrap();
+        let result = tracker
+            .record_game_end(&summoner, 12345, &match_result, false, true)
+            .await;
 
-        assert!(matches!(result, MatchLookup::Found(_)));
+        assert!(result.is_err());
     }
 }